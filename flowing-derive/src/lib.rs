@@ -0,0 +1,224 @@
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use syn::{
+    parse_macro_input, spanned::Spanned, Attribute, Data, DeriveInput, Error, Expr, Fields, Ident, LitStr, Meta,
+};
+
+/// Derives `flowing::Node` for a struct with named fields.
+///
+/// Fields annotated with `#[input]` or `#[output]` (of type `Value`) become inputs and outputs, numbered in field
+/// order and named after their fields. Port descriptors are set with `default`, `kind`, `min`, `max` and `unit`
+/// arguments (e.g. `#[input(kind = Continuous, min = 20.0, unit = "Hz")]`). Fields annotated with `#[parameter]` become
+/// parameters named after their fields. Processing is delegated to an inherent `fn process(&mut self)` of the struct.
+/// Annotate the struct with `#[node(delayed)]` if it introduces processing delay, with `#[node(pure)]` if its outputs
+/// only depend on its inputs (see `Node::is_pure`) and with `#[node(category = "...")]` to set its category (see
+/// `Node::category`).
+///
+/// ```ignore
+/// #[derive(Node)]
+/// struct Gain {
+///     #[input]
+///     signal: Value,
+///     #[input]
+///     gain: Value,
+///     #[output]
+///     amplified: Value,
+/// }
+/// impl Gain {
+///     fn process(&mut self) {
+///         self.amplified = self.signal * self.gain;
+///     }
+/// }
+/// ```
+#[proc_macro_derive(Node, attributes(input, node, output, parameter))]
+pub fn derive_node(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input).unwrap_or_else(Error::into_compile_error).into()
+}
+
+/// Expands derive of a struct.
+fn expand(input: DeriveInput) -> Result<proc_macro2::TokenStream, Error> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => return Err(Error::new(input.span(), "Node can only be derived for structs with named fields.")),
+        },
+        _ => return Err(Error::new(input.span(), "Node can only be derived for structs.")),
+    };
+
+    let (mut category, mut delayed, mut pure) = (None, false, false);
+    for attribute in input.attrs.iter().filter(|attribute| attribute.path().is_ident("node")) {
+        attribute.parse_nested_meta(|meta| {
+            if meta.path.is_ident("category") {
+                let value: LitStr = meta.value()?.parse()?;
+                category = Some(quote! {
+                    fn category(&self) -> &'static str {
+                        #value
+                    }
+                });
+            } else if meta.path.is_ident("delayed") {
+                delayed = true;
+            } else if meta.path.is_ident("pure") {
+                pure = true;
+            } else {
+                return Err(meta.error("Unsupported node attribute."));
+            }
+            Ok(())
+        })?;
+    }
+
+    let (mut inputs, mut input_infos) = (Vec::new(), Vec::new());
+    let (mut outputs, mut output_infos) = (Vec::new(), Vec::new());
+    let mut parameters = Vec::new();
+    for field in fields {
+        let name = field.ident.as_ref().unwrap();
+        for attribute in field.attrs.iter() {
+            if attribute.path().is_ident("input") {
+                inputs.push(name);
+                input_infos.push(port_info(attribute, name)?);
+            } else if attribute.path().is_ident("output") {
+                outputs.push(name);
+                output_infos.push(port_info(attribute, name)?);
+            } else if attribute.path().is_ident("parameter") {
+                attribute.meta.require_path_only()?;
+                parameters.push(name);
+            }
+        }
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+    let input_ids = (0..inputs.len() as u32).collect::<Vec<_>>();
+    let input_names = inputs.iter().map(|name| name.to_string()).collect::<Vec<_>>();
+    let output_names = outputs.iter().map(|name| name.to_string()).collect::<Vec<_>>();
+    let output_ids = (0..outputs.len() as u32).collect::<Vec<_>>();
+    let parameter_names = parameters.iter().map(|name| name.to_string()).collect::<Vec<_>>();
+    let guard = Ident::new(&format!("__{}ProcessGuard", ident), Span::call_site());
+    Ok(quote! {
+        const _: () = {
+            // Makes `Self::process` ambiguous (instead of recursive) if the struct has no inherent `process` method.
+            trait #guard {
+                fn process(&mut self);
+            }
+            impl #impl_generics #guard for #ident #type_generics #where_clause {
+                fn process(&mut self) {}
+            }
+
+            impl #impl_generics ::flowing::Node for #ident #type_generics #where_clause {
+                #category
+
+                fn delayed_processing(&self) -> bool {
+                    #delayed
+                }
+
+                fn get_output(&self, id: ::flowing::OutputId) -> ::flowing::Value {
+                    match id.0 {
+                        #(#output_ids => self.#outputs,)*
+                        _ => panic!("Output with id {} does not exist.", id.0),
+                    }
+                }
+
+                fn get_parameter(&self, name: &str) -> Option<::flowing::Value> {
+                    match name {
+                        #(#parameter_names => Some(self.#parameters),)*
+                        _ => None,
+                    }
+                }
+
+                fn input_info(&self, id: ::flowing::InputId) -> ::flowing::PortInfo<'_> {
+                    match id.0 {
+                        #(#input_ids => #input_infos,)*
+                        _ => ::flowing::PortInfo::new(None),
+                    }
+                }
+
+                fn input_name(&self, id: ::flowing::InputId) -> Option<&str> {
+                    match id.0 {
+                        #(#input_ids => Some(#input_names),)*
+                        _ => None,
+                    }
+                }
+
+                fn is_pure(&self) -> bool {
+                    #pure
+                }
+
+                fn list_inputs(&self) -> &[::flowing::InputId] {
+                    &[#(::flowing::InputId(#input_ids)),*]
+                }
+
+                fn list_outputs(&self) -> &[::flowing::OutputId] {
+                    &[#(::flowing::OutputId(#output_ids)),*]
+                }
+
+                fn output_info(&self, id: ::flowing::OutputId) -> ::flowing::PortInfo<'_> {
+                    match id.0 {
+                        #(#output_ids => #output_infos,)*
+                        _ => ::flowing::PortInfo::new(None),
+                    }
+                }
+
+                fn output_name(&self, id: ::flowing::OutputId) -> Option<&str> {
+                    match id.0 {
+                        #(#output_ids => Some(#output_names),)*
+                        _ => None,
+                    }
+                }
+
+                fn parameters(&self) -> &[&str] {
+                    &[#(#parameter_names),*]
+                }
+
+                fn process(&mut self) {
+                    use ::flowing::Node as _;
+                    Self::process(self)
+                }
+
+                fn set_input(&mut self, id: ::flowing::InputId, value: ::flowing::Value) {
+                    match id.0 {
+                        #(#input_ids => self.#inputs = value,)*
+                        _ => panic!("Input with id {} does not exist.", id.0),
+                    }
+                }
+
+                fn set_parameter(&mut self, name: &str, value: ::flowing::Value) {
+                    match name {
+                        #(#parameter_names => self.#parameters = value,)*
+                        _ => {}
+                    }
+                }
+            }
+        };
+    })
+}
+
+/// Expands port descriptor of a field from its attribute (e.g. `#[input(default = 440.0, min = 20.0, unit = "Hz")]`).
+fn port_info(attribute: &Attribute, name: &Ident) -> Result<proc_macro2::TokenStream, Error> {
+    let name = name.to_string();
+    let mut fields = Vec::new();
+    if let Meta::List(_) = attribute.meta {
+        attribute.parse_nested_meta(|meta| {
+            let value = meta.value()?;
+            let key = meta.path.get_ident().map(|ident| ident.to_string());
+            match key.as_deref() {
+                Some(key @ ("default" | "max" | "min")) => {
+                    let key = Ident::new(key, Span::call_site());
+                    let value: Expr = value.parse()?;
+                    fields.push(quote! { #key: (#value) as ::flowing::Value });
+                }
+                Some("kind") => {
+                    let value: Ident = value.parse()?;
+                    fields.push(quote! { kind: ::flowing::ValueKind::#value });
+                }
+                Some("unit") => {
+                    let value: LitStr = value.parse()?;
+                    fields.push(quote! { unit: Some(#value) });
+                }
+                _ => return Err(meta.error("Unsupported port attribute.")),
+            }
+            Ok(())
+        })?;
+    }
+    Ok(quote! { ::flowing::PortInfo { #(#fields,)* ..::flowing::PortInfo::new(Some(#name)) } })
+}
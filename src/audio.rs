@@ -0,0 +1,190 @@
+use crate::{Graph, GraphError, Node, NodeId, OutputId, Value};
+use cpal::{
+    traits::{DeviceTrait, HostTrait, StreamTrait},
+    FromSample, SampleFormat, SizedSample, Stream, StreamConfig,
+};
+use std::fmt;
+
+/// Driver that plays an output of a graph on the default audio output device.
+///
+/// The graph is moved to the audio thread, use parameter handles and probes to communicate with it while playing.
+pub struct AudioOutput {
+    /// Sample rate of the output stream (set on the graph).
+    sample_rate: f64,
+
+    /// Playing output stream (stopped when dropped).
+    stream: Stream,
+}
+impl AudioOutput {
+    /// Starts playing an output of a graph, processing one cycle per frame (the value is written to all channels).
+    pub fn start<N: Node + Send + 'static>(
+        graph: Graph<N>,
+        node: NodeId,
+        output: OutputId,
+    ) -> Result<Self, AudioError> {
+        Self::start_with(graph, node, output, Pull::Cycles)
+    }
+
+    /// Starts playing an output of a graph, processing blocks of frames (the value is written to all channels).
+    pub fn start_blocks<N: Node + Send + 'static>(
+        graph: Graph<N>,
+        node: NodeId,
+        output: OutputId,
+    ) -> Result<Self, AudioError> {
+        Self::start_with(graph, node, output, Pull::Blocks { position: usize::MAX })
+    }
+
+    /// Starts playing an output of a graph with the given processing mode.
+    fn start_with<N: Node + Send + 'static>(
+        mut graph: Graph<N>,
+        node: NodeId,
+        output: OutputId,
+        pull: Pull,
+    ) -> Result<Self, AudioError> {
+        graph.get_output(node, output)?;
+        let device = cpal::default_host().default_output_device().ok_or(AudioError::NoDevice)?;
+        let supported = device.default_output_config().map_err(|error| AudioError::Config(error.to_string()))?;
+        let config = supported.config();
+        let sample_rate = config.sample_rate as f64;
+        graph.set_sample_rate(sample_rate);
+
+        let source = Source { graph, node, output, pull };
+        let stream = match supported.sample_format() {
+            SampleFormat::F32 => build::<f32, N>(&device, &config, source),
+            SampleFormat::F64 => build::<f64, N>(&device, &config, source),
+            SampleFormat::I16 => build::<i16, N>(&device, &config, source),
+            SampleFormat::I32 => build::<i32, N>(&device, &config, source),
+            SampleFormat::U16 => build::<u16, N>(&device, &config, source),
+            format => return Err(AudioError::Config(format!("Sample format {} is not supported.", format))),
+        }?;
+        stream.play().map_err(|error| AudioError::Stream(error.to_string()))?;
+        Ok(AudioOutput { sample_rate, stream })
+    }
+
+    /// Returns sample rate of the output stream.
+    pub fn sample_rate(&self) -> f64 {
+        self.sample_rate
+    }
+
+    /// Stops playing (and drops the graph on the audio thread).
+    pub fn stop(self) {
+        drop(self.stream);
+    }
+}
+
+/// Audio error type.
+#[derive(PartialEq)]
+pub enum AudioError {
+    Config(String),
+    Graph(GraphError),
+    NoDevice,
+    Stream(String),
+}
+impl From<GraphError> for AudioError {
+    fn from(error: GraphError) -> Self {
+        AudioError::Graph(error)
+    }
+}
+impl fmt::Debug for AudioError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AudioError::Config(message) => write!(f, "Audio output could not be configured: {}", message),
+            AudioError::Graph(error) => write!(f, "{:?}", error),
+            AudioError::NoDevice => write!(f, "No audio output device available."),
+            AudioError::Stream(message) => write!(f, "Audio output stream failed: {}", message),
+        }
+    }
+}
+
+/// Processing mode of the audio thread.
+enum Pull {
+    /// Block processing (with position of next frame in current block).
+    Blocks { position: usize },
+
+    /// Cycle processing.
+    Cycles,
+}
+
+/// Graph output pulled by the audio thread.
+struct Source<N: Node> {
+    /// Processed graph.
+    graph: Graph<N>,
+
+    /// Played node.
+    node: NodeId,
+
+    /// Played output.
+    output: OutputId,
+
+    /// Processing mode.
+    pull: Pull,
+}
+impl<N: Node> Source<N> {
+    /// Returns value of the next frame.
+    fn next(&mut self) -> Value {
+        match &mut self.pull {
+            Pull::Blocks { position } => {
+                if *position >= self.graph.block_size() {
+                    self.graph.process_block();
+                    *position = 0;
+                }
+                let value = self.graph.get_block_output(self.node, self.output).map_or(0.0, |buffer| buffer[*position]);
+                *position += 1;
+                value
+            }
+            Pull::Cycles => {
+                self.graph.process();
+                self.graph.get_output(self.node, self.output).unwrap_or(0.0)
+            }
+        }
+    }
+}
+
+/// Builds output stream of a sample type.
+fn build<T: SizedSample + FromSample<Value>, N: Node + Send + 'static>(
+    device: &cpal::Device,
+    config: &StreamConfig,
+    mut source: Source<N>,
+) -> Result<Stream, AudioError> {
+    let channels = config.channels as usize;
+    device
+        .build_output_stream(config, move |data: &mut [T], _| fill(data, channels, || source.next()), |_| {}, None)
+        .map_err(|error| AudioError::Stream(error.to_string()))
+}
+
+/// Fills interleaved frames with values (written to all channels).
+fn fill<T: Copy + FromSample<Value>>(data: &mut [T], channels: usize, mut next: impl FnMut() -> Value) {
+    for frame in data.chunks_mut(channels) {
+        let value = T::from_sample_(next());
+        for sample in frame.iter_mut() {
+            *sample = value;
+        }
+    }
+}
+
+/// Unit tests (without audio device).
+#[cfg(all(test, feature = "dsp", feature = "generators", feature = "math"))]
+mod tests {
+    use super::*;
+    use crate::fixtures::accumulator;
+
+    #[test]
+    fn pulls_cycles() {
+        let (graph, [_, add1, _]) = accumulator(1.0, |node| node);
+        let mut source = Source { graph, node: add1, output: OutputId(0), pull: Pull::Cycles };
+        let mut data = [0.0f32; 6];
+        fill(&mut data, 2, || source.next());
+        assert_eq!(data, [1.0, 1.0, 2.0, 2.0, 3.0, 3.0]);
+    }
+
+    #[test]
+    fn pulls_blocks() {
+        let (mut graph, [_, add1, _]) = accumulator(1.0, |node| node);
+        graph.set_block_size(2);
+        let mut source = Source { graph, node: add1, output: OutputId(0), pull: Pull::Blocks { position: usize::MAX } };
+        let mut data = [0i16; 4];
+        fill(&mut data, 1, || source.next() / 4.0);
+        let (quarter, half) = (i16::from_sample_(0.25f32), i16::from_sample_(0.5f32));
+        assert_eq!(data, [quarter, quarter, quarter, half]);
+    }
+}
@@ -2,6 +2,7 @@ use crate::{InputId, NodeId, OutputId};
 
 /// Graph edge between source node output and target node input.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Connection {
     pub source_node: NodeId,
     pub source_output: OutputId,
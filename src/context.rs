@@ -0,0 +1,105 @@
+/// Timing information passed to nodes when processing.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct ProcessContext {
+    /// Time between frames in seconds (inverse of sample rate).
+    pub dt: f64,
+
+    /// Index of processed frame (counted from 0, advanced once per cycle).
+    pub frame: u64,
+
+    /// Number of frames per second.
+    pub sample_rate: f64,
+}
+impl ProcessContext {
+    /// Creates new context for first frame with given sample rate.
+    pub fn new(sample_rate: f64) -> Self {
+        ProcessContext { dt: 1.0 / sample_rate, frame: 0, sample_rate }
+    }
+
+    /// Returns context of a clock domain that processes every `divisor`-th frame.
+    pub fn divided(&self, divisor: u32) -> Self {
+        ProcessContext {
+            dt: self.dt * divisor as f64,
+            frame: self.frame / divisor as u64,
+            sample_rate: self.sample_rate / divisor as f64,
+        }
+    }
+
+    /// Returns time of processed frame in seconds.
+    pub fn time(&self) -> f64 {
+        self.frame as f64 * self.dt
+    }
+}
+impl Default for ProcessContext {
+    /// Creates new context for first frame with one frame per second.
+    fn default() -> Self {
+        Self::new(1.0)
+    }
+}
+
+/// Unit tests.
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Graph, InputId, Node, OutputId, Value};
+
+    /// Node that outputs the time of the processed frame.
+    struct Clock {
+        time: Value,
+    }
+    impl Node for Clock {
+        fn delayed_processing(&self) -> bool {
+            false
+        }
+        fn get_output(&self, _id: OutputId) -> Value {
+            self.time
+        }
+        fn list_inputs(&self) -> &[InputId] {
+            &[]
+        }
+        fn list_outputs(&self) -> &[OutputId] {
+            &[OutputId(0)]
+        }
+        fn process_with_context(&mut self, context: &ProcessContext) {
+            self.time = context.time() as Value;
+        }
+        fn set_input(&mut self, _id: InputId, _value: Value) {}
+    }
+
+    #[test]
+    fn new_context() {
+        let context = ProcessContext::new(4.0);
+        assert_eq!(context.dt, 0.25);
+        assert_eq!(context.time(), 0.0);
+        assert_eq!(ProcessContext { frame: 3, ..context }.time(), 0.75);
+        assert_eq!(ProcessContext::default().dt, 1.0);
+    }
+
+    #[test]
+    fn divided_context() {
+        let context = ProcessContext { frame: 6, ..ProcessContext::new(4.0) };
+        assert_eq!(context.divided(1), context);
+        assert_eq!(context.divided(2), ProcessContext { dt: 0.5, frame: 3, sample_rate: 2.0 });
+        assert_eq!(context.divided(2).time(), context.time());
+    }
+
+    #[test]
+    fn graph_advances_frames() {
+        let mut graph = Graph::new();
+        let clock = graph.add_node(Clock { time: -1.0 });
+        graph.set_sample_rate(2.0);
+        graph.process();
+        assert_eq!(graph.get_node(clock).unwrap().get_output(OutputId(0)), 0.0);
+        graph.process();
+        assert_eq!(graph.get_node(clock).unwrap().get_output(OutputId(0)), 0.5);
+        assert_eq!(graph.context().frame, 2);
+
+        // Frame counter is kept when changing sample rate and advanced per frame in blocks.
+        graph.set_sample_rate(4.0);
+        graph.set_block_size(3);
+        graph.process_block();
+        assert_eq!(graph.get_block_output(clock, OutputId(0)), Ok(&[0.5, 0.75, 1.0][..]));
+        assert_eq!(graph.context(), ProcessContext { dt: 0.25, frame: 5, sample_rate: 4.0 });
+    }
+}
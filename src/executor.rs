@@ -0,0 +1,17 @@
+use crate::{Graph, Node};
+
+/// Strategy for processing one cycle of a graph.
+///
+/// Executors process every node with `Graph::process_node` (first pass) and afterwards with
+/// `Graph::process_delayed_node` (second pass). Within a pass, nodes may be processed in any order that respects
+/// connections from nodes that do not introduce delay (e.g. the processing order of the graph).
+pub trait Executor<N: Node> {
+    /// Processes one cycle of the graph.
+    fn process(&mut self, graph: &mut Graph<N>);
+
+    /// Returns whether the executor processes cycles without heap allocation or locking (after preparing for a
+    /// topology change), required for processing on real-time threads.
+    fn realtime_safe(&self) -> bool {
+        true
+    }
+}
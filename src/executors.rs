@@ -0,0 +1,250 @@
+mod compiled;
+mod flat;
+mod parallel;
+mod pipelined;
+mod sequential;
+
+pub use compiled::Compiled;
+pub use flat::Flat;
+pub use parallel::Parallel;
+pub use pipelined::Pipelined;
+pub use sequential::Sequential;
+
+/// Conformance tests (shared by all executors).
+#[cfg(all(test, feature = "dsp", feature = "generators", feature = "math"))]
+mod tests {
+    use super::*;
+    use crate::fixtures::accumulator;
+    use crate::{
+        nodes, Connection, Executor, Graph, InputId, Node, NodeId, OutputId, ProcessContext, ProcessError, Value,
+    };
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        thread,
+        time::Duration,
+    };
+
+    type SendGraph = Graph<Box<dyn Node + Send>>;
+
+    /// Node that passes its input through and fails for negative values.
+    struct Sensor {
+        value: Value,
+    }
+    impl Node for Sensor {
+        fn delayed_processing(&self) -> bool {
+            false
+        }
+        fn get_output(&self, _id: OutputId) -> Value {
+            self.value
+        }
+        fn list_inputs(&self) -> &[InputId] {
+            &[InputId(0)]
+        }
+        fn list_outputs(&self) -> &[OutputId] {
+            &[OutputId(0)]
+        }
+        fn set_input(&mut self, _id: InputId, value: Value) {
+            self.value = value;
+        }
+        fn try_process(&mut self, _context: &ProcessContext) -> Result<(), ProcessError> {
+            if self.value < 0.0 {
+                return Err(ProcessError(1));
+            }
+            Ok(())
+        }
+    }
+
+    /// Node that passes its input through slowly, counting how many such nodes process at the same time.
+    struct Slow {
+        /// Number of nodes processing and maximum number of nodes that processed at the same time.
+        active: Arc<(AtomicUsize, AtomicUsize)>,
+        input: Value,
+        output: Value,
+    }
+    impl Node for Slow {
+        fn delayed_processing(&self) -> bool {
+            false
+        }
+        fn get_output(&self, _id: OutputId) -> Value {
+            self.output
+        }
+        fn list_inputs(&self) -> &[InputId] {
+            &[InputId(0)]
+        }
+        fn list_outputs(&self) -> &[OutputId] {
+            &[OutputId(0)]
+        }
+        fn set_input(&mut self, _id: InputId, value: Value) {
+            self.input = value;
+        }
+        fn try_process(&mut self, _context: &ProcessContext) -> Result<(), ProcessError> {
+            let (active, overlap) = &*self.active;
+            overlap.fetch_max(active.fetch_add(1, Ordering::SeqCst) + 1, Ordering::SeqCst);
+            thread::sleep(Duration::from_millis(20));
+            active.fetch_sub(1, Ordering::SeqCst);
+            self.output = self.input;
+            Ok(())
+        }
+    }
+
+    /// Executor built on the public graph api only.
+    struct Custom;
+    impl<N: Node> Executor<N> for Custom {
+        fn process(&mut self, graph: &mut Graph<N>) {
+            for index in 0..graph.processing_order().len() {
+                graph.process_node(graph.processing_order()[index]).unwrap();
+            }
+            for index in 0..graph.processing_order().len() {
+                graph.process_delayed_node(graph.processing_order()[index]).unwrap();
+            }
+        }
+    }
+
+    /// Builds graph with accumulators, fan-out, a slower clock domain and status outputs.
+    fn build() -> SendGraph {
+        let (mut graph, [_, add1, del2]) = accumulator(1.0, |node| node);
+        let add3 = graph.add_node(Box::from(nodes::Addition::new()));
+        let del4 = graph.add_node(Box::from(nodes::Delay::new()));
+        let var5 = graph.add_node(Box::from(nodes::Variable::new(-2.0)));
+        let add6 = graph.add_node(Box::from(nodes::Addition::new()));
+        let sensor7 = graph.add_node(Box::from(Sensor { value: 0.0 }));
+        let add8 = graph.add_node(Box::from(nodes::Addition::new()));
+        graph.add_connection(Connection::new(add1, OutputId(0), add3, InputId(0))).unwrap();
+        graph.add_connection(Connection::new(add3, OutputId(0), del4, InputId(0))).unwrap();
+        graph.add_connection(Connection::new(del4, OutputId(0), add3, InputId(1))).unwrap();
+        graph.add_connection(Connection::new(var5, OutputId(0), add6, InputId(0))).unwrap();
+        graph.add_connection(Connection::new(del2, OutputId(0), add6, InputId(1))).unwrap();
+        graph.add_connection(Connection::new(add6, OutputId(0), sensor7, InputId(0))).unwrap();
+        graph.add_connection(Connection::new(sensor7, OutputId::STATUS, add8, InputId(0))).unwrap();
+        graph.add_connection(Connection::new(add3, OutputId(0), add8, InputId(1))).unwrap();
+        graph.set_rate_divisor(add3, 2).unwrap();
+        graph.set_rate_divisor(del4, 2).unwrap();
+        graph
+    }
+
+    /// Returns all outputs (including status outputs) of all nodes, ordered by node id.
+    fn outputs(graph: &SendGraph) -> Vec<Value> {
+        let mut ids: Vec<NodeId> = graph.iter_nodes().map(|(&id, _)| id).collect();
+        ids.sort();
+        let mut values = Vec::new();
+        for id in ids {
+            for &output in graph.get_node(id).unwrap().list_outputs().iter().chain([OutputId::STATUS].iter()) {
+                values.push(graph.get_output(id, output).unwrap());
+            }
+        }
+        values
+    }
+
+    /// Checks that an executor processes graphs like the sequential reference (also after topology changes).
+    fn conformance<E: Executor<Box<dyn Node + Send>> + Send + 'static>(executor: E) {
+        let mut reference = build();
+        let mut graph = build();
+        graph.set_executor(executor);
+        for cycle in 0..12 {
+            if cycle == 6 {
+                for graph in [&mut reference, &mut graph] {
+                    let add9 = graph.add_node(Box::from(nodes::Addition::new()));
+                    graph.add_connection(Connection::new(add9, OutputId(0), NodeId(0), InputId(0))).unwrap();
+                    graph.add_connection(Connection::new(NodeId(8), OutputId(0), add9, InputId(0))).unwrap_err();
+                    graph.add_connection(Connection::new(NodeId(4), OutputId(0), add9, InputId(0))).unwrap();
+                    graph.remove_connection(Connection::new(NodeId(1), OutputId(0), NodeId(3), InputId(0))).unwrap();
+                }
+            }
+            reference.process();
+            graph.process();
+            assert_eq!(outputs(&graph), outputs(&reference), "cycle {}", cycle);
+        }
+    }
+
+    #[test]
+    fn compiled_conformance() {
+        conformance(Compiled::new());
+    }
+
+    #[test]
+    fn flat_conformance() {
+        conformance(Flat::new());
+    }
+
+    #[test]
+    fn custom_conformance() {
+        conformance(Custom);
+    }
+
+    #[test]
+    fn parallel_conformance() {
+        conformance(Parallel::new(1));
+        conformance(Parallel::new(4));
+    }
+
+    #[test]
+    fn parallel_components() {
+        // Disconnected accumulators feeding sensors (failing for some), in differently clocked gadgets.
+        let build = || {
+            let mut graph: SendGraph = Graph::new();
+            for gadget in 0..8 {
+                let var = graph.add_node(Box::from(nodes::Variable::new(gadget as Value - 3.0)));
+                let add = graph.add_node(Box::from(nodes::Addition::new()));
+                let del = graph.add_node(Box::from(nodes::Delay::new()));
+                let sensor = graph.add_node(Box::from(Sensor { value: 0.0 }));
+                graph.add_connection(Connection::new(var, OutputId(0), add, InputId(0))).unwrap();
+                graph.add_connection(Connection::new(add, OutputId(0), del, InputId(0))).unwrap();
+                graph.add_connection(Connection::new(del, OutputId(0), add, InputId(1))).unwrap();
+                graph.add_connection(Connection::new(add, OutputId(0), sensor, InputId(0))).unwrap();
+                graph.set_rate_divisor(add, gadget % 3 + 1).unwrap();
+            }
+            graph
+        };
+        let mut reference = build();
+        let mut graph = build();
+        graph.set_executor(Parallel::new(4));
+        assert_eq!(graph.components().len(), 8);
+        for cycle in 0..6 {
+            reference.process();
+            graph.process();
+            assert_eq!(outputs(&graph), outputs(&reference), "cycle {}", cycle);
+        }
+    }
+
+    #[test]
+    fn pipelined_conformance() {
+        conformance(Pipelined::new(1));
+    }
+
+    #[test]
+    fn pipelined_stages() {
+        // Variable feeding two slow nodes in a row, split into two stages (overlapping unless a budget is set).
+        for budget in [false, true] {
+            let active = Arc::new((AtomicUsize::new(0), AtomicUsize::new(0)));
+            let mut graph: SendGraph = Graph::new();
+            let var0 = graph.add_node(Box::from(nodes::Variable::new(1.0)));
+            let slow1 = graph.add_node(Box::from(Slow { active: active.clone(), input: 0.0, output: 0.0 }));
+            let slow2 = graph.add_node(Box::from(Slow { active: active.clone(), input: 0.0, output: 0.0 }));
+            let slow3 = graph.add_node(Box::from(Slow { active: active.clone(), input: 0.0, output: 0.0 }));
+            graph.add_connection(Connection::new(var0, OutputId(0), slow1, InputId(0))).unwrap();
+            graph.add_connection(Connection::new(slow1, OutputId(0), slow2, InputId(0))).unwrap();
+            graph.add_connection(Connection::new(slow2, OutputId(0), slow3, InputId(0))).unwrap();
+            if budget {
+                graph.set_cycle_budget(Duration::from_secs(60), 0);
+            }
+            graph.set_executor(Pipelined::new(2));
+
+            // The second stage (second and third slow node) lags one cycle behind the first stage.
+            let mut outputs = Vec::new();
+            for _ in 0..3 {
+                graph.process();
+                outputs.push([slow1, slow2, slow3].map(|id| graph.get_output(id, OutputId(0)).unwrap()));
+            }
+            assert_eq!(outputs, [[1.0, 0.0, 0.0], [1.0, 1.0, 1.0], [1.0, 1.0, 1.0]]);
+            assert_eq!(active.1.load(Ordering::SeqCst), if budget { 1 } else { 2 });
+        }
+    }
+
+    #[test]
+    fn sequential_conformance() {
+        conformance(Sequential);
+    }
+}
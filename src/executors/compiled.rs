@@ -0,0 +1,45 @@
+use crate::{Connection, Executor, Graph, Node, NodeId};
+
+/// Executor that compiles processing order and input connections of every node into a plan (rebuilt when the
+/// topology changes), so connections are not searched per node.
+#[derive(Default)]
+pub struct Compiled {
+    /// Nodes in processing order with their input connections.
+    plan: Vec<(NodeId, Vec<Connection>)>,
+
+    /// Topology version of the graph the plan was compiled for.
+    version: Option<u64>,
+}
+impl Compiled {
+    /// Creates new compiled executor.
+    pub fn new() -> Self {
+        Compiled { plan: Vec::new(), version: None }
+    }
+
+    /// Compiles plan for the current topology of a graph.
+    fn compile<N: Node>(&mut self, graph: &Graph<N>) {
+        self.plan = graph
+            .processing_order()
+            .iter()
+            .map(|&node| (node, graph.connections().iter().filter(|c| c.target_node == node).copied().collect()))
+            .collect();
+        self.version = Some(graph.topology_version());
+    }
+}
+impl<N: Node> Executor<N> for Compiled {
+    fn process(&mut self, graph: &mut Graph<N>) {
+        if self.version != Some(graph.topology_version()) {
+            // Compiling is allowed to allocate (once per topology change).
+            #[cfg(any(test, feature = "rt-check"))]
+            crate::rt_check::permit(|| self.compile(graph));
+            #[cfg(not(any(test, feature = "rt-check")))]
+            self.compile(graph);
+        }
+        for (node, inputs) in self.plan.iter() {
+            graph.step(*node, Some(inputs));
+        }
+        for (node, _) in self.plan.iter() {
+            graph.step_delayed(*node);
+        }
+    }
+}
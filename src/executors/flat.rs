@@ -0,0 +1,118 @@
+use crate::{Connection, Executor, Graph, InputId, Node, NodeId, OutputId, Value};
+use std::collections::HashMap;
+
+/// Compiled step of a node.
+struct Step {
+    /// Whether the node introduces delay.
+    delayed: bool,
+
+    /// Processed node.
+    node: NodeId,
+
+    /// Inputs populated from value slots.
+    reads: Vec<(usize, InputId)>,
+
+    /// Multichannel input connections (transferred from source nodes).
+    transfers: Vec<Connection>,
+
+    /// Connected outputs recorded into value slots.
+    writes: Vec<(OutputId, usize)>,
+}
+
+/// Executor that keeps connected output values in a flat array of slots (compiled like `Compiled`), so inputs are
+/// populated from contiguous memory instead of looking up source nodes.
+///
+/// Outputs are recorded into their slots once per cycle (outputs of delayed nodes at the start of the cycle).
+/// Multichannel connections are transferred from source nodes.
+#[derive(Default)]
+pub struct Flat {
+    /// Nodes in processing order with their slot accesses.
+    plan: Vec<Step>,
+
+    /// Values of connected outputs.
+    values: Vec<Value>,
+
+    /// Topology version of the graph the plan was compiled for.
+    version: Option<u64>,
+}
+impl Flat {
+    /// Creates new flat executor.
+    pub fn new() -> Self {
+        Flat { plan: Vec::new(), values: Vec::new(), version: None }
+    }
+
+    /// Compiles plan and slots for the current topology of a graph.
+    fn compile<N: Node>(&mut self, graph: &Graph<N>) {
+        let mut slots: Vec<(NodeId, OutputId)> = Vec::new();
+        let mut indices: HashMap<(NodeId, OutputId), usize> = HashMap::new();
+        let mut slot = |source: (NodeId, OutputId)| {
+            *indices.entry(source).or_insert_with(|| {
+                slots.push(source);
+                slots.len() - 1
+            })
+        };
+        let mut incoming: HashMap<NodeId, Vec<Connection>> = HashMap::new();
+        for &c in graph.connections() {
+            incoming.entry(c.target_node).or_default().push(c);
+        }
+        let mut plan: Vec<Step> = Vec::with_capacity(graph.processing_order().len());
+        for &node in graph.processing_order() {
+            let (mut reads, mut transfers) = (Vec::new(), Vec::new());
+            for &c in incoming.get(&node).into_iter().flatten() {
+                // Connections from nodes that do not exist are transferred (which reports them).
+                match (graph.get_node(c.source_node), c.source_output) {
+                    (Ok(_), OutputId::STATUS) => reads.push((slot((c.source_node, c.source_output)), c.target_input)),
+                    (Ok(source), output) if source.output_channels(output) == 1 => {
+                        reads.push((slot((c.source_node, c.source_output)), c.target_input))
+                    }
+                    _ => transfers.push(c),
+                }
+            }
+            let delayed = graph.get_node(node).is_ok_and(|node| node.delayed_processing());
+            plan.push(Step { delayed, node, reads, transfers, writes: Vec::new() });
+        }
+        let steps: HashMap<NodeId, usize> = plan.iter().enumerate().map(|(index, step)| (step.node, index)).collect();
+        for (index, &(node, output)) in slots.iter().enumerate() {
+            plan[steps[&node]].writes.push((output, index));
+        }
+        self.values = slots.iter().map(|&(node, output)| graph.output_value(node, output)).collect();
+        self.plan = plan;
+        self.version = Some(graph.topology_version());
+    }
+}
+impl<N: Node> Executor<N> for Flat {
+    fn process(&mut self, graph: &mut Graph<N>) {
+        if self.version != Some(graph.topology_version()) {
+            // Compiling is allowed to allocate (once per topology change).
+            #[cfg(any(test, feature = "rt-check"))]
+            crate::rt_check::permit(|| self.compile(graph));
+            #[cfg(not(any(test, feature = "rt-check")))]
+            self.compile(graph);
+        }
+        for step in self.plan.iter().filter(|step| step.delayed) {
+            for &(output, slot) in step.writes.iter() {
+                self.values[slot] = graph.output_value(step.node, output);
+            }
+        }
+        for step in self.plan.iter() {
+            let values = &self.values;
+            graph.step_with(step.node, |graph| {
+                for &(slot, input) in step.reads.iter() {
+                    graph.set_node_input(step.node, input, values[slot]);
+                }
+                for &connection in step.transfers.iter() {
+                    graph.transfer(connection)?;
+                }
+                Ok(())
+            });
+            if !step.delayed {
+                for &(output, slot) in step.writes.iter() {
+                    self.values[slot] = graph.output_value(step.node, output);
+                }
+            }
+        }
+        for step in self.plan.iter() {
+            graph.step_delayed(step.node);
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use crate::graph::Component;
+use crate::{Executor, Graph, Node, NodeId};
+
+/// Executor that processes independent nodes (nodes at the same depth of the processing order) on multiple threads.
+///
+/// Threads are spawned per depth level, which pays off for graphs with expensive nodes only (and is not real-time
+/// safe). Graphs with at least as many disconnected components as threads are processed component by component
+/// instead (each thread processing whole components), unless bypassed nodes, events, cycle budgets, deadlines,
+/// checks, clamps or status outputs are involved.
+pub struct Parallel {
+    /// Weakly connected components of the graph.
+    components: Vec<Component>,
+
+    /// Nodes grouped by depth level.
+    levels: Vec<Vec<NodeId>>,
+
+    /// Maximum number of threads per level.
+    threads: usize,
+
+    /// Topology version of the graph the levels were determined for.
+    version: Option<u64>,
+}
+impl Parallel {
+    /// Creates new parallel executor using up to given number of threads.
+    pub fn new(threads: usize) -> Self {
+        Parallel { components: Vec::new(), levels: Vec::new(), threads, version: None }
+    }
+}
+impl<N: Node + Send> Executor<N> for Parallel {
+    fn process(&mut self, graph: &mut Graph<N>) {
+        if self.version != Some(graph.topology_version()) {
+            self.components = graph.calc_components();
+            self.levels = graph.calc_levels();
+            self.version = Some(graph.topology_version());
+        }
+        if self.threads > 1 && self.components.len() >= self.threads && graph.has_independent_components() {
+            graph.process_components(&self.components, self.threads);
+        } else {
+            graph.process_levels(&self.levels, self.threads);
+        }
+    }
+
+    fn realtime_safe(&self) -> bool {
+        false
+    }
+}
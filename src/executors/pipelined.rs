@@ -0,0 +1,42 @@
+use crate::graph::Component;
+use crate::{Connection, Executor, Graph, Node};
+
+/// Executor that splits the processing order into stages of consecutive nodes, processed concurrently on one thread
+/// per stage like the stages of a pipeline.
+///
+/// Connections between stages carry the outputs of the previous cycle, so every stage boundary adds one cycle of
+/// latency (nodes that introduce delay keep their delay). Stages are processed one after another with the same
+/// latency if bypassed nodes, events, cycle budgets, deadlines, checks, clamps or status outputs are involved. Threads
+/// are spawned per cycle, which pays off for graphs with expensive nodes only (and is not real-time safe).
+pub struct Pipelined {
+    /// Connections between stages.
+    links: Vec<Connection>,
+
+    /// Maximum number of stages.
+    stage_count: usize,
+
+    /// Nodes grouped by stage.
+    stages: Vec<Component>,
+
+    /// Topology version of the graph the stages were determined for.
+    version: Option<u64>,
+}
+impl Pipelined {
+    /// Creates new pipelined executor splitting graphs into up to given number of stages (of equal node counts).
+    pub fn new(stages: usize) -> Self {
+        Pipelined { links: Vec::new(), stage_count: stages, stages: Vec::new(), version: None }
+    }
+}
+impl<N: Node + Send> Executor<N> for Pipelined {
+    fn process(&mut self, graph: &mut Graph<N>) {
+        if self.version != Some(graph.topology_version()) {
+            (self.stages, self.links) = graph.calc_stages(self.stage_count);
+            self.version = Some(graph.topology_version());
+        }
+        graph.process_stages(&self.stages, &self.links);
+    }
+
+    fn realtime_safe(&self) -> bool {
+        false
+    }
+}
@@ -0,0 +1,16 @@
+use crate::{Executor, Graph, Node};
+
+/// Reference executor that processes nodes one after another in processing order.
+pub struct Sequential;
+impl<N: Node> Executor<N> for Sequential {
+    fn process(&mut self, graph: &mut Graph<N>) {
+        for index in 0..graph.processing_order().len() {
+            let node = graph.processing_order()[index];
+            graph.step(node, None);
+        }
+        for index in 0..graph.processing_order().len() {
+            let node = graph.processing_order()[index];
+            graph.step_delayed(node);
+        }
+    }
+}
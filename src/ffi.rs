@@ -0,0 +1,404 @@
+use crate::{Connection, Graph, GraphError, InputId, Node, NodeId, NodeRegistry, OutputId, Value};
+use std::{
+    ffi::CStr,
+    os::raw::c_char,
+    panic::{self, AssertUnwindSafe},
+    ptr, slice,
+};
+
+/// Graph of builtin nodes for C hosts (opaque handle). Values are `double` (`float` with the `f32` feature).
+pub struct FlowingGraph {
+    /// Wrapped graph.
+    graph: Graph<Box<dyn Node>>,
+
+    /// Registry of builtin node types.
+    registry: NodeRegistry<Box<dyn Node>>,
+}
+
+/// Result status of C functions: `InvalidArgument` for null pointers or invalid strings, `Panicked` if the graph
+/// panicked (free it afterwards), `ProcessFailed` if a node reported an error while processing and the kind of the
+/// graph error otherwise.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FlowingStatus {
+    Ok = 0,
+    InvalidArgument,
+    Panicked,
+    ProcessFailed,
+    BackendUnavailable,
+    BufferSizeMismatch,
+    ChannelMismatch,
+    ConnectionNotExists,
+    CycleWithoutDelay,
+    ExternalInputNotExists,
+    ExternalOutputNotExists,
+    InputAlreadyConnected,
+    InputNotExists,
+    InvalidFormat,
+    InvalidRateDivisor,
+    LabelAlreadyUsed,
+    LabelNotExists,
+    NodeAlreadyExists,
+    NodeNotExists,
+    NodeTypeNotExists,
+    OutputNotExists,
+    ParameterNotExists,
+    PortNotExists,
+    ProbeNotExists,
+    RecordingNotExists,
+    Rejected,
+    TypeMismatch,
+    UnsupportedNode,
+    WrongNodeType,
+}
+impl From<GraphError> for FlowingStatus {
+    fn from(error: GraphError) -> Self {
+        match error {
+            GraphError::BackendUnavailable => FlowingStatus::BackendUnavailable,
+            GraphError::BufferSizeMismatch(..) => FlowingStatus::BufferSizeMismatch,
+            GraphError::ChannelMismatch(..) => FlowingStatus::ChannelMismatch,
+            GraphError::ConnectionNotExists(..) => FlowingStatus::ConnectionNotExists,
+            GraphError::CycleWithoutDelay => FlowingStatus::CycleWithoutDelay,
+            GraphError::ExternalInputNotExists(..) => FlowingStatus::ExternalInputNotExists,
+            GraphError::ExternalOutputNotExists(..) => FlowingStatus::ExternalOutputNotExists,
+            GraphError::InputAlreadyConnected(..) => FlowingStatus::InputAlreadyConnected,
+            GraphError::InputNotExists(..) => FlowingStatus::InputNotExists,
+            GraphError::InvalidFormat(..) => FlowingStatus::InvalidFormat,
+            GraphError::InvalidRateDivisor(..) => FlowingStatus::InvalidRateDivisor,
+            GraphError::LabelAlreadyUsed(..) => FlowingStatus::LabelAlreadyUsed,
+            GraphError::LabelNotExists(..) => FlowingStatus::LabelNotExists,
+            GraphError::NodeAlreadyExists(..) => FlowingStatus::NodeAlreadyExists,
+            GraphError::NodeNotExists(..) => FlowingStatus::NodeNotExists,
+            GraphError::NodeTypeNotExists(..) => FlowingStatus::NodeTypeNotExists,
+            GraphError::OutputNotExists(..) => FlowingStatus::OutputNotExists,
+            GraphError::ParameterNotExists(..) => FlowingStatus::ParameterNotExists,
+            GraphError::PortNotExists(..) => FlowingStatus::PortNotExists,
+            GraphError::ProbeNotExists(..) => FlowingStatus::ProbeNotExists,
+            GraphError::RecordingNotExists(..) => FlowingStatus::RecordingNotExists,
+            GraphError::Rejected(..) => FlowingStatus::Rejected,
+            GraphError::TypeMismatch(..) => FlowingStatus::TypeMismatch,
+            GraphError::UnsupportedNode(..) => FlowingStatus::UnsupportedNode,
+            GraphError::WrongNodeType(..) => FlowingStatus::WrongNodeType,
+        }
+    }
+}
+
+/// Runs body of a C function, catching panics (which must not unwind into the host).
+fn guard(body: impl FnOnce() -> FlowingStatus) -> FlowingStatus {
+    panic::catch_unwind(AssertUnwindSafe(body)).unwrap_or(FlowingStatus::Panicked)
+}
+
+/// Converts result to status.
+fn status(result: Result<(), GraphError>) -> FlowingStatus {
+    match result {
+        Ok(()) => FlowingStatus::Ok,
+        Err(error) => error.into(),
+    }
+}
+
+/// Adds a connection.
+///
+/// # Safety
+/// `graph` must be a valid pointer returned by `flowing_graph_new`.
+#[no_mangle]
+pub unsafe extern "C" fn flowing_graph_add_connection(
+    graph: *mut FlowingGraph,
+    source_node: u32,
+    source_output: u32,
+    target_node: u32,
+    target_input: u32,
+) -> FlowingStatus {
+    guard(|| {
+        let Some(graph) = graph.as_mut() else {
+            return FlowingStatus::InvalidArgument;
+        };
+        let connection =
+            Connection::new(NodeId(source_node), OutputId(source_output), NodeId(target_node), InputId(target_input));
+        status(graph.graph.add_connection(connection).map(|_| ()))
+    })
+}
+
+/// Adds a node of a builtin type (see `NodeRegistry::builtin`) with parameters and writes its id.
+///
+/// # Safety
+/// `graph` must be a valid pointer returned by `flowing_graph_new`, `type_name` a valid null-terminated string,
+/// `params` valid for `params_len` values (or null if `params_len` is 0) and `id` valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn flowing_graph_add_node(
+    graph: *mut FlowingGraph,
+    type_name: *const c_char,
+    params: *const Value,
+    params_len: usize,
+    id: *mut u32,
+) -> FlowingStatus {
+    guard(|| {
+        let (Some(graph), false, false) = (graph.as_mut(), type_name.is_null(), id.is_null()) else {
+            return FlowingStatus::InvalidArgument;
+        };
+        let Ok(type_name) = CStr::from_ptr(type_name).to_str() else {
+            return FlowingStatus::InvalidArgument;
+        };
+        let params = match (params.is_null(), params_len) {
+            (_, 0) => &[][..],
+            (false, len) => slice::from_raw_parts(params, len),
+            (true, _) => return FlowingStatus::InvalidArgument,
+        };
+        match graph.registry.create(type_name, params) {
+            Ok(node) => {
+                *id = graph.graph.add_node(node).0;
+                FlowingStatus::Ok
+            }
+            Err(error) => error.into(),
+        }
+    })
+}
+
+/// Destroys a graph.
+///
+/// # Safety
+/// `graph` must be a pointer returned by `flowing_graph_new` (or null) and must not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn flowing_graph_free(graph: *mut FlowingGraph) {
+    if !graph.is_null() {
+        let _ = panic::catch_unwind(AssertUnwindSafe(|| drop(Box::from_raw(graph))));
+    }
+}
+
+/// Writes value of a node output.
+///
+/// # Safety
+/// `graph` must be a valid pointer returned by `flowing_graph_new` and `value` valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn flowing_graph_get_output(
+    graph: *const FlowingGraph,
+    node: u32,
+    output: u32,
+    value: *mut Value,
+) -> FlowingStatus {
+    guard(|| {
+        let (Some(graph), false) = (graph.as_ref(), value.is_null()) else {
+            return FlowingStatus::InvalidArgument;
+        };
+        match graph.graph.get_output(NodeId(node), OutputId(output)) {
+            Ok(output) => {
+                *value = output;
+                FlowingStatus::Ok
+            }
+            Err(error) => error.into(),
+        }
+    })
+}
+
+/// Creates new empty graph (destroy with `flowing_graph_free`), returns null if creation panicked.
+#[no_mangle]
+pub extern "C" fn flowing_graph_new() -> *mut FlowingGraph {
+    panic::catch_unwind(|| {
+        Box::into_raw(Box::new(FlowingGraph { graph: Graph::new(), registry: NodeRegistry::builtin() }))
+    })
+    .unwrap_or(ptr::null_mut())
+}
+
+/// Processes nodes in graph for given number of cycles, returns `ProcessFailed` if a node reported an error in any of
+/// them (see `Graph::get_status`).
+///
+/// # Safety
+/// `graph` must be a valid pointer returned by `flowing_graph_new`.
+#[no_mangle]
+pub unsafe extern "C" fn flowing_graph_process(graph: *mut FlowingGraph, count: usize) -> FlowingStatus {
+    guard(|| {
+        let Some(graph) = graph.as_mut() else {
+            return FlowingStatus::InvalidArgument;
+        };
+        let mut result = FlowingStatus::Ok;
+        for _ in 0..count {
+            graph.graph.process();
+            if graph.graph.iter_nodes().any(|(&id, _)| graph.graph.get_status(id) != Ok(0)) {
+                result = FlowingStatus::ProcessFailed;
+            }
+        }
+        result
+    })
+}
+
+/// Removes a connection.
+///
+/// # Safety
+/// `graph` must be a valid pointer returned by `flowing_graph_new`.
+#[no_mangle]
+pub unsafe extern "C" fn flowing_graph_remove_connection(
+    graph: *mut FlowingGraph,
+    source_node: u32,
+    source_output: u32,
+    target_node: u32,
+    target_input: u32,
+) -> FlowingStatus {
+    guard(|| {
+        let Some(graph) = graph.as_mut() else {
+            return FlowingStatus::InvalidArgument;
+        };
+        let connection =
+            Connection::new(NodeId(source_node), OutputId(source_output), NodeId(target_node), InputId(target_input));
+        status(graph.graph.remove_connection(connection).map(|_| ()))
+    })
+}
+
+/// Removes a node (and its connections).
+///
+/// # Safety
+/// `graph` must be a valid pointer returned by `flowing_graph_new`.
+#[no_mangle]
+pub unsafe extern "C" fn flowing_graph_remove_node(graph: *mut FlowingGraph, node: u32) -> FlowingStatus {
+    guard(|| {
+        let Some(graph) = graph.as_mut() else {
+            return FlowingStatus::InvalidArgument;
+        };
+        status(graph.graph.remove_node(NodeId(node)).map(|_| ()))
+    })
+}
+
+/// Sets value of a node input.
+///
+/// # Safety
+/// `graph` must be a valid pointer returned by `flowing_graph_new`.
+#[no_mangle]
+pub unsafe extern "C" fn flowing_graph_set_input(
+    graph: *mut FlowingGraph,
+    node: u32,
+    input: u32,
+    value: Value,
+) -> FlowingStatus {
+    guard(|| {
+        let Some(graph) = graph.as_mut() else {
+            return FlowingStatus::InvalidArgument;
+        };
+        status(graph.graph.set_input(NodeId(node), InputId(input), value))
+    })
+}
+
+/// Sets value of a node parameter by name.
+///
+/// # Safety
+/// `graph` must be a valid pointer returned by `flowing_graph_new` and `name` a valid null-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn flowing_graph_set_parameter(
+    graph: *mut FlowingGraph,
+    node: u32,
+    name: *const c_char,
+    value: Value,
+) -> FlowingStatus {
+    guard(|| {
+        let (Some(graph), false) = (graph.as_mut(), name.is_null()) else {
+            return FlowingStatus::InvalidArgument;
+        };
+        let Ok(name) = CStr::from_ptr(name).to_str() else {
+            return FlowingStatus::InvalidArgument;
+        };
+        status(graph.graph.set_parameter(NodeId(node), name, value))
+    })
+}
+
+/// Unit tests.
+#[cfg(all(test, feature = "dsp", feature = "generators", feature = "math"))]
+mod tests {
+    use super::*;
+    use crate::{ProcessContext, ProcessError};
+
+    /// Node that fails for negative inputs and panics for inputs above one.
+    struct Checked {
+        value: Value,
+    }
+    impl Node for Checked {
+        fn delayed_processing(&self) -> bool {
+            false
+        }
+        fn get_output(&self, _id: OutputId) -> Value {
+            self.value
+        }
+        fn list_inputs(&self) -> &[InputId] {
+            &[InputId(0)]
+        }
+        fn list_outputs(&self) -> &[OutputId] {
+            &[OutputId(0)]
+        }
+        fn set_input(&mut self, _id: InputId, value: Value) {
+            self.value = value;
+        }
+        fn try_process(&mut self, _context: &ProcessContext) -> Result<(), ProcessError> {
+            assert!(self.value <= 1.0, "Value {} is out of range.", self.value);
+            if self.value < 0.0 {
+                return Err(ProcessError(1));
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn builds_and_processes() {
+        unsafe {
+            let graph = flowing_graph_new();
+            let (mut var0, mut add1, mut del2) = (0, 0, 0);
+            let value = 1.0;
+            assert_eq!(flowing_graph_add_node(graph, c"variable".as_ptr(), &value, 1, &mut var0), FlowingStatus::Ok);
+            assert_eq!(
+                flowing_graph_add_node(graph, c"addition".as_ptr(), ptr::null(), 0, &mut add1),
+                FlowingStatus::Ok
+            );
+            assert_eq!(flowing_graph_add_node(graph, c"delay".as_ptr(), ptr::null(), 0, &mut del2), FlowingStatus::Ok);
+            assert_eq!(flowing_graph_add_connection(graph, var0, 0, add1, 0), FlowingStatus::Ok);
+            assert_eq!(flowing_graph_add_connection(graph, add1, 0, del2, 0), FlowingStatus::Ok);
+            assert_eq!(flowing_graph_add_connection(graph, del2, 0, add1, 1), FlowingStatus::Ok);
+            assert_eq!(flowing_graph_set_input(graph, var0, 0, 2.0), FlowingStatus::Ok);
+            assert_eq!(flowing_graph_process(graph, 2), FlowingStatus::Ok);
+
+            let mut output = 0.0;
+            assert_eq!(flowing_graph_get_output(graph, add1, 0, &mut output), FlowingStatus::Ok);
+            assert_eq!(output, 4.0);
+            assert_eq!(flowing_graph_remove_connection(graph, del2, 0, add1, 1), FlowingStatus::Ok);
+            assert_eq!(flowing_graph_remove_node(graph, del2), FlowingStatus::Ok);
+            flowing_graph_free(graph);
+        }
+    }
+
+    #[test]
+    fn reports_errors() {
+        unsafe {
+            let graph = flowing_graph_new();
+            let mut id = 0;
+            let mut output = 0.0;
+            assert_eq!(
+                flowing_graph_add_node(graph, c"gain".as_ptr(), ptr::null(), 0, &mut id),
+                FlowingStatus::NodeTypeNotExists
+            );
+            assert_eq!(
+                flowing_graph_add_node(graph, c"delay".as_ptr(), ptr::null(), 1, &mut id),
+                FlowingStatus::InvalidArgument
+            );
+            assert_eq!(flowing_graph_set_input(graph, 0, 0, 1.0), FlowingStatus::NodeNotExists);
+            assert_eq!(flowing_graph_get_output(graph, 0, 0, &mut output), FlowingStatus::NodeNotExists);
+            assert_eq!(flowing_graph_add_connection(graph, 0, 0, 0, 0), FlowingStatus::NodeNotExists);
+            assert_eq!(flowing_graph_process(ptr::null_mut(), 1), FlowingStatus::InvalidArgument);
+            assert_eq!(flowing_graph_set_parameter(graph, 0, ptr::null(), 1.0), FlowingStatus::InvalidArgument);
+            assert_eq!(flowing_graph_add_node(graph, c"delay".as_ptr(), ptr::null(), 0, &mut id), FlowingStatus::Ok);
+            assert_eq!(
+                flowing_graph_set_parameter(graph, id, c"length".as_ptr(), 2.0),
+                FlowingStatus::ParameterNotExists
+            );
+            flowing_graph_free(graph);
+            flowing_graph_free(ptr::null_mut());
+        }
+    }
+
+    #[test]
+    fn reports_process_errors_and_panics() {
+        unsafe {
+            let graph = flowing_graph_new();
+            let id = (*graph).graph.add_node(Box::new(Checked { value: 0.0 })).0;
+            assert_eq!(flowing_graph_process(graph, 2), FlowingStatus::Ok);
+            assert_eq!(flowing_graph_set_input(graph, id, 0, -1.0), FlowingStatus::Ok);
+            assert_eq!(flowing_graph_process(graph, 2), FlowingStatus::ProcessFailed);
+            assert_eq!(flowing_graph_set_input(graph, id, 0, 2.0), FlowingStatus::Ok);
+            assert_eq!(flowing_graph_process(graph, 1), FlowingStatus::Panicked);
+            flowing_graph_free(graph);
+        }
+    }
+}
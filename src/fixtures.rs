@@ -0,0 +1,16 @@
+//! Graphs shared by unit tests.
+
+use crate::{nodes, Connection, Graph, InputId, Node, NodeId, OutputId, Value};
+
+/// Builds accumulator whose addition sums the value of the variable and its own output delayed by one cycle, with
+/// nodes converted by `boxed`, and returns the graph with the ids of the variable, the addition and the delay.
+pub(crate) fn accumulator<N: Node>(value: Value, boxed: impl Fn(Box<dyn Node + Send>) -> N) -> (Graph<N>, [NodeId; 3]) {
+    let mut graph = Graph::new();
+    let var0 = graph.add_node(boxed(Box::new(nodes::Variable::new(value))));
+    let add1 = graph.add_node(boxed(Box::new(nodes::Addition::new())));
+    let del2 = graph.add_node(boxed(Box::new(nodes::Delay::new())));
+    graph.add_connection(Connection::new(var0, OutputId(0), add1, InputId(0))).unwrap();
+    graph.add_connection(Connection::new(add1, OutputId(0), del2, InputId(0))).unwrap();
+    graph.add_connection(Connection::new(del2, OutputId(0), add1, InputId(1))).unwrap();
+    (graph, [var0, add1, del2])
+}
@@ -1,118 +1,457 @@
-use crate::{Connection, InputId, Node, NodeId, OutputId};
+#[cfg(any(test, feature = "rt-check"))]
+use crate::rt_check;
+use crate::{
+    executors, param::SharedParam, Connection, Executor, InputId, Node, NodeId, OutputId, ParamHandle, ProcessContext,
+    ProcessError, Run, Value,
+};
 use std::{
-    collections::{HashMap, LinkedList},
-    fmt,
+    cmp::Reverse,
+    collections::{BTreeMap, BinaryHeap, HashMap, HashSet},
+    fmt, mem,
+    sync::Arc,
+    time::{Duration, Instant},
 };
 
+mod automation;
+#[cfg(feature = "binary")]
+mod binary;
+mod bypass;
+mod clamp;
+mod clock;
+mod commands;
+mod conversions;
+mod deadline;
+mod denormals;
+mod dot;
+mod edits;
+mod events;
+mod finite;
+#[cfg(feature = "gpu")]
+mod gpu;
+#[cfg(feature = "graphml")]
+mod graphml;
+#[cfg(feature = "petgraph")]
+mod interop;
+#[cfg(feature = "jit")]
+mod jit;
+#[cfg(feature = "json")]
+mod json;
+#[cfg(any(
+    feature = "autodiff",
+    feature = "fixed",
+    feature = "gpu",
+    feature = "interval",
+    feature = "jit",
+    feature = "uncertainty"
+))]
+mod kernels;
+mod lazy;
+mod memo;
+mod meta;
+mod migration;
+#[cfg(all(feature = "generators", feature = "math"))]
+mod optimize;
+mod parallel;
+mod parameters;
+#[cfg(any(
+    feature = "binary",
+    feature = "graphml",
+    feature = "json",
+    feature = "ron",
+    feature = "toml",
+    feature = "yaml"
+))]
+mod patch;
+mod ports;
+mod priority;
+mod probe;
+mod profile;
+mod program;
+mod recording;
+#[cfg(feature = "ron")]
+mod ron;
+#[cfg(any(feature = "autodiff", feature = "fixed", feature = "interval", feature = "uncertainty"))]
+mod scalar;
+#[cfg(feature = "serde")]
+pub(crate) mod serialization;
+mod stats;
+mod structure;
+mod subscription;
+mod tags;
+mod terminals;
+#[cfg(feature = "toml")]
+mod toml;
+mod traversal;
+#[cfg(feature = "yaml")]
+mod yaml;
+
+pub use commands::GraphCommand;
+pub use deadline::{DeadlineAction, DeadlineMiss};
+pub use edits::{EditQueue, GraphEdit};
+pub use finite::NonFiniteValue;
+#[cfg(feature = "gpu")]
+pub use gpu::GpuBatch;
+#[cfg(feature = "jit")]
+pub use jit::JitProgram;
+#[cfg(all(feature = "generators", feature = "math"))]
+pub use optimize::OptimizationReport;
+pub use probe::ProbeId;
+pub use profile::NodeProfile;
+pub use program::Program;
+#[cfg(any(feature = "autodiff", feature = "fixed", feature = "interval", feature = "uncertainty"))]
+pub use scalar::ScalarProgram;
+pub use stats::GraphStats;
+pub use subscription::Overflow;
+pub use traversal::Visitor;
+
+pub(crate) use parallel::Component;
+
+/// Routing of a connected input in block processing.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum BlockRoute {
+    /// Source output buffer is copied to the input buffer.
+    Copied(NodeId, OutputId),
+
+    /// Source output buffer is lent to the target node directly.
+    Shared(NodeId, OutputId),
+}
+
+/// Application-defined validation of new connections (returning a rejection reason).
+type ConnectionValidator<N> = Box<dyn Fn(&Graph<N>, Connection) -> Result<(), String> + Send>;
+
 /// Processing graph consisting of nodes and connections.
 pub struct Graph<N: Node> {
+    /// Running automations of node ports.
+    automations: Vec<automation::Automation>,
+
+    /// Block processing routes of connected inputs, indexed by node and input.
+    block_routes: HashMap<(NodeId, InputId), BlockRoute>,
+
+    /// Number of frames per block in block processing.
+    block_size: usize,
+
+    /// Output values of bypassed nodes (ordered like their outputs), indexed by node id.
+    bypassed: HashMap<NodeId, Vec<Value>>,
+
+    /// Application-defined validation of new connections.
+    connection_validator: Option<ConnectionValidator<N>>,
+
     /// Connections in graph.
     connections: Vec<Connection>,
 
+    /// Timing information for processing (frame is advanced after each cycle or block).
+    context: ProcessContext,
+
+    /// Added connections with lossy value conversions that were not taken yet.
+    conversion_warnings: Vec<Connection>,
+
+    /// Number of started cycles and blocks, identifying the current one.
+    cycle: u64,
+
+    /// Processing time per cycle after which nodes below the minimum priority are skipped.
+    cycle_budget: Option<(Duration, i32)>,
+
+    /// Start of current cycle or block.
+    cycle_start: Instant,
+
+    /// Numbers of transferred denormal values, indexed by source node id (if counting is enabled).
+    denormal_counts: Option<HashMap<NodeId, u64>>,
+
+    /// Maximum processing time per cycle with its reaction (if set).
+    deadline: Option<deadline::Deadline>,
+
+    /// Errors of deferred edits that failed to apply.
+    edit_errors: Vec<GraphError>,
+
+    /// Deferred structural edits, applied at the start of the next cycle or block.
+    edits: Arc<edits::Pending<N>>,
+
+    /// Priorities of nodes including priorities inherited from dependent nodes, indexed by node id.
+    effective_priorities: HashMap<NodeId, i32>,
+
+    /// Event connections in graph.
+    event_connections: Vec<Connection>,
+
+    /// Fired events awaiting delivery.
+    events: Vec<events::Event>,
+
+    /// Strategy for processing cycles (taken while processing).
+    executor: Option<Box<dyn Executor<N> + Send>>,
+
+    /// Whether denormal values are flushed to zero when transferred.
+    flush_denormals: bool,
+
+    /// Block processing buffers of copied inputs, indexed by node and input.
+    input_buffers: HashMap<(NodeId, InputId), Vec<Value>>,
+
+    /// Values last set on inputs through the graph (start values of automations), indexed by node and input.
+    input_values: HashMap<(NodeId, InputId), Value>,
+
+    /// Unique node labels, indexed by node id.
+    labels: HashMap<NodeId, String>,
+
+    /// Whether connections with lossy value conversions are accepted.
+    lossy_conversions: bool,
+
+    /// Cached input values of pure nodes (see `Node::is_pure`), indexed by node id.
+    memos: HashMap<NodeId, memo::Memo>,
+
+    /// Opaque metadata of nodes (e.g. editor positions), indexed by node id and key.
+    meta: HashMap<NodeId, BTreeMap<String, String>>,
+
     /// Internal counter for next node id.
     next_node_id: NodeId,
 
     /// Nodes in graph, indexed by unique id.
     nodes: HashMap<NodeId, N>,
 
+    /// First non-finite value transferred over a connection (if checks are enabled).
+    non_finite: Option<Option<NonFiniteValue>>,
+
+    /// Block processing buffers of outputs, indexed by node and output.
+    output_buffers: HashMap<(NodeId, OutputId), Vec<Value>>,
+
+    /// Ranges that values of outputs are clamped to, indexed by node and output.
+    output_clamps: HashMap<(NodeId, OutputId), (Value, Value)>,
+
+    /// Mailboxes of thread-safe input updates, applied at the start of each cycle or block.
+    params: Vec<(NodeId, InputId, Arc<SharedParam>)>,
+
+    /// Identifier of next probe.
+    next_probe_id: ProbeId,
+
+    /// Explicitly set node priorities, indexed by node id.
+    priorities: HashMap<NodeId, i32>,
+
+    /// Whether cycles are processed for priming (without probes, recordings and statistics).
+    priming: bool,
+
+    /// Call counts and cumulative processing times of nodes, indexed by node id (if profiling is enabled).
+    profiles: Option<HashMap<NodeId, (u64, Duration)>>,
+
+    /// Probes invoked with output values after each cycle.
+    probes: Vec<probe::Probe>,
+
     /// Node processing order (result of topologial sort).
-    processing_order: LinkedList<NodeId>,
+    processing_order: Vec<NodeId>,
+
+    /// Clock domains of nodes running slower than every cycle, indexed by node id.
+    rate_divisors: HashMap<NodeId, u32>,
+
+    /// Ring buffers of the last values of recorded outputs.
+    recordings: Vec<((NodeId, OutputId), recording::Recording)>,
+
+    /// Nodes skipped in last cycle or block due to exhausted cycle budget.
+    skipped_nodes: Vec<NodeId>,
+
+    /// Runtime statistics of processed cycles.
+    stats: GraphStats,
+
+    /// Error codes of nodes whose last processing failed, indexed by node id.
+    statuses: HashMap<NodeId, u32>,
+
+    /// Tags of nodes (in the order they were attached), indexed by node id.
+    tags: HashMap<NodeId, Vec<String>>,
+
+    /// Counter for changes of processing order (e.g. to invalidate plans of executors).
+    topology_version: u64,
 }
 impl<N: Node> Graph<N> {
     /// Creates new empty graph.
     pub fn new() -> Self {
         Graph {
+            automations: Vec::new(),
+            block_routes: HashMap::new(),
+            block_size: 64,
+            bypassed: HashMap::new(),
+            connection_validator: None,
             connections: Vec::new(),
+            context: ProcessContext::default(),
+            conversion_warnings: Vec::new(),
+            cycle: 0,
+            cycle_budget: None,
+            cycle_start: Instant::now(),
+            deadline: None,
+            denormal_counts: None,
+            edit_errors: Vec::new(),
+            edits: Arc::new(edits::Pending::new()),
+            effective_priorities: HashMap::new(),
+            event_connections: Vec::new(),
+            events: Vec::new(),
+            executor: Some(Box::new(executors::Sequential)),
+            flush_denormals: false,
+            input_buffers: HashMap::new(),
+            input_values: HashMap::new(),
+            labels: HashMap::new(),
+            lossy_conversions: false,
+            memos: HashMap::new(),
+            meta: HashMap::new(),
             next_node_id: NodeId(0),
             nodes: HashMap::new(),
-            processing_order: LinkedList::new(),
+            non_finite: None,
+            output_buffers: HashMap::new(),
+            output_clamps: HashMap::new(),
+            params: Vec::new(),
+            next_probe_id: ProbeId(0),
+            priming: false,
+            priorities: HashMap::new(),
+            probes: Vec::new(),
+            profiles: None,
+            processing_order: Vec::new(),
+            rate_divisors: HashMap::new(),
+            recordings: Vec::new(),
+            skipped_nodes: Vec::new(),
+            stats: GraphStats::default(),
+            statuses: HashMap::new(),
+            tags: HashMap::new(),
+            topology_version: 0,
         }
     }
 
+    /// Creates new empty graph with storage reserved for a number of nodes and connections (see `reserve`).
+    pub fn with_capacity(nodes: usize, connections: usize) -> Self {
+        let mut graph = Self::new();
+        graph.reserve(nodes, connections);
+        graph
+    }
+
     /// Adds a connection to the graph.
     pub fn add_connection(&mut self, connection: Connection) -> Result<Connection, GraphError> {
-        // Validate connection and check whether input is free.
-        let connection = self.validate_connection(connection)?;
-        if self
-            .connections
-            .iter()
-            .find(|c| c.target_node == connection.target_node && c.target_input == connection.target_input)
-            .is_some()
-        {
-            return Err(GraphError::InputAlreadyConnected(connection.target_node, connection.target_input));
-        }
+        self.add_connections([connection]).map(|connections| connections[0])
+    }
 
-        // Add connection, update processing order (check for undelayed cycles).
-        self.connections.push(connection);
-        match self.calc_processing_order() {
-            Err(error) => {
-                // Revert change (most likely an undelayed cycle was introduced).
-                self.connections.pop();
-                Err(error)
-            }
-            Ok(order) => {
-                self.processing_order = order;
-                Ok(connection)
+    /// Adds connections to the graph, updating the processing order once (e.g. when building large graphs).
+    ///
+    /// Connections are validated as a whole: if any of them is invalid (including inputs connected twice in the batch)
+    /// or they introduce a cycle without delay, none of them is added.
+    pub fn add_connections<I: IntoIterator<Item = Connection>>(
+        &mut self,
+        connections: I,
+    ) -> Result<Vec<Connection>, GraphError> {
+        let count = self.connections.len();
+        let mut connected: HashSet<(NodeId, InputId)> =
+            self.connections.iter().map(|c| (c.target_node, c.target_input)).collect();
+        for connection in connections {
+            // Validate connection and check whether input is free.
+            let result = self.validate_connection(connection).and_then(|connection| {
+                match connected.insert((connection.target_node, connection.target_input)) {
+                    true => Ok(connection),
+                    false => Err(GraphError::InputAlreadyConnected(connection.target_node, connection.target_input)),
+                }
+            });
+            match result {
+                Ok(connection) => self.connections.push(connection),
+                Err(error) => {
+                    self.connections.truncate(count);
+                    return Err(error);
+                }
             }
         }
+
+        // Update processing order (check for undelayed cycles).
+        if let Err(error) = self.update_processing_order() {
+            // Revert change (most likely an undelayed cycle was introduced).
+            self.connections.truncate(count);
+            return Err(error);
+        }
+        let added = self.connections[count..].to_vec();
+        let lossy: Vec<Connection> = added.iter().copied().filter(|&connection| self.is_lossy(connection)).collect();
+        self.conversion_warnings.extend(lossy);
+        Ok(added)
     }
 
     /// Adds a node to the graph.
     pub fn add_node(&mut self, node: N) -> NodeId {
-        let id = self.next_node_id;
-        self.nodes.insert(id, node);
-        self.processing_order = self.calc_processing_order().unwrap();
-        self.next_node_id.0 += 1;
-        id
+        self.add_nodes([node])[0]
+    }
+
+    /// Adds nodes to the graph, updating the processing order once (e.g. when building large graphs), and returns
+    /// their ids.
+    pub fn add_nodes<I: IntoIterator<Item = N>>(&mut self, nodes: I) -> Vec<NodeId> {
+        let ids: Vec<NodeId> = nodes
+            .into_iter()
+            .map(|node| {
+                let id = self.next_node_id;
+                self.nodes.insert(id, node);
+                self.next_node_id.0 += 1;
+                id
+            })
+            .collect();
+
+        // Reserve per-node bookkeeping so processing does not allocate.
+        self.skipped_nodes.reserve(self.nodes.len());
+        self.statuses.reserve(self.nodes.len());
+        if let Some(profiles) = self.profiles.as_mut() {
+            profiles.reserve(self.nodes.len());
+        }
+        if let Some(counts) = self.denormal_counts.as_mut() {
+            counts.reserve(self.nodes.len());
+        }
+        self.reserve_events();
+        self.update_processing_order().unwrap();
+        ids
+    }
+
+    /// Applies pending thread-safe input updates.
+    fn apply_params(&mut self) {
+        for (node, input, shared) in self.params.iter() {
+            if let Some(value) = shared.take() {
+                if let Some(processor) = self.nodes.get_mut(node) {
+                    processor.set_input(*input, value);
+                }
+                if let Some(memo) = self.memos.get_mut(node) {
+                    memo.valid = false;
+                }
+            }
+        }
+    }
+
+    /// Returns number of frames per block in block processing.
+    pub fn block_size(&self) -> usize {
+        self.block_size
     }
 
     /// Determines processing order (new topological sorting, can fail due to undelayed cycles).
-    fn calc_processing_order(&self) -> Result<LinkedList<NodeId>, GraphError> {
+    fn calc_processing_order(&self) -> Result<Vec<NodeId>, GraphError> {
         // Calculate in-degree of nodes.
         let mut in_degree: HashMap<NodeId, usize> = HashMap::new();
+        let mut targets: HashMap<NodeId, Vec<NodeId>> = HashMap::new();
         for &node in self.nodes.keys() {
             in_degree.insert(node, 0);
         }
-        for connection in self.connections.iter() {
+        for connection in self.connections.iter().chain(self.event_connections.iter()) {
             // Nodes do not depend on nodes that introduce delay.
             if !self.get_node(connection.source_node).unwrap().delayed_processing() {
                 in_degree.entry(connection.target_node).and_modify(|d| *d += 1);
+                targets.entry(connection.source_node).or_default().push(connection.target_node);
             }
         }
 
-        // Find nodes with in-degree 0.
-        let mut queue: LinkedList<NodeId> = LinkedList::new();
-        for (&node, &degree) in in_degree.iter() {
-            if degree == 0 {
-                queue.push_back(node);
-            }
-        }
+        // Find nodes with in-degree 0 (ready nodes are taken by smallest id, so the order is deterministic).
+        let mut queue: BinaryHeap<Reverse<NodeId>> =
+            in_degree.iter().filter(|(_, &degree)| degree == 0).map(|(&node, _)| Reverse(node)).collect();
 
         // Topological sort.
-        let mut order: LinkedList<NodeId> = LinkedList::new();
-        let mut delayed: LinkedList<NodeId> = LinkedList::new();
-        while !queue.is_empty() {
-            let node = queue.pop_front().unwrap();
+        let mut order: Vec<NodeId> = Vec::with_capacity(self.nodes.len());
+        let mut delayed: Vec<NodeId> = Vec::new();
+        while let Some(Reverse(node)) = queue.pop() {
             if !self.get_node(node).unwrap().delayed_processing() {
                 // Reduce in-degree of connected nodes, add to queue once in-degree == 0.
-                for connection in self.connections.iter() {
-                    if connection.source_node == node {
-                        in_degree.entry(connection.target_node).and_modify(|d| *d -= 1);
-                        if *in_degree.get(&connection.target_node).unwrap() == 0 {
-                            queue.push_back(connection.target_node);
-                        }
+                for &target in targets.get(&node).into_iter().flatten() {
+                    in_degree.entry(target).and_modify(|d| *d -= 1);
+                    if *in_degree.get(&target).unwrap() == 0 {
+                        queue.push(Reverse(target));
                     }
                 }
-                order.push_back(node);
+                order.push(node);
             } else {
-                delayed.push_back(node);
+                delayed.push(node);
             }
         }
 
-        // Nodes that introduce delay are processed after all other nodes.
-        while !delayed.is_empty() {
-            order.push_back(delayed.pop_front().unwrap());
-        }
+        // Nodes that introduce delay are processed after all other nodes (ordered by id).
+        delayed.sort_unstable();
+        order.append(&mut delayed);
 
         // Number of nodes in order won't match if an undelayed cycle exists.
         if order.len() != self.nodes.len() {
@@ -122,122 +461,833 @@ impl<N: Node> Graph<N> {
         Ok(order)
     }
 
+    /// Returns connections in graph.
+    pub fn connections(&self) -> &[Connection] {
+        &self.connections
+    }
+
+    /// Returns timing information for the next cycle.
+    pub fn context(&self) -> ProcessContext {
+        self.context
+    }
+
+    /// Returns id of the node with given label.
+    pub fn find_node(&self, label: &str) -> Result<NodeId, GraphError> {
+        self.labels
+            .iter()
+            .find(|(_, l)| l.as_str() == label)
+            .map(|(&id, _)| id)
+            .ok_or_else(|| GraphError::LabelNotExists(label.to_string()))
+    }
+
+    /// Returns the buffer of an output from the last processed block.
+    pub fn get_block_output(&self, node: NodeId, output: OutputId) -> Result<&[Value], GraphError> {
+        if !has_output(self.get_node(node)?, output) {
+            return Err(GraphError::OutputNotExists(node, output));
+        }
+        Ok(self.output_buffers.get(&(node, output)).map_or(&[], |buffer| buffer.as_slice()))
+    }
+
+    /// Returns label of a node.
+    pub fn get_label(&self, id: NodeId) -> Result<Option<&str>, GraphError> {
+        self.get_node(id)?;
+        Ok(self.labels.get(&id).map(|label| label.as_str()))
+    }
+
+    /// Returns value of an output (including status outputs).
+    pub fn get_output(&self, node: NodeId, output: OutputId) -> Result<Value, GraphError> {
+        if !has_output(self.get_node(node)?, output) {
+            return Err(GraphError::OutputNotExists(node, output));
+        }
+        Ok(self.output_value(node, output))
+    }
+
+    /// Returns values of a set of outputs, indexed by node and output id.
+    pub fn get_outputs(
+        &self,
+        outputs: &[(NodeId, OutputId)],
+    ) -> Result<HashMap<(NodeId, OutputId), Value>, GraphError> {
+        outputs.iter().map(|&(node, output)| Ok(((node, output), self.get_output(node, output)?))).collect()
+    }
+
     /// Returns a node by id.
     pub fn get_node(&self, id: NodeId) -> Result<&N, GraphError> {
         self.nodes.get(&id).ok_or(GraphError::NodeNotExists(id))
     }
 
-    /// Returns a mutable node by id.
+    /// Returns a node by id as its concrete type (see `Node::as_any`), e.g. for nodes of a `Graph<BuiltinNode>`.
+    pub fn get_node_as<T: Node + 'static>(&self, id: NodeId) -> Result<&T, GraphError>
+    where
+        N: 'static,
+    {
+        self.get_node(id)?.as_any().downcast_ref().ok_or(GraphError::WrongNodeType(id))
+    }
+
+    /// Returns a mutable node by id as its concrete type (invalidates cached inputs of the node, see `get_node_as`).
+    pub fn get_node_as_mut<T: Node + 'static>(&mut self, id: NodeId) -> Result<&mut T, GraphError>
+    where
+        N: 'static,
+    {
+        self.get_node_mut(id)?.as_any_mut().downcast_mut().ok_or(GraphError::WrongNodeType(id))
+    }
+
+    /// Returns a mutable node by id (invalidates cached inputs of the node, see `Node::is_pure`).
     pub fn get_node_mut(&mut self, id: NodeId) -> Result<&mut N, GraphError> {
+        self.invalidate_memo(id);
         self.nodes.get_mut(&id).ok_or(GraphError::NodeNotExists(id))
     }
 
+    /// Returns status of a node (0 if last processing succeeded, error code otherwise).
+    pub fn get_status(&self, id: NodeId) -> Result<u32, GraphError> {
+        self.get_node(id)?;
+        Ok(self.statuses.get(&id).copied().unwrap_or(0))
+    }
+
+    /// Returns whether a node processes in the current cycle (tick of its clock domain).
+    fn is_tick(&self, node: NodeId) -> bool {
+        let divisor = self.rate_divisors.get(&node).copied().unwrap_or(1);
+        self.context.frame.is_multiple_of(divisor as u64)
+    }
+
     /// Returns iterator over nodes.
     pub fn iter_nodes(&self) -> impl Iterator<Item = (&NodeId, &N)> {
         self.nodes.iter()
     }
 
-    /// Returns mutable iterator over nodes.
+    /// Returns mutable iterator over nodes (invalidates cached inputs of all nodes, see `Node::is_pure`).
     pub fn iter_nodes_mut(&mut self) -> impl Iterator<Item = (&NodeId, &mut N)> {
+        self.memos.values_mut().for_each(|memo| memo.valid = false);
         self.nodes.iter_mut()
     }
 
-    /// Processes nodes in graph.
+    /// Returns value of an existing output (including status outputs).
+    pub(crate) fn output_value(&self, node: NodeId, output: OutputId) -> Value {
+        match output {
+            OutputId::STATUS => self.statuses.get(&node).copied().unwrap_or(0) as Value,
+            _ => match self.bypassed_output(node, output) {
+                Some(value) => self.clamp_output(node, output, value),
+                None => self.clamp_output(node, output, self.nodes[&node].get_output(output)),
+            },
+        }
+    }
+
+    /// Populates inputs of a node from connected outputs.
+    fn populate_inputs(&mut self, node: NodeId) -> Result<(), ProcessError> {
+        for index in 0..self.connections.len() {
+            let connection = self.connections[index];
+            if connection.target_node == node {
+                self.transfer(connection)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Allocates block processing buffers of all ports (resets buffers if block size changed) and plans routing.
+    fn prepare_block_buffers(&mut self) {
+        let size = self.block_size;
+        for (&id, node) in self.nodes.iter() {
+            for &output in node.list_outputs().iter().chain([OutputId::STATUS].iter()) {
+                let buffer = self.output_buffers.entry((id, output)).or_default();
+                if buffer.len() != size {
+                    *buffer = vec![0.0; size];
+                }
+            }
+        }
+
+        // Source output buffers are shared with an input (instead of copied) if they are lent exclusively and are
+        // not overwritten by the target node.
+        let mut fan_out: HashMap<(NodeId, OutputId), usize> = HashMap::new();
+        for connection in self.connections.iter() {
+            *fan_out.entry((connection.source_node, connection.source_output)).or_default() += 1;
+        }
+        self.block_routes.clear();
+        for connection in self.connections.iter() {
+            let source = (connection.source_node, connection.source_output);
+            let target = (connection.target_node, connection.target_input);
+            if fan_out[&source] == 1
+                && connection.source_node != connection.target_node
+                && !self.nodes[&connection.target_node].writes_block_inputs()
+            {
+                self.block_routes.insert(target, BlockRoute::Shared(source.0, source.1));
+                self.input_buffers.remove(&target);
+            } else {
+                self.block_routes.insert(target, BlockRoute::Copied(source.0, source.1));
+                let buffer = self.input_buffers.entry(target).or_default();
+                if buffer.len() != size {
+                    *buffer = vec![0.0; size];
+                }
+            }
+        }
+    }
+
+    /// Processes nodes in graph for given number of cycles without invoking probes (and subscriptions), recording
+    /// outputs or counting statistics, e.g. to let delay lines and filters settle before the output is used. Frames
+    /// advance as usual.
+    pub fn prime(&mut self, cycles: usize) {
+        self.priming = true;
+        self.process_n(cycles);
+        self.priming = false;
+    }
+
+    /// Processes nodes in graph (using the executor of the graph).
+    ///
+    /// Processing is real-time safe (no heap allocation or panicking apart from the nodes themselves) with real-time
+    /// safe executors once they prepared for the current topology, which is asserted with the `rt-check` feature and
+    /// the `RtCheck` allocator (for allocations only). The edit queue is only locked (without waiting) if edits are
+    /// enqueued. Block processing and cycles applying enqueued edits (see `enqueue`) are not real-time safe.
+    ///
+    /// Nodes in slower clock domains only process (and sample their inputs) on ticks of their domain and hold their
+    /// outputs in between. Nodes skipped due to an exhausted cycle budget hold their outputs as well.
     pub fn process(&mut self) {
-        // First pass.
-        for &node in self.processing_order.iter() {
-            // Populate inputs.
-            for connection in self.connections.iter() {
-                if connection.target_node == node {
-                    let value = self.nodes.get(&connection.source_node).unwrap().get_output(connection.source_output);
-                    self.nodes.get_mut(&connection.target_node).unwrap().set_input(connection.target_input, value);
+        self.apply_edits();
+        self.cycle += 1;
+        self.cycle_start = Instant::now();
+        self.skipped_nodes.clear();
+        let executor = self.executor.take();
+        #[cfg(any(test, feature = "rt-check"))]
+        let section = rt_check::Section::enter(executor.as_ref().is_some_and(|executor| executor.realtime_safe()));
+        self.apply_params();
+        self.apply_automations(1);
+        self.expire_events();
+        if let Some(mut executor) = executor {
+            executor.process(self);
+            self.executor = Some(executor);
+        }
+        if !self.priming {
+            self.call_probes();
+            self.record_outputs();
+        }
+        self.context.frame += 1;
+        if !self.priming {
+            self.record_cycle();
+        }
+        #[cfg(any(test, feature = "rt-check"))]
+        section.finish();
+    }
+
+    /// Processes a node and records its status.
+    fn process_and_record(&mut self, node: NodeId) {
+        if self.bypassed.contains_key(&node) {
+            self.bypass(node);
+            return;
+        }
+        if self.is_memoized(node) {
+            return;
+        }
+        let divisor = self.rate_divisors.get(&node).copied().unwrap_or(1);
+        let start = self.profiles.is_some().then(Instant::now);
+        let Some(processor) = self.nodes.get_mut(&node) else {
+            return;
+        };
+        let result = processor.try_process(&self.context.divided(divisor));
+        let delay = processor.delayed_processing() as u64;
+        profile::record_profile(&mut self.profiles, node, start.map(|start| start.elapsed()));
+        update_status(&mut self.statuses, node, result);
+        self.collect_events(node, delay);
+        self.check_deadline(node);
+    }
+
+    /// Processes nodes in graph for one block of frames.
+    ///
+    /// Nodes that introduce delay are processed last, so their outputs reach other nodes one block later. Nodes
+    /// skipped due to an exhausted cycle budget (applied per block) hold their outputs.
+    pub fn process_block(&mut self) {
+        self.run_block(&[]);
+    }
+
+    /// Processes one block of frames with external buffers of graph inputs (see `process_block_external`).
+    fn run_block(&mut self, external_inputs: &[&[Value]]) {
+        self.apply_edits();
+        self.cycle += 1;
+        self.cycle_start = Instant::now();
+        self.skipped_nodes.clear();
+        self.apply_params();
+        self.apply_automations(self.block_size as u64);
+        self.expire_events();
+        self.prepare_block_buffers();
+        let frames = self.block_size;
+
+        for index in 0..self.processing_order.len() {
+            let node = self.processing_order[index];
+            if self.skips(node) {
+                // Hold outputs.
+                self.skipped_nodes.push(node);
+                for &output in self.nodes[&node].list_outputs() {
+                    let buffer = self.output_buffers.get_mut(&(node, output)).unwrap();
+                    let value = buffer.last().copied().unwrap_or(0.0);
+                    buffer.fill(value);
                 }
+                continue;
+            }
+            if let Some(&values) = self.nodes[&node].graph_input().and_then(|index| external_inputs.get(index)) {
+                self.bind_external_input(node, values);
+                continue;
+            }
+            self.deliver_events(node);
+            if self.bypassed.contains_key(&node) {
+                self.bypass_block(node);
+                continue;
             }
+            let inputs = self.nodes[&node].list_inputs().to_vec();
+            let outputs = self.nodes[&node].list_outputs().to_vec();
 
-            // Process non-delayed nodes.
-            let node = self.nodes.get_mut(&node).unwrap();
-            if !node.delayed_processing() {
-                node.process();
+            // Lend buffers while processing (shared source output buffers are moved to the inputs).
+            let mut input_buffers: Vec<Option<Vec<Value>>> = inputs
+                .iter()
+                .map(|&input| match self.block_routes.get(&(node, input)) {
+                    Some(&BlockRoute::Copied(source, output)) => {
+                        let mut buffer = mem::take(self.input_buffers.get_mut(&(node, input)).unwrap());
+                        buffer.copy_from_slice(&self.output_buffers[&(source, output)]);
+                        Some(buffer)
+                    }
+                    Some(&BlockRoute::Shared(source, output)) => {
+                        Some(mem::take(self.output_buffers.get_mut(&(source, output)).unwrap()))
+                    }
+                    None => None,
+                })
+                .collect();
+            if self.non_finite == Some(None) {
+                self.check_block_finite(node, &inputs, &input_buffers);
+            }
+            if self.flush_denormals || self.denormal_counts.is_some() {
+                self.handle_block_denormals(node, &inputs, &mut input_buffers);
             }
+            let mut output_buffers: Vec<Vec<Value>> = outputs
+                .iter()
+                .map(|&output| mem::take(self.output_buffers.get_mut(&(node, output)).unwrap()))
+                .collect();
+            let processor = self.nodes.get_mut(&node).unwrap();
+            let start = self.profiles.is_some().then(Instant::now);
+            let result = match self.rate_divisors.get(&node) {
+                Some(&divisor) => clock::process_divided_block(
+                    processor,
+                    &self.context,
+                    divisor,
+                    frames,
+                    &input_buffers,
+                    &mut output_buffers,
+                ),
+                None => {
+                    let mut input_slices: Vec<Option<&mut [Value]>> =
+                        input_buffers.iter_mut().map(|buffer| buffer.as_deref_mut()).collect();
+                    let mut output_slices: Vec<&mut [Value]> =
+                        output_buffers.iter_mut().map(|buffer| buffer.as_mut_slice()).collect();
+                    processor.process_block(&self.context, frames, &mut input_slices, &mut output_slices)
+                }
+            };
+            profile::record_profile(&mut self.profiles, node, start.map(|start| start.elapsed()));
+            update_status(&mut self.statuses, node, result);
+            let delay = if self.nodes[&node].delayed_processing() { frames as u64 } else { 0 };
+            self.collect_events(node, delay);
+            let status = self.output_value(node, OutputId::STATUS);
+            self.output_buffers.get_mut(&(node, OutputId::STATUS)).unwrap().fill(status);
+            self.check_deadline(node);
+
+            if !self.output_clamps.is_empty() {
+                self.clamp_block_outputs(node, &outputs, &mut output_buffers);
+            }
+
+            // Return buffers.
+            for (&output, buffer) in outputs.iter().zip(output_buffers) {
+                self.output_buffers.insert((node, output), buffer);
+            }
+            for (&input, buffer) in inputs.iter().zip(input_buffers) {
+                match (self.block_routes.get(&(node, input)), buffer) {
+                    (Some(&BlockRoute::Copied(..)), Some(buffer)) => {
+                        self.input_buffers.insert((node, input), buffer);
+                    }
+                    (Some(&BlockRoute::Shared(source, output)), Some(buffer)) => {
+                        self.output_buffers.insert((source, output), buffer);
+                    }
+                    _ => {}
+                }
+            }
+        }
+        self.call_block_probes(frames);
+        self.record_block_outputs(frames);
+        self.context.frame += frames as u64;
+        self.record_cycle();
+    }
+
+    /// Processes a node that introduces delay in the current cycle (second pass, for custom executors).
+    pub fn process_delayed_node(&mut self, id: NodeId) -> Result<(), GraphError> {
+        self.get_node(id)?;
+        self.step_delayed(id);
+        Ok(())
+    }
+
+    /// Processes nodes in graph for given number of cycles.
+    pub fn process_n(&mut self, count: usize) {
+        for _ in 0..count {
+            self.process();
         }
+    }
 
-        // Second pass.
-        for &node in self.processing_order.iter() {
-            // Process delayed nodes.
-            let node = self.nodes.get_mut(&node).unwrap();
-            if node.delayed_processing() {
-                node.process();
+    /// Populates inputs of a node and processes it unless it introduces delay in the current cycle (first pass, for
+    /// custom executors).
+    pub fn process_node(&mut self, id: NodeId) -> Result<(), GraphError> {
+        self.get_node(id)?;
+        self.step(id, None);
+        Ok(())
+    }
+
+    /// Sets a batch of inputs, processes one cycle and returns values of a set of outputs (see `set_inputs` and
+    /// `get_outputs`). Nothing is processed if an input or output does not exist.
+    pub fn process_with(
+        &mut self,
+        inputs: &[(NodeId, InputId, Value)],
+        outputs: &[(NodeId, OutputId)],
+    ) -> Result<HashMap<(NodeId, OutputId), Value>, GraphError> {
+        for &(node, output) in outputs {
+            if !has_output(self.get_node(node)?, output) {
+                return Err(GraphError::OutputNotExists(node, output));
             }
         }
+        self.set_inputs(inputs)?;
+        self.process();
+        self.get_outputs(outputs)
+    }
+
+    /// Returns thread-safe handle for updating an input while the graph is processed on another thread (updates take
+    /// effect at the start of the next cycle, handles of removed nodes have no effect).
+    pub fn param_handle(&mut self, node: NodeId, input: InputId) -> Result<ParamHandle, GraphError> {
+        if !self.get_node(node)?.list_inputs().contains(&input) {
+            return Err(GraphError::InputNotExists(node, input));
+        }
+        if let Some((_, _, shared)) = self.params.iter().find(|(n, i, _)| *n == node && *i == input) {
+            return Ok(ParamHandle::new(shared.clone()));
+        }
+        let shared = Arc::new(SharedParam::default());
+        self.params.push((node, input, shared.clone()));
+        Ok(ParamHandle::new(shared))
+    }
+
+    /// Returns node processing order.
+    ///
+    /// The order is deterministic: nodes are sorted topologically, taking the ready node with the smallest id first
+    /// (i.e. independent nodes are processed in order of their ids). Nodes that introduce delay are processed last,
+    /// ordered by id.
+    pub fn processing_order(&self) -> &[NodeId] {
+        &self.processing_order
     }
 
     /// Removes a connection.
     pub fn remove_connection(&mut self, connection: Connection) -> Result<Connection, GraphError> {
         if self.connections.contains(&connection) {
             self.connections.retain(|&c| c != connection);
-            self.processing_order = self.calc_processing_order().unwrap();
+            self.input_buffers.remove(&(connection.target_node, connection.target_input));
+            self.update_processing_order().unwrap();
             Ok(connection)
         } else {
             Err(GraphError::ConnectionNotExists(connection))
         }
     }
 
+    /// Reserves storage for at least a number of additional nodes and connections (e.g. before building large
+    /// generated graphs).
+    pub fn reserve(&mut self, nodes: usize, connections: usize) {
+        self.nodes.reserve(nodes);
+        self.processing_order.reserve(nodes);
+        self.skipped_nodes.reserve(nodes);
+        self.statuses.reserve(nodes);
+        self.connections.reserve(connections);
+    }
+
+    /// Removes validation callback for new connections (see `set_connection_validator`).
+    pub fn remove_connection_validator(&mut self) {
+        self.connection_validator = None;
+    }
+
     /// Removes a node by id.
     pub fn remove_node(&mut self, id: NodeId) -> Result<N, GraphError> {
-        let node = self.nodes.remove(&id).ok_or(GraphError::NodeNotExists(id))?;
-        self.connections = self.connections.iter().cloned().filter(|&c| self.validate_connection(c).is_ok()).collect();
-        self.processing_order = self.calc_processing_order().unwrap();
-        Ok(node)
+        Ok(self.remove_nodes(&[id])?.pop().unwrap())
+    }
+
+    /// Removes nodes by id (and their connections), updating the processing order once, and returns them in the
+    /// given order. No node is removed if any of them does not exist (or is given twice).
+    pub fn remove_nodes(&mut self, ids: &[NodeId]) -> Result<Vec<N>, GraphError> {
+        let mut removed: HashSet<NodeId> = HashSet::with_capacity(ids.len());
+        for &id in ids {
+            if !self.nodes.contains_key(&id) || !removed.insert(id) {
+                return Err(GraphError::NodeNotExists(id));
+            }
+        }
+        let nodes = ids.iter().map(|id| self.nodes.remove(id).unwrap()).collect();
+        self.connections.retain(|c| !removed.contains(&c.source_node) && !removed.contains(&c.target_node));
+        let connected: HashSet<(NodeId, InputId)> =
+            self.connections.iter().map(|c| (c.target_node, c.target_input)).collect();
+        self.input_buffers.retain(|target, _| connected.contains(target));
+        self.output_buffers.retain(|(node, _), _| !removed.contains(node));
+        self.params.retain(|(node, _, _)| !removed.contains(node));
+        for &id in ids {
+            self.bypassed.remove(&id);
+            self.labels.remove(&id);
+            self.meta.remove(&id);
+            self.tags.remove(&id);
+            self.rate_divisors.remove(&id);
+            self.statuses.remove(&id);
+            self.priorities.remove(&id);
+            self.remove_automations(id);
+            self.remove_events(id);
+            self.remove_probes(id);
+            self.remove_recordings(id);
+            if let Some(profiles) = self.profiles.as_mut() {
+                profiles.remove(&id);
+            }
+            if let Some(counts) = self.denormal_counts.as_mut() {
+                counts.remove(&id);
+            }
+            self.output_clamps.retain(|&(node, _), _| node != id);
+        }
+        self.update_processing_order().unwrap();
+        Ok(nodes)
+    }
+
+    /// Resets state of all nodes to initial conditions (see `Node::reset`) and restarts processing at frame 0, e.g. to
+    /// rerun a simulation. Topology, inputs set through the graph and configuration are kept.
+    pub fn reset(&mut self) {
+        self.nodes.values_mut().for_each(|node| node.reset());
+        self.memos.values_mut().for_each(|memo| memo.valid = false);
+        self.bypassed.values_mut().for_each(|values| values.fill(0.0));
+        self.input_buffers.values_mut().for_each(|buffer| buffer.fill(0.0));
+        self.output_buffers.values_mut().for_each(|buffer| buffer.fill(0.0));
+        self.recordings.iter_mut().for_each(|(_, recording)| recording.values.clear());
+        self.events.clear();
+        self.skipped_nodes.clear();
+        self.statuses.clear();
+        self.context.frame = 0;
+    }
+
+    /// Keeps only nodes for which a predicate returns true (removing the others with their connections, see
+    /// `remove_nodes`).
+    pub fn retain_nodes<F: FnMut(NodeId, &N) -> bool>(&mut self, mut predicate: F) {
+        let mut ids: Vec<NodeId> =
+            self.nodes.iter().filter(|&(&id, node)| !predicate(id, node)).map(|(&id, _)| id).collect();
+        ids.sort_unstable();
+        self.remove_nodes(&ids).unwrap();
+    }
+
+    /// Returns iterator that processes the graph once per step.
+    pub fn run(&mut self) -> Run<'_, N> {
+        Run::new(self)
+    }
+
+    /// Sets number of frames per block in block processing.
+    pub fn set_block_size(&mut self, size: usize) {
+        self.block_size = size;
+        self.prepare_block_buffers();
+    }
+
+    /// Sets validation callback invoked for new connections after the builtin checks (e.g. to enforce domain rules),
+    /// connections are rejected with `GraphError::Rejected` if it returns a reason. Existing connections are not
+    /// revalidated.
+    pub fn set_connection_validator<F: Fn(&Graph<N>, Connection) -> Result<(), String> + Send + 'static>(
+        &mut self,
+        validator: F,
+    ) {
+        self.connection_validator = Some(Box::new(validator));
+    }
+
+    /// Sets strategy for processing cycles.
+    pub fn set_executor<E: Executor<N> + Send + 'static>(&mut self, executor: E) {
+        self.executor = Some(Box::new(executor));
+    }
+
+    /// Sets value of an input of a node (e.g. the value of a variable), connected inputs are overwritten when
+    /// processing.
+    pub fn set_input(&mut self, id: NodeId, input: InputId, value: Value) -> Result<(), GraphError> {
+        let node = self.get_node_mut(id)?;
+        if !node.list_inputs().contains(&input) {
+            return Err(GraphError::InputNotExists(id, input));
+        }
+        node.set_input(input, value);
+        self.input_values.insert((id, input), value);
+        Ok(())
+    }
+
+    /// Sets values of a batch of inputs (see `set_input`), none are set if an input does not exist.
+    pub fn set_inputs(&mut self, inputs: &[(NodeId, InputId, Value)]) -> Result<(), GraphError> {
+        for &(id, input, _) in inputs {
+            if !self.get_node(id)?.list_inputs().contains(&input) {
+                return Err(GraphError::InputNotExists(id, input));
+            }
+        }
+        for &(id, input, value) in inputs {
+            self.set_input(id, input, value)?;
+        }
+        Ok(())
+    }
+
+    /// Sets number of frames per second (frame counter is kept).
+    pub fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.context = ProcessContext { frame: self.context.frame, ..ProcessContext::new(sample_rate) };
+    }
+
+    /// Sets unique label of a node (`None` removes the label).
+    pub fn set_label(&mut self, id: NodeId, label: Option<&str>) -> Result<(), GraphError> {
+        self.get_node(id)?;
+        match label {
+            Some(label) => {
+                if self.labels.iter().any(|(&other, l)| other != id && l == label) {
+                    return Err(GraphError::LabelAlreadyUsed(label.to_string()));
+                }
+                self.labels.insert(id, label.to_string());
+            }
+            None => {
+                self.labels.remove(&id);
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns whether a node is skipped in the current cycle due to an exhausted cycle budget (and records it).
+    fn skip_or_record(&mut self, node: NodeId) -> bool {
+        let skip = self.skips(node);
+        if skip {
+            self.skipped_nodes.push(node);
+        }
+        skip
+    }
+
+    /// Skips a node whose inputs cannot be populated in the current cycle (holding its outputs) and reports the error
+    /// as its status.
+    fn skip_unpopulated(&mut self, node: NodeId, error: ProcessError) {
+        update_status(&mut self.statuses, node, Err(error));
+        self.skipped_nodes.push(node);
+    }
+
+    /// Populates inputs of a node (from given connections or from all connections) and processes it unless it
+    /// introduces delay (first pass of a cycle).
+    pub(crate) fn step(&mut self, node: NodeId, inputs: Option<&[Connection]>) {
+        self.step_with(node, |graph| match inputs {
+            Some(inputs) => {
+                for &connection in inputs.iter().filter(|c| c.target_node == node) {
+                    graph.transfer(connection)?;
+                }
+                Ok(())
+            }
+            None => graph.populate_inputs(node),
+        });
+    }
+
+    /// Sets an input of a node while populating it (for executors, see `step_with`).
+    pub(crate) fn set_node_input(&mut self, node: NodeId, input: InputId, value: Value) {
+        if let Some(processor) = self.nodes.get_mut(&node) {
+            processor.set_input(input, value);
+        }
+    }
+
+    /// Populates inputs of a node with a callback and processes it unless it introduces delay (first pass of a
+    /// cycle, the callback is not called if the node does not process in this cycle).
+    ///
+    /// Nodes whose inputs cannot be populated are skipped (holding their outputs) and report the error as status.
+    pub(crate) fn step_with<F: FnOnce(&mut Self) -> Result<(), ProcessError>>(&mut self, node: NodeId, populate: F) {
+        let Some(delayed) = self.nodes.get(&node).map(|processor| processor.delayed_processing()) else {
+            return;
+        };
+        if !self.is_tick(node) || self.skip_or_record(node) {
+            return;
+        }
+        self.deliver_events(node);
+        if let Err(error) = populate(self) {
+            self.skip_unpopulated(node, error);
+        } else if !delayed {
+            self.process_and_record(node);
+        }
+    }
+
+    /// Processes a node if it introduces delay (second pass of a cycle).
+    pub(crate) fn step_delayed(&mut self, node: NodeId) {
+        if !self.is_tick(node)
+            || !self.nodes.get(&node).is_some_and(|processor| processor.delayed_processing())
+            || self.skipped_nodes.contains(&node)
+        {
+            return;
+        }
+        if !self.skip_or_record(node) {
+            self.process_and_record(node);
+        }
+    }
+
+    /// Copies the value (all channels) of a connected output to the input (fails if the source node does not
+    /// exist).
+    pub(crate) fn transfer(&mut self, connection: Connection) -> Result<(), ProcessError> {
+        let channels = match (self.nodes.get(&connection.source_node), connection.source_output) {
+            (None, _) => return Err(ProcessError::MISSING_SOURCE),
+            (Some(_), OutputId::STATUS) => 1,
+            (Some(source), output) => source.output_channels(output),
+        };
+        if channels == 1 {
+            let value = self.output_value(connection.source_node, connection.source_output);
+            self.check_finite(connection, self.context.frame, value);
+            let value = self.handle_denormal(connection.source_node, value);
+            self.set_node_input(connection.target_node, connection.target_input, value);
+            return Ok(());
+        }
+        for channel in 0..channels {
+            let value = self.nodes[&connection.source_node].get_output_channel(connection.source_output, channel);
+            let value = self.clamp_output(connection.source_node, connection.source_output, value);
+            self.check_finite(connection, self.context.frame, value);
+            let value = self.handle_denormal(connection.source_node, value);
+            if let Some(target) = self.nodes.get_mut(&connection.target_node) {
+                target.set_input_channel(connection.target_input, channel, value);
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns counter for changes of processing order.
+    pub(crate) fn topology_version(&self) -> u64 {
+        self.topology_version
+    }
+
+    /// Determines processing order and dependent properties (can fail due to undelayed cycles).
+    fn update_processing_order(&mut self) -> Result<(), GraphError> {
+        self.processing_order = self.calc_processing_order()?;
+        self.topology_version += 1;
+        self.update_priorities();
+        self.prepare_memos();
+        Ok(())
     }
 
     /// Validates a connection (whether nodes and input/output exist).
     fn validate_connection(&self, connection: Connection) -> Result<Connection, GraphError> {
         let source = self.get_node(connection.source_node)?;
         let target = self.get_node(connection.target_node)?;
-        if !source.list_outputs().contains(&connection.source_output) {
+        if !has_output(source, connection.source_output) {
             return Err(GraphError::OutputNotExists(connection.source_node, connection.source_output));
         }
         if !target.list_inputs().contains(&connection.target_input) {
             return Err(GraphError::InputNotExists(connection.target_node, connection.target_input));
         }
+        let (source_kind, target_kind) = self.conversion_kinds(connection);
+        let lossy = self.lossy_conversions && target_kind.is_lossy_from(source_kind);
+        if !(target_kind.accepts(source_kind) || lossy) {
+            return Err(GraphError::TypeMismatch(connection));
+        }
+        let source_channels = match connection.source_output {
+            OutputId::STATUS => 1,
+            output => source.output_channels(output),
+        };
+        if target.input_channels(connection.target_input) != source_channels {
+            return Err(GraphError::ChannelMismatch(connection));
+        }
+        if let Some(validator) = self.connection_validator.as_ref() {
+            validator(self, connection).map_err(GraphError::Rejected)?;
+        }
         Ok(connection)
     }
 }
+impl<N: Node> Default for Graph<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns whether a node in a graph has an output (including status output).
+fn has_output<N: Node>(node: &N, output: OutputId) -> bool {
+    output == OutputId::STATUS || node.list_outputs().contains(&output)
+}
+
+/// Records status of a node after processing.
+fn update_status(statuses: &mut HashMap<NodeId, u32>, id: NodeId, result: Result<(), ProcessError>) {
+    match result {
+        Ok(()) => statuses.remove(&id),
+        Err(error) => statuses.insert(id, error.0),
+    };
+}
 
 /// Graph error type.
 #[derive(PartialEq)]
 pub enum GraphError {
+    BackendUnavailable,
+    BufferSizeMismatch(usize),
+    ChannelMismatch(Connection),
     ConnectionNotExists(Connection),
     CycleWithoutDelay,
+    ExternalInputNotExists(usize),
+    ExternalOutputNotExists(usize),
     InputAlreadyConnected(NodeId, InputId),
     InputNotExists(NodeId, InputId),
+    InvalidFormat(String),
+    InvalidRateDivisor(NodeId),
+    LabelAlreadyUsed(String),
+    LabelNotExists(String),
+    NodeAlreadyExists(NodeId),
     NodeNotExists(NodeId),
+    NodeTypeNotExists(String),
     OutputNotExists(NodeId, OutputId),
+    ParameterNotExists(NodeId, String),
+    PortNotExists(NodeId, String),
+    ProbeNotExists(ProbeId),
+    RecordingNotExists(NodeId, OutputId),
+    Rejected(String),
+    TypeMismatch(Connection),
+    UnsupportedNode(NodeId),
+    WrongNodeType(NodeId),
 }
 impl fmt::Debug for GraphError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
+            GraphError::BackendUnavailable => write!(f, "No device is available for the backend."),
+            GraphError::BufferSizeMismatch(length) => {
+                write!(f, "External buffer with length {} does not match the block size.", length)
+            }
+            GraphError::ChannelMismatch(connection) => {
+                write!(f, "{:?} connects ports with different numbers of channels.", connection)
+            }
             GraphError::ConnectionNotExists(connection) => write!(f, "{:?} does not exist in graph.", connection),
             GraphError::CycleWithoutDelay => write!(f, "Graph contains a cycle without delay."),
+            GraphError::ExternalInputNotExists(index) => {
+                write!(f, "Graph input with index {} does not exist in graph.", index)
+            }
+            GraphError::ExternalOutputNotExists(index) => {
+                write!(f, "Graph output with index {} does not exist in graph.", index)
+            }
             GraphError::InputAlreadyConnected(node, input) => {
                 write!(f, "Input with id {} on node with id {} is already connected.", input.0, node.0)
             }
             GraphError::InputNotExists(node, input) => {
                 write!(f, "Input with id {} does not exist on node with id {}.", input.0, node.0)
             }
+            GraphError::InvalidFormat(message) => write!(f, "Invalid format: {}", message),
+            GraphError::InvalidRateDivisor(node) => {
+                write!(f, "Rate divisor of node with id {} must be positive.", node.0)
+            }
+            GraphError::LabelAlreadyUsed(label) => write!(f, "Label '{}' is already used in graph.", label),
+            GraphError::LabelNotExists(label) => write!(f, "Label '{}' does not exist in graph.", label),
+            GraphError::NodeAlreadyExists(node) => write!(f, "Node with id {} already exists in graph.", node.0),
             GraphError::NodeNotExists(node) => write!(f, "Node with id {} does not exist in graph.", node.0),
+            GraphError::NodeTypeNotExists(name) => write!(f, "Node type {} is not registered.", name),
             GraphError::OutputNotExists(node, output) => {
                 write!(f, "Output with id {} does not exist on node with id {}.", output.0, node.0)
             }
+            GraphError::ParameterNotExists(node, name) => {
+                write!(f, "Parameter '{}' does not exist on node with id {}.", name, node.0)
+            }
+            GraphError::PortNotExists(node, name) => {
+                write!(f, "Port '{}' does not exist on node with id {}.", name, node.0)
+            }
+            GraphError::ProbeNotExists(probe) => write!(f, "Probe with id {} does not exist in graph.", probe.0),
+            GraphError::RecordingNotExists(node, output) => {
+                write!(f, "Output with id {} on node with id {} is not recorded.", output.0, node.0)
+            }
+            GraphError::Rejected(reason) => write!(f, "Connection was rejected by validator ({}).", reason),
+            GraphError::TypeMismatch(connection) => {
+                write!(f, "{:?} connects ports with incompatible value kinds.", connection)
+            }
+            GraphError::UnsupportedNode(node) => write!(f, "Node with id {} is not supported by the backend.", node.0),
+            GraphError::WrongNodeType(node) => write!(f, "Node with id {} has a different type.", node.0),
         }
     }
 }
 
 /// Unit tests.
-#[cfg(test)]
+#[cfg(all(test, feature = "dsp", feature = "generators", feature = "math"))]
 mod tests {
     use super::*;
-    use crate::nodes;
+    use crate::{nodes, PortInfo, ValueKind};
+    use std::sync::Mutex;
 
     #[test]
     fn add_connection() {
@@ -273,6 +1323,76 @@ mod tests {
         assert_eq!(graph.connections.len(), 1);
     }
 
+    /// Node that outputs whether its input is positive.
+    struct Comparator {
+        positive: bool,
+    }
+    impl Node for Comparator {
+        fn delayed_processing(&self) -> bool {
+            false
+        }
+        fn get_output(&self, _id: OutputId) -> Value {
+            self.positive as u8 as Value
+        }
+        fn input_info(&self, _id: InputId) -> PortInfo<'_> {
+            PortInfo { kind: ValueKind::Integer, ..PortInfo::new(None) }
+        }
+        fn list_inputs(&self) -> &[InputId] {
+            &[InputId(0)]
+        }
+        fn list_outputs(&self) -> &[OutputId] {
+            &[OutputId(0)]
+        }
+        fn output_info(&self, _id: OutputId) -> PortInfo<'_> {
+            PortInfo { kind: ValueKind::Boolean, ..PortInfo::new(None) }
+        }
+        fn set_input(&mut self, _id: InputId, value: Value) {
+            self.positive = value > 0.0;
+        }
+    }
+
+    #[test]
+    fn add_connection_checks_kinds() {
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        let cmp0 = graph.add_node(Box::from(Comparator { positive: false }));
+        let var1 = graph.add_node(Box::from(nodes::Variable::new(1.0)));
+        let cmp2 = graph.add_node(Box::from(Comparator { positive: false }));
+        let sensor3 = graph.add_node(Box::from(Sensor { value: 1.0 }));
+
+        let connection = Connection::new(cmp0, OutputId(0), var1, InputId(0));
+        assert_eq!(graph.add_connection(connection), Err(GraphError::TypeMismatch(connection)));
+        let connection = Connection::new(var1, OutputId(0), cmp2, InputId(0));
+        assert_eq!(graph.add_connection(connection), Err(GraphError::TypeMismatch(connection)));
+
+        // Status outputs are integers, which are accepted as continuous values.
+        graph.add_connection(Connection::new(sensor3, OutputId::STATUS, cmp2, InputId(0))).unwrap();
+        graph.add_connection(Connection::new(sensor3, OutputId::STATUS, var1, InputId(0))).unwrap();
+        assert!(ValueKind::Continuous.accepts(ValueKind::Integer));
+        assert!(!ValueKind::Integer.accepts(ValueKind::Continuous));
+    }
+
+    #[test]
+    fn connection_validator() {
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        let var0 = graph.add_node(Box::from(nodes::Variable::new(1.0)));
+        let del1 = graph.add_node(Box::from(nodes::Delay::new()));
+        let var2 = graph.add_node(Box::from(nodes::Variable::new(1.0)));
+        graph.set_label(del1, Some("audio")).unwrap();
+        graph.set_connection_validator(|graph, connection| match graph.get_label(connection.target_node) {
+            Ok(Some("audio")) => Err("audio inputs only accept delays".to_string()),
+            _ => Ok(()),
+        });
+
+        let connection = Connection::new(var0, OutputId(0), del1, InputId(0));
+        let error = GraphError::Rejected("audio inputs only accept delays".to_string());
+        assert_eq!(format!("{:?}", error), "Connection was rejected by validator (audio inputs only accept delays).");
+        assert_eq!(graph.add_connection(connection), Err(error));
+        graph.add_connection(Connection::new(del1, OutputId(0), var2, InputId(0))).unwrap();
+
+        graph.remove_connection_validator();
+        assert_eq!(graph.add_connection(connection), Ok(connection));
+    }
+
     #[test]
     fn add_node() {
         let mut graph: Graph<Box<dyn Node>> = Graph::new();
@@ -287,32 +1407,73 @@ mod tests {
         assert_eq!(node1, NodeId(1));
     }
 
+    #[test]
+    fn reserve() {
+        let mut graph: Graph<Box<dyn Node>> = Graph::with_capacity(100, 200);
+        assert!(graph.nodes.capacity() >= 100);
+        assert!(graph.connections.capacity() >= 200);
+        graph.add_node(Box::from(nodes::Variable::new(1.0)));
+        graph.reserve(1000, 0);
+        assert!(graph.nodes.capacity() >= 1001);
+    }
+
+    #[test]
+    fn add_nodes_and_connections() {
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        let chain = graph.add_nodes((0..1000).map(|_| Box::new(nodes::Variable::new(1.0)) as Box<dyn Node>));
+        assert_eq!(chain.len(), 1000);
+        assert_eq!(chain[999], NodeId(999));
+        let connections: Vec<Connection> =
+            chain.windows(2).map(|pair| Connection::new(pair[0], OutputId(0), pair[1], InputId(0))).collect();
+        assert_eq!(graph.add_connections(connections.clone()), Ok(connections));
+        assert_eq!(graph.processing_order(), chain.as_slice());
+
+        // Batches are validated as a whole.
+        let (add0, add1) =
+            (graph.add_node(Box::from(nodes::Addition::new())), graph.add_node(Box::from(nodes::Addition::new())));
+        let batch = [
+            Connection::new(add0, OutputId(0), add1, InputId(0)),
+            Connection::new(chain[0], OutputId(0), add1, InputId(0)),
+        ];
+        assert_eq!(graph.add_connections(batch), Err(GraphError::InputAlreadyConnected(add1, InputId(0))));
+        let batch = [
+            Connection::new(add0, OutputId(0), add1, InputId(0)),
+            Connection::new(add1, OutputId(0), add0, InputId(0)),
+        ];
+        assert_eq!(graph.add_connections(batch), Err(GraphError::CycleWithoutDelay));
+        assert_eq!(graph.connections().len(), 999);
+    }
+
     #[test]
     fn processing_order() {
         let mut graph: Graph<Box<dyn Node>> = Graph::new();
         let var0 = graph.add_node(Box::from(nodes::Variable::new(1.0)));
         let var1 = graph.add_node(Box::from(nodes::Variable::new(2.0)));
         graph.add_connection(Connection::new(var0, OutputId(0), var1, InputId(0))).unwrap();
-        assert_eq!(graph.processing_order.iter().cloned().collect::<Vec<NodeId>>(), vec![var0, var1]);
+        assert_eq!(graph.processing_order, vec![var0, var1]);
 
         let add2 = graph.add_node(Box::from(nodes::Addition::new()));
         graph.add_connection(Connection::new(var0, OutputId(0), add2, InputId(0))).unwrap();
         graph.add_connection(Connection::new(var1, OutputId(0), add2, InputId(1))).unwrap();
-        assert_eq!(graph.processing_order.iter().cloned().collect::<Vec<NodeId>>(), vec![var0, var1, add2]);
+        assert_eq!(graph.processing_order, vec![var0, var1, add2]);
 
         let var3 = graph.add_node(Box::from(nodes::Variable::new(3.0)));
         graph.add_connection(Connection::new(var3, OutputId(0), var0, InputId(0))).unwrap();
-        assert_eq!(graph.processing_order.iter().cloned().collect::<Vec<NodeId>>(), vec![var3, var0, var1, add2]);
+        assert_eq!(graph.processing_order, vec![var3, var0, var1, add2]);
 
         let delay4 = graph.add_node(Box::from(nodes::Delay::new()));
         let add5 = graph.add_node(Box::from(nodes::Addition::new()));
         graph.add_connection(Connection::new(add5, OutputId(0), delay4, InputId(0))).unwrap();
         graph.add_connection(Connection::new(delay4, OutputId(0), add5, InputId(0))).unwrap();
         graph.add_connection(Connection::new(add2, OutputId(0), add5, InputId(1))).unwrap();
-        assert_eq!(
-            graph.processing_order.iter().cloned().collect::<Vec<NodeId>>(),
-            vec![var3, var0, var1, add2, add5, delay4]
-        );
+        assert_eq!(graph.processing_order, vec![var3, var0, var1, add2, add5, delay4]);
+
+        // Independent nodes are ordered by id.
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        let ids = graph.add_nodes((0..10).map(|_| Box::from(nodes::Variable::new(0.0)) as Box<dyn Node>));
+        graph.add_connection(Connection::new(ids[9], OutputId(0), ids[3], InputId(0))).unwrap();
+        let expected: Vec<NodeId> = [0, 1, 2, 4, 5, 6, 7, 8, 9, 3].iter().map(|&index| ids[index]).collect();
+        assert_eq!(graph.processing_order(), expected.as_slice());
     }
 
     #[test]
@@ -325,6 +1486,144 @@ mod tests {
         assert_eq!(graph.get_node_mut(NodeId(0)).map(|n| n.get_output(OutputId(0))), Ok(1.0));
     }
 
+    #[test]
+    fn get_node_as() {
+        let mut graph: Graph<nodes::BuiltinNode> = Graph::new();
+        let del0 = graph.add_node(nodes::Delay::new().into());
+        graph.get_node_as_mut::<nodes::Delay>(del0).unwrap().reset_to(2.0);
+        assert_eq!(graph.get_node_as::<nodes::Delay>(del0).map(|n| n.get_output(OutputId(0))), Ok(2.0));
+        assert_eq!(graph.get_node_as::<nodes::Addition>(del0).err(), Some(GraphError::WrongNodeType(del0)));
+        assert_eq!(graph.get_node_as_mut::<nodes::Delay>(NodeId(1)).err(), Some(GraphError::NodeNotExists(NodeId(1))));
+    }
+
+    #[test]
+    fn labels() {
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        let var0 = graph.add_node(Box::from(nodes::Variable::new(1.0)));
+        let var1 = graph.add_node(Box::from(nodes::Variable::new(2.0)));
+        assert_eq!(graph.get_label(var0), Ok(None));
+        assert_eq!(graph.find_node("a"), Err(GraphError::LabelNotExists("a".to_string())));
+
+        graph.set_label(var0, Some("a")).unwrap();
+        assert_eq!(graph.get_label(var0), Ok(Some("a")));
+        assert_eq!(graph.find_node("a"), Ok(var0));
+        assert_eq!(graph.set_label(var1, Some("a")), Err(GraphError::LabelAlreadyUsed("a".to_string())));
+        assert_eq!(graph.set_label(NodeId(2), Some("b")), Err(GraphError::NodeNotExists(NodeId(2))));
+
+        graph.set_label(var0, Some("b")).unwrap();
+        graph.set_label(var1, Some("a")).unwrap();
+        assert_eq!(graph.find_node("b"), Ok(var0));
+        assert_eq!(graph.find_node("a"), Ok(var1));
+
+        graph.set_label(var0, None).unwrap();
+        assert_eq!(graph.get_label(var0), Ok(None));
+        graph.remove_node(var1).unwrap();
+        assert_eq!(graph.find_node("a"), Err(GraphError::LabelNotExists("a".to_string())));
+    }
+
+    #[test]
+    fn clearing_label_keeps_node_settings() {
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        let var0 = graph.add_node(Box::from(nodes::Variable::new(1.0)));
+        graph.set_label(var0, Some("a")).unwrap();
+        graph.set_rate_divisor(var0, 4).unwrap();
+        graph.set_priority(var0, 2).unwrap();
+
+        graph.set_label(var0, None).unwrap();
+        assert_eq!(graph.get_label(var0), Ok(None));
+        assert_eq!(graph.get_rate_divisor(var0), Ok(4));
+        assert_eq!(graph.get_priority(var0), Ok(2));
+    }
+
+    /// Node that passes its input through and fails for negative values.
+    struct Sensor {
+        value: Value,
+    }
+    impl Node for Sensor {
+        fn delayed_processing(&self) -> bool {
+            false
+        }
+        fn get_output(&self, _id: OutputId) -> Value {
+            self.value
+        }
+        fn list_inputs(&self) -> &[InputId] {
+            &[InputId(0)]
+        }
+        fn list_outputs(&self) -> &[OutputId] {
+            &[OutputId(0)]
+        }
+        fn set_input(&mut self, _id: InputId, value: Value) {
+            self.value = value;
+        }
+        fn try_process(&mut self, _context: &ProcessContext) -> Result<(), ProcessError> {
+            if self.value < 0.0 {
+                return Err(ProcessError(7));
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn status_output() {
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        let sensor0 = graph.add_node(Box::from(Sensor { value: 1.0 }));
+        let var1 = graph.add_node(Box::from(nodes::Variable::new(0.0)));
+        graph.add_connection(Connection::new(sensor0, OutputId::STATUS, var1, InputId(0))).unwrap();
+        assert_eq!(graph.get_status(sensor0), Ok(0));
+        assert_eq!(graph.get_output(sensor0, OutputId::STATUS), Ok(0.0));
+        assert_eq!(graph.get_output(var1, OutputId(1)), Err(GraphError::OutputNotExists(var1, OutputId(1))));
+
+        graph.get_node_mut(sensor0).unwrap().set_input(InputId(0), -1.0);
+        graph.process();
+        assert_eq!(graph.get_status(sensor0), Ok(7));
+        assert_eq!(graph.get_output(var1, OutputId(0)), Ok(7.0));
+
+        graph.get_node_mut(sensor0).unwrap().set_input(InputId(0), 2.0);
+        graph.process();
+        assert_eq!(graph.get_status(sensor0), Ok(0));
+        assert_eq!(graph.get_output(var1, OutputId(0)), Ok(0.0));
+
+        // Block status reports last failed frame.
+        graph.get_node_mut(sensor0).unwrap().set_input(InputId(0), -2.0);
+        graph.set_block_size(2);
+        graph.process_block();
+        assert_eq!(graph.get_block_output(sensor0, OutputId::STATUS), Ok(&[7.0, 7.0][..]));
+        assert_eq!(graph.get_block_output(var1, OutputId(0)), Ok(&[7.0, 7.0][..]));
+    }
+
+    #[test]
+    fn process_dangling_connection() {
+        type SendGraph = Graph<Box<dyn Node + Send>>;
+        let executors: [fn(&mut SendGraph); 5] = [
+            |graph| graph.set_executor(executors::Compiled::new()),
+            |graph| graph.set_executor(executors::Flat::new()),
+            |graph| graph.set_executor(executors::Parallel::new(2)),
+            |graph| graph.set_executor(executors::Pipelined::new(2)),
+            |graph| graph.set_executor(executors::Sequential),
+        ];
+        for set_executor in executors {
+            let mut graph: SendGraph = Graph::new();
+            let var0 = graph.add_node(Box::from(nodes::Variable::new(1.0)));
+            let add1 = graph.add_node(Box::from(nodes::Addition::new()));
+            let add2 = graph.add_node(Box::from(nodes::Addition::new()));
+            graph.add_connection(Connection::new(var0, OutputId(0), add1, InputId(0))).unwrap();
+            graph.add_connection(Connection::new(var0, OutputId(0), add2, InputId(0))).unwrap();
+            set_executor(&mut graph);
+            graph.process();
+
+            // Connection from a node that does not exist and a node missing in the graph.
+            graph.connections.push(Connection::new(NodeId(9), OutputId(0), add2, InputId(1)));
+            graph.processing_order.push(NodeId(8));
+            graph.topology_version += 1;
+            graph.set_input(var0, InputId(0), 2.0).unwrap();
+            graph.process();
+            assert_eq!(graph.get_output(add1, OutputId(0)), Ok(2.0));
+            assert_eq!(graph.get_output(add2, OutputId(0)), Ok(1.0));
+            assert_eq!(graph.get_status(add2), Ok(ProcessError::MISSING_SOURCE.0));
+            assert_eq!(graph.get_skipped_nodes(), [add2]);
+        }
+    }
+
     #[test]
     fn iter_node() {
         let mut graph: Graph<Box<dyn Node>> = Graph::new();
@@ -345,7 +1644,7 @@ mod tests {
         graph.add_connection(Connection::new(var0, OutputId(0), add1, InputId(0))).unwrap();
         graph.add_connection(Connection::new(add1, OutputId(0), del2, InputId(0))).unwrap();
         graph.add_connection(Connection::new(del2, OutputId(0), add1, InputId(1))).unwrap();
-        assert_eq!(graph.processing_order.iter().cloned().collect::<Vec<NodeId>>(), vec![var0, add1, del2]);
+        assert_eq!(graph.processing_order, vec![var0, add1, del2]);
 
         assert_eq!(graph.get_node(add1).unwrap().get_output(OutputId(0)), 0.0);
         graph.process();
@@ -356,6 +1655,87 @@ mod tests {
         assert_eq!(graph.get_node(add1).unwrap().get_output(OutputId(0)), 3.0);
     }
 
+    #[test]
+    fn process_block() {
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        let var0 = graph.add_node(Box::from(nodes::Variable::new(1.0)));
+        let var1 = graph.add_node(Box::from(nodes::Variable::new(2.0)));
+        let add2 = graph.add_node(Box::from(nodes::Addition::new()));
+        let del3 = graph.add_node(Box::from(nodes::Delay::new()));
+        graph.add_connection(Connection::new(var0, OutputId(0), add2, InputId(0))).unwrap();
+        graph.add_connection(Connection::new(var1, OutputId(0), add2, InputId(1))).unwrap();
+        graph.add_connection(Connection::new(add2, OutputId(0), del3, InputId(0))).unwrap();
+        graph.set_block_size(4);
+        assert_eq!(graph.block_size(), 4);
+        assert_eq!(graph.get_block_output(add2, OutputId(0)), Ok(&[0.0; 4][..]));
+        assert_eq!(graph.get_block_output(add2, OutputId(1)), Err(GraphError::OutputNotExists(add2, OutputId(1))));
+
+        graph.process_block();
+        assert_eq!(graph.get_block_output(add2, OutputId(0)), Ok(&[3.0; 4][..]));
+        assert_eq!(graph.get_block_output(del3, OutputId(0)), Ok(&[0.0, 3.0, 3.0, 3.0][..]));
+        assert_eq!(graph.get_node(del3).unwrap().get_output(OutputId(0)), 3.0);
+
+        // Unconnected inputs keep their value, removed connections no longer feed the input.
+        graph.get_node_mut(var1).unwrap().set_input(InputId(0), 4.0);
+        graph.remove_node(var0).unwrap();
+        graph.process_block();
+        assert_eq!(graph.get_block_output(add2, OutputId(0)), Ok(&[5.0; 4][..]));
+        assert_eq!(graph.get_block_output(del3, OutputId(0)), Ok(&[3.0, 5.0, 5.0, 5.0][..]));
+
+        // Feedback through delayed nodes reaches other nodes one block later.
+        graph.add_connection(Connection::new(del3, OutputId(0), add2, InputId(0))).unwrap();
+        graph.process_block();
+        assert_eq!(graph.get_block_output(add2, OutputId(0)), Ok(&[7.0, 9.0, 9.0, 9.0][..]));
+    }
+
+    #[test]
+    fn block_routes() {
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        let var0 = graph.add_node(Box::from(nodes::Variable::new(1.0)));
+        let var1 = graph.add_node(Box::from(nodes::Variable::new(2.0)));
+        let add2 = graph.add_node(Box::from(nodes::Addition::new()));
+        let add3 = graph.add_node(Box::from(nodes::Addition::new()));
+        let del4 = graph.add_node(Box::from(nodes::Delay::new()));
+        graph.add_connection(Connection::new(var0, OutputId(0), add2, InputId(0))).unwrap();
+        graph.add_connection(Connection::new(var1, OutputId(0), add2, InputId(1))).unwrap();
+        graph.add_connection(Connection::new(var1, OutputId(0), add3, InputId(0))).unwrap();
+        graph.add_connection(Connection::new(del4, OutputId(0), del4, InputId(0))).unwrap();
+        graph.set_block_size(2);
+        graph.process_block();
+
+        // Exclusive sources are shared, sources with multiple targets and self-connections are copied.
+        assert_eq!(graph.block_routes[&(add2, InputId(0))], BlockRoute::Shared(var0, OutputId(0)));
+        assert_eq!(graph.block_routes[&(add2, InputId(1))], BlockRoute::Copied(var1, OutputId(0)));
+        assert_eq!(graph.block_routes[&(add3, InputId(0))], BlockRoute::Copied(var1, OutputId(0)));
+        assert_eq!(graph.block_routes[&(del4, InputId(0))], BlockRoute::Copied(del4, OutputId(0)));
+        assert_eq!(graph.input_buffers.len(), 3);
+        assert_eq!(graph.get_block_output(var0, OutputId(0)), Ok(&[1.0, 1.0][..]));
+        assert_eq!(graph.get_block_output(add2, OutputId(0)), Ok(&[3.0, 3.0][..]));
+        assert_eq!(graph.get_block_output(add3, OutputId(0)), Ok(&[2.0, 2.0][..]));
+
+        // Routes follow topology changes.
+        graph.remove_connection(Connection::new(var1, OutputId(0), add3, InputId(0))).unwrap();
+        graph.process_block();
+        assert_eq!(graph.block_routes[&(add2, InputId(1))], BlockRoute::Shared(var1, OutputId(0)));
+        assert_eq!(graph.input_buffers.len(), 1);
+    }
+
+    #[test]
+    fn process_n() {
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        let var0 = graph.add_node(Box::from(nodes::Variable::new(1.0)));
+        let add1 = graph.add_node(Box::from(nodes::Addition::new()));
+        let del2 = graph.add_node(Box::from(nodes::Delay::new()));
+        graph.add_connection(Connection::new(var0, OutputId(0), add1, InputId(0))).unwrap();
+        graph.add_connection(Connection::new(add1, OutputId(0), del2, InputId(0))).unwrap();
+        graph.add_connection(Connection::new(del2, OutputId(0), add1, InputId(1))).unwrap();
+
+        graph.process_n(0);
+        assert_eq!(graph.get_node(add1).unwrap().get_output(OutputId(0)), 0.0);
+        graph.process_n(10);
+        assert_eq!(graph.get_node(add1).unwrap().get_output(OutputId(0)), 10.0);
+    }
+
     #[test]
     fn remove_connection() {
         let mut graph: Graph<Box<dyn Node>> = Graph::new();
@@ -393,4 +1773,111 @@ mod tests {
 
         assert_eq!(graph.remove_node(node1).err(), Some(GraphError::NodeNotExists(node1)));
     }
+
+    #[test]
+    fn remove_nodes() {
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        let ids = graph.add_nodes((0..6).map(|value| Box::new(nodes::Variable::new(value as Value)) as Box<dyn Node>));
+        let connections: Vec<Connection> =
+            ids.windows(2).map(|pair| Connection::new(pair[0], OutputId(0), pair[1], InputId(0))).collect();
+        graph.add_connections(connections).unwrap();
+
+        // Invalid selections leave the graph unchanged.
+        assert_eq!(graph.remove_nodes(&[ids[0], NodeId(9)]).err(), Some(GraphError::NodeNotExists(NodeId(9))));
+        assert_eq!(graph.remove_nodes(&[ids[0], ids[0]]).err(), Some(GraphError::NodeNotExists(ids[0])));
+        assert_eq!(graph.nodes.len(), 6);
+
+        let removed = graph.remove_nodes(&[ids[4], ids[1]]).unwrap();
+        assert_eq!(removed.iter().map(|node| node.get_output(OutputId(0))).collect::<Vec<_>>(), vec![4.0, 1.0]);
+        assert_eq!(graph.connections(), &[Connection::new(ids[2], OutputId(0), ids[3], InputId(0))]);
+
+        graph.retain_nodes(|id, node| id == ids[0] || node.get_output(OutputId(0)) > 2.0);
+        assert_eq!(graph.processing_order().len(), 3);
+        assert!(graph.get_node(ids[2]).is_err());
+        assert!(graph.connections().is_empty());
+    }
+
+    #[test]
+    fn prime() {
+        // Variable delayed twice, with the last delay probed and recorded.
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        let var0 = graph.add_node(Box::from(nodes::Variable::new(1.0)));
+        let del1 = graph.add_node(Box::from(nodes::Delay::new()));
+        let del2 = graph.add_node(Box::from(nodes::Delay::new()));
+        graph.add_connection(Connection::new(var0, OutputId(0), del1, InputId(0))).unwrap();
+        graph.add_connection(Connection::new(del1, OutputId(0), del2, InputId(0))).unwrap();
+        let frames = Arc::new(Mutex::new(Vec::with_capacity(1)));
+        let sink = frames.clone();
+        graph.add_probe(del2, OutputId(0), move |_, frame| sink.lock().unwrap().push(frame)).unwrap();
+        graph.record(del2, OutputId(0), 4).unwrap();
+
+        graph.prime(3);
+        assert_eq!(graph.context().frame, 3);
+        assert_eq!(graph.get_output(del2, OutputId(0)), Ok(1.0));
+        assert!(frames.lock().unwrap().is_empty());
+        assert!(graph.recording(del2, OutputId(0)).unwrap().is_empty());
+        assert_eq!(graph.stats().cycles, 0);
+
+        graph.process();
+        assert_eq!(*frames.lock().unwrap(), [3]);
+        assert_eq!(graph.recording(del2, OutputId(0)), Ok(&[1.0][..]));
+        assert_eq!(graph.stats().cycles, 1);
+    }
+
+    #[test]
+    fn reset() {
+        // Accumulator seeded by a delay with an initial value.
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        let var0 = graph.add_node(Box::from(nodes::Variable::new(1.0)));
+        let add1 = graph.add_node(Box::from(nodes::Addition::new()));
+        let del2 = graph.add_node(Box::from(nodes::Delay::with_initial(10.0)));
+        graph.add_connection(Connection::new(var0, OutputId(0), add1, InputId(0))).unwrap();
+        graph.add_connection(Connection::new(add1, OutputId(0), del2, InputId(0))).unwrap();
+        graph.add_connection(Connection::new(del2, OutputId(0), add1, InputId(1))).unwrap();
+        graph.set_input(var0, InputId(0), 2.0).unwrap();
+        graph.process_n(3);
+        assert_eq!(graph.get_output(add1, OutputId(0)), Ok(16.0));
+
+        // Inputs set through the graph are kept.
+        graph.reset();
+        assert_eq!(graph.context().frame, 0);
+        assert_eq!(graph.get_output(del2, OutputId(0)), Ok(10.0));
+        graph.process_n(3);
+        assert_eq!(graph.get_output(add1, OutputId(0)), Ok(16.0));
+    }
+
+    #[test]
+    fn set_input() {
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        let var0 = graph.add_node(Box::from(nodes::Variable::new(1.0)));
+        assert_eq!(graph.set_input(var0, InputId(0), 2.0), Ok(()));
+        assert_eq!(graph.get_output(var0, OutputId(0)), Ok(2.0));
+        assert_eq!(graph.set_input(var0, InputId(1), 2.0), Err(GraphError::InputNotExists(var0, InputId(1))));
+        assert_eq!(graph.set_input(NodeId(1), InputId(0), 2.0), Err(GraphError::NodeNotExists(NodeId(1))));
+    }
+
+    #[test]
+    fn process_with() {
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        let var0 = graph.add_node(Box::from(nodes::Variable::new(0.0)));
+        let var1 = graph.add_node(Box::from(nodes::Variable::new(0.0)));
+        let add2 = graph.add_node(Box::from(nodes::Addition::new()));
+        graph.add_connection(Connection::new(var0, OutputId(0), add2, InputId(0))).unwrap();
+        graph.add_connection(Connection::new(var1, OutputId(0), add2, InputId(1))).unwrap();
+
+        let outputs = graph.process_with(&[(var0, InputId(0), 1.0), (var1, InputId(0), 2.0)], &[(add2, OutputId(0))]);
+        assert_eq!(outputs, Ok(HashMap::from([((add2, OutputId(0)), 3.0)])));
+
+        // Invalid ports leave the graph unchanged.
+        assert_eq!(
+            graph.process_with(&[(var0, InputId(0), 5.0), (var1, InputId(1), 0.0)], &[]),
+            Err(GraphError::InputNotExists(var1, InputId(1)))
+        );
+        assert_eq!(
+            graph.process_with(&[(var0, InputId(0), 5.0)], &[(var0, OutputId(1))]),
+            Err(GraphError::OutputNotExists(var0, OutputId(1)))
+        );
+        assert_eq!(graph.get_outputs(&[(var0, OutputId(0)), (add2, OutputId(0))]).unwrap()[&(var0, OutputId(0))], 1.0);
+        assert_eq!(graph.context().frame, 1);
+    }
 }
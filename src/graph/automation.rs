@@ -0,0 +1,220 @@
+use super::Graph;
+use crate::{GraphError, InputId, Node, NodeId, Value};
+
+/// Automated port of a node.
+#[derive(Clone, Debug, PartialEq)]
+enum Port {
+    /// Value input.
+    Input(InputId),
+
+    /// Named parameter.
+    Parameter(String),
+}
+
+/// Ramp of a port value towards a target.
+pub(super) struct Automation {
+    /// Whether value changes exponentially.
+    exponential: bool,
+
+    /// Automated node.
+    node: NodeId,
+
+    /// Automated port.
+    port: Port,
+
+    /// Number of frames until the target is reached.
+    remaining: u64,
+
+    /// Change of the value per frame (factor for exponential ramps, summand for linear ramps).
+    step: Value,
+
+    /// Target value.
+    target: Value,
+
+    /// Current value.
+    value: Value,
+}
+
+impl<N: Node> Graph<N> {
+    /// Ramps an input linearly to a target value over a number of frames (replacing a running automation of the
+    /// input), starting at the value last set through the graph (or the default of the input).
+    ///
+    /// Automated values are applied at the start of each cycle (once per block in block processing, advancing by the
+    /// block size). Connected inputs are overwritten when processing.
+    pub fn automate(&mut self, node: NodeId, input: InputId, target: Value, frames: u64) -> Result<(), GraphError> {
+        self.automate_input(node, input, target, frames, false)
+    }
+
+    /// Ramps an input exponentially to a target value over a number of frames (see `automate`), falling back to a
+    /// linear ramp if start and target values are not both positive or both negative.
+    pub fn automate_exponential(
+        &mut self,
+        node: NodeId,
+        input: InputId,
+        target: Value,
+        frames: u64,
+    ) -> Result<(), GraphError> {
+        self.automate_input(node, input, target, frames, true)
+    }
+
+    /// Ramps a parameter linearly to a target value over a number of frames, starting at its current value (see
+    /// `automate`).
+    pub fn automate_parameter(
+        &mut self,
+        node: NodeId,
+        name: &str,
+        target: Value,
+        frames: u64,
+    ) -> Result<(), GraphError> {
+        let value = self.get_parameter(node, name)?;
+        self.schedule(node, Port::Parameter(name.to_string()), value, target, frames, false);
+        Ok(())
+    }
+
+    /// Returns whether an input of a node is automated.
+    pub fn is_automated(&self, node: NodeId, input: InputId) -> bool {
+        self.automations.iter().any(|automation| {
+            automation.node == node && automation.port == Port::Input(input) && automation.remaining > 0
+        })
+    }
+
+    /// Advances automations by a number of frames and applies their values (finished automations are kept until the
+    /// next automation is scheduled, so processing does not deallocate).
+    pub(super) fn apply_automations(&mut self, frames: u64) {
+        for automation in self.automations.iter_mut().filter(|automation| automation.remaining > 0) {
+            let frames = frames.min(automation.remaining);
+            automation.remaining -= frames;
+            automation.value = match automation.remaining {
+                0 => automation.target,
+                _ if automation.exponential => automation.value * automation.step.powi(frames as i32),
+                _ => automation.value + automation.step * frames as Value,
+            };
+            let Some(node) = self.nodes.get_mut(&automation.node) else {
+                continue;
+            };
+            match &automation.port {
+                Port::Input(input) => {
+                    node.set_input(*input, automation.value);
+                    self.input_values.insert((automation.node, *input), automation.value);
+                }
+                Port::Parameter(name) => {
+                    node.set_parameter(name, automation.value);
+                    if let Some(memo) = self.memos.get_mut(&automation.node) {
+                        memo.valid = false;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns whether any port of a node is automated.
+    #[cfg(all(feature = "generators", feature = "math"))]
+    pub(super) fn has_automations(&self, node: NodeId) -> bool {
+        self.automations.iter().any(|automation| automation.node == node && automation.remaining > 0)
+    }
+
+    /// Removes automations of a node.
+    pub(super) fn remove_automations(&mut self, node: NodeId) {
+        self.automations.retain(|automation| automation.node != node);
+        self.input_values.retain(|&(n, _), _| n != node);
+    }
+
+    /// Validates an input and schedules its automation.
+    fn automate_input(
+        &mut self,
+        node: NodeId,
+        input: InputId,
+        target: Value,
+        frames: u64,
+        exponential: bool,
+    ) -> Result<(), GraphError> {
+        let processor = self.get_node(node)?;
+        if !processor.list_inputs().contains(&input) {
+            return Err(GraphError::InputNotExists(node, input));
+        }
+        let value = match self.input_values.get(&(node, input)) {
+            Some(&value) => value,
+            None => processor.input_info(input).default,
+        };
+
+        // Entry is created up front so processing does not allocate.
+        self.input_values.insert((node, input), value);
+        self.schedule(node, Port::Input(input), value, target, frames, exponential);
+        Ok(())
+    }
+
+    /// Schedules automation of a port (replacing a running automation of the port).
+    fn schedule(&mut self, node: NodeId, port: Port, value: Value, target: Value, frames: u64, exponential: bool) {
+        let exponential = exponential && value * target > 0.0;
+        let step = match (frames, exponential) {
+            (0, _) => 0.0,
+            (_, true) => (target / value).powf(1.0 / frames as Value),
+            (_, false) => (target - value) / frames as Value,
+        };
+        self.automations
+            .retain(|automation| automation.remaining > 0 && (automation.node != node || automation.port != port));
+        self.automations.push(Automation { exponential, node, port, remaining: frames.max(1), step, target, value });
+    }
+}
+
+/// Unit tests.
+#[cfg(all(test, feature = "generators"))]
+mod tests {
+    use super::*;
+    use crate::{nodes, OutputId};
+
+    /// Returns output values of a node over a number of cycles.
+    fn record(graph: &mut Graph<Box<dyn Node>>, node: NodeId, cycles: usize) -> Vec<Value> {
+        let mut values = Vec::with_capacity(cycles);
+        for _ in 0..cycles {
+            graph.process();
+            values.push(graph.get_output(node, OutputId(0)).unwrap());
+        }
+        values
+    }
+
+    #[test]
+    fn ramps_linearly() {
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        let var0 = graph.add_node(Box::new(nodes::Variable::new(0.0)));
+        graph.set_input(var0, InputId(0), 1.0).unwrap();
+        graph.automate(var0, InputId(0), 3.0, 4).unwrap();
+        assert!(graph.is_automated(var0, InputId(0)));
+        assert_eq!(record(&mut graph, var0, 5), vec![1.5, 2.0, 2.5, 3.0, 3.0]);
+        assert!(!graph.is_automated(var0, InputId(0)));
+
+        // Next automation continues from the target.
+        graph.automate(var0, InputId(0), 2.0, 2).unwrap();
+        assert_eq!(record(&mut graph, var0, 2), vec![2.5, 2.0]);
+
+        // Block processing advances by the block size.
+        graph.set_block_size(2);
+        graph.automate(var0, InputId(0), 0.0, 4).unwrap();
+        graph.process_block();
+        assert_eq!(graph.get_output(var0, OutputId(0)), Ok(1.0));
+
+        // Invalid ports.
+        assert_eq!(graph.automate(var0, InputId(1), 0.0, 1), Err(GraphError::InputNotExists(var0, InputId(1))));
+        assert_eq!(
+            graph.automate_parameter(var0, "gain", 0.0, 1),
+            Err(GraphError::ParameterNotExists(var0, "gain".to_string()))
+        );
+        graph.remove_node(var0).unwrap();
+        assert!(graph.automations.is_empty());
+    }
+
+    #[test]
+    fn ramps_exponentially() {
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        let var0 = graph.add_node(Box::new(nodes::Variable::new(0.0)));
+        graph.set_input(var0, InputId(0), 1.0).unwrap();
+        graph.automate_exponential(var0, InputId(0), 8.0, 3).unwrap();
+        let values = record(&mut graph, var0, 3);
+        assert!((values[0] - 2.0).abs() < 1e-9 && (values[1] - 4.0).abs() < 1e-9);
+        assert_eq!(values[2], 8.0);
+
+        // Falls back to linear ramps through zero.
+        graph.automate_exponential(var0, InputId(0), -8.0, 2).unwrap();
+        assert_eq!(record(&mut graph, var0, 2), vec![0.0, -8.0]);
+    }
+}
@@ -0,0 +1,146 @@
+use super::Graph;
+use crate::{GraphError, InputId, Node, NodeId, OutputId, Value};
+use std::mem;
+
+impl<N: Node> Graph<N> {
+    /// Returns whether a node is bypassed.
+    pub fn is_bypassed(&self, node: NodeId) -> Result<bool, GraphError> {
+        self.get_node(node)?;
+        Ok(self.bypassed.contains_key(&node))
+    }
+
+    /// Sets whether a node is bypassed, i.e. its inputs are passed through to its outputs (see `Node::bypass_input`)
+    /// instead of processing it. Outputs without bypass input hold their values.
+    ///
+    /// Bypassed nodes are passed through when they would be processed, so bypassed nodes that introduce delay still
+    /// delay their inputs by one cycle.
+    pub fn set_bypassed(&mut self, node: NodeId, bypassed: bool) -> Result<(), GraphError> {
+        let processor = self.get_node(node)?;
+        match bypassed {
+            true if !self.bypassed.contains_key(&node) => {
+                let values = processor.list_outputs().iter().map(|&output| processor.get_output(output)).collect();
+                self.bypassed.insert(node, values);
+            }
+            true => {}
+            false => {
+                self.bypassed.remove(&node);
+            }
+        }
+        Ok(())
+    }
+
+    /// Passes inputs of a bypassed node through to its outputs.
+    pub(super) fn bypass(&mut self, node: NodeId) {
+        for index in 0..self.nodes[&node].list_outputs().len() {
+            let output = self.nodes[&node].list_outputs()[index];
+            if let Some(input) = self.nodes[&node].bypass_input(output) {
+                let value = match self.source_of(node, input) {
+                    Some((source, output)) => self.output_value(source, output),
+                    None => self.unconnected_value(node, input),
+                };
+                self.bypassed.get_mut(&node).unwrap()[index] = value;
+            }
+        }
+    }
+
+    /// Passes input buffers of a bypassed node through to its output buffers.
+    pub(super) fn bypass_block(&mut self, node: NodeId) {
+        for index in 0..self.nodes[&node].list_outputs().len() {
+            let output = self.nodes[&node].list_outputs()[index];
+            let mut buffer = mem::take(self.output_buffers.get_mut(&(node, output)).unwrap());
+            match self.nodes[&node].bypass_input(output) {
+                Some(input) => match self.source_of(node, input) {
+                    Some(source) if source != (node, output) => buffer.copy_from_slice(&self.output_buffers[&source]),
+                    Some(_) => {}
+                    None => buffer.fill(self.unconnected_value(node, input)),
+                },
+                None => buffer.fill(self.bypassed[&node][index]),
+            }
+            self.bypassed.get_mut(&node).unwrap()[index] = buffer.last().copied().unwrap_or(0.0);
+            self.output_buffers.insert((node, output), buffer);
+        }
+    }
+
+    /// Returns value of an output of a bypassed node (`None` if the node is not bypassed).
+    pub(super) fn bypassed_output(&self, node: NodeId, output: OutputId) -> Option<Value> {
+        let values = self.bypassed.get(&node)?;
+        let index = self.nodes[&node].list_outputs().iter().position(|&o| o == output)?;
+        Some(values[index])
+    }
+
+    /// Returns source output connected to an input.
+    pub(super) fn source_of(&self, node: NodeId, input: InputId) -> Option<(NodeId, OutputId)> {
+        self.connections
+            .iter()
+            .find(|c| c.target_node == node && c.target_input == input)
+            .map(|c| (c.source_node, c.source_output))
+    }
+
+    /// Returns value of an unconnected input (last set through the graph or its default).
+    pub(super) fn unconnected_value(&self, node: NodeId, input: InputId) -> Value {
+        match self.input_values.get(&(node, input)) {
+            Some(&value) => value,
+            None => self.nodes[&node].input_info(input).default,
+        }
+    }
+}
+
+/// Unit tests.
+#[cfg(all(test, feature = "generators"))]
+mod tests {
+    use super::*;
+    use crate::{executors, nodes, Connection};
+
+    /// Builds graph of a variable passed through a squaring node.
+    fn square() -> (Graph<Box<dyn Node + Send>>, NodeId, NodeId) {
+        let mut graph: Graph<Box<dyn Node + Send>> = Graph::new();
+        let var0 = graph.add_node(Box::new(nodes::Variable::new(3.0)));
+        let fn1 =
+            graph.add_node(Box::new(nodes::FnNode::new(1, 1, |inputs, outputs| outputs[0] = inputs[0] * inputs[0])));
+        graph.add_connection(Connection::new(var0, OutputId(0), fn1, InputId(0))).unwrap();
+        (graph, var0, fn1)
+    }
+
+    #[test]
+    fn passes_through() {
+        let (mut graph, var0, fn1) = square();
+        graph.process();
+        assert_eq!(graph.get_output(fn1, OutputId(0)), Ok(9.0));
+
+        graph.set_bypassed(fn1, true).unwrap();
+        assert_eq!(graph.is_bypassed(fn1), Ok(true));
+        assert_eq!(graph.get_output(fn1, OutputId(0)), Ok(9.0));
+        graph.process();
+        assert_eq!(graph.get_output(fn1, OutputId(0)), Ok(3.0));
+
+        graph.set_bypassed(fn1, false).unwrap();
+        graph.process();
+        assert_eq!(graph.get_output(fn1, OutputId(0)), Ok(9.0));
+
+        // Other executors and block processing.
+        for executor in 0..3 {
+            let (mut graph, _, fn1) = square();
+            graph.set_bypassed(fn1, true).unwrap();
+            match executor {
+                0 => graph.set_executor(executors::Compiled::new()),
+                1 => graph.set_executor(executors::Parallel::new(2)),
+                _ => {
+                    graph.set_block_size(4);
+                    graph.process_block();
+                    assert_eq!(graph.get_output(fn1, OutputId(0)), Ok(3.0));
+                    continue;
+                }
+            }
+            graph.process();
+            assert_eq!(graph.get_output(fn1, OutputId(0)), Ok(3.0));
+        }
+
+        // Unconnected inputs pass their last value.
+        graph.remove_node(var0).unwrap();
+        graph.set_bypassed(fn1, true).unwrap();
+        graph.set_input(fn1, InputId(0), 2.0).unwrap();
+        graph.process();
+        assert_eq!(graph.get_output(fn1, OutputId(0)), Ok(2.0));
+        assert_eq!(graph.set_bypassed(NodeId(2), true), Err(GraphError::NodeNotExists(NodeId(2))));
+    }
+}
@@ -0,0 +1,80 @@
+use super::{has_output, Graph};
+use crate::{GraphError, Node, NodeId, OutputId, Value};
+
+impl<N: Node> Graph<N> {
+    /// Returns range an output is clamped to (if any).
+    pub fn output_clamp(&self, node: NodeId, output: OutputId) -> Option<(Value, Value)> {
+        self.output_clamps.get(&(node, output)).copied()
+    }
+
+    /// Sets range (minimum, maximum) that values of an output are clamped to when read or transferred, e.g. to keep
+    /// an actuator command within its limits (`None` removes the clamp). NaN values are clamped to the minimum.
+    pub fn set_output_clamp(
+        &mut self,
+        node: NodeId,
+        output: OutputId,
+        range: Option<(Value, Value)>,
+    ) -> Result<(), GraphError> {
+        if !has_output(self.get_node(node)?, output) {
+            return Err(GraphError::OutputNotExists(node, output));
+        }
+        match range {
+            Some(range) => self.output_clamps.insert((node, output), range),
+            None => self.output_clamps.remove(&(node, output)),
+        };
+        self.prepare_memos();
+        Ok(())
+    }
+
+    /// Clamps output buffers of a node in block processing.
+    pub(super) fn clamp_block_outputs(&self, node: NodeId, outputs: &[OutputId], buffers: &mut [Vec<Value>]) {
+        for (&output, buffer) in outputs.iter().zip(buffers) {
+            if let Some(&(min, max)) = self.output_clamps.get(&(node, output)) {
+                buffer.iter_mut().for_each(|value| *value = value.max(min).min(max));
+            }
+        }
+    }
+
+    /// Clamps a value of an output (if a clamp is set).
+    pub(super) fn clamp_output(&self, node: NodeId, output: OutputId, value: Value) -> Value {
+        match self.output_clamps.get(&(node, output)) {
+            Some(&(min, max)) => value.max(min).min(max),
+            None => value,
+        }
+    }
+}
+
+/// Unit tests.
+#[cfg(all(test, feature = "generators", feature = "math"))]
+mod tests {
+    use super::*;
+    use crate::{nodes, Connection, InputId};
+
+    #[test]
+    fn clamps_outputs() {
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        let var0 = graph.add_node(Box::new(nodes::Variable::new(5.0)));
+        let add1 = graph.add_node(Box::new(nodes::Addition::new()));
+        graph.add_connection(Connection::new(var0, OutputId(0), add1, InputId(0))).unwrap();
+        graph.set_input(add1, InputId(1), 1.0).unwrap();
+        graph.set_output_clamp(var0, OutputId(0), Some((-2.0, 2.0))).unwrap();
+        graph.process();
+        assert_eq!(graph.get_output(var0, OutputId(0)), Ok(2.0));
+        assert_eq!(graph.get_output(add1, OutputId(0)), Ok(3.0));
+        assert_eq!(graph.output_clamp(var0, OutputId(0)), Some((-2.0, 2.0)));
+
+        // Block processing clamps output buffers.
+        graph.set_output_clamp(add1, OutputId(0), Some((0.0, 2.5))).unwrap();
+        graph.process_block();
+        assert_eq!(graph.get_output(add1, OutputId(0)), Ok(2.5));
+
+        graph.set_output_clamp(var0, OutputId(0), None).unwrap();
+        graph.set_output_clamp(add1, OutputId(0), None).unwrap();
+        graph.process();
+        assert_eq!(graph.get_output(add1, OutputId(0)), Ok(6.0));
+        assert_eq!(
+            graph.set_output_clamp(var0, OutputId(1), None),
+            Err(GraphError::OutputNotExists(var0, OutputId(1)))
+        );
+    }
+}
@@ -0,0 +1,108 @@
+use super::Graph;
+use crate::{GraphError, Node, NodeId, ProcessContext, ProcessError, Value};
+
+impl<N: Node> Graph<N> {
+    /// Returns rate divisor of a node (node processes every `divisor`-th cycle).
+    pub fn get_rate_divisor(&self, id: NodeId) -> Result<u32, GraphError> {
+        self.get_node(id)?;
+        Ok(self.rate_divisors.get(&id).copied().unwrap_or(1))
+    }
+
+    /// Assigns a node to the clock domain processing every `divisor`-th cycle (1 for every cycle).
+    ///
+    /// Connections between domains are bridged by sampling inputs on ticks of the slower domain and holding its
+    /// outputs in between.
+    pub fn set_rate_divisor(&mut self, id: NodeId, divisor: u32) -> Result<(), GraphError> {
+        self.get_node(id)?;
+        match divisor {
+            0 => return Err(GraphError::InvalidRateDivisor(id)),
+            1 => self.rate_divisors.remove(&id),
+            _ => self.rate_divisors.insert(id, divisor),
+        };
+        Ok(())
+    }
+}
+
+/// Processes a block of a node in a slower clock domain (inputs are sampled on ticks, outputs held in between).
+/// Returns the error of the last failed tick.
+pub(super) fn process_divided_block<N: Node>(
+    node: &mut N,
+    context: &ProcessContext,
+    divisor: u32,
+    frames: usize,
+    inputs: &[Option<Vec<Value>>],
+    outputs: &mut [Vec<Value>],
+) -> Result<(), ProcessError> {
+    let ticks: Vec<usize> =
+        (0..frames).filter(|&frame| (context.frame + frame as u64).is_multiple_of(divisor as u64)).collect();
+
+    // Process ticks only.
+    let mut tick_inputs: Vec<Option<Vec<Value>>> = inputs
+        .iter()
+        .map(|buffer| buffer.as_ref().map(|buffer| ticks.iter().map(|&tick| buffer[tick]).collect()))
+        .collect();
+    let mut tick_outputs: Vec<Vec<Value>> = vec![vec![0.0; ticks.len()]; outputs.len()];
+    let mut result = Ok(());
+    if let Some(&first) = ticks.first() {
+        let mut input_slices: Vec<Option<&mut [Value]>> =
+            tick_inputs.iter_mut().map(|buffer| buffer.as_deref_mut()).collect();
+        let mut output_slices: Vec<&mut [Value]> =
+            tick_outputs.iter_mut().map(|buffer| buffer.as_mut_slice()).collect();
+        let context = ProcessContext { frame: context.frame + first as u64, ..*context }.divided(divisor);
+        result = node.process_block(&context, ticks.len(), &mut input_slices, &mut output_slices);
+    }
+
+    // Hold outputs between ticks (starting with last value of previous block).
+    for (buffer, tick_buffer) in outputs.iter_mut().zip(tick_outputs) {
+        let mut value = buffer.last().copied().unwrap_or(0.0);
+        let mut next = 0;
+        for (frame, sample) in buffer.iter_mut().enumerate() {
+            if ticks.get(next) == Some(&frame) {
+                value = tick_buffer[next];
+                next += 1;
+            }
+            *sample = value;
+        }
+    }
+    result
+}
+
+/// Unit tests.
+#[cfg(all(test, feature = "dsp", feature = "generators", feature = "math"))]
+mod tests {
+    use crate::fixtures::accumulator;
+    use crate::{GraphError, Node, NodeId, OutputId, Value};
+
+    #[test]
+    fn rate_divisor() {
+        let (mut graph, [_, add1, _]) = accumulator(1.0, |node| node as Box<dyn Node>);
+        assert_eq!(graph.get_rate_divisor(add1), Ok(1));
+        assert_eq!(graph.set_rate_divisor(add1, 0), Err(GraphError::InvalidRateDivisor(add1)));
+        assert_eq!(graph.set_rate_divisor(NodeId(3), 2), Err(GraphError::NodeNotExists(NodeId(3))));
+        graph.set_rate_divisor(add1, 4).unwrap();
+        assert_eq!(graph.get_rate_divisor(add1), Ok(4));
+        graph.set_rate_divisor(add1, 1).unwrap();
+        assert_eq!(graph.get_rate_divisor(add1), Ok(1));
+    }
+
+    #[test]
+    fn process_divided() {
+        let (mut graph, [_, add1, del2]) = accumulator(1.0, |node| node as Box<dyn Node>);
+        graph.set_rate_divisor(add1, 2).unwrap();
+        let values: Vec<Vec<Value>> =
+            graph.run().select(add1, OutputId(0)).unwrap().select(del2, OutputId(0)).unwrap().take(5).collect();
+        assert_eq!(values, vec![vec![1.0, 1.0], vec![1.0, 1.0], vec![2.0, 2.0], vec![2.0, 2.0], vec![3.0, 3.0]]);
+    }
+
+    #[test]
+    fn process_divided_block() {
+        let (mut graph, [_, add1, del2]) = accumulator(1.0, |node| node as Box<dyn Node>);
+        graph.set_rate_divisor(add1, 2).unwrap();
+        graph.set_block_size(3);
+        graph.process_block();
+        assert_eq!(graph.get_block_output(add1, OutputId(0)), Ok(&[1.0, 1.0, 1.0][..]));
+        assert_eq!(graph.get_block_output(del2, OutputId(0)), Ok(&[0.0, 1.0, 1.0][..]));
+        graph.process_block();
+        assert_eq!(graph.get_block_output(add1, OutputId(0)), Ok(&[1.0, 2.0, 2.0][..]));
+    }
+}
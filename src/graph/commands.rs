@@ -0,0 +1,185 @@
+use super::Graph;
+use crate::{Connection, GraphError, InputId, Node, NodeId, Value};
+
+/// Edit of a graph that can be applied and undone with its inverse (see `Graph::apply`), e.g. for undo history,
+/// network sync or scripting.
+pub enum GraphCommand<N> {
+    AddNode(N),
+    Connect(Connection),
+    Disconnect(Connection),
+    /// Inserts a node with given id together with its connections and event connections (inverse of `RemoveNode`).
+    InsertNode {
+        connections: Vec<Connection>,
+        event_connections: Vec<Connection>,
+        id: NodeId,
+        node: N,
+    },
+    RemoveNode(NodeId),
+    /// Resets an input that was not set before to its default and restores the node from a snapshot (see
+    /// `Node::snapshot`, inverse of `SetInput`).
+    RestoreInput {
+        id: NodeId,
+        input: InputId,
+        snapshot: Vec<Value>,
+    },
+    SetInput(NodeId, InputId, Value),
+    SetParam(NodeId, String, Value),
+}
+
+impl<N: Node> Graph<N> {
+    /// Applies a command and returns its inverse, i.e. the command that undoes it.
+    ///
+    /// Removed nodes are restored with their id, connections and event connections, other settings of the node (e.g.
+    /// label or bypass) are not restored.
+    pub fn apply(&mut self, command: GraphCommand<N>) -> Result<GraphCommand<N>, GraphError> {
+        match command {
+            GraphCommand::AddNode(node) => Ok(GraphCommand::RemoveNode(self.add_node(node))),
+            GraphCommand::Connect(connection) => {
+                self.add_connection(connection)?;
+                Ok(GraphCommand::Disconnect(connection))
+            }
+            GraphCommand::Disconnect(connection) => {
+                self.remove_connection(connection)?;
+                Ok(GraphCommand::Connect(connection))
+            }
+            GraphCommand::InsertNode { connections, event_connections, id, node } => {
+                self.insert_node(id, node, connections, event_connections)?;
+                Ok(GraphCommand::RemoveNode(id))
+            }
+            GraphCommand::RemoveNode(id) => {
+                let touches = |c: &&Connection| c.source_node == id || c.target_node == id;
+                let connections = self.connections.iter().filter(touches).copied().collect();
+                let event_connections = self.event_connections.iter().filter(touches).copied().collect();
+                let node = self.remove_node(id)?;
+                Ok(GraphCommand::InsertNode { connections, event_connections, id, node })
+            }
+            GraphCommand::RestoreInput { id, input, snapshot } => {
+                let inverse = self.input_inverse(id, input)?;
+                let node = self.get_node_mut(id)?;
+                if !node.list_inputs().contains(&input) {
+                    return Err(GraphError::InputNotExists(id, input));
+                }
+                let default = node.input_info(input).default;
+                node.set_input(input, default);
+                node.restore(&snapshot);
+                self.input_values.remove(&(id, input));
+                Ok(inverse)
+            }
+            GraphCommand::SetInput(node, input, value) => {
+                let inverse = self.input_inverse(node, input)?;
+                self.set_input(node, input, value)?;
+                Ok(inverse)
+            }
+            GraphCommand::SetParam(node, name, value) => {
+                let previous = self.get_parameter(node, &name)?;
+                self.set_parameter(node, &name, value)?;
+                Ok(GraphCommand::SetParam(node, name, previous))
+            }
+        }
+    }
+
+    /// Returns command that restores the current value of an input (the node state if the input was not set, as
+    /// nodes do not report their input values).
+    fn input_inverse(&self, id: NodeId, input: InputId) -> Result<GraphCommand<N>, GraphError> {
+        Ok(match self.input_values.get(&(id, input)) {
+            Some(&value) => GraphCommand::SetInput(id, input, value),
+            None => GraphCommand::RestoreInput { id, input, snapshot: self.get_node(id)?.snapshot() },
+        })
+    }
+
+    /// Inserts a node with given id and its connections (nothing is inserted if any of them is invalid).
+    fn insert_node(
+        &mut self,
+        id: NodeId,
+        node: N,
+        connections: Vec<Connection>,
+        event_connections: Vec<Connection>,
+    ) -> Result<(), GraphError> {
+        if self.nodes.contains_key(&id) {
+            return Err(GraphError::NodeAlreadyExists(id));
+        }
+        self.nodes.insert(id, node);
+        let next_node_id = self.next_node_id;
+        self.next_node_id.0 = self.next_node_id.0.max(id.0 + 1);
+        self.skipped_nodes.reserve(self.nodes.len());
+        self.statuses.reserve(self.nodes.len());
+        self.reserve_events();
+        self.update_processing_order().unwrap();
+
+        let result = self.add_connections(connections).map(|_| ()).and_then(|_| {
+            event_connections.iter().try_for_each(|&connection| self.add_event_connection(connection).map(|_| ()))
+        });
+        if result.is_err() {
+            self.remove_node(id).unwrap();
+            self.next_node_id = next_node_id;
+        }
+        result
+    }
+}
+
+/// Unit tests.
+#[cfg(all(test, feature = "dsp", feature = "generators", feature = "math"))]
+mod tests {
+    use super::*;
+    use crate::{nodes, OutputId};
+
+    #[test]
+    fn undoes_commands() {
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        let var0 = graph.add_node(Box::new(nodes::Variable::new(1.0)));
+        let add1 = graph.add_node(Box::new(nodes::Addition::new()));
+        let connection = Connection::new(var0, OutputId(0), add1, InputId(0));
+        graph.add_connection(connection).unwrap();
+        graph.set_input(add1, InputId(1), 2.0).unwrap();
+
+        let commands: Vec<GraphCommand<Box<dyn Node>>> = vec![
+            GraphCommand::AddNode(Box::new(nodes::Delay::new())),
+            GraphCommand::Connect(Connection::new(add1, OutputId(0), NodeId(2), InputId(0))),
+            GraphCommand::SetInput(add1, InputId(1), 5.0),
+            GraphCommand::RemoveNode(var0),
+            GraphCommand::Disconnect(Connection::new(add1, OutputId(0), NodeId(2), InputId(0))),
+        ];
+        let mut inverses = Vec::new();
+        for command in commands {
+            inverses.push(graph.apply(command).unwrap());
+        }
+        assert_eq!(graph.connections(), []);
+        graph.process();
+        assert_eq!(graph.get_output(add1, OutputId(0)), Ok(5.0));
+
+        for inverse in inverses.into_iter().rev() {
+            graph.apply(inverse).unwrap();
+        }
+        assert_eq!(graph.iter_nodes().count(), 2);
+        assert_eq!(graph.connections(), [connection]);
+        graph.process();
+        assert_eq!(graph.get_output(add1, OutputId(0)), Ok(3.0));
+
+        // Failed commands leave the graph unchanged.
+        let insert: GraphCommand<Box<dyn Node>> = GraphCommand::InsertNode {
+            connections: vec![Connection::new(NodeId(3), OutputId(0), add1, InputId(0))],
+            event_connections: vec![],
+            id: NodeId(3),
+            node: Box::new(nodes::Variable::new(0.0)),
+        };
+        assert_eq!(graph.apply(insert).err(), Some(GraphError::InputAlreadyConnected(add1, InputId(0))));
+        assert_eq!(graph.iter_nodes().count(), 2);
+        assert_eq!(graph.add_node(Box::new(nodes::Delay::new())), NodeId(3));
+        let param = GraphCommand::SetParam(add1, "gain".to_string(), 1.0);
+        assert_eq!(graph.apply(param).err(), Some(GraphError::ParameterNotExists(add1, "gain".to_string())));
+    }
+
+    #[test]
+    fn undoes_first_input() {
+        // Variable with an initial value that was never set as input.
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        let var0 = graph.add_node(Box::new(nodes::Variable::new(1.0)));
+        let undo = graph.apply(GraphCommand::SetInput(var0, InputId(0), 5.0)).unwrap();
+        assert_eq!(graph.get_output(var0, OutputId(0)), Ok(5.0));
+
+        let redo = graph.apply(undo).unwrap();
+        assert_eq!(graph.get_output(var0, OutputId(0)), Ok(1.0));
+        graph.apply(redo).unwrap();
+        assert_eq!(graph.get_output(var0, OutputId(0)), Ok(5.0));
+    }
+}
@@ -0,0 +1,63 @@
+use super::Graph;
+use crate::{Connection, Node, OutputId, ValueKind};
+use std::mem;
+
+impl<N: Node> Graph<N> {
+    /// Sets whether connections with lossy value conversions (continuous values into integer inputs, which round
+    /// them) are accepted instead of rejected as type mismatch (disabled by default). Accepted lossy connections are
+    /// reported as warnings (see `take_conversion_warnings`).
+    pub fn set_lossy_conversions(&mut self, allowed: bool) {
+        self.lossy_conversions = allowed;
+    }
+
+    /// Returns and clears added connections with lossy value conversions.
+    pub fn take_conversion_warnings(&mut self) -> Vec<Connection> {
+        mem::take(&mut self.conversion_warnings)
+    }
+
+    /// Returns value kinds of the source and target of a connection between existing ports.
+    pub(super) fn conversion_kinds(&self, connection: Connection) -> (ValueKind, ValueKind) {
+        let source_kind = match connection.source_output {
+            OutputId::STATUS => ValueKind::Integer,
+            output => self.nodes[&connection.source_node].output_info(output).kind,
+        };
+        (source_kind, self.nodes[&connection.target_node].input_info(connection.target_input).kind)
+    }
+
+    /// Returns whether a connection between existing ports converts values lossily.
+    pub(super) fn is_lossy(&self, connection: Connection) -> bool {
+        let (source_kind, target_kind) = self.conversion_kinds(connection);
+        !target_kind.accepts(source_kind) && target_kind.is_lossy_from(source_kind)
+    }
+}
+
+/// Unit tests.
+#[cfg(all(test, feature = "generators", feature = "logic"))]
+mod tests {
+    use super::*;
+    use crate::{nodes, GraphError, InputId};
+
+    #[test]
+    fn lossy_conversions() {
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        let var0 = graph.add_node(Box::new(nodes::Variable::new(1.6)));
+        let cnt1 = graph.add_node(Box::new(nodes::Counter::new(0)));
+        let sel2 = graph.add_node(Box::new(nodes::SelectN::new(2)));
+        graph.set_input(sel2, InputId(2), 5.0).unwrap();
+        let modulus = Connection::new(var0, OutputId(0), cnt1, InputId(2));
+        assert_eq!(graph.add_connection(modulus), Err(GraphError::TypeMismatch(modulus)));
+
+        graph.set_lossy_conversions(true);
+        let index = Connection::new(var0, OutputId(0), sel2, InputId(0));
+        graph.add_connections([modulus, index]).unwrap();
+        graph.add_connection(Connection::new(cnt1, OutputId(0), sel2, InputId(1))).unwrap();
+        assert_eq!(graph.take_conversion_warnings(), [modulus, index]);
+        assert_eq!(graph.take_conversion_warnings(), []);
+
+        // Integer inputs round converted values.
+        graph.process();
+        assert_eq!(graph.get_output(sel2, OutputId(0)), Ok(5.0));
+        assert!(ValueKind::Integer.is_lossy_from(ValueKind::Continuous));
+        assert!(!ValueKind::Continuous.is_lossy_from(ValueKind::Integer));
+    }
+}
@@ -0,0 +1,157 @@
+use super::Graph;
+use crate::{Node, NodeId};
+use std::time::Duration;
+
+/// Reaction to a cycle (or block) exceeding its deadline (see `Graph::set_deadline`).
+pub enum DeadlineAction {
+    /// Skips all remaining nodes of the cycle (they hold their outputs).
+    Abort,
+
+    /// Invokes a callback with the miss on the processing thread, e.g. to report overload to the host (processing
+    /// continues).
+    Notify(Box<dyn FnMut(DeadlineMiss) + Send>),
+
+    /// Skips remaining nodes with a priority below a minimum (see `Graph::set_priority`).
+    SkipBelow(i32),
+}
+
+/// Cycle (or block) that exceeded its deadline.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DeadlineMiss {
+    /// Processing time of the cycle when the miss was detected.
+    pub elapsed: Duration,
+
+    /// Frame of the cycle (first frame for blocks).
+    pub frame: u64,
+
+    /// Node that was executing when the deadline passed.
+    pub node: NodeId,
+}
+
+/// Deadline of cycles with its reaction.
+pub(super) struct Deadline {
+    /// Reaction to misses.
+    action: DeadlineAction,
+
+    /// Maximum processing time per cycle.
+    duration: Duration,
+
+    /// Last miss with the number of its cycle (see `Graph::cycle`).
+    miss: Option<(DeadlineMiss, u64)>,
+}
+
+impl<N: Node> Graph<N> {
+    /// Returns the last cycle that exceeded the deadline (if any).
+    pub fn last_deadline_miss(&self) -> Option<DeadlineMiss> {
+        self.deadline.as_ref().and_then(|deadline| deadline.miss).map(|(miss, _)| miss)
+    }
+
+    /// Removes the deadline.
+    pub fn remove_deadline(&mut self) {
+        self.deadline = None;
+    }
+
+    /// Sets maximum processing time per cycle (or block), checked after each node, and the reaction once it is
+    /// exceeded (see `last_deadline_miss` for the node that was executing).
+    pub fn set_deadline(&mut self, deadline: Duration, action: DeadlineAction) {
+        self.deadline = Some(Deadline { action, duration: deadline, miss: None });
+    }
+
+    /// Records a miss if the deadline passed while processing a node (once per cycle).
+    pub(super) fn check_deadline(&mut self, node: NodeId) {
+        let Some(deadline) = self.deadline.as_mut() else {
+            return;
+        };
+        if deadline.miss.is_some_and(|(_, cycle)| cycle == self.cycle) {
+            return;
+        }
+        let elapsed = self.cycle_start.elapsed();
+        if elapsed >= deadline.duration {
+            let miss = DeadlineMiss { elapsed, frame: self.context.frame, node };
+            deadline.miss = Some((miss, self.cycle));
+            if let DeadlineAction::Notify(callback) = &mut deadline.action {
+                callback(miss);
+            }
+        }
+    }
+
+    /// Returns whether a node is skipped because the deadline of the current cycle was missed.
+    pub(super) fn misses_deadline(&self, node: NodeId) -> bool {
+        match self.deadline.as_ref() {
+            Some(deadline) if deadline.miss.is_some_and(|(_, cycle)| cycle == self.cycle) => match deadline.action {
+                DeadlineAction::Abort => true,
+                DeadlineAction::Notify(_) => false,
+                DeadlineAction::SkipBelow(min_priority) => {
+                    self.effective_priorities.get(&node).copied().unwrap_or(0) < min_priority
+                }
+            },
+            _ => false,
+        }
+    }
+}
+
+/// Unit tests.
+#[cfg(all(test, feature = "generators", feature = "math"))]
+mod tests {
+    use super::*;
+    use crate::{nodes, Connection, InputId, OutputId};
+    use std::sync::{Arc, Mutex};
+
+    /// Builds graph of a variable feeding an addition, and an independent variable.
+    fn build() -> (Graph<Box<dyn Node>>, [NodeId; 3]) {
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        let var0 = graph.add_node(Box::from(nodes::Variable::new(1.0)));
+        let add1 = graph.add_node(Box::from(nodes::Addition::new()));
+        let var2 = graph.add_node(Box::from(nodes::Variable::new(2.0)));
+        graph.add_connection(Connection::new(var0, OutputId(0), add1, InputId(0))).unwrap();
+        (graph, [var0, add1, var2])
+    }
+
+    #[test]
+    fn aborts_cycle() {
+        let (mut graph, [var0, add1, var2]) = build();
+        graph.set_deadline(Duration::ZERO, DeadlineAction::Abort);
+        graph.process();
+        let miss = graph.last_deadline_miss().unwrap();
+        assert_eq!((miss.frame, miss.node), (0, var0));
+        assert_eq!(graph.get_output(add1, OutputId(0)), Ok(0.0));
+        let mut skipped = graph.get_skipped_nodes().to_vec();
+        skipped.sort();
+        assert_eq!(skipped, [add1, var2]);
+
+        // Misses are detected again in later cycles and blocks.
+        graph.process_block();
+        assert_eq!(graph.last_deadline_miss().map(|miss| miss.frame), Some(1));
+        graph.set_deadline(Duration::from_secs(60), DeadlineAction::Abort);
+        graph.process();
+        assert_eq!(graph.get_output(add1, OutputId(0)), Ok(1.0));
+        assert_eq!(graph.last_deadline_miss(), None);
+        graph.remove_deadline();
+        graph.process();
+        assert_eq!(graph.get_skipped_nodes(), &[]);
+    }
+
+    #[test]
+    fn skips_low_priority() {
+        let (mut graph, [var0, add1, var2]) = build();
+        graph.set_priority(add1, 1).unwrap();
+        graph.set_deadline(Duration::ZERO, DeadlineAction::SkipBelow(1));
+        graph.process();
+        assert_eq!(graph.last_deadline_miss().map(|miss| miss.node), Some(var0));
+        assert_eq!(graph.get_output(add1, OutputId(0)), Ok(1.0));
+        assert_eq!(graph.get_skipped_nodes(), &[var2]);
+    }
+
+    #[test]
+    fn notifies() {
+        let (mut graph, [var0, ..]) = build();
+        // Callbacks are invoked on the processing thread, so recording does not allocate.
+        let misses = Arc::new(Mutex::new(Vec::with_capacity(2)));
+        let recorded = misses.clone();
+        let action = DeadlineAction::Notify(Box::new(move |miss| recorded.lock().unwrap().push(miss.node)));
+        graph.set_deadline(Duration::ZERO, action);
+        graph.process_n(2);
+        assert_eq!(*misses.lock().unwrap(), [var0, var0]);
+        assert_eq!(graph.get_skipped_nodes(), &[]);
+    }
+}
@@ -0,0 +1,95 @@
+use super::{BlockRoute, Graph};
+use crate::{InputId, Node, NodeId, Value};
+use std::collections::HashMap;
+
+impl<N: Node> Graph<N> {
+    /// Returns numbers of denormal (subnormal) values transferred from outputs of nodes since counting was enabled
+    /// (ordered by node id), empty unless counting is enabled.
+    pub fn denormal_counts(&self) -> Vec<(NodeId, u64)> {
+        let mut counts: Vec<(NodeId, u64)> = self.denormal_counts.iter().flatten().map(|(&n, &c)| (n, c)).collect();
+        counts.sort_by_key(|&(node, _)| node.0);
+        counts
+    }
+
+    /// Enables or disables counting denormal values transferred from outputs of nodes, e.g. to find decaying feedback
+    /// structures (disabled by default, enabling resets counts).
+    pub fn set_denormal_counting(&mut self, enabled: bool) {
+        self.denormal_counts = enabled.then(|| HashMap::with_capacity(self.nodes.len()));
+    }
+
+    /// Sets whether denormal values are flushed to zero when transferred over connections (disabled by default), which
+    /// avoids the processing cost of denormal arithmetic in long-running feedback structures. Changing the setting
+    /// invalidates cached inputs of pure nodes.
+    pub fn set_flush_denormals(&mut self, enabled: bool) {
+        self.flush_denormals = enabled;
+        self.prepare_memos();
+    }
+
+    /// Counts and flushes denormal values of the input buffers of a node in block processing (if enabled).
+    pub(super) fn handle_block_denormals(
+        &mut self,
+        node: NodeId,
+        inputs: &[InputId],
+        buffers: &mut [Option<Vec<Value>>],
+    ) {
+        for (&input, buffer) in inputs.iter().zip(buffers) {
+            let (Some(&(BlockRoute::Copied(source, _) | BlockRoute::Shared(source, _))), Some(buffer)) =
+                (self.block_routes.get(&(node, input)), buffer)
+            else {
+                continue;
+            };
+            for value in buffer.iter_mut() {
+                *value = self.handle_denormal(source, *value);
+            }
+        }
+    }
+
+    /// Counts and flushes a denormal value transferred from an output of a node (if enabled).
+    pub(super) fn handle_denormal(&mut self, source: NodeId, value: Value) -> Value {
+        if !value.is_subnormal() {
+            return value;
+        }
+        if let Some(counts) = self.denormal_counts.as_mut() {
+            *counts.entry(source).or_default() += 1;
+        }
+        match self.flush_denormals {
+            true => 0.0,
+            false => value,
+        }
+    }
+}
+
+/// Unit tests.
+#[cfg(all(test, feature = "generators", feature = "math"))]
+mod tests {
+    use super::*;
+    use crate::{nodes, Connection, OutputId};
+
+    #[test]
+    fn counts_and_flushes_denormals() {
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        let var0 = graph.add_node(Box::new(nodes::Variable::new(Value::MIN_POSITIVE / 2.0)));
+        let add1 = graph.add_node(Box::new(nodes::Addition::new()));
+        graph.add_connection(Connection::new(var0, OutputId(0), add1, InputId(0))).unwrap();
+
+        // Disabled by default.
+        graph.process();
+        assert_eq!(graph.get_output(add1, OutputId(0)), Ok(Value::MIN_POSITIVE / 2.0));
+        assert_eq!(graph.denormal_counts(), []);
+
+        graph.set_denormal_counting(true);
+        graph.process_n(2);
+        assert_eq!(graph.denormal_counts(), [(var0, 2)]);
+        graph.set_flush_denormals(true);
+        graph.process();
+        assert_eq!(graph.get_output(add1, OutputId(0)), Ok(0.0));
+        assert_eq!(graph.denormal_counts(), [(var0, 3)]);
+
+        // Block processing flushes copied values as well.
+        graph.process_block();
+        assert_eq!(graph.get_output(add1, OutputId(0)), Ok(0.0));
+        assert_eq!(graph.denormal_counts(), [(var0, 3 + graph.block_size() as u64)]);
+        graph.set_denormal_counting(false);
+        assert_eq!(graph.denormal_counts(), []);
+    }
+}
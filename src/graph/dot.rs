@@ -0,0 +1,93 @@
+use super::Graph;
+use crate::{Node, NodeId, OutputId};
+use std::fmt::Write;
+
+impl<N: Node> Graph<N> {
+    /// Returns Graphviz DOT digraph of the graph (nodes named by label or type, edges labeled with port numbers,
+    /// nodes that introduce delay drawn dashed and event connections drawn dotted).
+    pub fn to_dot(&self) -> String {
+        let mut ids: Vec<NodeId> = self.nodes.keys().copied().collect();
+        ids.sort_unstable();
+
+        let mut dot = String::from("digraph {\n");
+        for id in ids {
+            let node = &self.nodes[&id];
+            let name = match self.labels.get(&id) {
+                Some(label) => label.as_str(),
+                None => short_type_name(node.type_name()),
+            };
+            let style = if node.delayed_processing() { ", style=dashed" } else { "" };
+            writeln!(dot, "    {} [label=\"{}: {}\"{}];", id.0, id.0, escape(name), style).unwrap();
+        }
+        for connection in self.connections.iter() {
+            let output = match connection.source_output {
+                OutputId::STATUS => "status".to_string(),
+                output => output.0.to_string(),
+            };
+            writeln!(
+                dot,
+                "    {} -> {} [taillabel=\"{}\", headlabel=\"{}\"];",
+                connection.source_node.0, connection.target_node.0, output, connection.target_input.0
+            )
+            .unwrap();
+        }
+        for connection in self.event_connections.iter() {
+            writeln!(
+                dot,
+                "    {} -> {} [taillabel=\"{}\", headlabel=\"{}\", style=dotted];",
+                connection.source_node.0,
+                connection.target_node.0,
+                connection.source_output.0,
+                connection.target_input.0
+            )
+            .unwrap();
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/// Escapes quotes and backslashes for DOT strings.
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Returns type name without module path and generic arguments.
+fn short_type_name(type_name: &str) -> &str {
+    let name = type_name.split('<').next().unwrap_or(type_name);
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+/// Unit tests.
+#[cfg(all(test, feature = "dsp", feature = "generators", feature = "math"))]
+mod tests {
+    use super::*;
+    use crate::{nodes, Connection, InputId};
+
+    #[test]
+    fn to_dot() {
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        let var0 = graph.add_node(Box::from(nodes::Variable::new(1.0)));
+        let add1 = graph.add_node(Box::from(nodes::Addition::new()));
+        let del2 = graph.add_node(Box::from(nodes::Delay::new()));
+        graph.add_connection(Connection::new(var0, OutputId(0), add1, InputId(0))).unwrap();
+        graph.add_connection(Connection::new(add1, OutputId(0), del2, InputId(0))).unwrap();
+        graph.add_connection(Connection::new(del2, OutputId::STATUS, add1, InputId(1))).unwrap();
+        graph.set_label(var0, Some("gain \"in\"")).unwrap();
+
+        assert_eq!(
+            graph.to_dot(),
+            concat!(
+                "digraph {\n",
+                "    0 [label=\"0: gain \\\"in\\\"\"];\n",
+                "    1 [label=\"1: Addition\"];\n",
+                "    2 [label=\"2: Delay\", style=dashed];\n",
+                "    0 -> 1 [taillabel=\"0\", headlabel=\"0\"];\n",
+                "    1 -> 2 [taillabel=\"0\", headlabel=\"0\"];\n",
+                "    2 -> 1 [taillabel=\"status\", headlabel=\"1\"];\n",
+                "}\n"
+            )
+        );
+        assert_eq!(short_type_name("a::b::Node<c::D>"), "Node");
+    }
+}
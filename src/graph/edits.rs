@@ -0,0 +1,138 @@
+use super::Graph;
+use crate::{Connection, GraphError, Node, NodeId};
+use std::{
+    mem,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, PoisonError, TryLockError,
+    },
+};
+
+/// Structural edit of a graph, deferred until the next cycle (see `Graph::enqueue`).
+pub enum GraphEdit<N> {
+    AddConnection(Connection),
+    AddEventConnection(Connection),
+    AddNode(N),
+    RemoveConnection(Connection),
+    RemoveEventConnection(Connection),
+    RemoveNode(NodeId),
+}
+
+/// Pending edits shared between a graph and its edit queues.
+pub(super) struct Pending<N> {
+    /// Enqueued edits.
+    edits: Mutex<Vec<GraphEdit<N>>>,
+
+    /// Whether edits are pending (checked without locking).
+    flag: AtomicBool,
+}
+impl<N> Pending<N> {
+    /// Creates new empty set of pending edits.
+    pub(super) fn new() -> Self {
+        Pending { edits: Mutex::new(Vec::new()), flag: AtomicBool::new(false) }
+    }
+}
+
+/// Thread-safe handle for enqueueing structural edits while the graph is processed on another thread.
+pub struct EditQueue<N> {
+    /// Edits shared with the graph.
+    shared: Arc<Pending<N>>,
+}
+impl<N> EditQueue<N> {
+    /// Enqueues an edit to be applied at the start of the next cycle.
+    pub fn push(&self, edit: GraphEdit<N>) {
+        let mut edits = self.shared.edits.lock().unwrap_or_else(PoisonError::into_inner);
+        edits.push(edit);
+        self.shared.flag.store(true, Ordering::Release);
+    }
+}
+impl<N> Clone for EditQueue<N> {
+    fn clone(&self) -> Self {
+        EditQueue { shared: self.shared.clone() }
+    }
+}
+
+impl<N: Node> Graph<N> {
+    /// Returns handle for enqueueing edits from other threads (see `enqueue`).
+    pub fn edit_queue(&self) -> EditQueue<N> {
+        EditQueue { shared: self.edits.clone() }
+    }
+
+    /// Enqueues a structural edit that is applied at the start of the next cycle or block, together with all other
+    /// pending edits (in the order they were enqueued). Edits that fail are dropped and their errors are kept (see
+    /// `take_edit_errors`), added nodes get the next free ids.
+    ///
+    /// Cycles that apply edits are not real-time safe, the queue is skipped for a cycle if another thread holds it.
+    pub fn enqueue(&self, edit: GraphEdit<N>) {
+        self.edit_queue().push(edit);
+    }
+
+    /// Returns and clears errors of pending edits that failed to apply.
+    pub fn take_edit_errors(&mut self) -> Vec<GraphError> {
+        mem::take(&mut self.edit_errors)
+    }
+
+    /// Applies pending structural edits (only locks the queue if edits are pending).
+    pub(super) fn apply_edits(&mut self) {
+        if !self.edits.flag.load(Ordering::Acquire) {
+            return;
+        }
+        let edits = {
+            let mut edits = match self.edits.edits.try_lock() {
+                Ok(edits) => edits,
+                Err(TryLockError::Poisoned(error)) => error.into_inner(),
+                Err(TryLockError::WouldBlock) => return,
+            };
+            self.edits.flag.store(false, Ordering::Relaxed);
+            mem::take(&mut *edits)
+        };
+        for edit in edits {
+            let result = match edit {
+                GraphEdit::AddConnection(connection) => self.add_connection(connection).map(|_| ()),
+                GraphEdit::AddEventConnection(connection) => self.add_event_connection(connection).map(|_| ()),
+                GraphEdit::AddNode(node) => {
+                    self.add_node(node);
+                    Ok(())
+                }
+                GraphEdit::RemoveConnection(connection) => self.remove_connection(connection).map(|_| ()),
+                GraphEdit::RemoveEventConnection(connection) => self.remove_event_connection(connection).map(|_| ()),
+                GraphEdit::RemoveNode(node) => self.remove_node(node).map(|_| ()),
+            };
+            if let Err(error) = result {
+                self.edit_errors.push(error);
+            }
+        }
+    }
+}
+
+/// Unit tests.
+#[cfg(all(test, feature = "generators", feature = "math"))]
+mod tests {
+    use super::*;
+    use crate::{nodes, InputId, OutputId};
+    use std::thread;
+
+    #[test]
+    fn applies_between_cycles() {
+        let mut graph: Graph<Box<dyn Node + Send>> = Graph::new();
+        let var0 = graph.add_node(Box::new(nodes::Variable::new(2.0)));
+        let connection = Connection::new(var0, OutputId(0), NodeId(1), InputId(0));
+        graph.enqueue(GraphEdit::AddNode(Box::new(nodes::Addition::new())));
+        graph.enqueue(GraphEdit::AddConnection(connection));
+        graph.enqueue(GraphEdit::RemoveNode(NodeId(2)));
+        assert_eq!(graph.iter_nodes().count(), 1);
+
+        graph.process();
+        assert_eq!(graph.connections(), [connection]);
+        assert_eq!(graph.get_output(NodeId(1), OutputId(0)), Ok(2.0));
+        assert_eq!(graph.take_edit_errors(), [GraphError::NodeNotExists(NodeId(2))]);
+        assert_eq!(graph.take_edit_errors(), []);
+
+        // Edits from another thread.
+        let queue = graph.edit_queue();
+        thread::spawn(move || queue.push(GraphEdit::RemoveConnection(connection))).join().unwrap();
+        graph.set_block_size(4);
+        graph.process_block();
+        assert_eq!(graph.connections(), []);
+    }
+}
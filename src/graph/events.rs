@@ -0,0 +1,261 @@
+use super::Graph;
+use crate::{Connection, GraphError, Node, NodeId, OutputId, Value};
+
+/// Event fired by a node output.
+pub(super) struct Event {
+    /// Frame in which the event is delivered.
+    frame: u64,
+
+    /// Firing node.
+    node: NodeId,
+
+    /// Firing event output.
+    output: OutputId,
+
+    /// Payload of the event.
+    payload: Value,
+}
+
+impl<N: Node> Graph<N> {
+    /// Adds an event connection (event inputs may have multiple sources, their events are delivered one by one).
+    ///
+    /// Events fired while processing a node are delivered to connected nodes before they are processed in the same
+    /// cycle (in the next cycle if the source node introduces delay). Block processing delivers events pending after
+    /// the last frame of a block the same way, per block.
+    pub fn add_event_connection(&mut self, connection: Connection) -> Result<Connection, GraphError> {
+        self.validate_event_connection(connection)?;
+        if self.event_connections.contains(&connection) {
+            return Ok(connection);
+        }
+
+        // Event connections constrain processing order like value connections.
+        self.event_connections.push(connection);
+        if let Err(error) = self.update_processing_order() {
+            self.event_connections.pop();
+            return Err(error);
+        }
+        Ok(connection)
+    }
+
+    /// Returns event connections in graph.
+    pub fn event_connections(&self) -> &[Connection] {
+        &self.event_connections
+    }
+
+    /// Removes an event connection.
+    pub fn remove_event_connection(&mut self, connection: Connection) -> Result<Connection, GraphError> {
+        if !self.event_connections.contains(&connection) {
+            return Err(GraphError::ConnectionNotExists(connection));
+        }
+        self.event_connections.retain(|&c| c != connection);
+        self.update_processing_order().unwrap();
+        Ok(connection)
+    }
+
+    /// Records events fired by a processed node, delivered after given number of frames.
+    pub(super) fn collect_events(&mut self, node: NodeId, delay: u64) {
+        let processor = &self.nodes[&node];
+        for &output in processor.list_event_outputs() {
+            if let Some(payload) = processor.event_output(output) {
+                self.events.push(Event { frame: self.context.frame + delay, node, output, payload });
+            }
+        }
+    }
+
+    /// Delivers events of the current frame to a node before it is processed.
+    pub(super) fn deliver_events(&mut self, node: NodeId) {
+        let Some(processor) = self.nodes.get_mut(&node) else {
+            return;
+        };
+        for connection in self.event_connections.iter().filter(|c| c.target_node == node) {
+            for event in self.events.iter() {
+                if event.frame == self.context.frame
+                    && event.node == connection.source_node
+                    && event.output == connection.source_output
+                {
+                    processor.receive_event(connection.target_input, event.payload);
+                }
+            }
+        }
+    }
+
+    /// Drops events of past frames.
+    pub(super) fn expire_events(&mut self) {
+        let frame = self.context.frame;
+        self.events.retain(|event| event.frame >= frame);
+    }
+
+    /// Reserves storage for events so processing does not allocate (every event output fires at most once per
+    /// cycle, events of delayed nodes are kept for one more cycle).
+    pub(super) fn reserve_events(&mut self) {
+        let capacity = 2 * self.nodes.values().map(|node| node.list_event_outputs().len()).sum::<usize>();
+        self.events.reserve(capacity.saturating_sub(self.events.len()));
+    }
+
+    /// Validates an event connection (whether nodes and event input/output exist).
+    pub(super) fn validate_event_connection(&self, connection: Connection) -> Result<(), GraphError> {
+        let source = self.get_node(connection.source_node)?;
+        let target = self.get_node(connection.target_node)?;
+        if !source.list_event_outputs().contains(&connection.source_output) {
+            return Err(GraphError::OutputNotExists(connection.source_node, connection.source_output));
+        }
+        if !target.list_event_inputs().contains(&connection.target_input) {
+            return Err(GraphError::InputNotExists(connection.target_node, connection.target_input));
+        }
+        Ok(())
+    }
+
+    /// Removes event connections and pending events of a node.
+    pub(super) fn remove_events(&mut self, node: NodeId) {
+        self.event_connections.retain(|c| c.source_node != node && c.target_node != node);
+        self.events.retain(|event| event.node != node);
+    }
+}
+
+/// Unit tests.
+#[cfg(all(test, feature = "generators"))]
+mod tests {
+    use super::*;
+    use crate::{executors, nodes, InputId};
+
+    /// Node that fires an event with the beat number every given number of cycles.
+    struct Metronome {
+        beat: Option<Value>,
+        cycle: u32,
+        delayed: bool,
+        period: u32,
+    }
+    impl Node for Metronome {
+        fn delayed_processing(&self) -> bool {
+            self.delayed
+        }
+        fn event_output(&self, _id: OutputId) -> Option<Value> {
+            self.beat
+        }
+        fn get_output(&self, _id: OutputId) -> Value {
+            0.0
+        }
+        fn list_event_outputs(&self) -> &[OutputId] {
+            &[OutputId(0)]
+        }
+        fn list_inputs(&self) -> &[InputId] {
+            &[]
+        }
+        fn list_outputs(&self) -> &[OutputId] {
+            &[]
+        }
+        fn process(&mut self) {
+            self.cycle += 1;
+            self.beat = self.cycle.is_multiple_of(self.period).then_some((self.cycle / self.period) as Value);
+        }
+        fn set_input(&mut self, _id: InputId, _value: Value) {}
+    }
+
+    /// Node that sums payloads of received events (output 0) and exposes the count of the current cycle (output 1).
+    struct Accumulator {
+        count: Value,
+        received: Value,
+        sum: Value,
+    }
+    impl Node for Accumulator {
+        fn delayed_processing(&self) -> bool {
+            false
+        }
+        fn get_output(&self, id: OutputId) -> Value {
+            [self.sum, self.count][id.0 as usize]
+        }
+        fn list_event_inputs(&self) -> &[InputId] {
+            &[InputId(0)]
+        }
+        fn list_inputs(&self) -> &[InputId] {
+            &[]
+        }
+        fn list_outputs(&self) -> &[OutputId] {
+            &[OutputId(0), OutputId(1)]
+        }
+        fn process(&mut self) {
+            self.count = self.received;
+            self.received = 0.0;
+        }
+        fn receive_event(&mut self, _id: InputId, payload: Value) {
+            self.received += 1.0;
+            self.sum += payload;
+        }
+        fn set_input(&mut self, _id: InputId, _value: Value) {}
+    }
+
+    /// Builds graph with two metronomes firing into an accumulator.
+    fn metronomes(delayed: bool) -> (Graph<Box<dyn Node + Send>>, NodeId) {
+        let mut graph: Graph<Box<dyn Node + Send>> = Graph::new();
+        let acc0 = graph.add_node(Box::new(Accumulator { count: 0.0, received: 0.0, sum: 0.0 }));
+        let met1 = graph.add_node(Box::new(Metronome { beat: None, cycle: 0, delayed, period: 2 }));
+        let met2 = graph.add_node(Box::new(Metronome { beat: None, cycle: 0, delayed, period: 3 }));
+        graph.add_event_connection(Connection::new(met1, OutputId(0), acc0, InputId(0))).unwrap();
+        graph.add_event_connection(Connection::new(met2, OutputId(0), acc0, InputId(0))).unwrap();
+        (graph, acc0)
+    }
+
+    #[test]
+    fn delivers_events() {
+        let (mut graph, acc0) = metronomes(false);
+        let mut counts = Vec::with_capacity(6);
+        for _ in 0..6 {
+            graph.process();
+            counts.push(graph.get_output(acc0, OutputId(1)).unwrap());
+        }
+        assert_eq!(counts, vec![0.0, 1.0, 1.0, 1.0, 0.0, 2.0]);
+        assert_eq!(graph.get_output(acc0, OutputId(0)), Ok(9.0));
+
+        // Same delivery with other executors.
+        for executor in 0..2 {
+            let (mut graph, acc0) = metronomes(false);
+            match executor {
+                0 => graph.set_executor(executors::Compiled::new()),
+                _ => graph.set_executor(executors::Parallel::new(2)),
+            }
+            graph.process_n(6);
+            assert_eq!(graph.get_output(acc0, OutputId(0)), Ok(9.0));
+        }
+    }
+
+    #[test]
+    fn delays_events() {
+        let (mut graph, acc0) = metronomes(true);
+        let mut counts = Vec::with_capacity(7);
+        for _ in 0..7 {
+            graph.process();
+            counts.push(graph.get_output(acc0, OutputId(1)).unwrap());
+        }
+        assert_eq!(counts, vec![0.0, 0.0, 1.0, 1.0, 1.0, 0.0, 2.0]);
+
+        // Events of delayed nodes reach the next block.
+        let (mut graph, acc0) = metronomes(true);
+        graph.set_block_size(2);
+        graph.process_block();
+        graph.process_block();
+        assert_eq!(graph.get_output(acc0, OutputId(0)), Ok(1.0));
+    }
+
+    #[test]
+    fn add_event_connection() {
+        let (mut graph, acc0) = metronomes(false);
+        let var3 = graph.add_node(Box::new(nodes::Variable::new(0.0)));
+        assert_eq!(graph.event_connections().len(), 2);
+
+        // Invalid connections.
+        assert_eq!(
+            graph.add_event_connection(Connection::new(var3, OutputId(0), acc0, InputId(0))),
+            Err(GraphError::OutputNotExists(var3, OutputId(0)))
+        );
+        assert_eq!(
+            graph.add_event_connection(Connection::new(NodeId(1), OutputId(0), var3, InputId(0))),
+            Err(GraphError::InputNotExists(var3, InputId(0)))
+        );
+
+        let connection = Connection::new(NodeId(1), OutputId(0), acc0, InputId(0));
+        assert_eq!(graph.remove_event_connection(connection), Ok(connection));
+        assert_eq!(graph.remove_event_connection(connection), Err(GraphError::ConnectionNotExists(connection)));
+        graph.remove_node(NodeId(2)).unwrap();
+        assert!(graph.event_connections().is_empty());
+    }
+}
@@ -0,0 +1,93 @@
+use super::{BlockRoute, Graph};
+use crate::{Connection, InputId, Node, NodeId, Value};
+
+/// Non-finite value (NaN or infinite) transferred over a connection.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NonFiniteValue {
+    /// Connection that transferred the value.
+    pub connection: Connection,
+
+    /// Frame in which the value was transferred.
+    pub frame: u64,
+
+    /// Transferred value.
+    pub value: Value,
+}
+
+impl<N: Node> Graph<N> {
+    /// Returns the first non-finite value transferred since checks were enabled (`None` if there was none or checks
+    /// are disabled).
+    pub fn non_finite_value(&self) -> Option<NonFiniteValue> {
+        self.non_finite.flatten()
+    }
+
+    /// Enables or disables checking every transferred value for NaN and infinity, e.g. to find the connection where
+    /// NaNs enter a patch (disabled by default, enabling resets the reported value).
+    pub fn set_finite_checks(&mut self, enabled: bool) {
+        self.non_finite = enabled.then_some(None);
+    }
+
+    /// Records the first non-finite value of the input buffers of a node in block processing (with checks enabled).
+    pub(super) fn check_block_finite(&mut self, node: NodeId, inputs: &[InputId], buffers: &[Option<Vec<Value>>]) {
+        for (&input, buffer) in inputs.iter().zip(buffers) {
+            let (Some(&(BlockRoute::Copied(source, output) | BlockRoute::Shared(source, output))), Some(buffer)) =
+                (self.block_routes.get(&(node, input)), buffer)
+            else {
+                continue;
+            };
+            if let Some(index) = buffer.iter().position(|value| !value.is_finite()) {
+                let connection = Connection::new(source, output, node, input);
+                self.check_finite(connection, self.context.frame + index as u64, buffer[index]);
+            }
+        }
+    }
+
+    /// Records a transferred value if it is the first non-finite one (with checks enabled).
+    pub(super) fn check_finite(&mut self, connection: Connection, frame: u64, value: Value) {
+        if let Some(non_finite @ None) = self.non_finite.as_mut() {
+            if !value.is_finite() {
+                *non_finite = Some(NonFiniteValue { connection, frame, value });
+            }
+        }
+    }
+}
+
+/// Unit tests.
+#[cfg(all(test, feature = "generators", feature = "math"))]
+mod tests {
+    use super::*;
+    use crate::{nodes, InputId, OutputId};
+
+    #[test]
+    fn reports_first_non_finite_value() {
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        let var0 = graph.add_node(Box::new(nodes::Variable::new(1.0)));
+        let add1 = graph.add_node(Box::new(nodes::Addition::new()));
+        let add2 = graph.add_node(Box::new(nodes::Addition::new()));
+        let first = Connection::new(var0, OutputId(0), add1, InputId(0));
+        graph.add_connection(first).unwrap();
+        graph.add_connection(Connection::new(add1, OutputId(0), add2, InputId(0))).unwrap();
+
+        // Disabled by default.
+        graph.set_input(var0, InputId(0), Value::NAN).unwrap();
+        graph.process();
+        assert_eq!(graph.non_finite_value(), None);
+
+        graph.set_finite_checks(true);
+        graph.set_input(var0, InputId(0), 1.0).unwrap();
+        graph.process();
+        assert_eq!(graph.non_finite_value(), None);
+        graph.set_input(var0, InputId(0), Value::INFINITY).unwrap();
+        graph.process_n(2);
+        let expected = NonFiniteValue { connection: first, frame: 2, value: Value::INFINITY };
+        assert_eq!(graph.non_finite_value(), Some(expected));
+
+        // Block processing checks copied values as well.
+        graph.set_finite_checks(true);
+        graph.set_input(var0, InputId(0), Value::NEG_INFINITY).unwrap();
+        graph.process_block();
+        assert_eq!(graph.non_finite_value().map(|non_finite| non_finite.connection), Some(first));
+        graph.set_finite_checks(false);
+        assert_eq!(graph.non_finite_value(), None);
+    }
+}
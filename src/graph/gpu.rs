@@ -0,0 +1,273 @@
+use super::{
+    kernels::{Kernel, Layout},
+    Graph,
+};
+use crate::{GraphError, InputId, Node, NodeId, OutputId, Value};
+use std::{
+    collections::HashMap,
+    fmt::Write,
+    future::Future,
+    mem,
+    pin::pin,
+    task::{Context, Poll, Waker},
+};
+use wgpu::util::DeviceExt;
+
+/// Value type of the compute shader (WGSL has no 64-bit floats).
+type ShaderValue = f32;
+
+/// Number of invocations per workgroup of the compute shader.
+const WORKGROUP_SIZE: usize = 64;
+
+/// Graph of built-in nodes compiled into a compute shader that processes a batch of independent instances on the GPU
+/// (see `Graph::compile_gpu`).
+pub struct GpuBatch {
+    /// Bind group of the slot and cycle buffers.
+    bind_group: wgpu::BindGroup,
+
+    /// Buffer holding the number of instances and cycles to process.
+    cycles: wgpu::Buffer,
+
+    /// Device executing the shader.
+    device: wgpu::Device,
+
+    /// Slots of unconnected inputs.
+    inputs: HashMap<(NodeId, InputId), usize>,
+
+    /// Slots of outputs.
+    outputs: HashMap<(NodeId, OutputId), usize>,
+
+    /// Compiled compute pipeline.
+    pipeline: wgpu::ComputePipeline,
+
+    /// Queue of the device.
+    queue: wgpu::Queue,
+
+    /// Buffer mapped to read slots back.
+    readback: wgpu::Buffer,
+
+    /// Number of slots per instance.
+    stride: usize,
+
+    /// Buffer holding the slots of all instances on the device.
+    storage: wgpu::Buffer,
+
+    /// Values of all instances (outputs, unconnected inputs and pending inputs of delays).
+    values: Vec<ShaderValue>,
+}
+
+impl<N: Node> Graph<N> {
+    /// Compiles the graph into a compute shader processing `batch` independent instances of it at once. Instances start
+    /// from the current outputs, delay states and unconnected input values of the graph and compute in single
+    /// precision.
+    ///
+    /// Experimental, returns `GraphError::UnsupportedNode` for nodes without arithmetic kernel and
+    /// `GraphError::BackendUnavailable` if no GPU adapter is available.
+    pub fn compile_gpu(&self, batch: usize) -> Result<GpuBatch, GraphError> {
+        let layout = self.kernel_layout()?;
+        let stride = layout.slots.len().max(1);
+
+        // One statement per node, first pass computes nodes without delay and samples inputs of delays, second pass
+        // updates delays.
+        let mut body = String::new();
+        for &id in self.processing_order.iter() {
+            let operands: Vec<String> =
+                self.operand_slots(&layout, id).iter().map(|slot| format!("slots[base + {}u]", slot)).collect();
+            let output = layout.outputs.get(&(id, OutputId(0))).copied();
+            let (slot, value) = match layout.kernels[&id] {
+                Kernel::Average if operands.is_empty() => (output.unwrap(), "0.0".to_string()),
+                Kernel::Average => (output.unwrap(), format!("({}) / {}.0", operands.join(" + "), operands.len())),
+                Kernel::Delay => (layout.pending[&id], operands[0].clone()),
+                Kernel::Product if operands.is_empty() => (output.unwrap(), "1.0".to_string()),
+                Kernel::Product => (output.unwrap(), operands.join(" * ")),
+                Kernel::Sum if operands.is_empty() => (output.unwrap(), "0.0".to_string()),
+                Kernel::Sum => (output.unwrap(), operands.join(" + ")),
+                Kernel::Variable if layout.inputs.get(&(id, InputId(0))) != output.as_ref() => {
+                    (output.unwrap(), operands[0].clone())
+                }
+                Kernel::Constant | Kernel::Variable => continue,
+            };
+            writeln!(body, "        slots[base + {}u] = {};", slot, value).unwrap();
+        }
+        for &id in self.processing_order.iter().filter(|id| layout.kernels[id] == Kernel::Delay) {
+            let (output, pending) = (layout.outputs[&(id, OutputId(0))], layout.pending[&id]);
+            writeln!(body, "        slots[base + {}u] = slots[base + {}u];", output, pending).unwrap();
+        }
+        let source = format!(
+            "@group(0) @binding(0) var<storage, read_write> slots: array<f32>;
+@group(0) @binding(1) var<uniform> cycles: vec4<u32>;
+
+@compute @workgroup_size({WORKGROUP_SIZE})
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {{
+    if (id.x >= cycles.x) {{
+        return;
+    }}
+    let base = id.x * {stride}u;
+    for (var cycle = 0u; cycle < cycles.y; cycle++) {{
+{body}    }}
+}}
+"
+        );
+
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+        let adapter = block_on(instance.request_adapter(&wgpu::RequestAdapterOptions::default()))
+            .ok_or(GraphError::BackendUnavailable)?;
+        let (device, queue) = block_on(adapter.request_device(&wgpu::DeviceDescriptor::default(), None))
+            .map_err(|_| GraphError::BackendUnavailable)?;
+        let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: None,
+            source: wgpu::ShaderSource::Wgsl(source.into()),
+        });
+        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            cache: None,
+            compilation_options: Default::default(),
+            entry_point: Some("main"),
+            label: None,
+            layout: None,
+            module: &module,
+        });
+
+        let values: Vec<ShaderValue> =
+            (0..batch).flat_map(|_| layout.slots.iter().map(|&value| value as ShaderValue)).collect();
+        let size = (batch.max(1) * stride * mem::size_of::<ShaderValue>()) as u64;
+        let storage = device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            mapped_at_creation: false,
+            size,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::COPY_SRC,
+        });
+        let readback = device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            mapped_at_creation: false,
+            size,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+        });
+        let cycles = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            contents: &[0; 4 * mem::size_of::<u32>()],
+            label: None,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            entries: &[
+                wgpu::BindGroupEntry { binding: 0, resource: storage.as_entire_binding() },
+                wgpu::BindGroupEntry { binding: 1, resource: cycles.as_entire_binding() },
+            ],
+            label: None,
+            layout: &pipeline.get_bind_group_layout(0),
+        });
+        let Layout { inputs, outputs, .. } = layout;
+        Ok(GpuBatch { bind_group, cycles, device, inputs, outputs, pipeline, queue, readback, stride, storage, values })
+    }
+}
+
+impl GpuBatch {
+    /// Returns value of an output of an instance.
+    ///
+    /// Panics if the instance is out of bounds.
+    pub fn get_output(&self, instance: usize, node: NodeId, output: OutputId) -> Result<Value, GraphError> {
+        assert!(instance < self.len(), "Instance {} is out of bounds.", instance);
+        match self.outputs.get(&(node, output)) {
+            Some(&slot) => Ok(self.values[instance * self.stride + slot] as Value),
+            None => Err(GraphError::OutputNotExists(node, output)),
+        }
+    }
+
+    /// Returns whether the batch has no instances.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns number of instances.
+    pub fn len(&self) -> usize {
+        self.values.len() / self.stride
+    }
+
+    /// Processes one cycle of all instances.
+    pub fn process(&mut self) {
+        self.process_n(1);
+    }
+
+    /// Processes given number of cycles of all instances in a single dispatch.
+    pub fn process_n(&mut self, count: usize) {
+        if self.is_empty() || count == 0 {
+            return;
+        }
+        let bytes: Vec<u8> = self.values.iter().flat_map(|value| value.to_ne_bytes()).collect();
+        let cycles: Vec<u8> = [self.len() as u32, count as u32, 0, 0].iter().flat_map(|v| v.to_ne_bytes()).collect();
+        self.queue.write_buffer(&self.storage, 0, &bytes);
+        self.queue.write_buffer(&self.cycles, 0, &cycles);
+
+        let mut encoder = self.device.create_command_encoder(&Default::default());
+        {
+            let mut pass = encoder.begin_compute_pass(&Default::default());
+            pass.set_pipeline(&self.pipeline);
+            pass.set_bind_group(0, &self.bind_group, &[]);
+            pass.dispatch_workgroups(self.len().div_ceil(WORKGROUP_SIZE) as u32, 1, 1);
+        }
+        encoder.copy_buffer_to_buffer(&self.storage, 0, &self.readback, 0, bytes.len() as u64);
+        self.queue.submit([encoder.finish()]);
+
+        let slice = self.readback.slice(..bytes.len() as u64);
+        slice.map_async(wgpu::MapMode::Read, |_| {});
+        self.device.poll(wgpu::Maintain::Wait);
+        for (value, bytes) in
+            self.values.iter_mut().zip(slice.get_mapped_range().chunks_exact(mem::size_of::<ShaderValue>()))
+        {
+            *value = ShaderValue::from_ne_bytes(bytes.try_into().unwrap());
+        }
+        self.readback.unmap();
+    }
+
+    /// Sets value of an unconnected input of an instance (connected inputs cannot be set).
+    ///
+    /// Panics if the instance is out of bounds.
+    pub fn set_input(&mut self, instance: usize, node: NodeId, input: InputId, value: Value) -> Result<(), GraphError> {
+        assert!(instance < self.len(), "Instance {} is out of bounds.", instance);
+        let slot = self.inputs.get(&(node, input)).ok_or(GraphError::InputNotExists(node, input))?;
+        self.values[instance * self.stride + slot] = value as ShaderValue;
+        Ok(())
+    }
+}
+
+/// Polls a future of the device API to completion (which resolves immediately on native backends).
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let mut context = Context::from_waker(Waker::noop());
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+            return output;
+        }
+    }
+}
+
+/// Unit tests.
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::accumulator;
+    use crate::{nodes, Connection};
+
+    #[test]
+    fn processes_batch() {
+        let (mut graph, [var0, add1, _]) = accumulator(1.0, |node| node as Box<dyn Node>);
+        let avg3 = graph.add_node(Box::new(nodes::AverageN::new(2)));
+        graph.add_connection(Connection::new(add1, OutputId(0), avg3, InputId(0))).unwrap();
+
+        // Skipped on hosts without GPU adapter.
+        let mut batch = match graph.compile_gpu(100) {
+            Ok(batch) => batch,
+            Err(error) => return assert_eq!(error, GraphError::BackendUnavailable),
+        };
+        assert_eq!(batch.len(), 100);
+        for instance in 0..batch.len() {
+            batch.set_input(instance, var0, InputId(0), instance as Value).unwrap();
+        }
+        batch.process_n(3);
+        assert_eq!(batch.get_output(7, add1, OutputId(0)), Ok(21.0));
+        assert_eq!(batch.get_output(7, avg3, OutputId(0)), Ok(10.5));
+        assert_eq!(batch.set_input(0, add1, InputId(0), 0.0), Err(GraphError::InputNotExists(add1, InputId(0))));
+
+        graph.add_node(Box::new(nodes::Split::new(2)));
+        assert_eq!(graph.compile_gpu(1).err(), Some(GraphError::UnsupportedNode(NodeId(4))));
+    }
+}
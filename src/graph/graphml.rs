@@ -0,0 +1,318 @@
+use super::{
+    patch::{Patch, PatchNode},
+    Graph,
+};
+use crate::{Connection, GraphError, InputId, Node, NodeId, NodeRegistry, OutputId, Value};
+use std::{collections::HashMap, fmt::Write};
+
+/// Data keys of GraphML documents (element, name and type of the attribute).
+const KEYS: [(&str, &str, &str); 11] = [
+    ("graph", "block_size", "int"),
+    ("graph", "sample_rate", "double"),
+    ("node", "label", "string"),
+    ("node", "params", "string"),
+    ("node", "priority", "int"),
+    ("node", "rate_divisor", "int"),
+    ("node", "type", "string"),
+    ("node", "version", "int"),
+    ("edge", "event", "boolean"),
+    ("edge", "source_output", "long"),
+    ("edge", "target_input", "long"),
+];
+
+impl<N: Node> Graph<N> {
+    /// Loads graph from a GraphML document (see `to_graphml`), constructing nodes with a registry.
+    ///
+    /// Data is looked up by attribute name, so documents edited by other tools (e.g. with layout data added) can be
+    /// loaded. Nodes with ids of the form `n<id>` keep their id, other nodes get the next free ids in document order.
+    pub fn from_graphml(xml: &str, registry: &NodeRegistry<N>) -> Result<Self, GraphError> {
+        let document = roxmltree::Document::parse(xml).map_err(|error| GraphError::InvalidFormat(error.to_string()))?;
+        let root = document.root_element();
+        let keys: HashMap<&str, &str> = root
+            .children()
+            .filter(|element| element.has_tag_name("key"))
+            .filter_map(|key| Some((key.attribute("id")?, key.attribute("attr.name")?)))
+            .collect();
+        let element = root
+            .children()
+            .find(|element| element.has_tag_name("graph"))
+            .ok_or_else(|| GraphError::InvalidFormat("Missing graph element.".to_string()))?;
+        let graph_data = data(element, &keys);
+        let mut patch = Patch {
+            block_size: parse(&graph_data, "block_size")?.unwrap_or(64),
+            connections: Vec::new(),
+            event_connections: Vec::new(),
+            nodes: Vec::new(),
+            sample_rate: parse(&graph_data, "sample_rate")?.unwrap_or(1.0),
+        };
+
+        // Nodes.
+        let elements: Vec<_> = element.children().filter(|element| element.has_tag_name("node")).collect();
+        let node_id = |element: &roxmltree::Node| {
+            let id = element.attribute("id").unwrap_or_default();
+            id.strip_prefix('n').and_then(|id| id.parse().ok()).map(NodeId)
+        };
+        let mut next_id = match elements.iter().filter_map(node_id).map(|id| id.0).max() {
+            Some(max) => max.checked_add(1),
+            None => Some(0),
+        };
+        let mut ids = HashMap::new();
+        for element in elements {
+            let id = match node_id(&element) {
+                Some(id) => id,
+                None => {
+                    let id = next_id.ok_or_else(|| GraphError::InvalidFormat("No node id left.".to_string()))?;
+                    next_id = id.checked_add(1);
+                    NodeId(id)
+                }
+            };
+            ids.insert(element.attribute("id").unwrap_or_default(), id);
+            let node_data = data(element, &keys);
+            let type_name = node_data
+                .get("type")
+                .ok_or_else(|| GraphError::InvalidFormat(format!("Node with id {} has no type.", id.0)))?;
+            let params = node_data
+                .get("params")
+                .map(|params| params.split_whitespace().map(|param| parse_value("params", param)).collect())
+                .transpose()?;
+            patch.nodes.push(PatchNode {
+                id,
+                label: node_data.get("label").map(|label| label.to_string()),
+                meta: Default::default(),
+                parameters: Default::default(),
+                params: params.unwrap_or_default(),
+                priority: parse(&node_data, "priority")?.unwrap_or(0),
+                rate_divisor: parse(&node_data, "rate_divisor")?.unwrap_or(1),
+                tags: Vec::new(),
+                type_name: type_name.to_string(),
+                version: parse(&node_data, "version")?.unwrap_or(0),
+            });
+        }
+
+        // Connections.
+        for element in element.children().filter(|element| element.has_tag_name("edge")) {
+            let endpoint = |name| {
+                let id = element.attribute(name).unwrap_or_default();
+                ids.get(id).copied().ok_or_else(|| GraphError::InvalidFormat(format!("Unknown node {}.", id)))
+            };
+            let edge_data = data(element, &keys);
+            let connection = Connection::new(
+                endpoint("source")?,
+                OutputId(parse(&edge_data, "source_output")?.unwrap_or(0)),
+                endpoint("target")?,
+                InputId(parse(&edge_data, "target_input")?.unwrap_or(0)),
+            );
+            match parse(&edge_data, "event")?.unwrap_or(false) {
+                true => patch.event_connections.push(connection),
+                false => patch.connections.push(connection),
+            }
+        }
+        Graph::from_patch(patch, registry)
+    }
+
+    /// Saves graph as GraphML document, naming node types with a registry, e.g. for layout and analysis in general
+    /// graph tools.
+    ///
+    /// Nodes have the ids `n<id>` and carry their registered type, label, constructor parameters (space separated),
+    /// priority, rate divisor and version. Edges carry their ports and whether they are event connections. Node
+    /// parameters, tags and metadata are not included, so they are lost when the document is loaded again (use
+    /// `to_json` to keep them).
+    pub fn to_graphml(&self, registry: &NodeRegistry<N>) -> Result<String, GraphError> {
+        let patch = self.to_patch(registry)?;
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml.push_str("<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n");
+        for (element, name, kind) in KEYS {
+            writeln!(xml, "  <key id=\"{}\" for=\"{}\" attr.name=\"{}\" attr.type=\"{}\"/>", name, element, name, kind)
+                .unwrap();
+        }
+        xml.push_str("  <graph id=\"G\" edgedefault=\"directed\">\n");
+        writeln!(xml, "    <data key=\"block_size\">{}</data>", patch.block_size).unwrap();
+        writeln!(xml, "    <data key=\"sample_rate\">{}</data>", patch.sample_rate).unwrap();
+        for node in patch.nodes {
+            writeln!(xml, "    <node id=\"n{}\">", node.id.0).unwrap();
+            if let Some(label) = node.label {
+                writeln!(xml, "      <data key=\"label\">{}</data>", escape(&label)).unwrap();
+            }
+            if !node.params.is_empty() {
+                let params: Vec<String> = node.params.iter().map(Value::to_string).collect();
+                writeln!(xml, "      <data key=\"params\">{}</data>", params.join(" ")).unwrap();
+            }
+            if node.priority != 0 {
+                writeln!(xml, "      <data key=\"priority\">{}</data>", node.priority).unwrap();
+            }
+            if node.rate_divisor != 1 {
+                writeln!(xml, "      <data key=\"rate_divisor\">{}</data>", node.rate_divisor).unwrap();
+            }
+            writeln!(xml, "      <data key=\"type\">{}</data>", escape(&node.type_name)).unwrap();
+            if node.version != 0 {
+                writeln!(xml, "      <data key=\"version\">{}</data>", node.version).unwrap();
+            }
+            xml.push_str("    </node>\n");
+        }
+        let connections = patch.connections.iter().map(|connection| (connection, false));
+        let event_connections = patch.event_connections.iter().map(|connection| (connection, true));
+        for (connection, event) in connections.chain(event_connections) {
+            writeln!(
+                xml,
+                "    <edge source=\"n{}\" target=\"n{}\">",
+                connection.source_node.0, connection.target_node.0
+            )
+            .unwrap();
+            if event {
+                xml.push_str("      <data key=\"event\">true</data>\n");
+            }
+            writeln!(xml, "      <data key=\"source_output\">{}</data>", connection.source_output.0).unwrap();
+            writeln!(xml, "      <data key=\"target_input\">{}</data>", connection.target_input.0).unwrap();
+            xml.push_str("    </edge>\n");
+        }
+        xml.push_str("  </graph>\n</graphml>\n");
+        Ok(xml)
+    }
+}
+
+/// Returns data of an element by attribute name.
+fn data<'a>(element: roxmltree::Node<'a, '_>, keys: &HashMap<&str, &'a str>) -> HashMap<&'a str, &'a str> {
+    element
+        .children()
+        .filter(|data| data.has_tag_name("data"))
+        .filter_map(|data| Some((*keys.get(data.attribute("key")?)?, data.text().unwrap_or_default().trim())))
+        .collect()
+}
+
+/// Escapes special characters for XML text.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+/// Parses optional data of an element.
+fn parse<T: std::str::FromStr>(data: &HashMap<&str, &str>, name: &str) -> Result<Option<T>, GraphError> {
+    data.get(name).map(|value| parse_value(name, value)).transpose()
+}
+
+/// Parses a data value.
+fn parse_value<T: std::str::FromStr>(name: &str, value: &str) -> Result<T, GraphError> {
+    value.parse().map_err(|_| GraphError::InvalidFormat(format!("Invalid value {} of {}.", value, name)))
+}
+
+/// Unit tests.
+#[cfg(all(test, feature = "dsp", feature = "generators"))]
+mod tests {
+    use super::*;
+    use crate::nodes;
+
+    #[test]
+    fn from_graphml() {
+        // Document edited by another tool (renamed keys, layout data and node ids).
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <graphml xmlns="http://graphml.graphdrawing.org/xmlns">
+              <key id="d0" for="node" attr.name="type" attr.type="string"/>
+              <key id="d1" for="node" attr.name="params" attr.type="string"/>
+              <key id="d2" for="edge" attr.name="target_input" attr.type="long"/>
+              <key id="d3" for="node" attr.name="x" attr.type="double"/>
+              <graph id="G" edgedefault="directed">
+                <node id="n0"><data key="d0">variable</data><data key="d1">2</data><data key="d3">1.5</data></node>
+                <node id="sum"><data key="d0">addition</data></node>
+                <node id="n3"><data key="d0">delay</data></node>
+                <edge source="n0" target="sum"/>
+                <edge source="sum" target="n3"/>
+                <edge source="n3" target="sum"><data key="d2">1</data></edge>
+              </graph>
+            </graphml>"#;
+        let mut graph = Graph::from_graphml(xml, &NodeRegistry::builtin()).unwrap();
+        graph.process_n(3);
+        assert_eq!(graph.get_output(NodeId(4), OutputId(0)), Ok(6.0));
+
+        // Invalid documents.
+        assert!(matches!(
+            Graph::from_graphml("<graphml>", &NodeRegistry::builtin()),
+            Err(GraphError::InvalidFormat(_))
+        ));
+        let xml = r#"<graphml><graph><node id="n0"/></graph></graphml>"#;
+        assert_eq!(
+            Graph::from_graphml(xml, &NodeRegistry::builtin()).err(),
+            Some(GraphError::InvalidFormat("Node with id 0 has no type.".to_string()))
+        );
+        let xml = r#"<graphml><graph><edge source="n0" target="n1"/></graph></graphml>"#;
+        assert_eq!(
+            Graph::from_graphml(xml, &NodeRegistry::builtin()).err(),
+            Some(GraphError::InvalidFormat("Unknown node n0.".to_string()))
+        );
+
+        // The highest id is reserved.
+        let node = |id| format!(r#"<node id="{}"><data key="type">variable</data></node>"#, id);
+        let keys = r#"<key id="type" for="node" attr.name="type"/>"#;
+        let xml = format!("<graphml>{}<graph>{}</graph></graphml>", keys, node("n4294967294"));
+        assert!(Graph::from_graphml(&xml, &NodeRegistry::builtin()).is_ok());
+        let xml = format!("<graphml>{}<graph>{}</graph></graphml>", keys, node("n4294967295"));
+        assert_eq!(
+            Graph::from_graphml(&xml, &NodeRegistry::builtin()).err(),
+            Some(GraphError::InvalidFormat("Node id 4294967295 is out of range.".to_string()))
+        );
+        let xml = format!("<graphml>{}<graph>{}{}</graph></graphml>", keys, node("n4294967295"), node("var"));
+        assert_eq!(
+            Graph::from_graphml(&xml, &NodeRegistry::builtin()).err(),
+            Some(GraphError::InvalidFormat("No node id left.".to_string()))
+        );
+    }
+
+    #[test]
+    fn to_graphml() {
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        let var0 = graph.add_node(Box::new(nodes::Variable::new(2.0)));
+        let del1 = graph.add_node(Box::new(nodes::Delay::new()));
+        graph.add_connection(Connection::new(var0, OutputId(0), del1, InputId(0))).unwrap();
+        graph.set_label(var0, Some("<in>")).unwrap();
+        graph.set_priority(del1, 3).unwrap();
+
+        let xml = graph.to_graphml(&NodeRegistry::builtin()).unwrap();
+        assert_eq!(
+            xml,
+            concat!(
+                "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+                "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n",
+                "  <key id=\"block_size\" for=\"graph\" attr.name=\"block_size\" attr.type=\"int\"/>\n",
+                "  <key id=\"sample_rate\" for=\"graph\" attr.name=\"sample_rate\" attr.type=\"double\"/>\n",
+                "  <key id=\"label\" for=\"node\" attr.name=\"label\" attr.type=\"string\"/>\n",
+                "  <key id=\"params\" for=\"node\" attr.name=\"params\" attr.type=\"string\"/>\n",
+                "  <key id=\"priority\" for=\"node\" attr.name=\"priority\" attr.type=\"int\"/>\n",
+                "  <key id=\"rate_divisor\" for=\"node\" attr.name=\"rate_divisor\" attr.type=\"int\"/>\n",
+                "  <key id=\"type\" for=\"node\" attr.name=\"type\" attr.type=\"string\"/>\n",
+                "  <key id=\"version\" for=\"node\" attr.name=\"version\" attr.type=\"int\"/>\n",
+                "  <key id=\"event\" for=\"edge\" attr.name=\"event\" attr.type=\"boolean\"/>\n",
+                "  <key id=\"source_output\" for=\"edge\" attr.name=\"source_output\" attr.type=\"long\"/>\n",
+                "  <key id=\"target_input\" for=\"edge\" attr.name=\"target_input\" attr.type=\"long\"/>\n",
+                "  <graph id=\"G\" edgedefault=\"directed\">\n",
+                "    <data key=\"block_size\">64</data>\n",
+                "    <data key=\"sample_rate\">1</data>\n",
+                "    <node id=\"n0\">\n",
+                "      <data key=\"label\">&lt;in&gt;</data>\n",
+                "      <data key=\"params\">2</data>\n",
+                "      <data key=\"type\">variable</data>\n",
+                "    </node>\n",
+                "    <node id=\"n1\">\n",
+                "      <data key=\"params\">0 0</data>\n",
+                "      <data key=\"priority\">3</data>\n",
+                "      <data key=\"type\">delay</data>\n",
+                "    </node>\n",
+                "    <edge source=\"n0\" target=\"n1\">\n",
+                "      <data key=\"source_output\">0</data>\n",
+                "      <data key=\"target_input\">0</data>\n",
+                "    </edge>\n",
+                "  </graph>\n",
+                "</graphml>\n",
+            )
+        );
+        let restored = Graph::from_graphml(&xml, &NodeRegistry::builtin()).unwrap();
+        assert_eq!(restored.find_node("<in>"), Ok(var0));
+        assert_eq!(restored.to_graphml(&NodeRegistry::builtin()).as_ref(), Ok(&xml));
+
+        // Tags and metadata are not saved.
+        graph.add_tag(var0, "input").unwrap();
+        graph.set_meta(var0, "x", "10").unwrap();
+        let tagged = graph.to_graphml(&NodeRegistry::builtin()).unwrap();
+        let restored = Graph::from_graphml(&tagged, &NodeRegistry::builtin()).unwrap();
+        assert_eq!(restored.tags(var0), Ok(&[][..]));
+        assert_eq!(restored.get_meta(var0, "x"), Ok(None));
+        assert_eq!(restored.to_graphml(&NodeRegistry::builtin()), Ok(xml));
+    }
+}
@@ -0,0 +1,49 @@
+use super::Graph;
+use crate::{Connection, Node, NodeId};
+use petgraph::graph::{DiGraph, NodeIndex};
+use std::collections::HashMap;
+
+impl<N: Node> Graph<N> {
+    /// Returns topology as petgraph digraph (node weights are node ids in ascending order, edge weights are
+    /// connections), e.g. to apply petgraph algorithms.
+    pub fn to_petgraph(&self) -> DiGraph<NodeId, Connection> {
+        let mut ids: Vec<NodeId> = self.nodes.keys().copied().collect();
+        ids.sort_unstable();
+
+        let mut topology = DiGraph::with_capacity(ids.len(), self.connections.len());
+        let indices: HashMap<NodeId, NodeIndex> = ids.into_iter().map(|id| (id, topology.add_node(id))).collect();
+        for &connection in self.connections.iter() {
+            topology.add_edge(indices[&connection.source_node], indices[&connection.target_node], connection);
+        }
+        topology
+    }
+}
+
+/// Unit tests.
+#[cfg(all(test, feature = "dsp", feature = "generators", feature = "math"))]
+mod tests {
+    use super::*;
+    use crate::fixtures::accumulator;
+    use petgraph::algo;
+
+    #[test]
+    fn to_petgraph() {
+        let (graph, [var0, add1, del2]) = accumulator(1.0, |node| node as Box<dyn Node>);
+
+        let topology = graph.to_petgraph();
+        assert_eq!(topology.node_weights().copied().collect::<Vec<_>>(), vec![var0, add1, del2]);
+        assert_eq!(topology.edge_weights().copied().collect::<Vec<_>>(), graph.connections);
+
+        // Feedback loop is a strongly connected component.
+        let mut components: Vec<Vec<NodeId>> = algo::kosaraju_scc(&topology)
+            .into_iter()
+            .map(|component| {
+                let mut ids: Vec<NodeId> = component.into_iter().map(|index| topology[index]).collect();
+                ids.sort_unstable();
+                ids
+            })
+            .collect();
+        components.sort_unstable();
+        assert_eq!(components, vec![vec![var0], vec![add1, del2]]);
+    }
+}
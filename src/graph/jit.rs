@@ -0,0 +1,189 @@
+use super::{
+    kernels::{Kernel, Layout},
+    Graph,
+};
+use crate::{GraphError, InputId, Node, NodeId, OutputId, Value};
+use cranelift_codegen::ir::{self, types, AbiParam, InstBuilder, MemFlags, Type};
+use cranelift_frontend::{FunctionBuilder, FunctionBuilderContext};
+use cranelift_jit::{JITBuilder, JITModule};
+use cranelift_module::{default_libcall_names, Linkage, Module};
+use std::{collections::HashMap, mem};
+
+/// Cranelift type of values.
+#[cfg(not(feature = "f32"))]
+const VALUE_TYPE: Type = types::F64;
+
+/// Cranelift type of values.
+#[cfg(feature = "f32")]
+const VALUE_TYPE: Type = types::F32;
+
+/// Graph of built-in nodes compiled into native code that processes one cycle (see `Graph::compile_jit`).
+pub struct JitProgram {
+    /// Compiled cycle, operating on the value slots.
+    function: extern "C" fn(*mut Value),
+
+    /// Slots of unconnected inputs.
+    inputs: HashMap<(NodeId, InputId), usize>,
+
+    /// Module owning the compiled code.
+    _module: JITModule,
+
+    /// Slots of outputs.
+    outputs: HashMap<(NodeId, OutputId), usize>,
+
+    /// Values of outputs, unconnected inputs and pending inputs of delays.
+    slots: Vec<Value>,
+}
+
+impl<N: Node> Graph<N> {
+    /// Compiles the graph into native code for the per-cycle hot path, with the same processing order and delay
+    /// semantics. Current outputs, delay states and unconnected input values are carried over, other settings of the
+    /// graph (e.g. bypass or automations) are dropped.
+    ///
+    /// Returns `GraphError::UnsupportedNode` for nodes without arithmetic kernel and panics if the host architecture
+    /// is not supported by Cranelift.
+    pub fn compile_jit(&self) -> Result<JitProgram, GraphError> {
+        let layout = self.kernel_layout()?;
+
+        let mut module = JITModule::new(JITBuilder::new(default_libcall_names()).expect("Unsupported host."));
+        let mut context = module.make_context();
+        context.func.signature.params.push(AbiParam::new(module.target_config().pointer_type()));
+        let mut builder_context = FunctionBuilderContext::new();
+        let mut builder = FunctionBuilder::new(&mut context.func, &mut builder_context);
+        let block = builder.create_block();
+        builder.append_block_params_for_function_params(block);
+        builder.switch_to_block(block);
+        builder.seal_block(block);
+        let base = builder.block_params(block)[0];
+        let load = |builder: &mut FunctionBuilder, slot: usize| {
+            builder.ins().load(VALUE_TYPE, MemFlags::trusted(), base, (slot * mem::size_of::<Value>()) as i32)
+        };
+        let store = |builder: &mut FunctionBuilder, slot: usize, value: ir::Value| {
+            builder.ins().store(MemFlags::trusted(), value, base, (slot * mem::size_of::<Value>()) as i32);
+        };
+
+        // First pass computes nodes without delay and samples inputs of delays, second pass updates delays.
+        for &id in self.processing_order.iter() {
+            let operands = self.operand_slots(&layout, id);
+            let output = layout.outputs.get(&(id, OutputId(0))).copied();
+            let value = match layout.kernels[&id] {
+                Kernel::Average | Kernel::Sum | Kernel::Product => {
+                    let (mut value, product) = match layout.kernels[&id] {
+                        Kernel::Product => (constant(&mut builder, 1.0), true),
+                        _ => (constant(&mut builder, 0.0), false),
+                    };
+                    for &operand in operands.iter() {
+                        let operand = load(&mut builder, operand);
+                        value = match product {
+                            true => builder.ins().fmul(value, operand),
+                            false => builder.ins().fadd(value, operand),
+                        };
+                    }
+                    if layout.kernels[&id] == Kernel::Average && !operands.is_empty() {
+                        let count = constant(&mut builder, operands.len() as Value);
+                        value = builder.ins().fdiv(value, count);
+                    }
+                    value
+                }
+                Kernel::Delay => {
+                    let value = load(&mut builder, operands[0]);
+                    store(&mut builder, layout.pending[&id], value);
+                    continue;
+                }
+                Kernel::Variable if operands[0] != output.unwrap() => load(&mut builder, operands[0]),
+                Kernel::Constant | Kernel::Variable => continue,
+            };
+            store(&mut builder, output.unwrap(), value);
+        }
+        for &id in self.processing_order.iter().filter(|id| layout.kernels[id] == Kernel::Delay) {
+            let value = load(&mut builder, layout.pending[&id]);
+            store(&mut builder, layout.outputs[&(id, OutputId(0))], value);
+        }
+        builder.ins().return_(&[]);
+        builder.finalize();
+
+        let function = module.declare_function("cycle", Linkage::Local, &context.func.signature).unwrap();
+        module.define_function(function, &mut context).expect("Code generation failed.");
+        module.finalize_definitions().unwrap();
+        let code = module.get_finalized_function(function);
+
+        // SAFETY: The function was compiled with the default calling convention and a single pointer parameter.
+        let function = unsafe { mem::transmute::<*const u8, extern "C" fn(*mut Value)>(code) };
+        let Layout { inputs, outputs, slots, .. } = layout;
+        Ok(JitProgram { function, inputs, _module: module, outputs, slots })
+    }
+}
+
+impl JitProgram {
+    /// Returns value of an output.
+    pub fn get_output(&self, node: NodeId, output: OutputId) -> Result<Value, GraphError> {
+        match self.outputs.get(&(node, output)) {
+            Some(&slot) => Ok(self.slots[slot]),
+            None => Err(GraphError::OutputNotExists(node, output)),
+        }
+    }
+
+    /// Processes one cycle.
+    pub fn process(&mut self) {
+        (self.function)(self.slots.as_mut_ptr());
+    }
+
+    /// Processes given number of cycles.
+    pub fn process_n(&mut self, count: usize) {
+        for _ in 0..count {
+            self.process();
+        }
+    }
+
+    /// Sets value of an unconnected input (connected inputs cannot be set).
+    pub fn set_input(&mut self, node: NodeId, input: InputId, value: Value) -> Result<(), GraphError> {
+        let slot = self.inputs.get(&(node, input)).ok_or(GraphError::InputNotExists(node, input))?;
+        self.slots[*slot] = value;
+        Ok(())
+    }
+}
+
+/// Emits a constant value.
+fn constant(builder: &mut FunctionBuilder, value: Value) -> ir::Value {
+    #[cfg(not(feature = "f32"))]
+    let value = builder.ins().f64const(value);
+    #[cfg(feature = "f32")]
+    let value = builder.ins().f32const(value);
+    value
+}
+
+/// Unit tests.
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::accumulator;
+    use crate::{nodes, Connection};
+
+    #[test]
+    fn processes_like_graph() {
+        let (mut graph, [var0, add1, del2]) = accumulator(1.0, |node| node as Box<dyn Node>);
+        let prd3 = graph.add_node(Box::new(nodes::ProductN::new(2)));
+        let avg4 = graph.add_node(Box::new(nodes::AverageN::new(2)));
+        graph.add_connection(Connection::new(add1, OutputId(0), prd3, InputId(0))).unwrap();
+        graph.add_connection(Connection::new(prd3, OutputId(0), avg4, InputId(0))).unwrap();
+        graph.set_input(prd3, InputId(1), 3.0).unwrap();
+        graph.process();
+
+        let mut program = graph.compile_jit().unwrap();
+        for _ in 0..3 {
+            graph.process();
+            program.process();
+            for node in [add1, del2, prd3, avg4] {
+                assert_eq!(program.get_output(node, OutputId(0)), graph.get_output(node, OutputId(0)));
+            }
+        }
+        program.set_input(var0, InputId(0), 2.0).unwrap();
+        program.process();
+        assert_eq!(program.get_output(add1, OutputId(0)), Ok(6.0));
+        assert_eq!(program.set_input(add1, InputId(0), 0.0), Err(GraphError::InputNotExists(add1, InputId(0))));
+
+        // Other nodes are not supported.
+        graph.add_node(Box::new(nodes::Split::new(2)));
+        assert_eq!(graph.compile_jit().err(), Some(GraphError::UnsupportedNode(NodeId(5))));
+    }
+}
@@ -0,0 +1,157 @@
+use super::{patch::Patch, Graph};
+use crate::{GraphError, Node, NodeRegistry};
+
+impl<N: Node> Graph<N> {
+    /// Loads graph from a JSON patch (see `to_json` for the format), constructing nodes with a registry.
+    pub fn from_json(json: &str, registry: &NodeRegistry<N>) -> Result<Self, GraphError> {
+        let patch: Patch = serde_json::from_str(json).map_err(|error| GraphError::InvalidFormat(error.to_string()))?;
+        Graph::from_patch(patch, registry)
+    }
+
+    /// Saves graph as JSON patch, naming node types with a registry.
+    ///
+    /// A patch is an object with the following fields (optional fields may be omitted):
+    ///
+    /// ```text
+    /// {
+    ///   "block_size": 64,                   // optional, frames per block
+    ///   "connections": [                    // optional
+    ///     { "source_node": 0, "source_output": 0, "target_input": 0, "target_node": 1 }
+    ///   ],
+    ///   "event_connections": [],            // optional, same format as connections
+    ///   "nodes": [
+    ///     {
+    ///       "id": 0,                        // unique node id
+    ///       "label": "gain",                // optional, unique label
+    ///       "meta": { "color": "red" },     // optional, opaque metadata (e.g. of editors)
+    ///       "parameters": { "mode": 1.0 },  // optional, node parameters by name
+    ///       "params": [0.5],                // optional, constructor parameters (arguments and state snapshot)
+    ///       "priority": 0,                  // optional
+    ///       "rate_divisor": 1,              // optional, clock domain divisor
+    ///       "tags": ["voice1"],             // optional, group tags
+    ///       "type": "variable",             // registered node type
+    ///       "version": 1                    // optional, version of the node type (see `NodeRegistry::set_version`)
+    ///     }
+    ///   ],
+    ///   "sample_rate": 48000.0              // optional, frames per second
+    /// }
+    /// ```
+    pub fn to_json(&self, registry: &NodeRegistry<N>) -> Result<String, GraphError> {
+        let patch = self.to_patch(registry)?;
+        serde_json::to_string_pretty(&patch).map_err(|error| GraphError::InvalidFormat(error.to_string()))
+    }
+}
+
+/// Unit tests.
+#[cfg(all(test, feature = "dsp", feature = "generators", feature = "math"))]
+mod tests {
+    use super::*;
+    use crate::{nodes, Connection, InputId, NodeId, OutputId};
+
+    #[test]
+    fn from_json() {
+        let json = r#"{
+            "connections": [
+                { "source_node": 0, "source_output": 0, "target_input": 0, "target_node": 4 },
+                { "source_node": 4, "source_output": 0, "target_input": 0, "target_node": 7 },
+                { "source_node": 7, "source_output": 0, "target_input": 1, "target_node": 4 }
+            ],
+            "nodes": [
+                { "id": 0, "label": "input", "params": [2.0], "type": "variable" },
+                { "id": 4, "type": "addition" },
+                { "id": 7, "rate_divisor": 2, "type": "delay" }
+            ]
+        }"#;
+        let mut graph = Graph::from_json(json, &NodeRegistry::builtin()).unwrap();
+        assert_eq!(graph.find_node("input"), Ok(NodeId(0)));
+        assert_eq!(graph.get_rate_divisor(NodeId(7)), Ok(2));
+        assert_eq!(graph.block_size(), 64);
+        graph.process_n(3);
+        assert_eq!(graph.get_output(NodeId(4), OutputId(0)), Ok(4.0));
+        assert_eq!(graph.add_node(Box::new(nodes::Delay::new())), NodeId(8));
+
+        // Invalid patches.
+        assert!(matches!(Graph::from_json("{", &NodeRegistry::builtin()), Err(GraphError::InvalidFormat(_))));
+        let json = r#"{ "nodes": [{ "id": 0, "type": "multiplication" }] }"#;
+        assert_eq!(
+            Graph::from_json(json, &NodeRegistry::builtin()).err(),
+            Some(GraphError::NodeTypeNotExists("multiplication".to_string()))
+        );
+        let json = r#"{ "nodes": [{ "id": 0, "type": "delay" }, { "id": 0, "type": "delay" }] }"#;
+        assert_eq!(
+            Graph::from_json(json, &NodeRegistry::builtin()).err(),
+            Some(GraphError::NodeAlreadyExists(NodeId(0)))
+        );
+        let json = r#"{
+            "connections": [{ "source_node": 0, "source_output": 0, "target_input": 1, "target_node": 0 }],
+            "nodes": [{ "id": 0, "type": "delay" }]
+        }"#;
+        assert_eq!(
+            Graph::from_json(json, &NodeRegistry::builtin()).err(),
+            Some(GraphError::InputNotExists(NodeId(0), InputId(1)))
+        );
+    }
+
+    #[test]
+    fn migrate_versions() {
+        // Variables now store their value in units instead of cents.
+        let mut registry = NodeRegistry::builtin();
+        registry
+            .set_version("variable", 1, |version, saved| {
+                if version < 1 {
+                    saved.state.iter_mut().for_each(|value| *value /= 100.0);
+                }
+            })
+            .unwrap();
+        let json = r#"{ "nodes": [{ "id": 0, "params": [250.0], "type": "variable" }] }"#;
+        let graph = Graph::from_json(json, &registry).unwrap();
+        assert_eq!(graph.get_output(NodeId(0), OutputId(0)), Ok(2.5));
+
+        // Current versions are stored and not migrated again, newer versions are rejected.
+        let json = graph.to_json(&registry).unwrap();
+        assert!(json.contains(r#""version": 1"#));
+        let restored = Graph::from_json(&json, &registry).unwrap();
+        assert_eq!(restored.get_output(NodeId(0), OutputId(0)), Ok(2.5));
+        assert_eq!(
+            Graph::from_json(&json, &NodeRegistry::builtin()).err(),
+            Some(GraphError::InvalidFormat(
+                "Node type variable has version 1, which is newer than the registered version 0.".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn to_json() {
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        let var0 = graph.add_node(Box::new(nodes::Variable::new(2.0)));
+        let del1 = graph.add_node(Box::new(nodes::Delay::new()));
+        graph.add_connection(Connection::new(var0, OutputId(0), del1, InputId(0))).unwrap();
+        graph.set_label(var0, Some("input")).unwrap();
+        graph.set_priority(del1, 3).unwrap();
+        graph.add_tag(del1, "feedback").unwrap();
+        graph.process();
+
+        let json = graph.to_json(&NodeRegistry::builtin()).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "block_size": 64,
+                "connections": [{ "source_node": 0, "source_output": 0, "target_input": 0, "target_node": 1 }],
+                "nodes": [
+                    { "id": 0, "label": "input", "params": [2.0], "type": "variable" },
+                    { "id": 1, "params": [2.0, 2.0], "priority": 3, "tags": ["feedback"], "type": "delay" }
+                ],
+                "sample_rate": 1.0
+            })
+        );
+        let restored = Graph::from_json(&json, &NodeRegistry::builtin()).unwrap();
+        assert_eq!(restored.to_json(&NodeRegistry::builtin()), Ok(json));
+
+        graph.add_node(Box::new(nodes::Addition::new()));
+        assert_eq!(
+            graph.to_json(&NodeRegistry::new()).err(),
+            Some(GraphError::NodeTypeNotExists("flowing::nodes::variable::Variable".to_string()))
+        );
+    }
+}
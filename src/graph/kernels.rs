@@ -0,0 +1,126 @@
+use super::Graph;
+use crate::{nodes, GraphError, InputId, Node, NodeId, OutputId, Value};
+use std::{any::type_name, collections::HashMap};
+
+/// Built-in node kernel supported by code generating backends.
+#[derive(Clone, Copy, PartialEq)]
+pub(super) enum Kernel {
+    /// Average of inputs (`AverageN`).
+    Average,
+
+    /// Fixed outputs (`Constant`).
+    Constant,
+
+    /// Input delayed by one cycle (`Delay`).
+    Delay,
+
+    /// Product of inputs (`ProductN`).
+    Product,
+
+    /// Sum of inputs (`Addition`, `SumN`).
+    Sum,
+
+    /// Held input value (`Variable`).
+    Variable,
+}
+
+/// Assignment of node kernels and values to slots of a flat value array.
+pub(super) struct Layout {
+    /// Slots of unconnected inputs (shared with the output of variables).
+    pub inputs: HashMap<(NodeId, InputId), usize>,
+
+    /// Kernels of nodes.
+    pub kernels: HashMap<NodeId, Kernel>,
+
+    /// Slots of outputs.
+    pub outputs: HashMap<(NodeId, OutputId), usize>,
+
+    /// Slots of pending inputs of delays.
+    pub pending: HashMap<NodeId, usize>,
+
+    /// Initial values of slots.
+    pub slots: Vec<Value>,
+}
+
+impl<N: Node> Graph<N> {
+    /// Lays out a graph of built-in arithmetic nodes (`Addition`, `AverageN`, `Constant`, `Delay`, `ProductN`, `SumN`
+    /// and `Variable` without range), initialized with current outputs, delay states and unconnected input values. This
+    /// is the set of nodes supported by all code generating backends.
+    pub(super) fn kernel_layout(&self) -> Result<Layout, GraphError> {
+        let mut kernels = HashMap::with_capacity(self.nodes.len());
+        for (&id, node) in self.nodes.iter() {
+            kernels.insert(id, kernel(node).ok_or(GraphError::UnsupportedNode(id))?);
+        }
+        if let Some(c) = self.connections.iter().find(|c| c.source_output == OutputId::STATUS) {
+            return Err(GraphError::UnsupportedNode(c.source_node));
+        }
+
+        let mut layout =
+            Layout { inputs: HashMap::new(), kernels, outputs: HashMap::new(), pending: HashMap::new(), slots: vec![] };
+        let mut ids: Vec<NodeId> = self.nodes.keys().copied().collect();
+        ids.sort_unstable();
+        for &id in ids.iter() {
+            let node = &self.nodes[&id];
+            for &output in node.list_outputs() {
+                layout.outputs.insert((id, output), layout.slots.len());
+                layout.slots.push(node.get_output(output));
+            }
+            for &input in node.list_inputs() {
+                if self.source_of(id, input).is_some() {
+                    continue;
+                }
+                let slot = match layout.kernels[&id] {
+                    Kernel::Variable => layout.outputs[&(id, OutputId(0))],
+                    _ => {
+                        layout.slots.push(self.unconnected_value(id, input));
+                        layout.slots.len() - 1
+                    }
+                };
+                layout.inputs.insert((id, input), slot);
+            }
+            if layout.kernels[&id] == Kernel::Delay {
+                layout.pending.insert(id, layout.slots.len());
+                layout.slots.push(node.snapshot()[0]);
+            }
+        }
+        Ok(layout)
+    }
+
+    /// Returns slots read by the inputs of a node.
+    pub(super) fn operand_slots(&self, layout: &Layout, node: NodeId) -> Vec<usize> {
+        self.nodes[&node]
+            .list_inputs()
+            .iter()
+            .map(|&input| match self.source_of(node, input) {
+                Some(source) => layout.outputs[&source],
+                None => layout.inputs[&(node, input)],
+            })
+            .collect()
+    }
+}
+
+/// Returns whether a variable clamps set values to a range (not supported by kernels).
+fn is_ranged<N: Node>(node: &N) -> bool {
+    let info = node.input_info(InputId(0));
+    info.min != Value::NEG_INFINITY || info.max != Value::INFINITY
+}
+
+/// Returns kernel of a built-in node.
+fn kernel<N: Node>(node: &N) -> Option<Kernel> {
+    let name = node.type_name();
+    if name == type_name::<nodes::Addition>() || name == type_name::<nodes::SumN>() {
+        Some(Kernel::Sum)
+    } else if name == type_name::<nodes::AverageN>() {
+        Some(Kernel::Average)
+    } else if name == type_name::<nodes::Constant>() {
+        Some(Kernel::Constant)
+    } else if name == type_name::<nodes::Delay>() {
+        Some(Kernel::Delay)
+    } else if name == type_name::<nodes::ProductN>() {
+        Some(Kernel::Product)
+    } else if name == type_name::<nodes::Variable>() && !is_ranged(node) {
+        Some(Kernel::Variable)
+    } else {
+        None
+    }
+}
@@ -0,0 +1,86 @@
+use super::{has_output, Graph};
+use crate::{GraphError, Node, NodeId, OutputId, Value};
+use std::{collections::HashSet, time::Instant};
+
+impl<N: Node> Graph<N> {
+    /// Processes one cycle of only the ancestors of a node (nodes its inputs depend on through value or event
+    /// connections) and returns the value of an output, e.g. for sparse queries over large graphs.
+    ///
+    /// Ancestors of nodes that introduce delay are processed as well, so their state advances like in `process`
+    /// (other nodes keep their state and outputs, but miss the cycle). Evaluation is not real-time safe.
+    pub fn evaluate(&mut self, node: NodeId, output: OutputId) -> Result<Value, GraphError> {
+        if !has_output(self.get_node(node)?, output) {
+            return Err(GraphError::OutputNotExists(node, output));
+        }
+        let ancestors = self.ancestors(node);
+
+        self.cycle += 1;
+        self.cycle_start = Instant::now();
+        self.skipped_nodes.clear();
+        self.apply_params();
+        self.apply_automations(1);
+        self.expire_events();
+        for index in 0..self.processing_order.len() {
+            let id = self.processing_order[index];
+            if ancestors.contains(&id) {
+                self.step(id, None);
+            }
+        }
+        for index in 0..self.processing_order.len() {
+            let id = self.processing_order[index];
+            if ancestors.contains(&id) {
+                self.step_delayed(id);
+            }
+        }
+        self.call_probes();
+        self.record_outputs();
+        self.context.frame += 1;
+        Ok(self.output_value(node, output))
+    }
+
+    /// Returns a node and all nodes it depends on through value or event connections.
+    fn ancestors(&self, node: NodeId) -> HashSet<NodeId> {
+        let mut ancestors = HashSet::from([node]);
+        let mut pending = vec![node];
+        while let Some(id) = pending.pop() {
+            for connection in self.connections.iter().chain(self.event_connections.iter()) {
+                if connection.target_node == id && ancestors.insert(connection.source_node) {
+                    pending.push(connection.source_node);
+                }
+            }
+        }
+        ancestors
+    }
+}
+
+/// Unit tests.
+#[cfg(all(test, feature = "dsp", feature = "generators", feature = "math"))]
+mod tests {
+    use super::*;
+    use crate::fixtures::accumulator;
+    use crate::{nodes, Connection, InputId};
+    use std::{cell::Cell, rc::Rc};
+
+    #[test]
+    fn evaluates_ancestors() {
+        let (mut graph, [var0, add1, del2]) = accumulator(1.0, |node| node as Box<dyn Node>);
+        let calls = Rc::new(Cell::new(0));
+        let counter = calls.clone();
+        let fn3 = graph.add_node(Box::new(nodes::FnNode::new(1, 1, move |inputs, outputs| {
+            counter.set(counter.get() + 1);
+            outputs[0] = inputs[0];
+        })));
+        graph.add_connection(Connection::new(var0, OutputId(0), fn3, InputId(0))).unwrap();
+
+        // Accumulator advances through its delay, unrelated node is not processed.
+        assert_eq!(graph.evaluate(add1, OutputId(0)), Ok(1.0));
+        assert_eq!(graph.evaluate(add1, OutputId(0)), Ok(2.0));
+        assert_eq!(graph.evaluate(del2, OutputId(0)), Ok(3.0));
+        assert_eq!(calls.get(), 0);
+        assert_eq!(graph.context().frame, 3);
+
+        assert_eq!(graph.evaluate(fn3, OutputId(0)), Ok(1.0));
+        assert_eq!(calls.get(), 1);
+        assert_eq!(graph.evaluate(fn3, OutputId(1)), Err(GraphError::OutputNotExists(fn3, OutputId(1))));
+    }
+}
@@ -0,0 +1,135 @@
+use super::Graph;
+use crate::{InputId, Node, NodeId, OutputId, Value};
+use std::mem;
+
+/// Cached input values of a pure node.
+pub(super) struct Memo {
+    /// Input values (all channels) of the last processing.
+    inputs: Vec<Value>,
+
+    /// Whether outputs of the node still correspond to the cached input values.
+    pub(super) valid: bool,
+}
+
+impl<N: Node> Graph<N> {
+    /// Invalidates cached inputs of a node, so it is processed in the next cycle.
+    pub(super) fn invalidate_memo(&mut self, node: NodeId) {
+        if let Some(memo) = self.memos.get_mut(&node) {
+            memo.valid = false;
+        }
+    }
+
+    /// Returns whether outputs of a pure node are still valid for its current input values (see `Node::is_pure`),
+    /// recording the values otherwise.
+    pub(super) fn is_memoized(&mut self, node: NodeId) -> bool {
+        let Some(memo) = self.memos.get_mut(&node) else {
+            return false;
+        };
+
+        // Values are compared and recorded in place, so processing does not allocate.
+        let (mut inputs, mut valid) = (mem::take(&mut memo.inputs), memo.valid);
+        let mut index = 0;
+        for &input in self.nodes[&node].list_inputs() {
+            for channel in 0..self.nodes[&node].input_channels(input) {
+                let value = self.input_value(node, input, channel);
+                valid &= inputs[index].to_bits() == value.to_bits();
+                inputs[index] = value;
+                index += 1;
+            }
+        }
+        let memo = self.memos.get_mut(&node).unwrap();
+        (memo.inputs, memo.valid) = (inputs, true);
+        valid
+    }
+
+    /// Prepares (and invalidates) cached inputs of pure nodes without event ports after topology changes.
+    pub(super) fn prepare_memos(&mut self) {
+        self.memos.clear();
+        for (&id, node) in self.nodes.iter() {
+            if node.is_pure() && node.list_event_inputs().is_empty() && node.list_event_outputs().is_empty() {
+                let channels = node.list_inputs().iter().map(|&input| node.input_channels(input)).sum();
+                self.memos.insert(id, Memo { inputs: vec![0.0; channels], valid: false });
+            }
+        }
+    }
+
+    /// Returns current value of an input channel (from the connected output, last set through the graph or default).
+    fn input_value(&self, node: NodeId, input: InputId, channel: usize) -> Value {
+        match self.source_of(node, input) {
+            Some((source, OutputId::STATUS)) => self.output_value(source, OutputId::STATUS),
+            Some((source, output)) if self.nodes[&source].output_channels(output) == 1 => {
+                self.output_value(source, output)
+            }
+            Some((source, output)) => self.nodes[&source].get_output_channel(output, channel),
+            None => self.unconnected_value(node, input),
+        }
+    }
+}
+
+/// Unit tests.
+#[cfg(all(test, feature = "generators"))]
+mod tests {
+    use super::*;
+    use crate::{nodes, Connection};
+    use std::{cell::Cell, rc::Rc};
+
+    /// Pure node that doubles its input and counts its processing.
+    struct Double {
+        calls: Rc<Cell<u32>>,
+        input: Value,
+        output: Value,
+    }
+    impl Node for Double {
+        fn delayed_processing(&self) -> bool {
+            false
+        }
+        fn get_output(&self, _id: OutputId) -> Value {
+            self.output
+        }
+        fn is_pure(&self) -> bool {
+            true
+        }
+        fn list_inputs(&self) -> &[InputId] {
+            &[InputId(0)]
+        }
+        fn list_outputs(&self) -> &[OutputId] {
+            &[OutputId(0)]
+        }
+        fn process(&mut self) {
+            self.calls.set(self.calls.get() + 1);
+            self.output = 2.0 * self.input;
+        }
+        fn set_input(&mut self, _id: InputId, value: Value) {
+            self.input = value;
+        }
+    }
+
+    #[test]
+    fn skips_unchanged_inputs() {
+        let calls = Rc::new(Cell::new(0));
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        let var0 = graph.add_node(Box::new(nodes::Variable::new(1.0)));
+        let dbl1 = graph.add_node(Box::new(Double { calls: calls.clone(), input: 0.0, output: 0.0 }));
+        let dbl2 = graph.add_node(Box::new(Double { calls: calls.clone(), input: 0.0, output: 0.0 }));
+        graph.add_connection(Connection::new(var0, OutputId(0), dbl1, InputId(0))).unwrap();
+        graph.set_input(dbl2, InputId(0), 3.0).unwrap();
+
+        graph.process_n(3);
+        assert_eq!(graph.get_output(dbl1, OutputId(0)), Ok(2.0));
+        assert_eq!(graph.get_output(dbl2, OutputId(0)), Ok(6.0));
+        assert_eq!(calls.get(), 2);
+
+        // Changed inputs (connected or set through the graph) are recomputed.
+        graph.set_input(var0, InputId(0), 2.0).unwrap();
+        graph.set_input(dbl2, InputId(0), 4.0).unwrap();
+        graph.process_n(2);
+        assert_eq!(graph.get_output(dbl1, OutputId(0)), Ok(4.0));
+        assert_eq!(graph.get_output(dbl2, OutputId(0)), Ok(8.0));
+        assert_eq!(calls.get(), 4);
+
+        // Direct access to nodes invalidates cached inputs.
+        graph.get_node_mut(dbl1).unwrap();
+        graph.process();
+        assert_eq!(calls.get(), 5);
+    }
+}
@@ -0,0 +1,64 @@
+use super::Graph;
+use crate::{GraphError, Node, NodeId};
+
+impl<N: Node> Graph<N> {
+    /// Returns metadata value of a node by key (`None` if not set).
+    pub fn get_meta(&self, node: NodeId, key: &str) -> Result<Option<&str>, GraphError> {
+        self.get_node(node)?;
+        Ok(self.meta.get(&node).and_then(|meta| meta.get(key)).map(String::as_str))
+    }
+
+    /// Removes metadata value of a node by key and returns it (`None` if not set).
+    pub fn remove_meta(&mut self, node: NodeId, key: &str) -> Result<Option<String>, GraphError> {
+        self.get_node(node)?;
+        let Some(meta) = self.meta.get_mut(&node) else {
+            return Ok(None);
+        };
+        let value = meta.remove(key);
+        if meta.is_empty() {
+            self.meta.remove(&node);
+        }
+        Ok(value)
+    }
+
+    /// Sets opaque metadata value of a node by key (e.g. canvas position or color in an editor), which is kept with
+    /// the node (including serialization) but ignored by processing.
+    pub fn set_meta(&mut self, node: NodeId, key: &str, value: &str) -> Result<(), GraphError> {
+        self.get_node(node)?;
+        self.meta.entry(node).or_default().insert(key.to_string(), value.to_string());
+        Ok(())
+    }
+}
+
+/// Unit tests.
+#[cfg(all(test, feature = "generators"))]
+mod tests {
+    use super::*;
+    use crate::nodes;
+
+    #[test]
+    fn set_meta() {
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        let var0 = graph.add_node(Box::new(nodes::Variable::new(1.0)));
+        graph.set_meta(var0, "position", "10,20").unwrap();
+        graph.set_meta(var0, "color", "red").unwrap();
+        graph.set_meta(var0, "color", "blue").unwrap();
+        assert_eq!(graph.get_meta(var0, "position"), Ok(Some("10,20")));
+        assert_eq!(graph.get_meta(var0, "color"), Ok(Some("blue")));
+        assert_eq!(graph.remove_meta(var0, "color"), Ok(Some("blue".to_string())));
+        assert_eq!(graph.get_meta(var0, "color"), Ok(None));
+        assert_eq!(graph.set_meta(NodeId(1), "color", "red"), Err(GraphError::NodeNotExists(NodeId(1))));
+
+        // Metadata is kept in JSON patches.
+        #[cfg(feature = "json")]
+        {
+            let registry = crate::NodeRegistry::builtin();
+            let json = graph.to_json(&registry).unwrap();
+            let restored = Graph::from_json(&json, &registry).unwrap();
+            assert_eq!(restored.get_meta(var0, "position"), Ok(Some("10,20")));
+        }
+
+        graph.remove_node(var0).unwrap();
+        assert!(graph.meta.is_empty());
+    }
+}
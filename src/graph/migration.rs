@@ -0,0 +1,161 @@
+use super::Graph;
+use crate::{Connection, GraphError, Node, NodeId};
+use std::{collections::HashMap, mem};
+
+impl<N: Node> Graph<N> {
+    /// Carries over runtime state from matching nodes of a previous graph (e.g. when hot reloading) and returns the
+    /// matched node ids (previous and own), ordered by own id.
+    ///
+    /// Nodes are matched by label, unlabeled nodes by type if the type is unique among unlabeled nodes in both graphs.
+    /// Matched nodes always have the same type.
+    pub fn migrate_state<M: Node>(&mut self, previous: &Graph<M>) -> Vec<(NodeId, NodeId)> {
+        let mut matches = Vec::new();
+
+        // Match labeled nodes.
+        for (&id, label) in self.labels.iter() {
+            if let Ok(previous_id) = previous.find_node(label) {
+                if previous.nodes[&previous_id].type_name() == self.nodes[&id].type_name() {
+                    matches.push((previous_id, id));
+                }
+            }
+        }
+
+        // Match unlabeled nodes of unique type.
+        let previous_types = unlabeled_types(previous);
+        for (type_name, ids) in unlabeled_types(self) {
+            if let (&[id], Some(&[previous_id])) = (ids.as_slice(), previous_types.get(type_name).map(Vec::as_slice)) {
+                matches.push((previous_id, id));
+            }
+        }
+
+        // Transfer state.
+        matches.sort_by_key(|&(_, id)| id);
+        for &(previous_id, id) in matches.iter() {
+            let snapshot = previous.nodes[&previous_id].snapshot();
+            self.nodes.get_mut(&id).unwrap().restore(&snapshot);
+        }
+        matches
+    }
+
+    /// Replaces a node with a new implementation, keeping its id, label, connections and other settings, and returns
+    /// the replaced node. Runtime state is transferred through snapshots if `transfer_state` is set (see
+    /// `Node::snapshot`).
+    ///
+    /// The new node takes effect in the next cycle (running automations of the node are stopped). It must provide all
+    /// connected ports (with matching kinds and channels) and must not introduce cycles without delay, otherwise the
+    /// graph is left unchanged and the new node is dropped.
+    pub fn swap_node(&mut self, id: NodeId, mut node: N, transfer_state: bool) -> Result<N, GraphError> {
+        let previous = self.get_node_mut(id)?;
+        if transfer_state {
+            node.restore(&previous.snapshot());
+        }
+        let previous = mem::replace(previous, node);
+        let involves = |c: &&Connection| c.source_node == id || c.target_node == id;
+        let mut result =
+            self.connections.iter().filter(involves).try_for_each(|&c| self.validate_connection(c).map(|_| ()));
+        if result.is_ok() {
+            result =
+                self.event_connections.iter().filter(involves).try_for_each(|&c| self.validate_event_connection(c));
+        }
+        if result.is_ok() {
+            result = self.update_processing_order();
+        }
+        if let Err(error) = result {
+            self.nodes.insert(id, previous);
+            return Err(error);
+        }
+
+        // Reset bookkeeping depending on the ports of the node.
+        if self.bypassed.contains_key(&id) {
+            self.bypassed.remove(&id);
+            self.set_bypassed(id, true).unwrap();
+        }
+        self.output_buffers.retain(|&(node, _), _| node != id);
+        self.remove_automations(id);
+        self.reserve_events();
+        Ok(previous)
+    }
+}
+
+/// Groups unlabeled nodes of a graph by type.
+fn unlabeled_types<N: Node>(graph: &Graph<N>) -> HashMap<&'static str, Vec<NodeId>> {
+    let mut types: HashMap<&'static str, Vec<NodeId>> = HashMap::new();
+    for (&id, node) in graph.nodes.iter().filter(|(id, _)| !graph.labels.contains_key(id)) {
+        types.entry(node.type_name()).or_default().push(id);
+    }
+    types
+}
+
+/// Unit tests.
+#[cfg(all(test, feature = "dsp", feature = "generators", feature = "math"))]
+mod tests {
+    use super::*;
+    use crate::{nodes, Connection, InputId, OutputId};
+
+    #[test]
+    fn migrate_state() {
+        let mut previous: Graph<Box<dyn Node>> = Graph::new();
+        let var0 = previous.add_node(Box::from(nodes::Variable::new(1.0)));
+        let del1 = previous.add_node(Box::from(nodes::Delay::new()));
+        let del2 = previous.add_node(Box::from(nodes::Delay::new()));
+        let del3 = previous.add_node(Box::from(nodes::Delay::new()));
+        previous.add_connection(Connection::new(var0, OutputId(0), del1, InputId(0))).unwrap();
+        previous.add_connection(Connection::new(var0, OutputId(0), del2, InputId(0))).unwrap();
+        previous.set_label(del1, Some("state")).unwrap();
+        previous.set_label(del3, Some("other")).unwrap();
+        previous.process();
+
+        // Labeled delay keeps its state, unique unlabeled delay too, relabeled node with other type is skipped.
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        let add0 = graph.add_node(Box::from(nodes::Addition::new()));
+        let del1 = graph.add_node(Box::from(nodes::Delay::new()));
+        let del2 = graph.add_node(Box::from(nodes::Delay::new()));
+        graph.set_label(add0, Some("other")).unwrap();
+        graph.set_label(del2, Some("state")).unwrap();
+        assert_eq!(graph.migrate_state(&previous), vec![(NodeId(2), del1), (NodeId(1), del2)]);
+        assert_eq!(graph.get_node(del1).unwrap().get_output(OutputId(0)), 1.0);
+        assert_eq!(graph.get_node(del2).unwrap().get_output(OutputId(0)), 1.0);
+
+        // Ambiguous unlabeled nodes are not matched.
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        graph.add_node(Box::from(nodes::Delay::new()));
+        graph.add_node(Box::from(nodes::Delay::new()));
+        assert_eq!(graph.migrate_state(&previous), vec![]);
+    }
+
+    #[test]
+    fn swap_node() {
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        let var0 = graph.add_node(Box::from(nodes::Variable::new(2.0)));
+        let fn1 = graph.add_node(Box::from(nodes::FnNode::new(1, 1, |inputs, outputs| outputs[0] = inputs[0] + 1.0)));
+        let del2 = graph.add_node(Box::from(nodes::Delay::new()));
+        graph.add_connection(Connection::new(var0, OutputId(0), fn1, InputId(0))).unwrap();
+        graph.add_connection(Connection::new(fn1, OutputId(0), del2, InputId(0))).unwrap();
+        graph.set_label(fn1, Some("effect")).unwrap();
+        graph.process();
+        assert_eq!(graph.get_output(del2, OutputId(0)), Ok(3.0));
+
+        // Connections and label are kept.
+        let square = nodes::FnNode::new(1, 1, |inputs, outputs| outputs[0] = inputs[0] * inputs[0]);
+        graph.swap_node(fn1, Box::from(square), false).unwrap();
+        assert_eq!(graph.find_node("effect"), Ok(fn1));
+        graph.process();
+        assert_eq!(graph.get_output(del2, OutputId(0)), Ok(4.0));
+
+        // State is transferred.
+        let mut del = nodes::Delay::new();
+        del.set_input(InputId(0), 5.0);
+        graph.swap_node(del2, Box::from(del), true).unwrap();
+        assert_eq!(graph.get_output(del2, OutputId(0)), Ok(4.0));
+
+        // Nodes without connected ports are rejected.
+        let generator = nodes::FnNode::new(0, 1, |_, outputs| outputs[0] = 1.0);
+        assert_eq!(
+            graph.swap_node(fn1, Box::from(generator), false).err(),
+            Some(GraphError::InputNotExists(fn1, InputId(0)))
+        );
+        assert_eq!(graph.connections().len(), 2);
+        graph.process();
+        assert_eq!(graph.get_output(del2, OutputId(0)), Ok(4.0));
+    }
+}
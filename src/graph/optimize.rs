@@ -0,0 +1,379 @@
+use super::Graph;
+use crate::{nodes, Connection, InputId, Node, NodeId, OutputId, Value};
+use std::{
+    any::type_name,
+    collections::{BTreeSet, HashSet},
+};
+
+/// Changes made by optimizing a graph.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct OptimizationReport {
+    /// Added constant nodes replacing folded nodes.
+    pub constants: Vec<NodeId>,
+
+    /// Nodes removed by constant folding (sorted).
+    pub folded: Vec<NodeId>,
+
+    /// Groups of fused nodes (sorted) and the node replacing them.
+    pub fused: Vec<(Vec<NodeId>, NodeId)>,
+
+    /// Removed passthrough nodes.
+    pub passthroughs: Vec<NodeId>,
+}
+
+/// Associative operation of arithmetic nodes that can be fused.
+#[derive(Clone, Copy, PartialEq)]
+enum Operation {
+    /// Product of inputs (`ProductN`).
+    Product,
+
+    /// Sum of inputs (`Addition`, `SumN`).
+    Sum,
+}
+
+impl<N: Node + From<nodes::Constant> + From<nodes::ProductN> + From<nodes::SumN>> Graph<N> {
+    /// Optimizes the graph and returns the changes made (ids of removed nodes become invalid).
+    ///
+    /// Only nodes that are not modified externally are rewritten: nodes with mono ports without event ports, labels,
+    /// param handles, automations, probes, bypass or rate divisor. Nodes without outgoing connections are kept, so
+    /// their outputs can still be read. Passes run in order:
+    /// - Constant folding evaluates pure nodes (see `Node::is_pure`) that are fed only by variables or other pure
+    ///   nodes once, and replaces them with a single constant node.
+    /// - Passthrough elimination removes variables used as wires (with a connected input), connecting their source
+    ///   directly.
+    /// - Fusion replaces trees of sums (or products) whose intermediate results are not used otherwise with a single
+    ///   `SumN` (or `ProductN`), which may change rounding.
+    pub fn optimize(&mut self) -> OptimizationReport {
+        // Rewritten connections are equivalent to validated ones.
+        let validator = self.connection_validator.take();
+        let mut report = OptimizationReport::default();
+        self.fold_constants(&mut report);
+        self.eliminate_passthroughs(&mut report);
+        self.fuse_arithmetic(&mut report);
+        self.connection_validator = validator;
+        report
+    }
+
+    /// Removes variables with a connected input, connecting their source to their targets.
+    fn eliminate_passthroughs(&mut self, report: &mut OptimizationReport) {
+        let variables: Vec<NodeId> = self
+            .processing_order
+            .iter()
+            .copied()
+            .filter(|&id| self.nodes[&id].type_name() == type_name::<nodes::Variable>() && self.is_rewritable(id))
+            .collect();
+        for id in variables {
+            let Some(&input) = self.connections.iter().find(|c| c.target_node == id) else {
+                continue;
+            };
+            let connections: Vec<Connection> = self
+                .connections
+                .iter()
+                .filter(|c| c.source_node == id)
+                .map(|c| Connection::new(input.source_node, input.source_output, c.target_node, c.target_input))
+                .collect();
+            if connections.is_empty() || connections.iter().any(|&c| self.validate_connection(c).is_err()) {
+                continue;
+            }
+            self.remove_node(id).unwrap();
+            self.add_connections(connections).unwrap();
+            report.passthroughs.push(id);
+        }
+    }
+
+    /// Replaces constant subgraphs with a single constant node.
+    fn fold_constants(&mut self, report: &mut OptimizationReport) {
+        let mut constant: HashSet<NodeId> = HashSet::new();
+        for &id in self.processing_order.iter() {
+            let inputs_constant = self
+                .connections
+                .iter()
+                .filter(|c| c.target_node == id)
+                .all(|c| constant.contains(&c.source_node) && c.source_output != OutputId::STATUS);
+            let node = &self.nodes[&id];
+            let pure = node.is_pure() || node.type_name() == type_name::<nodes::Variable>();
+            if inputs_constant && pure && self.is_rewritable(id) {
+                constant.insert(id);
+            }
+        }
+
+        // Sinks are kept, folding is only worth it if a value is computed from connected inputs.
+        let folded: BTreeSet<NodeId> =
+            constant.iter().copied().filter(|&id| self.connections.iter().any(|c| c.source_node == id)).collect();
+        if !self.connections.iter().any(|c| folded.contains(&c.target_node)) {
+            return;
+        }
+
+        // Evaluate folded nodes once.
+        for index in 0..self.processing_order.len() {
+            let id = self.processing_order[index];
+            if folded.contains(&id) && self.populate_inputs(id).is_ok() {
+                self.process_and_record(id);
+            }
+        }
+        let targets: Vec<Connection> = self
+            .connections
+            .iter()
+            .copied()
+            .filter(|c| folded.contains(&c.source_node) && !folded.contains(&c.target_node))
+            .collect();
+        let sources: BTreeSet<(NodeId, OutputId)> = targets.iter().map(|c| (c.source_node, c.source_output)).collect();
+        let values = sources.iter().map(|&(node, output)| self.output_value(node, output)).collect();
+        let kinds = sources.iter().map(|&(node, output)| self.nodes[&node].output_info(output).kind).collect();
+
+        let ids: Vec<NodeId> = folded.into_iter().collect();
+        self.remove_nodes(&ids).unwrap();
+        let id = self.add_node(N::from(nodes::Constant::with_kinds(values, kinds)));
+        let connections = targets.into_iter().map(|c| {
+            let index = sources.iter().position(|&source| source == (c.source_node, c.source_output)).unwrap();
+            Connection::new(id, OutputId(index as u32), c.target_node, c.target_input)
+        });
+        self.add_connections(connections).unwrap();
+        report.constants.push(id);
+        report.folded.extend(ids);
+    }
+
+    /// Replaces trees of sums (or products) with a single node.
+    fn fuse_arithmetic(&mut self, report: &mut OptimizationReport) {
+        let roots: Vec<NodeId> = self
+            .processing_order
+            .iter()
+            .copied()
+            .filter(|&id| self.operation(id).is_some() && !self.is_fusable_into_target(id))
+            .collect();
+        for root in roots {
+            let operation = self.operation(root).unwrap();
+            let (mut members, mut leaves) = (Vec::new(), Vec::new());
+            self.collect_fusable(root, &mut members, &mut leaves);
+            let targets: Vec<Connection> = self.connections.iter().copied().filter(|c| c.source_node == root).collect();
+            if members.len() < 2 || targets.is_empty() {
+                continue;
+            }
+
+            // Leaves keep their sources or unconnected values.
+            let sources: Vec<Result<(NodeId, OutputId), Value>> = leaves
+                .iter()
+                .map(|&(node, input)| self.source_of(node, input).ok_or_else(|| self.unconnected_value(node, input)))
+                .collect();
+            members.sort_unstable();
+            self.remove_nodes(&members).unwrap();
+            let id = self.add_node(match operation {
+                Operation::Product => N::from(nodes::ProductN::new(leaves.len() as u32)),
+                Operation::Sum => N::from(nodes::SumN::new(leaves.len() as u32)),
+            });
+            let mut connections = Vec::with_capacity(sources.len() + targets.len());
+            for (index, source) in sources.into_iter().enumerate() {
+                match source {
+                    Ok((node, output)) => connections.push(Connection::new(node, output, id, InputId(index as u32))),
+                    Err(value) => self.set_input(id, InputId(index as u32), value).unwrap(),
+                }
+            }
+            connections.extend(targets.iter().map(|c| Connection::new(id, OutputId(0), c.target_node, c.target_input)));
+            self.add_connections(connections).unwrap();
+            report.fused.push((members, id));
+        }
+    }
+
+    /// Collects a node and the fusable nodes feeding it (depth first), and their remaining inputs.
+    fn collect_fusable(&self, node: NodeId, members: &mut Vec<NodeId>, leaves: &mut Vec<(NodeId, InputId)>) {
+        members.push(node);
+        for &input in self.nodes[&node].list_inputs() {
+            match self.source_of(node, input) {
+                Some((source, _)) if self.is_fusable_into_target(source) => {
+                    self.collect_fusable(source, members, leaves)
+                }
+                _ => leaves.push((node, input)),
+            }
+        }
+    }
+
+    /// Returns whether an arithmetic node only feeds a single node of the same operation.
+    fn is_fusable_into_target(&self, id: NodeId) -> bool {
+        let mut targets = self.connections.iter().filter(|c| c.source_node == id);
+        match (targets.next(), targets.next()) {
+            (Some(c), None) => {
+                c.source_output == OutputId(0)
+                    && self.operation(id).is_some()
+                    && self.operation(id) == self.operation(c.target_node)
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns whether nodes can be rewritten (no external references, see `optimize`).
+    fn is_rewritable(&self, id: NodeId) -> bool {
+        let node = &self.nodes[&id];
+        node.list_event_inputs().is_empty()
+            && node.list_event_outputs().is_empty()
+            && node.list_inputs().iter().all(|&input| node.input_channels(input) == 1)
+            && node.list_outputs().iter().all(|&output| node.output_channels(output) == 1)
+            && !self.labels.contains_key(&id)
+            && !self.bypassed.contains_key(&id)
+            && !self.params.iter().any(|&(node, _, _)| node == id)
+            && !self.has_automations(id)
+            && !self.is_probed(id)
+            && self.rate_divisors.get(&id).is_none_or(|&divisor| divisor == 1)
+    }
+
+    /// Returns associative operation of a rewritable arithmetic node.
+    fn operation(&self, id: NodeId) -> Option<Operation> {
+        let name = self.nodes[&id].type_name();
+        if !self.is_rewritable(id) {
+            None
+        } else if name == type_name::<nodes::ProductN>() {
+            Some(Operation::Product)
+        } else if name == type_name::<nodes::Addition>() || name == type_name::<nodes::SumN>() {
+            Some(Operation::Sum)
+        } else {
+            None
+        }
+    }
+}
+
+/// Unit tests.
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{InputId, ValueKind};
+
+    #[test]
+    fn folds_constants() {
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        let var0 = graph.add_node(Box::new(nodes::Variable::new(1.0)));
+        let var1 = graph.add_node(Box::new(nodes::Variable::new(2.0)));
+        let add2 = graph.add_node(Box::new(nodes::Addition::new()));
+        let add3 = graph.add_node(Box::new(nodes::Addition::new()));
+        let del4 = graph.add_node(Box::new(nodes::Delay::new()));
+        graph.add_connection(Connection::new(var0, OutputId(0), add2, InputId(0))).unwrap();
+        graph.add_connection(Connection::new(var1, OutputId(0), add2, InputId(1))).unwrap();
+        graph.add_connection(Connection::new(add2, OutputId(0), add3, InputId(0))).unwrap();
+        graph.add_connection(Connection::new(del4, OutputId(0), add3, InputId(1))).unwrap();
+        graph.add_connection(Connection::new(add3, OutputId(0), del4, InputId(0))).unwrap();
+
+        // Variables and their sum are folded, the accumulator depends on a delay.
+        let report = graph.optimize();
+        assert_eq!(
+            report,
+            OptimizationReport { constants: vec![NodeId(5)], folded: vec![var0, var1, add2], ..Default::default() }
+        );
+        assert_eq!(graph.connections().len(), 3);
+        assert_eq!(graph.get_node(NodeId(5)).unwrap().output_info(OutputId(0)).kind, ValueKind::Continuous);
+        graph.process_n(3);
+        assert_eq!(graph.get_output(add3, OutputId(0)), Ok(9.0));
+
+        // Nothing left to fold.
+        assert_eq!(graph.optimize(), OptimizationReport::default());
+    }
+
+    #[test]
+    fn keeps_external_nodes() {
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        let var0 = graph.add_node(Box::new(nodes::Variable::new(1.0)));
+        let add1 = graph.add_node(Box::new(nodes::Addition::new()));
+        let del2 = graph.add_node(Box::new(nodes::Delay::new()));
+        graph.add_connection(Connection::new(var0, OutputId(0), add1, InputId(0))).unwrap();
+        graph.add_connection(Connection::new(add1, OutputId(0), del2, InputId(0))).unwrap();
+        graph.set_label(var0, Some("gain")).unwrap();
+        assert_eq!(graph.optimize(), OptimizationReport::default());
+
+        // Sinks are kept.
+        graph.set_label(var0, None).unwrap();
+        graph.remove_node(del2).unwrap();
+        assert_eq!(graph.optimize(), OptimizationReport::default());
+    }
+
+    #[test]
+    fn eliminates_passthroughs() {
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        let del0 = graph.add_node(Box::new(nodes::Delay::new()));
+        let var1 = graph.add_node(Box::new(nodes::Variable::new(0.0)));
+        let var2 = graph.add_node(Box::new(nodes::Variable::new(0.0)));
+        let add3 = graph.add_node(Box::new(nodes::Addition::new()));
+        graph.add_connection(Connection::new(del0, OutputId(0), var1, InputId(0))).unwrap();
+        graph.add_connection(Connection::new(var1, OutputId(0), var2, InputId(0))).unwrap();
+        graph.add_connection(Connection::new(var2, OutputId(0), add3, InputId(0))).unwrap();
+        graph.add_connection(Connection::new(add3, OutputId(0), del0, InputId(0))).unwrap();
+        graph.set_input(add3, InputId(1), 1.0).unwrap();
+
+        let report = graph.optimize();
+        assert_eq!(report.passthroughs, vec![var1, var2]);
+        assert_eq!(
+            graph.connections(),
+            &[
+                Connection::new(add3, OutputId(0), del0, InputId(0)),
+                Connection::new(del0, OutputId(0), add3, InputId(0))
+            ]
+        );
+        graph.process_n(3);
+        assert_eq!(graph.get_output(add3, OutputId(0)), Ok(3.0));
+    }
+
+    #[test]
+    fn fuses_arithmetic() {
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        let del0 = graph.add_node(Box::new(nodes::Delay::new()));
+        let add1 = graph.add_node(Box::new(nodes::Addition::new()));
+        let sum2 = graph.add_node(Box::new(nodes::SumN::new(3)));
+        let add3 = graph.add_node(Box::new(nodes::Addition::new()));
+        let del4 = graph.add_node(Box::new(nodes::Delay::new()));
+        graph.add_connection(Connection::new(del0, OutputId(0), add1, InputId(0))).unwrap();
+        graph.add_connection(Connection::new(del0, OutputId(0), add1, InputId(1))).unwrap();
+        graph.add_connection(Connection::new(add1, OutputId(0), sum2, InputId(1))).unwrap();
+        graph.add_connection(Connection::new(sum2, OutputId(0), add3, InputId(0))).unwrap();
+        graph.add_connection(Connection::new(add3, OutputId(0), del4, InputId(0))).unwrap();
+        graph.add_connection(Connection::new(add3, OutputId(0), del0, InputId(0))).unwrap();
+        graph.set_input(sum2, InputId(0), 1.0).unwrap();
+        graph.set_input(add3, InputId(1), 2.0).unwrap();
+
+        // Sums fed by sums are fused, unconnected values are kept.
+        let report = graph.optimize();
+        assert_eq!(report.fused, vec![(vec![add1, sum2, add3], NodeId(5))]);
+        assert_eq!(graph.get_node(NodeId(5)).unwrap().list_inputs().len(), 5);
+        graph.process_n(2);
+        assert_eq!(graph.get_output(NodeId(5), OutputId(0)), Ok(9.0));
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn saves_optimized() {
+        use crate::NodeRegistry;
+
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        let var0 = graph.add_node(Box::new(nodes::Variable::new(2.0)));
+        let var1 = graph.add_node(Box::new(nodes::Variable::new(3.0)));
+        let prod2 = graph.add_node(Box::new(nodes::ProductN::new(2)));
+        let del3 = graph.add_node(Box::new(nodes::Delay::new()));
+        let add4 = graph.add_node(Box::new(nodes::Addition::new()));
+        let sum5 = graph.add_node(Box::new(nodes::SumN::new(2)));
+        let prod6 = graph.add_node(Box::new(nodes::ProductN::new(2)));
+        let prod7 = graph.add_node(Box::new(nodes::ProductN::new(2)));
+        let del8 = graph.add_node(Box::new(nodes::Delay::new()));
+        graph.add_connection(Connection::new(var0, OutputId(0), prod2, InputId(0))).unwrap();
+        graph.add_connection(Connection::new(var1, OutputId(0), prod2, InputId(1))).unwrap();
+        graph.add_connection(Connection::new(del3, OutputId(0), add4, InputId(0))).unwrap();
+        graph.add_connection(Connection::new(del3, OutputId(0), add4, InputId(1))).unwrap();
+        graph.add_connection(Connection::new(add4, OutputId(0), sum5, InputId(0))).unwrap();
+        graph.add_connection(Connection::new(prod2, OutputId(0), sum5, InputId(1))).unwrap();
+        graph.add_connection(Connection::new(sum5, OutputId(0), del3, InputId(0))).unwrap();
+        graph.add_connection(Connection::new(sum5, OutputId(0), prod6, InputId(0))).unwrap();
+        graph.add_connection(Connection::new(del3, OutputId(0), prod6, InputId(1))).unwrap();
+        graph.add_connection(Connection::new(prod6, OutputId(0), prod7, InputId(0))).unwrap();
+        graph.add_connection(Connection::new(del3, OutputId(0), prod7, InputId(1))).unwrap();
+        graph.add_connection(Connection::new(prod7, OutputId(0), del8, InputId(0))).unwrap();
+
+        // Constants and fused nodes are saved with their outputs and inputs.
+        let report = graph.optimize();
+        assert_eq!(report.constants.len(), 1);
+        assert_eq!(report.fused.len(), 2);
+        let registry = NodeRegistry::builtin();
+        let json = graph.to_json(&registry).unwrap();
+        let mut restored = Graph::from_json(&json, &registry).unwrap();
+        assert_eq!(restored.to_json(&registry), Ok(json));
+        graph.process_n(3);
+        restored.process_n(3);
+        for id in report.constants.into_iter().chain(report.fused.into_iter().map(|(_, id)| id)) {
+            assert_eq!(restored.get_node(id).unwrap().describe(), graph.get_node(id).unwrap().describe());
+            assert_eq!(restored.get_output(id, OutputId(0)), graph.get_output(id, OutputId(0)));
+        }
+    }
+}
@@ -0,0 +1,295 @@
+use super::{profile, update_status, Graph};
+use crate::{Connection, InputId, Node, NodeId, OutputId, ProcessError};
+use std::{
+    collections::{HashMap, HashSet},
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Nodes of a weakly connected component or of a pipeline stage in processing order, with the connections between
+/// them feeding each node (source node as index into the component nodes).
+pub(crate) struct Component {
+    inputs: Vec<Vec<(usize, OutputId, InputId)>>,
+    nodes: Vec<NodeId>,
+}
+
+/// Result of processing a node on a thread (with elapsed time if profiling).
+type NodeResult = (NodeId, Result<(), ProcessError>, Option<Duration>);
+
+impl<N: Node> Graph<N> {
+    /// Builds component of given nodes (in processing order), leaving out connections from other nodes.
+    fn calc_component(&self, nodes: Vec<NodeId>) -> Component {
+        let positions: HashMap<NodeId, usize> = nodes.iter().enumerate().map(|(index, &node)| (node, index)).collect();
+        let inputs = nodes
+            .iter()
+            .map(|&node| {
+                self.connections
+                    .iter()
+                    .filter(|c| c.target_node == node)
+                    .filter_map(|c| {
+                        positions.get(&c.source_node).map(|&source| (source, c.source_output, c.target_input))
+                    })
+                    .collect()
+            })
+            .collect();
+        Component { inputs, nodes }
+    }
+
+    /// Determines weakly connected components with their nodes in processing order.
+    pub(crate) fn calc_components(&self) -> Vec<Component> {
+        let groups = self.components();
+        let mut membership = HashMap::new();
+        for (index, group) in groups.iter().enumerate() {
+            membership.extend(group.iter().map(|&node| (node, index)));
+        }
+        let mut nodes: Vec<Vec<NodeId>> = groups.iter().map(|_| Vec::new()).collect();
+        for &node in self.processing_order.iter() {
+            if let Some(&index) = membership.get(&node) {
+                nodes[index].push(node);
+            }
+        }
+        nodes.into_iter().map(|nodes| self.calc_component(nodes)).collect()
+    }
+
+    /// Returns whether components can be processed independently of each other, i.e. no graph-wide state is involved
+    /// in transfers or processing (bypassed nodes, events, budgets, deadlines, checks, clamps, status outputs or
+    /// connections from nodes that do not exist).
+    pub(crate) fn has_independent_components(&self) -> bool {
+        self.bypassed.is_empty()
+            && self.event_connections.is_empty()
+            && self.cycle_budget.is_none()
+            && self.deadline.is_none()
+            && self.non_finite.is_none()
+            && self.denormal_counts.is_none()
+            && !self.flush_denormals
+            && self.output_clamps.is_empty()
+            && self
+                .connections
+                .iter()
+                .all(|c| c.source_output != OutputId::STATUS && self.nodes.contains_key(&c.source_node))
+    }
+
+    /// Splits the processing order into up to given number of stages of consecutive nodes, with the connections
+    /// between stages.
+    pub(crate) fn calc_stages(&self, count: usize) -> (Vec<Component>, Vec<Connection>) {
+        let size = self.processing_order.len().div_ceil(count.max(1)).max(1);
+        let stages: Vec<Component> =
+            self.processing_order.chunks(size).map(|nodes| self.calc_component(nodes.to_vec())).collect();
+        let mut membership = HashMap::new();
+        for (index, stage) in stages.iter().enumerate() {
+            membership.extend(stage.nodes.iter().map(|&node| (node, index)));
+        }
+        let links = self
+            .connections
+            .iter()
+            .filter(|c| membership.get(&c.source_node) != membership.get(&c.target_node))
+            .copied()
+            .collect();
+        (stages, links)
+    }
+
+    /// Groups nodes by depth level (nodes only depend on nodes of lower levels or on nodes that introduce delay).
+    pub(crate) fn calc_levels(&self) -> Vec<Vec<NodeId>> {
+        let mut depths: HashMap<NodeId, usize> = HashMap::new();
+        let mut levels: Vec<Vec<NodeId>> = Vec::new();
+        for &node in self.processing_order.iter() {
+            let depth = self
+                .connections
+                .iter()
+                .chain(self.event_connections.iter())
+                .filter(|c| c.target_node == node)
+                .filter(|c| self.nodes.get(&c.source_node).is_some_and(|source| !source.delayed_processing()))
+                .filter_map(|c| depths.get(&c.source_node).map(|depth| depth + 1))
+                .max()
+                .unwrap_or(0);
+            depths.insert(node, depth);
+            if levels.len() <= depth {
+                levels.resize(depth + 1, Vec::new());
+            }
+            levels[depth].push(node);
+        }
+        levels
+    }
+}
+impl<N: Node + Send> Graph<N> {
+    /// Processes one cycle level by level, with nodes of a level processed concurrently.
+    pub(crate) fn process_levels(&mut self, levels: &[Vec<NodeId>], threads: usize) {
+        // First pass (inputs are populated sequentially).
+        let mut due = HashSet::new();
+        for level in levels {
+            for &node in level {
+                if self.is_tick(node) && !self.skip_or_record(node) {
+                    self.deliver_events(node);
+                    if let Err(error) = self.populate_inputs(node) {
+                        self.skip_unpopulated(node, error);
+                    } else if self.nodes.get(&node).is_some_and(|processor| !processor.delayed_processing()) {
+                        due.insert(node);
+                    }
+                }
+            }
+            self.process_concurrently(&due, threads);
+            due.clear();
+        }
+
+        // Second pass.
+        for index in 0..self.processing_order.len() {
+            let node = self.processing_order[index];
+            if self.is_tick(node)
+                && self.nodes.get(&node).is_some_and(|processor| processor.delayed_processing())
+                && !self.skipped_nodes.contains(&node)
+                && !self.skip_or_record(node)
+            {
+                due.insert(node);
+            }
+        }
+        self.process_concurrently(&due, threads);
+    }
+
+    /// Processes one cycle component by component, with components distributed across up to given number of threads
+    /// (requires independent components, see `has_independent_components`).
+    pub(crate) fn process_components(&mut self, components: &[Component], threads: usize) {
+        let mut slots: Vec<Vec<Option<&mut N>>> =
+            components.iter().map(|component| component.nodes.iter().map(|_| None).collect()).collect();
+        let mut positions = HashMap::new();
+        for (index, component) in components.iter().enumerate() {
+            positions.extend(component.nodes.iter().enumerate().map(|(position, &node)| (node, (index, position))));
+        }
+        for (id, node) in self.nodes.iter_mut() {
+            let (index, position) = positions[id];
+            slots[index][position] = Some(node);
+        }
+        let mut work: Vec<(&Component, Vec<&mut N>)> = components
+            .iter()
+            .zip(slots)
+            .map(|(component, slots)| (component, slots.into_iter().flatten().collect()))
+            .collect();
+
+        let context = self.context;
+        let divisors = &self.rate_divisors;
+        let profiling = self.profiles.is_some();
+        let process = |(component, nodes): &mut (&Component, Vec<&mut N>)| -> Vec<NodeResult> {
+            let mut results = Vec::with_capacity(nodes.len());
+            for delayed in [false, true] {
+                for (index, &id) in component.nodes.iter().enumerate() {
+                    let divisor = divisors.get(&id).copied().unwrap_or(1);
+                    if !context.frame.is_multiple_of(divisor as u64) {
+                        continue;
+                    }
+
+                    // Inputs are populated in the first pass (also for nodes that introduce delay).
+                    if !delayed {
+                        for &(source, output, input) in component.inputs[index].iter() {
+                            match nodes[source].output_channels(output) {
+                                1 => {
+                                    let value = nodes[source].get_output(output);
+                                    nodes[index].set_input(input, value);
+                                }
+                                channels => {
+                                    for channel in 0..channels {
+                                        let value = nodes[source].get_output_channel(output, channel);
+                                        nodes[index].set_input_channel(input, channel, value);
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    if nodes[index].delayed_processing() == delayed {
+                        let start = profiling.then(Instant::now);
+                        let result = nodes[index].try_process(&context.divided(divisor));
+                        results.push((id, result, start.map(|start| start.elapsed())));
+                    }
+                }
+            }
+            results
+        };
+        let results: Vec<NodeResult> = if threads <= 1 || work.len() <= 1 {
+            work.iter_mut().flat_map(process).collect()
+        } else {
+            let chunk_size = work.len().div_ceil(threads);
+            thread::scope(|scope| {
+                let handles: Vec<_> = work
+                    .chunks_mut(chunk_size)
+                    .map(|chunk| scope.spawn(move || chunk.iter_mut().flat_map(process).collect::<Vec<_>>()))
+                    .collect();
+                handles.into_iter().flat_map(|handle| handle.join().unwrap()).collect()
+            })
+        };
+        for (id, result, elapsed) in results {
+            profile::record_profile(&mut self.profiles, id, elapsed);
+            update_status(&mut self.statuses, id, result);
+            let delay = self.nodes[&id].delayed_processing() as u64;
+            self.collect_events(id, delay);
+            self.check_deadline(id);
+        }
+    }
+
+    /// Processes one cycle stage by stage (see `calc_stages`), with connections between stages carrying the outputs
+    /// of the previous cycle.
+    ///
+    /// Stages are processed concurrently if components can be processed independently (see
+    /// `has_independent_components`), with the links populated before. Otherwise, stages are processed in reverse
+    /// order, so that their inputs from earlier stages are not processed yet.
+    pub(crate) fn process_stages(&mut self, stages: &[Component], links: &[Connection]) {
+        if stages.len() > 1 && self.has_independent_components() {
+            for &link in links {
+                if self.is_tick(link.target_node) {
+                    // Cannot fail, sources exist in independent components.
+                    let _ = self.transfer(link);
+                }
+            }
+            self.process_components(stages, stages.len());
+            return;
+        }
+        for stage in stages.iter().rev() {
+            for &node in stage.nodes.iter() {
+                self.step(node, None);
+            }
+        }
+        for index in 0..self.processing_order.len() {
+            self.step_delayed(self.processing_order[index]);
+        }
+    }
+
+    /// Processes given nodes on up to given number of threads.
+    fn process_concurrently(&mut self, due: &HashSet<NodeId>, threads: usize) {
+        for &node in due.iter() {
+            if self.bypassed.contains_key(&node) {
+                self.bypass(node);
+            }
+        }
+        let context = self.context;
+        let divisors = &self.rate_divisors;
+        let profiling = self.profiles.is_some();
+        let process = |(id, node): &mut (NodeId, &mut N)| -> NodeResult {
+            let start = profiling.then(Instant::now);
+            let result = node.try_process(&context.divided(divisors.get(id).copied().unwrap_or(1)));
+            (*id, result, start.map(|start| start.elapsed()))
+        };
+
+        let mut processors: Vec<(NodeId, &mut N)> = self
+            .nodes
+            .iter_mut()
+            .filter(|(id, _)| due.contains(id) && !self.bypassed.contains_key(id))
+            .map(|(&id, node)| (id, node))
+            .collect();
+        let results: Vec<NodeResult> = if threads <= 1 || processors.len() <= 1 {
+            processors.iter_mut().map(process).collect()
+        } else {
+            let chunk_size = processors.len().div_ceil(threads);
+            thread::scope(|scope| {
+                let handles: Vec<_> = processors
+                    .chunks_mut(chunk_size)
+                    .map(|chunk| scope.spawn(move || chunk.iter_mut().map(process).collect::<Vec<_>>()))
+                    .collect();
+                handles.into_iter().flat_map(|handle| handle.join().unwrap()).collect()
+            })
+        };
+        for (id, result, elapsed) in results {
+            profile::record_profile(&mut self.profiles, id, elapsed);
+            update_status(&mut self.statuses, id, result);
+            let delay = self.nodes[&id].delayed_processing() as u64;
+            self.collect_events(id, delay);
+            self.check_deadline(id);
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use super::Graph;
+use crate::{GraphError, Node, NodeId, Value};
+
+impl<N: Node> Graph<N> {
+    /// Returns value of a node parameter.
+    pub fn get_parameter(&self, node: NodeId, name: &str) -> Result<Value, GraphError> {
+        self.get_node(node)?.get_parameter(name).ok_or_else(|| GraphError::ParameterNotExists(node, name.to_string()))
+    }
+
+    /// Sets value of a node parameter (takes effect in the next cycle).
+    pub fn set_parameter(&mut self, node: NodeId, name: &str, value: Value) -> Result<(), GraphError> {
+        let processor = self.get_node_mut(node)?;
+        if !processor.parameters().contains(&name) {
+            return Err(GraphError::ParameterNotExists(node, name.to_string()));
+        }
+        processor.set_parameter(name, value);
+        Ok(())
+    }
+}
+
+/// Unit tests.
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{InputId, OutputId};
+
+    /// Node that scales its input by a gain parameter.
+    struct Scale {
+        gain: Value,
+        input: Value,
+        output: Value,
+    }
+    impl Scale {
+        fn new(gain: Value) -> Self {
+            Scale { gain, input: 0.0, output: 0.0 }
+        }
+    }
+    impl Node for Scale {
+        fn delayed_processing(&self) -> bool {
+            false
+        }
+        fn get_output(&self, _id: OutputId) -> Value {
+            self.output
+        }
+        fn get_parameter(&self, name: &str) -> Option<Value> {
+            (name == "gain").then_some(self.gain)
+        }
+        fn list_inputs(&self) -> &[InputId] {
+            &[InputId(0)]
+        }
+        fn list_outputs(&self) -> &[OutputId] {
+            &[OutputId(0)]
+        }
+        fn parameters(&self) -> &[&str] {
+            &["gain"]
+        }
+        fn process(&mut self) {
+            self.output = self.gain * self.input;
+        }
+        fn set_input(&mut self, _id: InputId, value: Value) {
+            self.input = value;
+        }
+        fn set_parameter(&mut self, name: &str, value: Value) {
+            if name == "gain" {
+                self.gain = value;
+            }
+        }
+    }
+
+    #[test]
+    fn set_parameter() {
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        let scale0 = graph.add_node(Box::new(Scale::new(2.0)));
+        graph.set_input(scale0, InputId(0), 3.0).unwrap();
+        graph.process();
+        assert_eq!(graph.get_output(scale0, OutputId(0)), Ok(6.0));
+
+        assert_eq!(graph.set_parameter(scale0, "gain", 0.5), Ok(()));
+        assert_eq!(graph.get_parameter(scale0, "gain"), Ok(0.5));
+        graph.process();
+        assert_eq!(graph.get_output(scale0, OutputId(0)), Ok(1.5));
+
+        // Invalid parameters.
+        let error = || GraphError::ParameterNotExists(scale0, "mode".to_string());
+        assert_eq!(graph.set_parameter(scale0, "mode", 1.0), Err(error()));
+        assert_eq!(graph.get_parameter(scale0, "mode"), Err(error()));
+        assert_eq!(graph.set_parameter(NodeId(1), "gain", 1.0), Err(GraphError::NodeNotExists(NodeId(1))));
+    }
+
+    #[test]
+    fn automates_parameter() {
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        let scale0 = graph.add_node(Box::new(Scale::new(1.0)));
+        graph.automate_parameter(scale0, "gain", 2.0, 2).unwrap();
+        graph.process();
+        assert_eq!(graph.get_parameter(scale0, "gain"), Ok(1.5));
+        graph.process();
+        assert_eq!(graph.get_parameter(scale0, "gain"), Ok(2.0));
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn saves_parameters() {
+        let mut registry = crate::NodeRegistry::<Box<dyn Node>>::new();
+        registry.register("scale", |_| Box::new(Scale::new(1.0)));
+        let mut graph = Graph::new();
+        let scale0 = graph.add_node(registry.create("scale", &[]).unwrap());
+        graph.set_parameter(scale0, "gain", 4.0).unwrap();
+
+        let json = graph.to_json(&registry).unwrap();
+        assert!(json.contains(r#""gain": 4.0"#));
+        let graph = Graph::from_json(&json, &registry).unwrap();
+        assert_eq!(graph.get_parameter(scale0, "gain"), Ok(4.0));
+
+        // Unknown parameters are rejected.
+        let json = json.replace(r#""gain""#, r#""mode""#);
+        assert_eq!(
+            Graph::from_json(&json, &registry).err(),
+            Some(GraphError::ParameterNotExists(scale0, "mode".to_string()))
+        );
+    }
+}
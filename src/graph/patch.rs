@@ -0,0 +1,181 @@
+use super::{serialization::Topology, Graph};
+use crate::{Connection, GraphError, Node, NodeId, NodeRegistry, SavedNode, Value};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Patch of a graph in text formats (see `Graph::to_json`).
+#[derive(Deserialize, Serialize)]
+pub(super) struct Patch {
+    /// Number of frames per block in block processing.
+    #[serde(default = "default_block_size")]
+    pub(super) block_size: usize,
+
+    /// Connections in graph.
+    #[serde(default)]
+    pub(super) connections: Vec<Connection>,
+
+    /// Event connections in graph.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(super) event_connections: Vec<Connection>,
+
+    /// Nodes in graph (ordered by id).
+    pub(super) nodes: Vec<PatchNode>,
+
+    /// Number of frames per second.
+    #[serde(default = "default_sample_rate")]
+    pub(super) sample_rate: f64,
+}
+
+/// Node of a patch.
+#[derive(Deserialize, Serialize)]
+pub(super) struct PatchNode {
+    /// Identifier of the node.
+    pub(super) id: NodeId,
+
+    /// Label of the node.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(super) label: Option<String>,
+
+    /// Opaque metadata of the node (e.g. editor position).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub(super) meta: BTreeMap<String, String>,
+
+    /// Parameters of the node (see `Node::parameters`).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub(super) parameters: BTreeMap<String, Value>,
+
+    /// Parameters passed to the constructor of the node type (construction arguments followed by the state snapshot of
+    /// the node).
+    #[serde(default)]
+    pub(super) params: Vec<Value>,
+
+    /// Priority of the node.
+    #[serde(default, skip_serializing_if = "is_default_priority")]
+    pub(super) priority: i32,
+
+    /// Rate divisor of the node.
+    #[serde(default = "default_rate_divisor", skip_serializing_if = "is_default_rate_divisor")]
+    pub(super) rate_divisor: u32,
+
+    /// Tags of the node.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(super) tags: Vec<String>,
+
+    /// Registered name of the node type.
+    #[serde(rename = "type")]
+    pub(super) type_name: String,
+
+    /// Version of the node type the node was saved with (see `NodeRegistry::set_version`).
+    #[serde(default, skip_serializing_if = "is_initial_version")]
+    pub(super) version: u32,
+}
+
+/// Returns default block size of patches.
+fn default_block_size() -> usize {
+    64
+}
+
+/// Returns default rate divisor of patch nodes.
+fn default_rate_divisor() -> u32 {
+    1
+}
+
+/// Returns default sample rate of patches.
+fn default_sample_rate() -> f64 {
+    1.0
+}
+
+/// Returns whether priority of a patch node is the default.
+fn is_default_priority(priority: &i32) -> bool {
+    *priority == 0
+}
+
+/// Returns whether version of a patch node is the initial one.
+fn is_initial_version(version: &u32) -> bool {
+    *version == 0
+}
+
+/// Returns whether rate divisor of a patch node is the default.
+fn is_default_rate_divisor(divisor: &u32) -> bool {
+    *divisor == 1
+}
+
+impl<N: Node> Graph<N> {
+    /// Builds graph from a patch, constructing nodes with a registry (nodes saved with an older version of their type
+    /// are migrated first, see `NodeRegistry::set_version`).
+    pub(super) fn from_patch(patch: Patch, registry: &NodeRegistry<N>) -> Result<Self, GraphError> {
+        let mut topology = Topology {
+            block_size: patch.block_size,
+            connections: patch.connections,
+            event_connections: patch.event_connections,
+            labels: Vec::new(),
+            meta: Vec::new(),
+            nodes: Vec::new(),
+            priorities: Vec::new(),
+            rate_divisors: Vec::new(),
+            sample_rate: patch.sample_rate,
+            tags: Vec::new(),
+        };
+        for node in patch.nodes {
+            let mut saved = SavedNode { parameters: node.parameters.into_iter().collect(), state: node.params };
+            registry.migrate(&node.type_name, node.version, &mut saved)?;
+            let mut processor = registry.create(&node.type_name, &saved.state)?;
+            processor.restore(saved.state.get(processor.arguments().len()..).unwrap_or_default());
+            for (name, value) in saved.parameters {
+                if !processor.parameters().contains(&name.as_str()) {
+                    return Err(GraphError::ParameterNotExists(node.id, name));
+                }
+                processor.set_parameter(&name, value);
+            }
+            topology.nodes.push((node.id, processor));
+            if let Some(label) = node.label {
+                topology.labels.push((node.id, label));
+            }
+            topology.priorities.push((node.id, node.priority));
+            topology.rate_divisors.push((node.id, node.rate_divisor));
+            topology.meta.extend(node.meta.into_iter().map(|(key, value)| (node.id, key, value)));
+            topology.tags.extend(node.tags.into_iter().map(|tag| (node.id, tag)));
+        }
+        topology.build()
+    }
+
+    /// Converts graph to a patch, naming node types with a registry.
+    pub(super) fn to_patch(&self, registry: &NodeRegistry<N>) -> Result<Patch, GraphError> {
+        let topology = Topology::from_graph(self, |node| {
+            let type_name =
+                registry.name_of(node).ok_or_else(|| GraphError::NodeTypeNotExists(node.type_name().to_string()))?;
+            let parameters = node
+                .parameters()
+                .iter()
+                .filter_map(|&name| Some((name.to_string(), node.get_parameter(name)?)))
+                .collect();
+            let params = node.arguments().into_iter().chain(node.snapshot()).collect();
+            Ok((type_name.to_string(), params, parameters))
+        })?;
+        Ok(Patch {
+            block_size: topology.block_size,
+            connections: topology.connections,
+            event_connections: topology.event_connections,
+            nodes: topology
+                .nodes
+                .into_iter()
+                .map(|(id, (type_name, params, parameters))| {
+                    let version = registry.version_of(&type_name).unwrap_or(0);
+                    PatchNode {
+                        id,
+                        label: self.labels.get(&id).cloned(),
+                        meta: self.meta.get(&id).cloned().unwrap_or_default(),
+                        parameters,
+                        params,
+                        priority: self.priorities.get(&id).copied().unwrap_or(0),
+                        rate_divisor: self.rate_divisors.get(&id).copied().unwrap_or(1),
+                        tags: self.tags.get(&id).cloned().unwrap_or_default(),
+                        type_name,
+                        version,
+                    }
+                })
+                .collect(),
+            sample_rate: topology.sample_rate,
+        })
+    }
+}
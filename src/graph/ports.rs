@@ -0,0 +1,84 @@
+use super::Graph;
+use crate::{Connection, GraphError, InputId, Node, NodeId, OutputId};
+
+impl<N: Node> Graph<N> {
+    /// Connects an output to an input by node labels and port names.
+    pub fn connect_by_name(
+        &mut self,
+        source: &str,
+        output: &str,
+        target: &str,
+        input: &str,
+    ) -> Result<Connection, GraphError> {
+        let (source, target) = (self.find_node(source)?, self.find_node(target)?);
+        let connection =
+            Connection::new(source, self.find_output(source, output)?, target, self.find_input(target, input)?);
+        self.add_connection(connection)
+    }
+
+    /// Returns input of a node by name (unnamed inputs are found by their id, e.g. `"0"`).
+    pub fn find_input(&self, node: NodeId, name: &str) -> Result<InputId, GraphError> {
+        let processor = self.get_node(node)?;
+        let inputs = processor.list_inputs();
+        if let Some(&input) = inputs.iter().find(|&&input| processor.input_name(input) == Some(name)) {
+            return Ok(input);
+        }
+        match name.parse().map(InputId) {
+            Ok(input) if inputs.contains(&input) && processor.input_name(input).is_none() => Ok(input),
+            _ => Err(GraphError::PortNotExists(node, name.to_string())),
+        }
+    }
+
+    /// Returns output of a node by name (unnamed outputs are found by their id, e.g. `"0"`).
+    pub fn find_output(&self, node: NodeId, name: &str) -> Result<OutputId, GraphError> {
+        let processor = self.get_node(node)?;
+        let outputs = processor.list_outputs();
+        if let Some(&output) = outputs.iter().find(|&&output| processor.output_name(output) == Some(name)) {
+            return Ok(output);
+        }
+        match name.parse().map(OutputId) {
+            Ok(output) if outputs.contains(&output) && processor.output_name(output).is_none() => Ok(output),
+            _ => Err(GraphError::PortNotExists(node, name.to_string())),
+        }
+    }
+}
+
+/// Unit tests.
+#[cfg(all(test, feature = "generators", feature = "math"))]
+mod tests {
+    use super::*;
+    use crate::{nodes, PortInfo};
+
+    #[test]
+    fn connect_by_name() {
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        let var0 = graph.add_node(Box::new(nodes::Variable::new(2.0)));
+        let add1 = graph.add_node(Box::new(nodes::Addition::new()));
+        let fn2 = graph.add_node(Box::new(nodes::FnNode::new(1, 1, |inputs, outputs| outputs[0] = inputs[0])));
+        graph.set_label(var0, Some("input")).unwrap();
+        graph.set_label(add1, Some("add")).unwrap();
+        graph.set_label(fn2, Some("copy")).unwrap();
+
+        assert_eq!(
+            graph.connect_by_name("input", "value", "add", "b"),
+            Ok(Connection::new(var0, OutputId(0), add1, InputId(1)))
+        );
+        assert_eq!(
+            graph.connect_by_name("add", "sum", "copy", "0"),
+            Ok(Connection::new(add1, OutputId(0), fn2, InputId(0)))
+        );
+        assert_eq!(graph.find_output(fn2, "0"), Ok(OutputId(0)));
+        assert_eq!(graph.get_node(add1).unwrap().input_info(InputId(1)), PortInfo::new(Some("b")));
+        graph.process();
+        assert_eq!(graph.get_output(fn2, OutputId(0)), Ok(2.0));
+
+        // Invalid names.
+        assert_eq!(graph.find_input(add1, "c"), Err(GraphError::PortNotExists(add1, "c".to_string())));
+        assert_eq!(graph.find_input(add1, "0"), Err(GraphError::PortNotExists(add1, "0".to_string())));
+        assert_eq!(graph.find_output(fn2, "1"), Err(GraphError::PortNotExists(fn2, "1".to_string())));
+        assert_eq!(
+            graph.connect_by_name("input", "value", "mul", "a"),
+            Err(GraphError::LabelNotExists("mul".to_string()))
+        );
+    }
+}
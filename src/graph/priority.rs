@@ -0,0 +1,135 @@
+use super::Graph;
+use crate::{GraphError, Node, NodeId};
+use std::time::Duration;
+
+impl<N: Node> Graph<N> {
+    /// Returns priority of a node (defaults to 0).
+    pub fn get_priority(&self, id: NodeId) -> Result<i32, GraphError> {
+        self.get_node(id)?;
+        Ok(self.priorities.get(&id).copied().unwrap_or(0))
+    }
+
+    /// Returns nodes skipped in the last cycle or block due to an exhausted cycle budget, a missed deadline or inputs
+    /// connected to nodes that do not exist.
+    pub fn get_skipped_nodes(&self) -> &[NodeId] {
+        &self.skipped_nodes
+    }
+
+    /// Removes the cycle budget (all nodes are processed).
+    pub fn remove_cycle_budget(&mut self) {
+        self.cycle_budget = None;
+    }
+
+    /// Sets processing time per cycle (or block) after which remaining nodes with a priority below `min_priority`
+    /// are skipped (and hold their outputs).
+    pub fn set_cycle_budget(&mut self, budget: Duration, min_priority: i32) {
+        self.cycle_budget = Some((budget, min_priority));
+    }
+
+    /// Sets priority of a node, which is inherited by all nodes it depends on (so whole branches feeding a
+    /// high-priority node are processed).
+    pub fn set_priority(&mut self, id: NodeId, priority: i32) -> Result<(), GraphError> {
+        self.get_node(id)?;
+        match priority {
+            0 => self.priorities.remove(&id),
+            _ => self.priorities.insert(id, priority),
+        };
+        self.update_priorities();
+        Ok(())
+    }
+
+    /// Returns whether a node is skipped because the cycle budget is exhausted or the deadline was missed.
+    pub(super) fn skips(&self, node: NodeId) -> bool {
+        if self.misses_deadline(node) {
+            return true;
+        }
+        match self.cycle_budget {
+            Some((budget, min_priority)) => {
+                self.effective_priorities.get(&node).copied().unwrap_or(0) < min_priority
+                    && self.cycle_start.elapsed() >= budget
+            }
+            None => false,
+        }
+    }
+
+    /// Determines priorities including priorities inherited from dependent nodes.
+    pub(super) fn update_priorities(&mut self) {
+        self.effective_priorities.clear();
+        if self.priorities.is_empty() {
+            return;
+        }
+
+        // Propagate in reverse processing order, repeat until stable (cycles with delay).
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for &node in self.processing_order.iter().rev() {
+                let mut priority = self.priorities.get(&node).copied().unwrap_or(0);
+                for connection in self.connections.iter().filter(|c| c.source_node == node) {
+                    if let Some(&inherited) = self.effective_priorities.get(&connection.target_node) {
+                        priority = priority.max(inherited);
+                    }
+                }
+                if self.effective_priorities.insert(node, priority) != Some(priority) {
+                    changed = true;
+                }
+            }
+        }
+    }
+}
+
+/// Unit tests.
+#[cfg(all(test, feature = "dsp", feature = "generators", feature = "math"))]
+mod tests {
+    use crate::fixtures::accumulator;
+    use crate::{nodes, Connection, Graph, GraphError, InputId, Node, NodeId, OutputId};
+    use std::time::Duration;
+
+    #[test]
+    fn priority() {
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        let var0 = graph.add_node(Box::from(nodes::Variable::new(1.0)));
+        let var1 = graph.add_node(Box::from(nodes::Variable::new(2.0)));
+        graph.add_connection(Connection::new(var0, OutputId(0), var1, InputId(0))).unwrap();
+        assert_eq!(graph.get_priority(var0), Ok(0));
+        assert_eq!(graph.set_priority(NodeId(2), 1), Err(GraphError::NodeNotExists(NodeId(2))));
+
+        // Priorities are inherited upstream.
+        graph.set_priority(var1, 2).unwrap();
+        assert_eq!(graph.get_priority(var1), Ok(2));
+        assert_eq!(graph.effective_priorities[&var0], 2);
+        graph.remove_connection(Connection::new(var0, OutputId(0), var1, InputId(0))).unwrap();
+        assert_eq!(graph.effective_priorities[&var0], 0);
+    }
+
+    #[test]
+    fn skip_low_priority() {
+        let (mut graph, [_, add1, _]) = accumulator(1.0, |node| node as Box<dyn Node>);
+        let var3 = graph.add_node(Box::from(nodes::Variable::new(2.0)));
+        let add4 = graph.add_node(Box::from(nodes::Addition::new()));
+        graph.add_connection(Connection::new(var3, OutputId(0), add4, InputId(0))).unwrap();
+        graph.set_priority(add1, 1).unwrap();
+
+        // Exhausted budget skips low-priority branch only.
+        graph.set_cycle_budget(Duration::ZERO, 1);
+        graph.process_n(3);
+        assert_eq!(graph.get_output(add1, OutputId(0)), Ok(3.0));
+        assert_eq!(graph.get_output(add4, OutputId(0)), Ok(0.0));
+        let mut skipped = graph.get_skipped_nodes().to_vec();
+        skipped.sort();
+        assert_eq!(skipped, vec![var3, add4]);
+
+        graph.set_block_size(2);
+        graph.process_block();
+        assert_eq!(graph.get_block_output(add4, OutputId(0)), Ok(&[0.0, 0.0][..]));
+
+        // Sufficient budget processes all nodes.
+        graph.set_cycle_budget(Duration::from_secs(60), 1);
+        graph.process();
+        assert_eq!(graph.get_output(add4, OutputId(0)), Ok(2.0));
+        assert_eq!(graph.get_skipped_nodes(), &[]);
+        graph.remove_cycle_budget();
+        graph.process();
+        assert_eq!(graph.get_skipped_nodes(), &[]);
+    }
+}
@@ -0,0 +1,116 @@
+use super::Graph;
+use crate::{GraphError, Node, NodeId, OutputId, Value};
+
+/// Identifier of a probe in a graph.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct ProbeId(pub u32);
+
+/// Callback invoked with the value of an output after each cycle.
+pub(super) struct Probe {
+    /// Callback invoked with value and frame number.
+    callback: Box<dyn FnMut(Value, u64) + Send>,
+
+    /// Identifier of the probe.
+    id: ProbeId,
+
+    /// Probed node.
+    node: NodeId,
+
+    /// Probed output.
+    output: OutputId,
+}
+
+impl<N: Node> Graph<N> {
+    /// Adds a probe that invokes a callback with the value of an output (including status outputs) and the frame
+    /// number after each cycle (after each frame in block processing).
+    ///
+    /// Callbacks are invoked on the processing thread and should not block (e.g. forward values over a lock-free
+    /// queue).
+    pub fn add_probe<F: FnMut(Value, u64) + Send + 'static>(
+        &mut self,
+        node: NodeId,
+        output: OutputId,
+        callback: F,
+    ) -> Result<ProbeId, GraphError> {
+        self.get_output(node, output)?;
+        let id = self.next_probe_id;
+        self.probes.push(Probe { callback: Box::new(callback), id, node, output });
+        self.next_probe_id.0 += 1;
+        Ok(id)
+    }
+
+    /// Invokes probes with the output values of a processed block.
+    pub(super) fn call_block_probes(&mut self, frames: usize) {
+        for probe in self.probes.iter_mut() {
+            let buffer = &self.output_buffers[&(probe.node, probe.output)];
+            for (frame, &value) in buffer.iter().take(frames).enumerate() {
+                (probe.callback)(value, self.context.frame + frame as u64);
+            }
+        }
+    }
+
+    /// Invokes probes with the output values of a processed cycle.
+    pub(super) fn call_probes(&mut self) {
+        for index in 0..self.probes.len() {
+            let value = self.output_value(self.probes[index].node, self.probes[index].output);
+            (self.probes[index].callback)(value, self.context.frame);
+        }
+    }
+
+    /// Removes a probe.
+    pub fn remove_probe(&mut self, id: ProbeId) -> Result<(), GraphError> {
+        let index = self.probes.iter().position(|probe| probe.id == id).ok_or(GraphError::ProbeNotExists(id))?;
+        self.probes.remove(index);
+        Ok(())
+    }
+
+    /// Returns whether an output of a node is probed.
+    #[cfg(all(feature = "generators", feature = "math"))]
+    pub(super) fn is_probed(&self, node: NodeId) -> bool {
+        self.probes.iter().any(|probe| probe.node == node)
+    }
+
+    /// Removes probes of a node.
+    pub(super) fn remove_probes(&mut self, node: NodeId) {
+        self.probes.retain(|probe| probe.node != node);
+    }
+}
+
+/// Unit tests.
+#[cfg(all(test, feature = "dsp", feature = "generators", feature = "math"))]
+mod tests {
+    use super::*;
+    use crate::fixtures::accumulator;
+    use crate::Node;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn probe() {
+        let (mut graph, [_, add1, del2]) = accumulator(1.0, |node| node as Box<dyn Node>);
+        let values = Arc::new(Mutex::new(Vec::with_capacity(8)));
+        let sink = values.clone();
+        let probe0 = graph.add_probe(add1, OutputId(0), move |value, frame| sink.lock().unwrap().push((value, frame)));
+        let sink = values.clone();
+        let probe1 =
+            graph.add_probe(add1, OutputId::STATUS, move |value, frame| sink.lock().unwrap().push((value, frame)));
+        assert_eq!(probe0, Ok(ProbeId(0)));
+        assert_eq!(probe1, Ok(ProbeId(1)));
+        assert_eq!(graph.add_probe(add1, OutputId(1), |_, _| {}), Err(GraphError::OutputNotExists(add1, OutputId(1))));
+        assert_eq!(graph.add_probe(NodeId(3), OutputId(0), |_, _| {}), Err(GraphError::NodeNotExists(NodeId(3))));
+
+        graph.process_n(2);
+        assert_eq!(*values.lock().unwrap(), vec![(1.0, 0), (0.0, 0), (2.0, 1), (0.0, 1)]);
+
+        values.lock().unwrap().clear();
+        assert_eq!(graph.remove_probe(ProbeId(1)), Ok(()));
+        assert_eq!(graph.remove_probe(ProbeId(1)), Err(GraphError::ProbeNotExists(ProbeId(1))));
+        graph.set_block_size(2);
+        graph.process_block();
+        assert_eq!(*values.lock().unwrap(), vec![(1.0, 2), (1.0, 3)]);
+
+        // Probes of removed nodes are removed.
+        graph.remove_node(add1).unwrap();
+        assert_eq!(graph.probes.len(), 0);
+        graph.add_probe(del2, OutputId(0), |_, _| {}).unwrap();
+    }
+}
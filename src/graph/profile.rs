@@ -0,0 +1,113 @@
+use super::Graph;
+use crate::{Node, NodeId};
+use std::{collections::HashMap, time::Duration};
+
+/// Processing statistics of a node.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NodeProfile {
+    /// Number of times the node was processed (once per cycle or block).
+    pub calls: u64,
+
+    /// Profiled node.
+    pub node: NodeId,
+
+    /// Cumulative processing time.
+    pub total: Duration,
+}
+impl NodeProfile {
+    /// Returns average processing time per call.
+    pub fn average(&self) -> Duration {
+        match self.calls {
+            0 => Duration::ZERO,
+            calls => self.total.div_f64(calls as f64),
+        }
+    }
+}
+
+impl<N: Node> Graph<N> {
+    /// Returns processing statistics of all processed nodes (slowest first), empty unless profiling is enabled.
+    pub fn profile_report(&self) -> Vec<NodeProfile> {
+        let mut report: Vec<NodeProfile> =
+            self.profiles.iter().flatten().map(|(&node, &(calls, total))| NodeProfile { calls, node, total }).collect();
+        report.sort_by(|a, b| b.total.cmp(&a.total).then(a.node.0.cmp(&b.node.0)));
+        report
+    }
+
+    /// Enables or disables profiling of node processing times (enabling resets statistics).
+    pub fn set_profiling(&mut self, enabled: bool) {
+        self.profiles = enabled.then(|| HashMap::with_capacity(self.nodes.len()));
+    }
+}
+
+/// Records processing time of a node (if profiling is enabled).
+pub(super) fn record_profile(
+    profiles: &mut Option<HashMap<NodeId, (u64, Duration)>>,
+    node: NodeId,
+    elapsed: Option<Duration>,
+) {
+    if let (Some(profiles), Some(elapsed)) = (profiles.as_mut(), elapsed) {
+        let (calls, total) = profiles.entry(node).or_default();
+        *calls += 1;
+        *total += elapsed;
+    }
+}
+
+/// Unit tests.
+#[cfg(all(test, feature = "generators"))]
+mod tests {
+    use super::*;
+    use crate::{nodes, Connection, InputId, OutputId, Value};
+    use std::{thread, time::Duration};
+
+    /// Node that passes its input through slowly.
+    struct Slow;
+    impl Node for Slow {
+        fn delayed_processing(&self) -> bool {
+            false
+        }
+        fn get_output(&self, _id: OutputId) -> Value {
+            0.0
+        }
+        fn list_inputs(&self) -> &[InputId] {
+            &[InputId(0)]
+        }
+        fn list_outputs(&self) -> &[OutputId] {
+            &[OutputId(0)]
+        }
+        fn process(&mut self) {
+            thread::sleep(Duration::from_millis(2));
+        }
+        fn set_input(&mut self, _id: InputId, _value: Value) {}
+    }
+
+    #[test]
+    fn profile() {
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        let var0 = graph.add_node(Box::from(nodes::Variable::new(1.0)));
+        let slow1 = graph.add_node(Box::from(Slow));
+        graph.add_connection(Connection::new(var0, OutputId(0), slow1, InputId(0))).unwrap();
+        graph.process();
+        assert_eq!(graph.profile_report(), vec![]);
+
+        graph.set_profiling(true);
+        graph.process_n(2);
+        graph.set_block_size(4);
+        graph.process_block();
+        let report = graph.profile_report();
+        assert_eq!(
+            report.iter().map(|profile| (profile.node, profile.calls)).collect::<Vec<_>>(),
+            [(slow1, 3), (var0, 3)]
+        );
+        assert!(report[0].total >= Duration::from_millis(2 * 2 + 4 * 2));
+        assert!(report[0].average() >= Duration::from_millis(2));
+        assert_eq!(NodeProfile { calls: 0, node: var0, total: Duration::ZERO }.average(), Duration::ZERO);
+
+        // Enabling resets statistics.
+        graph.set_profiling(true);
+        graph.process();
+        assert_eq!(graph.profile_report().iter().map(|profile| profile.calls).collect::<Vec<_>>(), [1, 1]);
+        graph.set_profiling(false);
+        graph.process();
+        assert_eq!(graph.profile_report(), vec![]);
+    }
+}
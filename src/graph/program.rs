@@ -0,0 +1,208 @@
+use super::{has_output, Graph};
+use crate::{GraphError, InputId, Node, NodeId, OutputId, ProcessContext, Value};
+
+/// Instruction of a program (nodes and slots are indices).
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Instruction {
+    /// Sets an input channel of a node to the value of a slot.
+    Load { channel: usize, input: InputId, node: usize, slot: usize },
+
+    /// Processes a node and records its status.
+    Process { node: usize },
+
+    /// Stores an output channel of a node into a slot.
+    Store { channel: usize, node: usize, output: OutputId, slot: usize },
+
+    /// Stores the status of a node into a slot.
+    StoreStatus { node: usize, slot: usize },
+}
+
+/// Frozen graph lowered into a flat instruction stream (load inputs, process node, store outputs) that is executed
+/// by a tight loop without looking up nodes or connections (see `Graph::into_program`).
+pub struct Program<N: Node> {
+    /// Timing information of the current cycle.
+    context: ProcessContext,
+
+    /// Node ids (sorted), indices of nodes.
+    ids: Vec<NodeId>,
+
+    /// Instructions of one cycle.
+    instructions: Vec<Instruction>,
+
+    /// Nodes, indexed like ids.
+    nodes: Vec<N>,
+
+    /// Last status of nodes (0 if processing succeeded), indexed like ids.
+    statuses: Vec<u32>,
+
+    /// Values of connected output channels.
+    values: Vec<Value>,
+}
+
+impl<N: Node> Graph<N> {
+    /// Freezes the graph into a program with the same processing order and delay semantics.
+    ///
+    /// Only nodes, connections, input values and statuses are carried over, other settings of the graph (e.g. events,
+    /// bypass, automations or clock domains) are dropped.
+    pub fn into_program(mut self) -> Program<N> {
+        let mut ids: Vec<NodeId> = self.nodes.keys().copied().collect();
+        ids.sort_unstable();
+        let index = |id: NodeId| ids.binary_search(&id).unwrap();
+
+        // Slots of connected output channels, initialized with current values.
+        let mut slots: Vec<(NodeId, OutputId, usize)> = Vec::new();
+        let mut values = Vec::new();
+        for c in self.connections.iter() {
+            let channels = match c.source_output {
+                OutputId::STATUS => 1,
+                output => self.nodes[&c.source_node].output_channels(output),
+            };
+            for channel in 0..channels {
+                if !slots.contains(&(c.source_node, c.source_output, channel)) {
+                    slots.push((c.source_node, c.source_output, channel));
+                    values.push(match c.source_output {
+                        OutputId::STATUS => self.output_value(c.source_node, c.source_output),
+                        output => self.nodes[&c.source_node].get_output_channel(output, channel),
+                    });
+                }
+            }
+        }
+        let slot = |source: (NodeId, OutputId, usize)| slots.iter().position(|&s| s == source).unwrap();
+        let stores = |id: NodeId, instructions: &mut Vec<Instruction>| {
+            for (slot, &(_, output, channel)) in slots.iter().enumerate().filter(|(_, s)| s.0 == id) {
+                instructions.push(match output {
+                    OutputId::STATUS => Instruction::StoreStatus { node: index(id), slot },
+                    output => Instruction::Store { channel, node: index(id), output, slot },
+                });
+            }
+        };
+
+        // First pass processes nodes without delay, second pass delayed nodes.
+        let mut instructions = Vec::new();
+        for &id in self.processing_order.iter() {
+            for c in self.connections.iter().filter(|c| c.target_node == id) {
+                for channel in 0..self.nodes[&id].input_channels(c.target_input) {
+                    let slot = slot((c.source_node, c.source_output, channel));
+                    instructions.push(Instruction::Load { channel, input: c.target_input, node: index(id), slot });
+                }
+            }
+            if !self.nodes[&id].delayed_processing() {
+                instructions.push(Instruction::Process { node: index(id) });
+                stores(id, &mut instructions);
+            }
+        }
+        for &id in self.processing_order.iter().filter(|id| self.nodes[id].delayed_processing()) {
+            instructions.push(Instruction::Process { node: index(id) });
+            stores(id, &mut instructions);
+        }
+
+        let statuses = ids.iter().map(|id| self.statuses.get(id).copied().unwrap_or(0)).collect();
+        let nodes = ids.iter().map(|id| self.nodes.remove(id).unwrap()).collect();
+        Program { context: self.context, ids, instructions, nodes, statuses, values }
+    }
+}
+
+impl<N: Node> Program<N> {
+    /// Returns timing information of the next cycle.
+    pub fn context(&self) -> ProcessContext {
+        self.context
+    }
+
+    /// Returns value of an output (including status outputs).
+    pub fn get_output(&self, node: NodeId, output: OutputId) -> Result<Value, GraphError> {
+        let index = self.index(node)?;
+        match output {
+            OutputId::STATUS => Ok(self.statuses[index] as Value),
+            output if has_output(&self.nodes[index], output) => Ok(self.nodes[index].get_output(output)),
+            output => Err(GraphError::OutputNotExists(node, output)),
+        }
+    }
+
+    /// Returns whether the program has no instructions.
+    pub fn is_empty(&self) -> bool {
+        self.instructions.is_empty()
+    }
+
+    /// Returns number of instructions per cycle.
+    pub fn len(&self) -> usize {
+        self.instructions.len()
+    }
+
+    /// Processes one cycle.
+    pub fn process(&mut self) {
+        for &instruction in self.instructions.iter() {
+            match instruction {
+                Instruction::Load { channel, input, node, slot } => {
+                    self.nodes[node].set_input_channel(input, channel, self.values[slot])
+                }
+                Instruction::Process { node } => {
+                    self.statuses[node] = match self.nodes[node].try_process(&self.context) {
+                        Ok(()) => 0,
+                        Err(error) => error.0,
+                    }
+                }
+                Instruction::Store { channel, node, output, slot } => {
+                    self.values[slot] = self.nodes[node].get_output_channel(output, channel)
+                }
+                Instruction::StoreStatus { node, slot } => self.values[slot] = self.statuses[node] as Value,
+            }
+        }
+        self.context.frame += 1;
+    }
+
+    /// Processes given number of cycles.
+    pub fn process_n(&mut self, count: usize) {
+        for _ in 0..count {
+            self.process();
+        }
+    }
+
+    /// Sets value of an unconnected input of a node (connected inputs are overwritten when processing).
+    pub fn set_input(&mut self, node: NodeId, input: InputId, value: Value) -> Result<(), GraphError> {
+        let index = self.index(node)?;
+        if !self.nodes[index].list_inputs().contains(&input) {
+            return Err(GraphError::InputNotExists(node, input));
+        }
+        self.nodes[index].set_input(input, value);
+        Ok(())
+    }
+
+    /// Returns index of a node.
+    fn index(&self, node: NodeId) -> Result<usize, GraphError> {
+        self.ids.binary_search(&node).map_err(|_| GraphError::NodeNotExists(node))
+    }
+}
+
+/// Unit tests.
+#[cfg(all(test, feature = "dsp", feature = "generators", feature = "math"))]
+mod tests {
+    use super::*;
+    use crate::fixtures::accumulator;
+
+    #[test]
+    fn processes_like_graph() {
+        let (mut graph, _) = accumulator(1.0, |node| node as Box<dyn Node>);
+        graph.process_n(2);
+        let (mut program, _) = accumulator(1.0, |node| node as Box<dyn Node>);
+        program.process_n(2);
+        let mut program = program.into_program();
+        assert_eq!(program.len(), 9);
+
+        for _ in 0..3 {
+            graph.process();
+            program.process();
+            assert_eq!(program.get_output(NodeId(1), OutputId(0)), graph.get_output(NodeId(1), OutputId(0)));
+        }
+        assert_eq!(program.context(), graph.context());
+
+        program.set_input(NodeId(0), InputId(0), 2.0).unwrap();
+        program.process();
+        assert_eq!(program.get_output(NodeId(1), OutputId(0)), Ok(7.0));
+        assert_eq!(program.get_output(NodeId(1), OutputId::STATUS), Ok(0.0));
+        assert_eq!(program.set_input(NodeId(3), InputId(0), 0.0), Err(GraphError::NodeNotExists(NodeId(3))));
+        assert_eq!(
+            program.get_output(NodeId(0), OutputId(1)),
+            Err(GraphError::OutputNotExists(NodeId(0), OutputId(1)))
+        );
+    }
+}
@@ -0,0 +1,119 @@
+use super::Graph;
+use crate::{GraphError, Node, NodeId, OutputId, Value};
+use std::collections::VecDeque;
+
+/// Ring buffer of the last values of a recorded output.
+pub(super) struct Recording {
+    /// Maximum number of kept values.
+    capacity: usize,
+
+    /// Recorded values (oldest first).
+    pub(super) values: VecDeque<Value>,
+}
+impl Recording {
+    /// Creates empty recording keeping up to given number of values.
+    fn new(capacity: usize) -> Self {
+        Recording { capacity, values: VecDeque::with_capacity(capacity) }
+    }
+
+    /// Pushes a value, dropping the oldest value once full (without reallocating).
+    fn push(&mut self, value: Value) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.values.len() == self.capacity {
+            self.values.pop_front();
+        }
+        self.values.push_back(value);
+    }
+}
+
+impl<N: Node> Graph<N> {
+    /// Starts recording the last values of an output (including status outputs) after each cycle (after each frame
+    /// in block processing) into a ring buffer of given capacity, e.g. for oscilloscope views. Recording an output
+    /// again clears its buffer, recording stops when the node is removed.
+    pub fn record(&mut self, node: NodeId, output: OutputId, capacity: usize) -> Result<(), GraphError> {
+        self.get_output(node, output)?;
+        self.recordings.retain(|(key, _)| *key != (node, output));
+        self.recordings.push(((node, output), Recording::new(capacity)));
+        Ok(())
+    }
+
+    /// Returns recorded values of an output (oldest first, see `record`).
+    pub fn recording(&mut self, node: NodeId, output: OutputId) -> Result<&[Value], GraphError> {
+        match self.recordings.iter_mut().find(|(key, _)| *key == (node, output)) {
+            Some((_, recording)) => Ok(recording.values.make_contiguous()),
+            None => Err(GraphError::RecordingNotExists(node, output)),
+        }
+    }
+
+    /// Stops recording an output.
+    pub fn stop_recording(&mut self, node: NodeId, output: OutputId) -> Result<(), GraphError> {
+        let index = self.recordings.iter().position(|(key, _)| *key == (node, output));
+        self.recordings.remove(index.ok_or(GraphError::RecordingNotExists(node, output))?);
+        Ok(())
+    }
+
+    /// Records output values of a processed block.
+    pub(super) fn record_block_outputs(&mut self, frames: usize) {
+        for index in 0..self.recordings.len() {
+            let (node, output) = self.recordings[index].0;
+            for frame in 0..frames {
+                let value = self.output_buffers[&(node, output)][frame];
+                self.recordings[index].1.push(value);
+            }
+        }
+    }
+
+    /// Records output values of a processed cycle.
+    pub(super) fn record_outputs(&mut self) {
+        for index in 0..self.recordings.len() {
+            let (node, output) = self.recordings[index].0;
+            let value = self.output_value(node, output);
+            self.recordings[index].1.push(value);
+        }
+    }
+
+    /// Stops recordings of a node.
+    pub(super) fn remove_recordings(&mut self, node: NodeId) {
+        self.recordings.retain(|((n, _), _)| *n != node);
+    }
+}
+
+/// Unit tests.
+#[cfg(all(test, feature = "dsp", feature = "generators", feature = "math"))]
+mod tests {
+    use super::*;
+    use crate::fixtures::accumulator;
+
+    #[test]
+    fn record() {
+        let (mut graph, [_, add1, del2]) = accumulator(1.0, |node| node as Box<dyn Node>);
+        graph.record(add1, OutputId(0), 3).unwrap();
+        assert_eq!(graph.recording(add1, OutputId(0)), Ok(&[][..]));
+
+        graph.process_n(4);
+        assert_eq!(graph.recording(add1, OutputId(0)), Ok(&[2.0, 3.0, 4.0][..]));
+        graph.set_block_size(2);
+        graph.process_block();
+        let block = graph.get_block_output(add1, OutputId(0)).unwrap().to_vec();
+        assert_eq!(graph.recording(add1, OutputId(0)), Ok(&[4.0, block[0], block[1]][..]));
+
+        assert_eq!(graph.record(add1, OutputId(1), 3), Err(GraphError::OutputNotExists(add1, OutputId(1))));
+        assert_eq!(graph.stop_recording(add1, OutputId(0)), Ok(()));
+        assert_eq!(graph.recording(add1, OutputId(0)), Err(GraphError::RecordingNotExists(add1, OutputId(0))));
+        graph.record(del2, OutputId(0), 1).unwrap();
+        graph.remove_node(del2).unwrap();
+        assert_eq!(graph.stop_recording(del2, OutputId(0)), Err(GraphError::RecordingNotExists(del2, OutputId(0))));
+    }
+
+    #[test]
+    fn keeps_capacity() {
+        // Buffers may allocate more than requested, only the requested number of values is kept.
+        for capacity in 0..10 {
+            let mut recording = Recording::new(capacity);
+            (0..=capacity).for_each(|value| recording.push(value as Value));
+            assert_eq!(recording.values, (1..=capacity).map(|value| value as Value).collect::<Vec<_>>());
+        }
+    }
+}
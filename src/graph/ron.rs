@@ -0,0 +1,86 @@
+use super::{patch::Patch, Graph};
+use crate::{GraphError, Node, NodeRegistry};
+use ::ron::{extensions::Extensions, ser::PrettyConfig, Options};
+
+/// Returns RON options of patches (node ids and ports are written as plain numbers).
+fn options() -> Options {
+    Options::default().with_default_extension(Extensions::UNWRAP_NEWTYPES)
+}
+
+impl<N: Node> Graph<N> {
+    /// Loads graph from a RON patch (see `to_ron`), constructing nodes with a registry.
+    pub fn from_ron(ron: &str, registry: &NodeRegistry<N>) -> Result<Self, GraphError> {
+        let patch: Patch = options().from_str(ron).map_err(|error| GraphError::InvalidFormat(error.to_string()))?;
+        Graph::from_patch(patch, registry)
+    }
+
+    /// Saves graph as RON patch, naming node types with a registry, e.g. for hand-written test fixtures.
+    ///
+    /// A RON patch has the same fields as a JSON patch (see `to_json`), with maps for metadata and parameters:
+    ///
+    /// ```text
+    /// (
+    ///     connections: [(source_node: 0, source_output: 0, target_input: 0, target_node: 1)],
+    ///     nodes: [
+    ///         (id: 0, label: Some("gain"), parameters: {"mode": 1.0}, params: [0.5], type: "variable"),
+    ///         (id: 1, type: "delay"),
+    ///     ],
+    /// )
+    /// ```
+    pub fn to_ron(&self, registry: &NodeRegistry<N>) -> Result<String, GraphError> {
+        let patch = self.to_patch(registry)?;
+        options()
+            .to_string_pretty(&patch, PrettyConfig::default())
+            .map_err(|error| GraphError::InvalidFormat(error.to_string()))
+    }
+}
+
+/// Unit tests.
+#[cfg(all(test, feature = "dsp", feature = "generators"))]
+mod tests {
+    use super::*;
+    use crate::{nodes, NodeId, OutputId};
+
+    #[test]
+    fn from_ron() {
+        let ron = r#"(
+            connections: [
+                (source_node: 0, source_output: 0, target_input: 0, target_node: 4),
+                (source_node: 4, source_output: 0, target_input: 0, target_node: 7),
+                (source_node: 7, source_output: 0, target_input: 1, target_node: 4),
+            ],
+            nodes: [
+                (id: 0, label: Some("input"), params: [2.0], type: "variable"),
+                (id: 4, type: "addition"),
+                (id: 7, tags: ["feedback"], type: "delay"),
+            ],
+        )"#;
+        let mut graph = Graph::from_ron(ron, &NodeRegistry::builtin()).unwrap();
+        assert_eq!(graph.find_node("input"), Ok(NodeId(0)));
+        assert_eq!(graph.nodes_with_tag("feedback"), [NodeId(7)]);
+        graph.process_n(3);
+        assert_eq!(graph.get_output(NodeId(4), OutputId(0)), Ok(6.0));
+
+        // Invalid patches.
+        assert!(matches!(Graph::from_ron("(", &NodeRegistry::builtin()), Err(GraphError::InvalidFormat(_))));
+        assert_eq!(
+            Graph::from_ron(r#"(nodes: [(id: 0, type: "multiplication")])"#, &NodeRegistry::builtin()).err(),
+            Some(GraphError::NodeTypeNotExists("multiplication".to_string()))
+        );
+    }
+
+    #[test]
+    fn to_ron() {
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        let var0 = graph.add_node(Box::new(nodes::Variable::new(2.0)));
+        graph.add_node(Box::new(nodes::Delay::new()));
+        graph.set_label(var0, Some("input")).unwrap();
+        graph.set_meta(var0, "color", "red").unwrap();
+        graph.process();
+
+        let ron = graph.to_ron(&NodeRegistry::builtin()).unwrap();
+        let restored = Graph::from_ron(&ron, &NodeRegistry::builtin()).unwrap();
+        assert_eq!(restored.find_node("input"), Ok(var0));
+        assert_eq!(restored.to_ron(&NodeRegistry::builtin()), Ok(ron));
+    }
+}
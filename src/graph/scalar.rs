@@ -0,0 +1,209 @@
+use super::{
+    kernels::{Kernel, Layout},
+    Graph,
+};
+use crate::{GraphError, InputId, Node, NodeId, OutputId, Scalar};
+use std::collections::HashMap;
+
+/// Graph of built-in nodes whose kernels are evaluated with a scalar type, e.g. fixed point, dual numbers, intervals or
+/// uncertain values (see `Graph::compile_scalar`).
+pub struct ScalarProgram<T: Scalar> {
+    /// Slots of inputs and outputs of delays sampled in the first pass, written to the outputs in the second pass.
+    delays: Vec<(usize, usize)>,
+
+    /// Slots of unconnected inputs.
+    inputs: HashMap<(NodeId, InputId), usize>,
+
+    /// Slots of outputs.
+    outputs: HashMap<(NodeId, OutputId), usize>,
+
+    /// Values of outputs, unconnected inputs and pending inputs of delays.
+    slots: Vec<T>,
+
+    /// Kernels computed in processing order with their operand slots and output slot.
+    steps: Vec<(Kernel, Vec<usize>, usize)>,
+}
+
+impl<N: Node> Graph<N> {
+    /// Compiles the graph into a program that evaluates its node kernels with a scalar type (e.g. `Q16` for targets
+    /// without floating-point unit, `Dual` for derivatives, `Interval` for range analysis or `Uncertain` for error
+    /// propagation). Current outputs, delay states and unconnected input values are converted to the scalar type.
+    pub fn compile_scalar<T: Scalar>(&self) -> Result<ScalarProgram<T>, GraphError> {
+        let layout = self.kernel_layout()?;
+        let mut steps = Vec::new();
+        let mut delays = Vec::new();
+        for &id in self.processing_order.iter() {
+            let operands = self.operand_slots(&layout, id);
+            let output = layout.outputs.get(&(id, OutputId(0))).copied();
+            match layout.kernels[&id] {
+                Kernel::Delay => {
+                    steps.push((Kernel::Delay, operands, layout.pending[&id]));
+                    delays.push((layout.pending[&id], output.unwrap()));
+                }
+                Kernel::Variable if operands[0] != output.unwrap() => {
+                    steps.push((Kernel::Variable, operands, output.unwrap()))
+                }
+                Kernel::Constant | Kernel::Variable => {}
+                kernel => steps.push((kernel, operands, output.unwrap())),
+            }
+        }
+        let Layout { inputs, outputs, slots, .. } = layout;
+        let slots = slots.into_iter().map(T::from_value).collect();
+        Ok(ScalarProgram { delays, inputs, outputs, slots, steps })
+    }
+}
+
+impl<T: Scalar> ScalarProgram<T> {
+    /// Returns value of an output.
+    pub fn get_output(&self, node: NodeId, output: OutputId) -> Result<T, GraphError> {
+        match self.outputs.get(&(node, output)) {
+            Some(&slot) => Ok(self.slots[slot]),
+            None => Err(GraphError::OutputNotExists(node, output)),
+        }
+    }
+
+    /// Processes one cycle.
+    pub fn process(&mut self) {
+        for (kernel, operands, output) in self.steps.iter() {
+            let mut operands = operands.iter().map(|&slot| self.slots[slot]);
+            let value = match kernel {
+                Kernel::Average if operands.len() > 0 => {
+                    let count = T::from_count(operands.len());
+                    operands.fold(T::ZERO, |sum, operand| sum + operand) / count
+                }
+                Kernel::Average | Kernel::Sum => operands.fold(T::ZERO, |sum, operand| sum + operand),
+                Kernel::Product => operands.fold(T::ONE, |product, operand| product * operand),
+                Kernel::Constant | Kernel::Delay | Kernel::Variable => operands.next().unwrap_or(T::ZERO),
+            };
+            self.slots[*output] = value;
+        }
+        for &(pending, output) in self.delays.iter() {
+            self.slots[output] = self.slots[pending];
+        }
+    }
+
+    /// Processes given number of cycles.
+    pub fn process_n(&mut self, count: usize) {
+        for _ in 0..count {
+            self.process();
+        }
+    }
+
+    /// Sets value of an unconnected input (connected inputs cannot be set).
+    pub fn set_input(&mut self, node: NodeId, input: InputId, value: T) -> Result<(), GraphError> {
+        let slot = self.inputs.get(&(node, input)).ok_or(GraphError::InputNotExists(node, input))?;
+        self.slots[*slot] = value;
+        Ok(())
+    }
+}
+
+/// Unit tests.
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "fixed")]
+    use crate::fixtures::accumulator;
+    #[cfg(feature = "autodiff")]
+    use crate::Dual;
+    #[cfg(feature = "interval")]
+    use crate::Interval;
+    #[cfg(feature = "uncertainty")]
+    use crate::Uncertain;
+    use crate::{nodes, Connection};
+    #[cfg(feature = "fixed")]
+    use crate::{Value, Q16};
+
+    #[cfg(feature = "autodiff")]
+    #[test]
+    fn differentiates_outputs() {
+        // out = (gain * x) averaged with a delayed copy of itself.
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        let prd0 = graph.add_node(Box::new(nodes::ProductN::new(2)));
+        let del1 = graph.add_node(Box::new(nodes::Delay::new()));
+        let avg2 = graph.add_node(Box::new(nodes::AverageN::new(2)));
+        graph.add_connection(Connection::new(prd0, OutputId(0), del1, InputId(0))).unwrap();
+        graph.add_connection(Connection::new(prd0, OutputId(0), avg2, InputId(0))).unwrap();
+        graph.add_connection(Connection::new(del1, OutputId(0), avg2, InputId(1))).unwrap();
+
+        let mut program = graph.compile_scalar::<Dual<2>>().unwrap();
+        program.set_input(prd0, InputId(0), Dual::variable(0.5, 0)).unwrap();
+        program.set_input(prd0, InputId(1), Dual::variable(4.0, 1)).unwrap();
+        program.process_n(2);
+        let output = program.get_output(avg2, OutputId(0)).unwrap();
+        assert_eq!(output, Dual { partials: [4.0, 0.5], value: 2.0 });
+    }
+
+    #[cfg(feature = "interval")]
+    #[test]
+    fn bounds_outputs() {
+        // Command = gain * (setpoint + disturbance) plus the command of the previous frame.
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        let sum0 = graph.add_node(Box::new(nodes::SumN::new(2)));
+        let prd1 = graph.add_node(Box::new(nodes::ProductN::new(2)));
+        let del2 = graph.add_node(Box::new(nodes::Delay::new()));
+        let add3 = graph.add_node(Box::new(nodes::Addition::new()));
+        graph.add_connection(Connection::new(sum0, OutputId(0), prd1, InputId(0))).unwrap();
+        graph.add_connection(Connection::new(prd1, OutputId(0), add3, InputId(0))).unwrap();
+        graph.add_connection(Connection::new(del2, OutputId(0), add3, InputId(1))).unwrap();
+        graph.add_connection(Connection::new(prd1, OutputId(0), del2, InputId(0))).unwrap();
+
+        let mut program = graph.compile_scalar::<Interval>().unwrap();
+        program.set_input(sum0, InputId(0), Interval::new(0.0, 1.0)).unwrap();
+        program.set_input(sum0, InputId(1), Interval::new(-0.5, 0.5)).unwrap();
+        program.set_input(prd1, InputId(1), Interval::new(0.5, 2.0)).unwrap();
+        program.process_n(2);
+        let command = program.get_output(add3, OutputId(0)).unwrap();
+        assert_eq!(command, Interval::new(-2.0, 6.0));
+        assert!(command.within(-2.0, 6.0) && !command.within(-1.0, 1.0));
+    }
+
+    #[cfg(feature = "fixed")]
+    #[test]
+    fn processes_like_graph() {
+        let (mut graph, [var0, add1, del2]) = accumulator(0.5, |node| node as Box<dyn Node>);
+        let prd3 = graph.add_node(Box::new(nodes::ProductN::new(2)));
+        let avg4 = graph.add_node(Box::new(nodes::AverageN::new(2)));
+        graph.add_connection(Connection::new(add1, OutputId(0), prd3, InputId(0))).unwrap();
+        graph.add_connection(Connection::new(prd3, OutputId(0), avg4, InputId(0))).unwrap();
+        graph.set_input(prd3, InputId(1), 0.75).unwrap();
+        graph.process();
+
+        let mut floating = graph.compile_scalar::<Value>().unwrap();
+        let mut fixed = graph.compile_scalar::<Q16>().unwrap();
+        graph.process_n(3);
+        floating.process_n(3);
+        fixed.process_n(3);
+        for node in [var0, add1, del2, prd3, avg4] {
+            let expected = graph.get_output(node, OutputId(0)).unwrap();
+            assert_eq!(floating.get_output(node, OutputId(0)), Ok(expected));
+            assert_eq!(fixed.get_output(node, OutputId(0)).map(Q16::to_value), Ok(expected));
+        }
+
+        fixed.set_input(prd3, InputId(1), Q16::ONE).unwrap();
+        fixed.process();
+        assert_eq!(fixed.get_output(prd3, OutputId(0)), Ok(Q16::from_value(2.5)));
+        assert_eq!(fixed.set_input(add1, InputId(0), Q16::ONE), Err(GraphError::InputNotExists(add1, InputId(0))));
+
+        // Clamping variables have no kernel.
+        let var5 = graph.add_node(Box::new(nodes::Variable::with_range(0.0, 0.0, 1.0)));
+        assert_eq!(graph.compile_scalar::<Q16>().err(), Some(GraphError::UnsupportedNode(var5)));
+    }
+
+    #[cfg(feature = "uncertainty")]
+    #[test]
+    fn propagates_uncertainty() {
+        // Two sensors averaged, then scaled by an exact gain.
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        let avg0 = graph.add_node(Box::new(nodes::AverageN::new(2)));
+        let prd1 = graph.add_node(Box::new(nodes::ProductN::new(2)));
+        graph.add_connection(Connection::new(avg0, OutputId(0), prd1, InputId(0))).unwrap();
+        graph.set_input(prd1, InputId(1), 2.0).unwrap();
+
+        let mut program = graph.compile_scalar::<Uncertain>().unwrap();
+        program.set_input(avg0, InputId(0), Uncertain::new(10.0, 1.0)).unwrap();
+        program.set_input(avg0, InputId(1), Uncertain::new(12.0, 1.0)).unwrap();
+        program.process();
+        assert_eq!(program.get_output(avg0, OutputId(0)), Ok(Uncertain::new(11.0, 0.5)));
+        assert_eq!(program.get_output(prd1, OutputId(0)), Ok(Uncertain::new(22.0, 2.0)));
+    }
+}
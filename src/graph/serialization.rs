@@ -0,0 +1,207 @@
+use super::Graph;
+use crate::{Connection, GraphError, Node, NodeId};
+use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
+
+/// Serialized topology of a graph (runtime state like buffers, probes or the executor is not included).
+#[derive(Deserialize, Serialize)]
+pub(crate) struct Topology<N> {
+    /// Number of frames per block in block processing.
+    pub(crate) block_size: usize,
+
+    /// Connections in graph.
+    pub(crate) connections: Vec<Connection>,
+
+    /// Event connections in graph.
+    #[serde(default)]
+    pub(crate) event_connections: Vec<Connection>,
+
+    /// Node labels (ordered by node id).
+    pub(crate) labels: Vec<(NodeId, String)>,
+
+    /// Node metadata by key (ordered by node id and key).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) meta: Vec<(NodeId, String, String)>,
+
+    /// Nodes (ordered by node id).
+    pub(crate) nodes: Vec<(NodeId, N)>,
+
+    /// Explicitly set node priorities (ordered by node id).
+    pub(crate) priorities: Vec<(NodeId, i32)>,
+
+    /// Rate divisors of nodes in slower clock domains (ordered by node id).
+    pub(crate) rate_divisors: Vec<(NodeId, u32)>,
+
+    /// Number of frames per second.
+    pub(crate) sample_rate: f64,
+
+    /// Node tags (ordered by node id).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) tags: Vec<(NodeId, String)>,
+}
+impl<N> Topology<N> {
+    /// Creates topology of a graph with converted nodes.
+    pub(crate) fn from_graph<'a, M: Node>(
+        graph: &'a Graph<M>,
+        mut convert: impl FnMut(&'a M) -> Result<N, GraphError>,
+    ) -> Result<Self, GraphError> {
+        let nodes = sorted(graph.nodes.iter().map(|(&id, node)| (id, node)));
+        Ok(Topology {
+            block_size: graph.block_size,
+            connections: graph.connections.clone(),
+            event_connections: graph.event_connections.clone(),
+            labels: sorted(graph.labels.iter().map(|(&id, label)| (id, label.clone()))),
+            meta: sorted(graph.meta.iter().map(|(&id, meta)| (id, meta)))
+                .into_iter()
+                .flat_map(|(id, meta)| meta.iter().map(move |(key, value)| (id, key.clone(), value.clone())))
+                .collect(),
+            nodes: nodes.into_iter().map(|(id, node)| Ok((id, convert(node)?))).collect::<Result<_, GraphError>>()?,
+            priorities: sorted(graph.priorities.iter().map(|(&id, &priority)| (id, priority))),
+            rate_divisors: sorted(graph.rate_divisors.iter().map(|(&id, &divisor)| (id, divisor))),
+            sample_rate: graph.context.sample_rate,
+            tags: sorted(graph.tags.iter().flat_map(|(&id, tags)| tags.iter().map(move |tag| (id, tag.clone())))),
+        })
+    }
+
+    /// Converts nodes of topology.
+    pub(crate) fn map_nodes<M>(
+        self,
+        mut convert: impl FnMut(N) -> Result<M, GraphError>,
+    ) -> Result<Topology<M>, GraphError> {
+        Ok(Topology {
+            block_size: self.block_size,
+            connections: self.connections,
+            event_connections: self.event_connections,
+            labels: self.labels,
+            meta: self.meta,
+            nodes: self
+                .nodes
+                .into_iter()
+                .map(|(id, node)| Ok((id, convert(node)?)))
+                .collect::<Result<_, GraphError>>()?,
+            priorities: self.priorities,
+            rate_divisors: self.rate_divisors,
+            sample_rate: self.sample_rate,
+            tags: self.tags,
+        })
+    }
+}
+impl<N: Node> Topology<N> {
+    /// Builds graph from topology (validating connections and node properties).
+    pub(crate) fn build(self) -> Result<Graph<N>, GraphError> {
+        let mut graph = Graph::new();
+        for (id, node) in self.nodes {
+            if graph.nodes.insert(id, node).is_some() {
+                return Err(GraphError::NodeAlreadyExists(id));
+            }
+            // The highest id is reserved, so later added nodes get an id.
+            let next_id =
+                id.0.checked_add(1)
+                    .ok_or_else(|| GraphError::InvalidFormat(format!("Node id {} is out of range.", id.0)))?;
+            graph.next_node_id.0 = graph.next_node_id.0.max(next_id);
+        }
+        let node_count = graph.nodes.len();
+        graph.skipped_nodes.reserve(node_count);
+        graph.statuses.reserve(node_count);
+        graph.reserve_events();
+        graph.update_processing_order()?;
+        graph.add_connections(self.connections)?;
+        for connection in self.event_connections {
+            graph.add_event_connection(connection)?;
+        }
+        for (id, label) in self.labels {
+            graph.set_label(id, Some(&label))?;
+        }
+        for (id, priority) in self.priorities {
+            graph.set_priority(id, priority)?;
+        }
+        for (id, divisor) in self.rate_divisors {
+            graph.set_rate_divisor(id, divisor)?;
+        }
+        for (id, key, value) in self.meta {
+            graph.set_meta(id, &key, &value)?;
+        }
+        for (id, tag) in self.tags {
+            graph.add_tag(id, &tag)?;
+        }
+        graph.set_block_size(self.block_size);
+        graph.set_sample_rate(self.sample_rate);
+        Ok(graph)
+    }
+}
+
+/// Returns entries of a map ordered by node id.
+fn sorted<V>(entries: impl Iterator<Item = (NodeId, V)>) -> Vec<(NodeId, V)> {
+    let mut entries: Vec<(NodeId, V)> = entries.collect();
+    entries.sort_by_key(|&(id, _)| id);
+    entries
+}
+
+impl<N: Node + Serialize> Serialize for Graph<N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Topology::from_graph(self, Ok)
+            .map_err(|error| ser::Error::custom(format!("{:?}", error)))?
+            .serialize(serializer)
+    }
+}
+
+impl<'de, N: Node + Deserialize<'de>> Deserialize<'de> for Graph<N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Topology::deserialize(deserializer)?.build().map_err(|error| de::Error::custom(format!("{:?}", error)))
+    }
+}
+
+/// Unit tests.
+#[cfg(all(test, feature = "dsp"))]
+mod tests {
+    use super::*;
+    use crate::{nodes, InputId, OutputId};
+
+    #[test]
+    fn serialize() {
+        let mut graph: Graph<nodes::Delay> = Graph::new();
+        let del0 = graph.add_node(nodes::Delay::new());
+        let del1 = graph.add_node(nodes::Delay::new());
+        let del2 = graph.add_node(nodes::Delay::new());
+        graph.remove_node(del0).unwrap();
+        graph.add_connection(Connection::new(del1, OutputId(0), del2, InputId(0))).unwrap();
+        graph.add_connection(Connection::new(del2, OutputId(0), del1, InputId(0))).unwrap();
+        graph.set_label(del2, Some("out")).unwrap();
+        graph.set_priority(del1, 2).unwrap();
+        graph.set_rate_divisor(del2, 4).unwrap();
+        graph.set_sample_rate(48000.0);
+
+        let json = serde_json::to_string(&graph).unwrap();
+        assert_eq!(
+            json,
+            concat!(
+                r#"{"block_size":64,"connections":["#,
+                r#"{"source_node":1,"source_output":0,"target_input":0,"target_node":2},"#,
+                r#"{"source_node":2,"source_output":0,"target_input":0,"target_node":1}],"event_connections":[],"#,
+                r#""labels":[[2,"out"]],"#,
+                r#""nodes":[[1,{"value":[0.0,0.0]}],[2,{"value":[0.0,0.0]}]],"#,
+                r#""priorities":[[1,2]],"rate_divisors":[[2,4]],"sample_rate":48000.0}"#
+            )
+        );
+
+        let mut restored: Graph<nodes::Delay> = serde_json::from_str(&json).unwrap();
+        assert_eq!(serde_json::to_string(&restored).unwrap(), json);
+        assert_eq!(restored.find_node("out"), Ok(del2));
+        assert_eq!(restored.add_node(nodes::Delay::new()), NodeId(3));
+    }
+
+    #[test]
+    fn rejects_invalid_topology() {
+        let error = |json: &str| serde_json::from_str::<Graph<nodes::Delay>>(json).err().unwrap().to_string();
+        let json = concat!(
+            r#"{"block_size":64,"connections":[],"labels":[],"#,
+            r#""nodes":[[0,{"value":[0.0,0.0]}],[0,{"value":[0.0,0.0]}]],"#,
+            r#""priorities":[],"rate_divisors":[],"sample_rate":1.0}"#
+        );
+        assert_eq!(error(json), "Node with id 0 already exists in graph.");
+        let json = concat!(
+            r#"{"block_size":64,"connections":[],"labels":[],"nodes":[],"#,
+            r#""priorities":[],"rate_divisors":[[1,2]],"sample_rate":1.0}"#
+        );
+        assert_eq!(error(json), "Node with id 1 does not exist in graph.");
+    }
+}
@@ -0,0 +1,117 @@
+use super::Graph;
+use crate::Node;
+use std::time::Duration;
+
+/// Number of buckets of the cycle duration histogram (see `GraphStats::histogram`).
+const BUCKETS: usize = 20;
+
+/// Runtime statistics of graph processing (see `Graph::stats`).
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct GraphStats {
+    /// Number of processed cycles (a processed block counts as one cycle).
+    pub cycles: u64,
+
+    /// Histogram of cycle durations: bucket `i` counts cycles shorter than `2^i` microseconds that did not fit into
+    /// previous buckets, the last bucket counts all longer cycles (see `bucket_limit`).
+    pub histogram: [u64; BUCKETS],
+
+    /// Duration of the last cycle.
+    pub last: Duration,
+
+    /// Duration of the longest cycle.
+    pub max: Duration,
+
+    /// Cumulative wall-clock time spent processing.
+    pub total: Duration,
+}
+impl GraphStats {
+    /// Returns average duration of a cycle.
+    pub fn average(&self) -> Duration {
+        match self.cycles {
+            0 => Duration::ZERO,
+            cycles => self.total.div_f64(cycles as f64),
+        }
+    }
+
+    /// Returns exclusive upper limit of the durations counted by a histogram bucket (`Duration::MAX` for the last).
+    pub fn bucket_limit(index: usize) -> Duration {
+        match index + 1 < BUCKETS {
+            true => Duration::from_micros(1 << index),
+            false => Duration::MAX,
+        }
+    }
+
+    /// Returns number of cycles that took at least a duration, e.g. to detect missed real-time deadlines (exact for
+    /// powers of two microseconds, cycles of the bucket containing the duration are counted otherwise).
+    pub fn cycles_over(&self, duration: Duration) -> u64 {
+        let first = (0..BUCKETS).find(|&index| duration < Self::bucket_limit(index)).unwrap_or(BUCKETS - 1);
+        self.histogram[first..].iter().sum()
+    }
+
+    /// Records a processed cycle.
+    fn record(&mut self, duration: Duration) {
+        let index = (0..BUCKETS).find(|&index| duration < Self::bucket_limit(index)).unwrap_or(BUCKETS - 1);
+        self.cycles += 1;
+        self.histogram[index] += 1;
+        self.last = duration;
+        self.max = self.max.max(duration);
+        self.total += duration;
+    }
+}
+
+impl<N: Node> Graph<N> {
+    /// Resets runtime statistics.
+    pub fn reset_stats(&mut self) {
+        self.stats = GraphStats::default();
+    }
+
+    /// Returns runtime statistics (cycles, processing time and cycle duration histogram) since creation or the last
+    /// `reset_stats`.
+    pub fn stats(&self) -> GraphStats {
+        self.stats
+    }
+
+    /// Records duration of the current cycle or block.
+    pub(super) fn record_cycle(&mut self) {
+        self.stats.record(self.cycle_start.elapsed());
+    }
+}
+
+/// Unit tests.
+#[cfg(all(test, feature = "generators"))]
+mod tests {
+    use super::*;
+    use crate::nodes;
+
+    #[test]
+    fn records_cycles() {
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        graph.add_node(Box::new(nodes::Variable::new(1.0)));
+        graph.process_n(3);
+        graph.process_block();
+        let stats = graph.stats();
+        assert_eq!(stats.cycles, 4);
+        assert_eq!(stats.histogram.iter().sum::<u64>(), 4);
+        assert!(stats.max >= stats.last && stats.total >= stats.max);
+        assert_eq!(stats.cycles_over(Duration::ZERO), 4);
+        assert_eq!(stats.cycles_over(Duration::from_secs(3600)), 0);
+
+        graph.reset_stats();
+        assert_eq!(graph.stats(), GraphStats::default());
+        assert_eq!(graph.stats().average(), Duration::ZERO);
+    }
+
+    #[test]
+    fn buckets() {
+        let mut stats = GraphStats::default();
+        stats.record(Duration::from_micros(3));
+        stats.record(Duration::from_millis(2));
+        stats.record(Duration::from_secs(10));
+        assert_eq!(stats.histogram[2], 1);
+        assert_eq!(stats.histogram[11], 1);
+        assert_eq!(stats.histogram[BUCKETS - 1], 1);
+        assert_eq!(stats.cycles_over(Duration::from_millis(1)), 2);
+        assert_eq!(GraphStats::bucket_limit(2), Duration::from_micros(4));
+        assert_eq!(stats.average(), Duration::from_micros(10_002_000 + 3).div_f64(3.0));
+    }
+}
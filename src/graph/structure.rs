@@ -0,0 +1,295 @@
+use super::Graph;
+use crate::{Connection, Node, NodeId, Value};
+use std::{
+    collections::{HashMap, HashSet},
+    hash::{Hash, Hasher},
+};
+
+/// Connection endpoints and ports of a node (with whether it is an event connection), indexed by node id.
+type Adjacency = HashMap<NodeId, Vec<(Connection, bool)>>;
+
+impl<N: Node> Graph<N> {
+    /// Returns whether two graphs have the same structure regardless of node ids, i.e. there is a one-to-one mapping
+    /// of their nodes with equal types and parameters that maps the connections and event connections of one graph
+    /// onto the other. Labels, metadata and input values are ignored.
+    pub fn structurally_equal<M: Node>(&self, other: &Graph<M>) -> bool {
+        if self.nodes.len() != other.nodes.len()
+            || self.connections.len() != other.connections.len()
+            || self.event_connections.len() != other.event_connections.len()
+        {
+            return false;
+        }
+        let (colors, other_colors) = (self.structure_colors(), other.structure_colors());
+        let mut classes: HashMap<u64, Vec<NodeId>> = HashMap::new();
+        for (&id, &color) in other_colors.iter() {
+            classes.entry(color).or_default().push(id);
+        }
+        let mut counts: HashMap<u64, usize> = HashMap::new();
+        for &color in colors.values() {
+            *counts.entry(color).or_default() += 1;
+        }
+        if counts.len() != classes.len() || counts.iter().any(|(c, &n)| classes.get(c).map(Vec::len) != Some(n)) {
+            return false;
+        }
+
+        // Match nodes of small classes first, backtracking on connections that cannot be mapped.
+        let mut order: Vec<NodeId> = colors.keys().copied().collect();
+        order.sort_unstable_by_key(|id| (counts[&colors[id]], colors[id], *id));
+        let edges: HashSet<(Connection, bool)> = other.adjacency().into_values().flatten().collect();
+        let mut matching = Matching {
+            adjacency: self.adjacency(),
+            classes,
+            colors,
+            edges,
+            mapping: HashMap::new(),
+            used: HashSet::new(),
+        };
+        matching.extend(&order)
+    }
+
+    /// Returns a content hash over the topology and node configuration (types and parameters) that does not depend on
+    /// node ids, i.e. structurally equal graphs (see `structurally_equal`) have equal hashes. The hash is stable across
+    /// runs and platforms, but may change between versions of this crate.
+    pub fn structure_hash(&self) -> u64 {
+        let mut colors: Vec<u64> = self.structure_colors().into_values().collect();
+        colors.sort_unstable();
+        let mut hasher = StableHasher::new();
+        (colors, self.connections.len(), self.event_connections.len()).hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Returns connections and event connections of all nodes.
+    fn adjacency(&self) -> Adjacency {
+        let mut adjacency: Adjacency = self.nodes.keys().map(|&id| (id, Vec::new())).collect();
+        let connections = self.connections.iter().map(|&c| (c, false));
+        for (connection, event) in connections.chain(self.event_connections.iter().map(|&c| (c, true))) {
+            adjacency.get_mut(&connection.source_node).unwrap().push((connection, event));
+            if connection.target_node != connection.source_node {
+                adjacency.get_mut(&connection.target_node).unwrap().push((connection, event));
+            }
+        }
+        adjacency
+    }
+
+    /// Returns colors of nodes derived from their type and parameters, refined by the colors and ports of their
+    /// neighbors until the partition into colors is stable (nodes that can be mapped onto each other have equal
+    /// colors).
+    fn structure_colors(&self) -> HashMap<NodeId, u64> {
+        let mut colors: HashMap<NodeId, u64> = self
+            .nodes
+            .iter()
+            .map(|(&id, node)| {
+                let mut hasher = StableHasher::new();
+                node.type_name().hash(&mut hasher);
+                for &name in node.parameters() {
+                    (name, node.get_parameter(name).map(Value::to_bits)).hash(&mut hasher);
+                }
+                (id, hasher.finish())
+            })
+            .collect();
+        let adjacency = self.adjacency();
+        let mut classes = count_classes(&colors);
+        loop {
+            let refined: HashMap<NodeId, u64> = colors
+                .iter()
+                .map(|(&id, &color)| {
+                    let mut neighbors: Vec<_> = adjacency[&id]
+                        .iter()
+                        .map(|&(c, event)| {
+                            let direction = (c.source_node == id, c.target_node == id);
+                            (
+                                direction,
+                                event,
+                                c.source_output,
+                                c.target_input,
+                                colors[&c.source_node],
+                                colors[&c.target_node],
+                            )
+                        })
+                        .collect();
+                    neighbors.sort_unstable();
+                    let mut hasher = StableHasher::new();
+                    (color, neighbors).hash(&mut hasher);
+                    (id, hasher.finish())
+                })
+                .collect();
+            colors = refined;
+            let refined_classes = count_classes(&colors);
+            if refined_classes == classes {
+                return colors;
+            }
+            classes = refined_classes;
+        }
+    }
+}
+
+/// State of the search for a structure preserving mapping between two graphs.
+struct Matching {
+    /// Connections of the nodes of the first graph.
+    adjacency: Adjacency,
+
+    /// Nodes of the second graph, indexed by color.
+    classes: HashMap<u64, Vec<NodeId>>,
+
+    /// Colors of the nodes of the first graph.
+    colors: HashMap<NodeId, u64>,
+
+    /// Connections of the second graph.
+    edges: HashSet<(Connection, bool)>,
+
+    /// Mapping from nodes of the first graph to nodes of the second graph.
+    mapping: HashMap<NodeId, NodeId>,
+
+    /// Mapped nodes of the second graph.
+    used: HashSet<NodeId>,
+}
+impl Matching {
+    /// Extends the mapping to given remaining nodes, returns whether it succeeded.
+    fn extend(&mut self, remaining: &[NodeId]) -> bool {
+        let Some((&node, remaining)) = remaining.split_first() else {
+            return true;
+        };
+        for index in 0..self.classes[&self.colors[&node]].len() {
+            let candidate = self.classes[&self.colors[&node]][index];
+            if self.used.contains(&candidate) {
+                continue;
+            }
+            self.mapping.insert(node, candidate);
+            if self.is_consistent(node) {
+                self.used.insert(candidate);
+                if self.extend(remaining) {
+                    return true;
+                }
+                self.used.remove(&candidate);
+            }
+            self.mapping.remove(&node);
+        }
+        false
+    }
+
+    /// Returns whether all connections of a node to mapped nodes exist in the second graph.
+    fn is_consistent(&self, node: NodeId) -> bool {
+        self.adjacency[&node].iter().all(|&(c, event)| {
+            match (self.mapping.get(&c.source_node), self.mapping.get(&c.target_node)) {
+                (Some(&source), Some(&target)) => {
+                    self.edges.contains(&(Connection::new(source, c.source_output, target, c.target_input), event))
+                }
+                _ => true,
+            }
+        })
+    }
+}
+
+/// FNV-1a hasher with platform independent integer encoding.
+struct StableHasher(u64);
+impl StableHasher {
+    /// Creates new hasher.
+    fn new() -> Self {
+        StableHasher(0xcbf2_9ce4_8422_2325)
+    }
+}
+impl Hasher for StableHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = (self.0 ^ byte as u64).wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn write_u16(&mut self, value: u16) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn write_u32(&mut self, value: u32) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn write_u64(&mut self, value: u64) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn write_usize(&mut self, value: usize) {
+        self.write_u64(value as u64);
+    }
+}
+
+/// Returns number of distinct colors.
+fn count_classes(colors: &HashMap<NodeId, u64>) -> usize {
+    colors.values().collect::<HashSet<_>>().len()
+}
+
+/// Unit tests.
+#[cfg(all(test, feature = "dsp", feature = "generators", feature = "math"))]
+mod tests {
+    use super::*;
+    use crate::{nodes, InputId, OutputId};
+
+    /// Builds graph of two variables added and delayed in a feedback loop, adding nodes in given order.
+    fn feedback(reversed: bool) -> (Graph<Box<dyn Node>>, Vec<NodeId>) {
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        let mut ids = Vec::new();
+        let mut constructors: Vec<fn() -> Box<dyn Node>> = vec![
+            || Box::new(nodes::Variable::new(1.0)),
+            || Box::new(nodes::Variable::new(1.0)),
+            || Box::new(nodes::SumN::new(3)),
+            || Box::new(nodes::Delay::new()),
+        ];
+        if reversed {
+            constructors.reverse();
+        }
+        for constructor in constructors {
+            ids.push(graph.add_node(constructor()));
+        }
+        if reversed {
+            ids.reverse();
+        }
+        let [var0, var1, sum2, del3] = ids[..] else { unreachable!() };
+        graph.add_connection(Connection::new(var0, OutputId(0), sum2, InputId(0))).unwrap();
+        graph.add_connection(Connection::new(var1, OutputId(0), sum2, InputId(1))).unwrap();
+        graph.add_connection(Connection::new(sum2, OutputId(0), del3, InputId(0))).unwrap();
+        graph.add_connection(Connection::new(del3, OutputId(0), sum2, InputId(2))).unwrap();
+        (graph, ids)
+    }
+
+    #[test]
+    fn structurally_equal() {
+        let (graph, ids) = feedback(false);
+        let (mut other, other_ids) = feedback(true);
+        assert!(graph.structurally_equal(&graph));
+        assert!(graph.structurally_equal(&other));
+
+        // Connected ports matter.
+        let connection = Connection::new(other_ids[3], OutputId(0), other_ids[2], InputId(2));
+        other.remove_connection(connection).unwrap();
+        assert!(!graph.structurally_equal(&other));
+        other.add_connection(Connection::new(other_ids[0], OutputId(0), other_ids[2], InputId(2))).unwrap();
+        assert!(!graph.structurally_equal(&other));
+
+        // Node types matter.
+        let (mut other, _) = feedback(true);
+        let mut graph = graph;
+        graph.remove_node(ids[0]).unwrap();
+        other.remove_node(other_ids[0]).unwrap();
+        assert!(graph.structurally_equal(&other));
+        graph.add_node(Box::new(nodes::Addition::new()));
+        other.add_node(Box::new(nodes::SumN::new(2)));
+        assert!(!graph.structurally_equal(&other));
+    }
+
+    #[test]
+    fn structure_hash() {
+        let (graph, _) = feedback(false);
+        let (mut other, other_ids) = feedback(true);
+        assert_eq!(graph.structure_hash(), other.structure_hash());
+        assert_eq!(Graph::<Box<dyn Node>>::new().structure_hash(), 0x81d2_3fd7_003c_2305);
+
+        let connection = Connection::new(other_ids[3], OutputId(0), other_ids[2], InputId(2));
+        other.remove_connection(connection).unwrap();
+        assert_ne!(graph.structure_hash(), other.structure_hash());
+        other.add_connection(connection).unwrap();
+        assert_eq!(graph.structure_hash(), other.structure_hash());
+    }
+}
@@ -0,0 +1,67 @@
+use super::Graph;
+use crate::{GraphError, Node, NodeId, OutputId, Value};
+use std::sync::mpsc::{self, Receiver};
+
+/// Policy for values of a subscription whose channel is full (see `Graph::subscribe`).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Overflow {
+    /// Processing waits until the receiver takes a value (not real-time safe).
+    Block,
+
+    /// New values are dropped until the receiver takes a value.
+    DropNewest,
+}
+
+impl<N: Node> Graph<N> {
+    /// Subscribes to an output (including status outputs), i.e. sends its frame number and value after each cycle
+    /// (after each frame in block processing) through a bounded channel of given capacity, e.g. to observe a running
+    /// graph from a UI thread. Values are sent from the processing thread (see `add_probe`) until the node is removed.
+    pub fn subscribe(
+        &mut self,
+        node: NodeId,
+        output: OutputId,
+        capacity: usize,
+        overflow: Overflow,
+    ) -> Result<Receiver<(u64, Value)>, GraphError> {
+        let (sender, receiver) = mpsc::sync_channel(capacity);
+        self.add_probe(node, output, move |value, frame| {
+            let _ = match overflow {
+                // Waiting may allocate, which is fine as blocking is not real-time safe anyway.
+                #[cfg(any(test, feature = "rt-check"))]
+                Overflow::Block => crate::rt_check::permit(|| sender.send((frame, value))).map_err(|_| ()),
+                #[cfg(not(any(test, feature = "rt-check")))]
+                Overflow::Block => sender.send((frame, value)).map_err(|_| ()),
+                Overflow::DropNewest => sender.try_send((frame, value)).map_err(|_| ()),
+            };
+        })?;
+        Ok(receiver)
+    }
+}
+
+/// Unit tests.
+#[cfg(all(test, feature = "generators"))]
+mod tests {
+    use super::*;
+    use crate::{nodes, InputId};
+    use std::thread;
+
+    #[test]
+    fn subscribe() {
+        let mut graph: Graph<Box<dyn Node + Send>> = Graph::new();
+        let var0 = graph.add_node(Box::new(nodes::Variable::new(1.0)));
+        let dropping = graph.subscribe(var0, OutputId(0), 2, Overflow::DropNewest).unwrap();
+        let blocking = graph.subscribe(var0, OutputId(0), 1, Overflow::Block).unwrap();
+        assert_eq!(
+            graph.subscribe(var0, OutputId(1), 1, Overflow::Block).err(),
+            Some(GraphError::OutputNotExists(var0, OutputId(1)))
+        );
+
+        // Processing waits for the receiving thread while the blocking channel is full.
+        let receiver = thread::spawn(move || blocking.iter().take(3).collect::<Vec<_>>());
+        graph.process();
+        graph.set_input(var0, InputId(0), 2.0).unwrap();
+        graph.process_n(2);
+        assert_eq!(receiver.join().unwrap(), [(0, 1.0), (1, 2.0), (2, 2.0)]);
+        assert_eq!(dropping.try_iter().collect::<Vec<_>>(), [(0, 1.0), (1, 2.0)]);
+    }
+}
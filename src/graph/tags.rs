@@ -0,0 +1,99 @@
+use super::Graph;
+use crate::{GraphError, Node, NodeId};
+
+impl<N: Node> Graph<N> {
+    /// Attaches a tag to a node (e.g. to group the nodes of a voice), tags can be attached to multiple nodes.
+    pub fn add_tag(&mut self, node: NodeId, tag: &str) -> Result<(), GraphError> {
+        self.get_node(node)?;
+        let tags = self.tags.entry(node).or_default();
+        if !tags.iter().any(|t| t == tag) {
+            tags.push(tag.to_string());
+        }
+        Ok(())
+    }
+
+    /// Returns nodes with a tag (ordered by id).
+    pub fn nodes_with_tag(&self, tag: &str) -> Vec<NodeId> {
+        let mut nodes: Vec<NodeId> =
+            self.tags.iter().filter(|(_, tags)| tags.iter().any(|t| t == tag)).map(|(&id, _)| id).collect();
+        nodes.sort_unstable();
+        nodes
+    }
+
+    /// Removes nodes with a tag (and their connections) and returns them (ordered by id).
+    pub fn remove_nodes_with_tag(&mut self, tag: &str) -> Vec<(NodeId, N)> {
+        let ids = self.nodes_with_tag(tag);
+        let nodes = self.remove_nodes(&ids).unwrap();
+        ids.into_iter().zip(nodes).collect()
+    }
+
+    /// Detaches a tag from a node and returns whether it was attached.
+    pub fn remove_tag(&mut self, node: NodeId, tag: &str) -> Result<bool, GraphError> {
+        self.get_node(node)?;
+        let Some(tags) = self.tags.get_mut(&node) else {
+            return Ok(false);
+        };
+        let count = tags.len();
+        tags.retain(|t| t != tag);
+        let removed = tags.len() != count;
+        if tags.is_empty() {
+            self.tags.remove(&node);
+        }
+        Ok(removed)
+    }
+
+    /// Sets whether nodes with a tag are bypassed (see `set_bypassed`).
+    pub fn set_bypassed_with_tag(&mut self, tag: &str, bypassed: bool) {
+        for id in self.nodes_with_tag(tag) {
+            self.set_bypassed(id, bypassed).unwrap();
+        }
+    }
+
+    /// Returns tags of a node (in the order they were attached).
+    pub fn tags(&self, node: NodeId) -> Result<&[String], GraphError> {
+        self.get_node(node)?;
+        Ok(self.tags.get(&node).map(Vec::as_slice).unwrap_or(&[]))
+    }
+}
+
+/// Unit tests.
+#[cfg(all(test, feature = "generators", feature = "math"))]
+mod tests {
+    use super::*;
+    use crate::{nodes, Connection, InputId, OutputId};
+
+    #[test]
+    fn group_operations() {
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        let var0 = graph.add_node(Box::new(nodes::Variable::new(1.0)));
+        let add1 = graph.add_node(Box::new(nodes::Addition::new()));
+        let add2 = graph.add_node(Box::new(nodes::Addition::new()));
+        graph.add_connection(Connection::new(var0, OutputId(0), add1, InputId(1))).unwrap();
+        graph.add_connection(Connection::new(add1, OutputId(0), add2, InputId(0))).unwrap();
+        graph.add_tag(add1, "voice1").unwrap();
+        graph.add_tag(add1, "voice1").unwrap();
+        graph.add_tag(add1, "effects").unwrap();
+        graph.add_tag(add2, "effects").unwrap();
+        assert_eq!(graph.tags(add1), Ok(&["voice1".to_string(), "effects".to_string()][..]));
+        assert_eq!(graph.nodes_with_tag("effects"), vec![add1, add2]);
+        assert_eq!(graph.nodes_with_tag("voice2"), vec![]);
+
+        // Bypassing a group.
+        graph.set_input(add1, InputId(0), 2.0).unwrap();
+        graph.set_bypassed_with_tag("effects", true);
+        graph.process();
+        assert_eq!(graph.get_output(add2, OutputId(0)), Ok(2.0));
+        graph.set_bypassed_with_tag("effects", false);
+        graph.process();
+        assert_eq!(graph.get_output(add2, OutputId(0)), Ok(3.0));
+
+        // Removing tags and groups.
+        assert_eq!(graph.remove_tag(add1, "effects"), Ok(true));
+        assert_eq!(graph.remove_tag(add1, "effects"), Ok(false));
+        assert_eq!(graph.nodes_with_tag("effects"), vec![add2]);
+        let removed = graph.remove_nodes_with_tag("voice1");
+        assert_eq!(removed.iter().map(|(id, _)| *id).collect::<Vec<_>>(), vec![add1]);
+        assert!(graph.connections().is_empty());
+        assert_eq!(graph.tags(add1), Err(GraphError::NodeNotExists(add1)));
+    }
+}
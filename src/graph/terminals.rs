@@ -0,0 +1,134 @@
+use super::Graph;
+use crate::{GraphError, InputId, Node, NodeId, OutputId, Value};
+
+impl<N: Node> Graph<N> {
+    /// Copies an external buffer to the output buffer of a graph input node in block processing (instead of
+    /// processing the node, which holds the last value).
+    pub(super) fn bind_external_input(&mut self, node: NodeId, values: &[Value]) {
+        self.output_buffers.get_mut(&(node, OutputId(0))).unwrap().copy_from_slice(values);
+        if let Some(&last) = values.last() {
+            self.nodes.get_mut(&node).unwrap().set_input(InputId(0), last);
+        }
+    }
+
+    /// Returns value of a graph output by index (see `nodes::GraphOutput`, the one with the smallest id if the index
+    /// is used by multiple nodes).
+    pub fn external_output(&self, index: usize) -> Result<Value, GraphError> {
+        let node = self.external_output_node(index)?;
+        Ok(self.nodes[&node].get_output(OutputId(0)))
+    }
+
+    /// Returns graph output node with an index (the one with the smallest id if the index is used by multiple nodes).
+    fn external_output_node(&self, index: usize) -> Result<NodeId, GraphError> {
+        self.nodes
+            .iter()
+            .filter(|(_, node)| node.graph_output() == Some(index))
+            .map(|(&id, _)| id)
+            .min()
+            .ok_or(GraphError::ExternalOutputNotExists(index))
+    }
+
+    /// Processes one block of frames (see `process_block`) reading graph inputs from and writing graph outputs to
+    /// host-owned buffers by index (see `nodes::GraphInput` and `nodes::GraphOutput`), e.g. audio buffers of a plugin
+    /// host. Buffers must have the length of the block size. Nothing is processed if a buffer has a different length
+    /// or its index is not used by a node.
+    pub fn process_block_external(
+        &mut self,
+        inputs: &[&[Value]],
+        outputs: &mut [&mut [Value]],
+    ) -> Result<(), GraphError> {
+        for (index, buffer) in inputs.iter().enumerate() {
+            if !self.nodes.values().any(|node| node.graph_input() == Some(index)) {
+                return Err(GraphError::ExternalInputNotExists(index));
+            }
+            if buffer.len() != self.block_size {
+                return Err(GraphError::BufferSizeMismatch(buffer.len()));
+            }
+        }
+        for (index, buffer) in outputs.iter().enumerate() {
+            self.external_output_node(index)?;
+            if buffer.len() != self.block_size {
+                return Err(GraphError::BufferSizeMismatch(buffer.len()));
+            }
+        }
+        self.run_block(inputs);
+        for (index, buffer) in outputs.iter_mut().enumerate() {
+            if let Ok(node) = self.external_output_node(index) {
+                buffer.copy_from_slice(&self.output_buffers[&(node, OutputId(0))]);
+            }
+        }
+        Ok(())
+    }
+
+    /// Sets value of a graph input by index (see `nodes::GraphInput`, all nodes with the index receive the value).
+    pub fn set_external_input(&mut self, index: usize, value: Value) -> Result<(), GraphError> {
+        let ids: Vec<NodeId> =
+            self.nodes.iter().filter(|(_, node)| node.graph_input() == Some(index)).map(|(&id, _)| id).collect();
+        if ids.is_empty() {
+            return Err(GraphError::ExternalInputNotExists(index));
+        }
+        for id in ids {
+            self.set_input(id, InputId(0), value)?;
+        }
+        Ok(())
+    }
+}
+
+/// Unit tests.
+#[cfg(all(test, feature = "io", feature = "math"))]
+mod tests {
+    use super::*;
+    use crate::{nodes, Connection};
+
+    #[test]
+    fn routes_external_values() {
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        let in0 = graph.add_node(Box::new(nodes::GraphInput::new(0)));
+        let in1 = graph.add_node(Box::new(nodes::GraphInput::new(1)));
+        let add2 = graph.add_node(Box::new(nodes::Addition::new()));
+        let out3 = graph.add_node(Box::new(nodes::GraphOutput::new(0)));
+        graph.add_connection(Connection::new(in0, OutputId(0), add2, InputId(0))).unwrap();
+        graph.add_connection(Connection::new(in1, OutputId(0), add2, InputId(1))).unwrap();
+        graph.add_connection(Connection::new(add2, OutputId(0), out3, InputId(0))).unwrap();
+
+        graph.set_external_input(0, 1.0).unwrap();
+        graph.set_external_input(1, 2.0).unwrap();
+        graph.process();
+        assert_eq!(graph.external_output(0), Ok(3.0));
+
+        // Unknown indices.
+        assert_eq!(graph.set_external_input(2, 0.0), Err(GraphError::ExternalInputNotExists(2)));
+        assert_eq!(graph.external_output(1), Err(GraphError::ExternalOutputNotExists(1)));
+    }
+
+    #[test]
+    fn processes_external_buffers() {
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        graph.set_block_size(4);
+        let in0 = graph.add_node(Box::new(nodes::GraphInput::new(0)));
+        let in1 = graph.add_node(Box::new(nodes::GraphInput::new(1)));
+        let add2 = graph.add_node(Box::new(nodes::Addition::new()));
+        let out3 = graph.add_node(Box::new(nodes::GraphOutput::new(0)));
+        graph.add_connection(Connection::new(in0, OutputId(0), add2, InputId(0))).unwrap();
+        graph.add_connection(Connection::new(in1, OutputId(0), add2, InputId(1))).unwrap();
+        graph.add_connection(Connection::new(add2, OutputId(0), out3, InputId(0))).unwrap();
+
+        let (left, right) = ([1.0, 2.0, 3.0, 4.0], [10.0, 20.0, 30.0, 40.0]);
+        let mut mixed = [0.0; 4];
+        graph.process_block_external(&[&left, &right], &mut [&mut mixed]).unwrap();
+        assert_eq!(mixed, [11.0, 22.0, 33.0, 44.0]);
+        assert_eq!(graph.external_output(0), Ok(44.0));
+
+        // Invalid buffers.
+        assert_eq!(graph.process_block_external(&[&left[..2]], &mut []), Err(GraphError::BufferSizeMismatch(2)));
+        assert_eq!(
+            graph.process_block_external(&[&left, &right, &left], &mut []),
+            Err(GraphError::ExternalInputNotExists(2))
+        );
+        let mut extra = [0.0; 4];
+        assert_eq!(
+            graph.process_block_external(&[], &mut [&mut mixed, &mut extra]),
+            Err(GraphError::ExternalOutputNotExists(1))
+        );
+    }
+}
@@ -0,0 +1,116 @@
+use super::{patch::Patch, Graph};
+use crate::{GraphError, Node, NodeRegistry};
+
+impl<N: Node> Graph<N> {
+    /// Loads graph from a TOML patch (see `to_toml`), constructing nodes with a registry.
+    ///
+    /// To reload a changed file while running, load it into a new graph and carry over the state of the previous one
+    /// with `migrate_state`.
+    pub fn from_toml(toml: &str, registry: &NodeRegistry<N>) -> Result<Self, GraphError> {
+        let patch: Patch = ::toml::from_str(toml).map_err(|error| GraphError::InvalidFormat(error.to_string()))?;
+        Graph::from_patch(patch, registry)
+    }
+
+    /// Saves graph as TOML patch, naming node types with a registry, e.g. for pipelines defined in config files.
+    ///
+    /// A TOML patch has the same fields as a JSON patch (see `to_json`), with nodes and connections as arrays of
+    /// tables:
+    ///
+    /// ```text
+    /// sample_rate = 1.0
+    ///
+    /// [[nodes]]
+    /// id = 0
+    /// label = "metric"
+    /// type = "variable"
+    ///
+    /// [[nodes]]
+    /// id = 1
+    /// tags = ["smoothing"]
+    /// type = "delay"
+    ///
+    /// [[connections]]
+    /// source_node = 0
+    /// source_output = 0
+    /// target_input = 0
+    /// target_node = 1
+    /// ```
+    pub fn to_toml(&self, registry: &NodeRegistry<N>) -> Result<String, GraphError> {
+        let patch = self.to_patch(registry)?;
+        ::toml::to_string(&patch).map_err(|error| GraphError::InvalidFormat(error.to_string()))
+    }
+}
+
+/// Unit tests.
+#[cfg(all(test, feature = "dsp", feature = "generators"))]
+mod tests {
+    use super::*;
+    use crate::{nodes, Connection, InputId, NodeId, OutputId};
+
+    #[test]
+    fn from_toml() {
+        let toml = r#"
+            [[nodes]]
+            id = 0
+            label = "metric"
+            params = [2.0]
+            type = "variable"
+
+            [[nodes]]
+            id = 1
+            type = "addition"
+
+            [[nodes]]
+            id = 2
+            meta = { owner = "ops" }
+            type = "delay"
+
+            [[connections]]
+            source_node = 0
+            source_output = 0
+            target_input = 0
+            target_node = 1
+
+            [[connections]]
+            source_node = 1
+            source_output = 0
+            target_input = 0
+            target_node = 2
+
+            [[connections]]
+            source_node = 2
+            source_output = 0
+            target_input = 1
+            target_node = 1
+        "#;
+        let mut graph = Graph::from_toml(toml, &NodeRegistry::builtin()).unwrap();
+        assert_eq!(graph.find_node("metric"), Ok(NodeId(0)));
+        assert_eq!(graph.get_meta(NodeId(2), "owner"), Ok(Some("ops")));
+        graph.process_n(3);
+        assert_eq!(graph.get_output(NodeId(1), OutputId(0)), Ok(6.0));
+
+        // Invalid patches.
+        assert!(matches!(Graph::from_toml("[[nodes]", &NodeRegistry::builtin()), Err(GraphError::InvalidFormat(_))));
+        assert_eq!(
+            Graph::from_toml("[[nodes]]\nid = 0\ntype = \"multiplication\"", &NodeRegistry::builtin()).err(),
+            Some(GraphError::NodeTypeNotExists("multiplication".to_string()))
+        );
+    }
+
+    #[test]
+    fn to_toml() {
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        let var0 = graph.add_node(Box::new(nodes::Variable::new(2.0)));
+        let del1 = graph.add_node(Box::new(nodes::Delay::new()));
+        graph.add_connection(Connection::new(var0, OutputId(0), del1, InputId(0))).unwrap();
+        graph.set_label(var0, Some("metric")).unwrap();
+        graph.set_priority(del1, 3).unwrap();
+        graph.process();
+
+        let toml = graph.to_toml(&NodeRegistry::builtin()).unwrap();
+        let restored = Graph::from_toml(&toml, &NodeRegistry::builtin()).unwrap();
+        assert_eq!(restored.find_node("metric"), Ok(var0));
+        assert_eq!(restored.get_priority(del1), Ok(3));
+        assert_eq!(restored.to_toml(&NodeRegistry::builtin()), Ok(toml));
+    }
+}
@@ -0,0 +1,213 @@
+use super::Graph;
+use crate::{Connection, GraphError, Node, NodeId};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Hooks of graph traversals along connections and event connections (see `Graph::visit_depth_first_from` and
+/// `Graph::visit_breadth_first_from`).
+pub trait Visitor {
+    /// Called when a node is reached for the first time.
+    fn discover_node(&mut self, _node: NodeId) {}
+
+    /// Called for each followed connection of a discovered node, before its target is visited (targets may already be
+    /// discovered).
+    fn examine_connection(&mut self, _connection: Connection) {}
+
+    /// Called when all connections of a node have been examined (after its descendants in depth-first traversals).
+    fn finish_node(&mut self, _node: NodeId) {}
+
+    /// Returns whether connections from nodes that introduce delay are followed.
+    fn follows_delayed(&self) -> bool {
+        true
+    }
+}
+
+impl<N: Node> Graph<N> {
+    /// Returns weakly connected components (nodes linked by connections or event connections in any direction), with
+    /// nodes ordered by id and components ordered by their first node.
+    pub fn components(&self) -> Vec<Vec<NodeId>> {
+        let mut neighbours: HashMap<NodeId, Vec<NodeId>> = HashMap::new();
+        for connection in self.connections.iter().chain(self.event_connections.iter()) {
+            neighbours.entry(connection.source_node).or_default().push(connection.target_node);
+            neighbours.entry(connection.target_node).or_default().push(connection.source_node);
+        }
+        let mut nodes: Vec<NodeId> = self.nodes.keys().copied().collect();
+        nodes.sort_unstable();
+        let mut discovered = HashSet::new();
+        let mut components = Vec::new();
+        for node in nodes {
+            if !discovered.insert(node) {
+                continue;
+            }
+            let mut component = vec![node];
+            let mut stack = vec![node];
+            while let Some(node) = stack.pop() {
+                for &neighbour in neighbours.get(&node).into_iter().flatten() {
+                    if discovered.insert(neighbour) {
+                        component.push(neighbour);
+                        stack.push(neighbour);
+                    }
+                }
+            }
+            component.sort_unstable();
+            components.push(component);
+        }
+        components
+    }
+
+    /// Returns nodes without outgoing connections or event connections (ordered by id), e.g. to read results.
+    pub fn sinks(&self) -> impl Iterator<Item = NodeId> {
+        self.unconnected_nodes(|connection| connection.source_node)
+    }
+
+    /// Returns nodes without incoming connections or event connections (ordered by id), e.g. to inject stimulus.
+    pub fn sources(&self) -> impl Iterator<Item = NodeId> {
+        self.unconnected_nodes(|connection| connection.target_node)
+    }
+
+    /// Visits nodes reachable from a node in breadth-first order (connections in the order they were added).
+    pub fn visit_breadth_first_from<V: Visitor>(&self, node: NodeId, visitor: &mut V) -> Result<(), GraphError> {
+        self.get_node(node)?;
+        let targets = self.traversal_targets(visitor.follows_delayed());
+        let mut discovered = HashSet::from([node]);
+        let mut queue = VecDeque::from([node]);
+        visitor.discover_node(node);
+        while let Some(node) = queue.pop_front() {
+            for &connection in targets.get(&node).into_iter().flatten() {
+                visitor.examine_connection(connection);
+                if discovered.insert(connection.target_node) {
+                    visitor.discover_node(connection.target_node);
+                    queue.push_back(connection.target_node);
+                }
+            }
+            visitor.finish_node(node);
+        }
+        Ok(())
+    }
+
+    /// Visits nodes reachable from a node in depth-first order (connections in the order they were added).
+    pub fn visit_depth_first_from<V: Visitor>(&self, node: NodeId, visitor: &mut V) -> Result<(), GraphError> {
+        self.get_node(node)?;
+        let targets = self.traversal_targets(visitor.follows_delayed());
+        let mut discovered = HashSet::from([node]);
+        let mut stack = vec![(node, 0)];
+        visitor.discover_node(node);
+        while let Some((node, index)) = stack.pop() {
+            match targets.get(&node).and_then(|connections| connections.get(index)) {
+                Some(&connection) => {
+                    stack.push((node, index + 1));
+                    visitor.examine_connection(connection);
+                    if discovered.insert(connection.target_node) {
+                        visitor.discover_node(connection.target_node);
+                        stack.push((connection.target_node, 0));
+                    }
+                }
+                None => visitor.finish_node(node),
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns nodes that are not an endpoint of any connection or event connection (ordered by id).
+    fn unconnected_nodes(&self, endpoint: fn(&Connection) -> NodeId) -> std::vec::IntoIter<NodeId> {
+        let connected: HashSet<NodeId> =
+            self.connections.iter().chain(self.event_connections.iter()).map(endpoint).collect();
+        let mut nodes: Vec<NodeId> = self.nodes.keys().filter(|node| !connected.contains(node)).copied().collect();
+        nodes.sort_unstable();
+        nodes.into_iter()
+    }
+
+    /// Returns outgoing connections and event connections of nodes.
+    fn traversal_targets(&self, follows_delayed: bool) -> HashMap<NodeId, Vec<Connection>> {
+        let mut targets: HashMap<NodeId, Vec<Connection>> = HashMap::new();
+        for &connection in self.connections.iter().chain(self.event_connections.iter()) {
+            if follows_delayed || !self.nodes[&connection.source_node].delayed_processing() {
+                targets.entry(connection.source_node).or_default().push(connection);
+            }
+        }
+        targets
+    }
+}
+
+/// Unit tests.
+#[cfg(all(test, feature = "dsp", feature = "generators", feature = "math"))]
+mod tests {
+    use super::*;
+    use crate::{nodes, InputId, OutputId};
+
+    /// Visitor recording hook calls (`d` discover, `e` examine, `f` finish).
+    #[derive(Default)]
+    struct Recorder {
+        calls: Vec<String>,
+        follows_delayed: bool,
+    }
+    impl Visitor for Recorder {
+        fn discover_node(&mut self, node: NodeId) {
+            self.calls.push(format!("d{}", node.0));
+        }
+        fn examine_connection(&mut self, connection: Connection) {
+            self.calls.push(format!("e{}-{}", connection.source_node.0, connection.target_node.0));
+        }
+        fn finish_node(&mut self, node: NodeId) {
+            self.calls.push(format!("f{}", node.0));
+        }
+        fn follows_delayed(&self) -> bool {
+            self.follows_delayed
+        }
+    }
+
+    #[test]
+    fn visit() {
+        // Variable feeding two additions, second one feeding a delay back into the first one.
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        let var0 = graph.add_node(Box::new(nodes::Variable::new(1.0)));
+        let add1 = graph.add_node(Box::new(nodes::Addition::new()));
+        let add2 = graph.add_node(Box::new(nodes::Addition::new()));
+        let del3 = graph.add_node(Box::new(nodes::Delay::new()));
+        graph.add_connection(Connection::new(var0, OutputId(0), add1, InputId(0))).unwrap();
+        graph.add_connection(Connection::new(var0, OutputId(0), add2, InputId(0))).unwrap();
+        graph.add_connection(Connection::new(add2, OutputId(0), del3, InputId(0))).unwrap();
+        graph.add_connection(Connection::new(del3, OutputId(0), add1, InputId(1))).unwrap();
+
+        let mut recorder = Recorder { follows_delayed: true, ..Default::default() };
+        graph.visit_depth_first_from(var0, &mut recorder).unwrap();
+        assert_eq!(recorder.calls.join(" "), "d0 e0-1 d1 f1 e0-2 d2 e2-3 d3 e3-1 f3 f2 f0");
+
+        let mut recorder = Recorder::default();
+        graph.visit_breadth_first_from(var0, &mut recorder).unwrap();
+        assert_eq!(recorder.calls.join(" "), "d0 e0-1 d1 e0-2 d2 f0 f1 e2-3 d3 f2 f3");
+        assert_eq!(graph.visit_depth_first_from(NodeId(4), &mut recorder), Err(GraphError::NodeNotExists(NodeId(4))));
+    }
+
+    #[test]
+    fn components() {
+        // Accumulator with a delayed feedback, a variable feeding an addition and an isolated variable.
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        let var0 = graph.add_node(Box::new(nodes::Variable::new(1.0)));
+        let var1 = graph.add_node(Box::new(nodes::Variable::new(2.0)));
+        let add2 = graph.add_node(Box::new(nodes::Addition::new()));
+        let del3 = graph.add_node(Box::new(nodes::Delay::new()));
+        let var4 = graph.add_node(Box::new(nodes::Variable::new(3.0)));
+        let add5 = graph.add_node(Box::new(nodes::Addition::new()));
+        graph.add_connection(Connection::new(var0, OutputId(0), add2, InputId(0))).unwrap();
+        graph.add_connection(Connection::new(add2, OutputId(0), del3, InputId(0))).unwrap();
+        graph.add_connection(Connection::new(del3, OutputId(0), add2, InputId(1))).unwrap();
+        graph.add_connection(Connection::new(var1, OutputId(0), add5, InputId(1))).unwrap();
+        assert_eq!(graph.components(), [vec![var0, add2, del3], vec![var1, add5], vec![var4]]);
+    }
+
+    #[test]
+    fn sources_and_sinks() {
+        // Two variables added into a delay, plus an isolated variable.
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        let var0 = graph.add_node(Box::new(nodes::Variable::new(1.0)));
+        let var1 = graph.add_node(Box::new(nodes::Variable::new(2.0)));
+        let add2 = graph.add_node(Box::new(nodes::Addition::new()));
+        let del3 = graph.add_node(Box::new(nodes::Delay::new()));
+        let var4 = graph.add_node(Box::new(nodes::Variable::new(3.0)));
+        graph.add_connection(Connection::new(var0, OutputId(0), add2, InputId(0))).unwrap();
+        graph.add_connection(Connection::new(var1, OutputId(0), add2, InputId(1))).unwrap();
+        graph.add_connection(Connection::new(add2, OutputId(0), del3, InputId(0))).unwrap();
+        assert_eq!(graph.sources().collect::<Vec<_>>(), [var0, var1, var4]);
+        assert_eq!(graph.sinks().collect::<Vec<_>>(), [del3, var4]);
+    }
+}
@@ -0,0 +1,89 @@
+use super::{patch::Patch, Graph};
+use crate::{GraphError, Node, NodeRegistry};
+use serde::Deserialize;
+
+impl<N: Node> Graph<N> {
+    /// Loads graph from a YAML patch (see `to_yaml`), constructing nodes with a registry.
+    ///
+    /// Anchors, aliases and merge keys (`<<`) are resolved before loading, so repeated blocks can be declared once
+    /// (unknown top-level keys are ignored and may hold the anchored blocks):
+    ///
+    /// ```text
+    /// smoother: &smoother { type: delay, tags: [smoothing], meta: { stage: post } }
+    /// nodes:
+    ///   - { id: 0, type: variable }
+    ///   - { <<: *smoother, id: 1 }
+    ///   - { <<: *smoother, id: 2 }
+    /// ```
+    pub fn from_yaml(yaml: &str, registry: &NodeRegistry<N>) -> Result<Self, GraphError> {
+        let invalid = |error: serde_yaml_ng::Error| GraphError::InvalidFormat(error.to_string());
+        let mut value: serde_yaml_ng::Value = serde_yaml_ng::from_str(yaml).map_err(invalid)?;
+        value.apply_merge().map_err(invalid)?;
+        let patch = Patch::deserialize(value).map_err(invalid)?;
+        Graph::from_patch(patch, registry)
+    }
+
+    /// Saves graph as YAML patch, naming node types with a registry, e.g. for pipelines kept in YAML configs.
+    ///
+    /// A YAML patch has the same fields as a JSON patch (see `to_json`).
+    pub fn to_yaml(&self, registry: &NodeRegistry<N>) -> Result<String, GraphError> {
+        let patch = self.to_patch(registry)?;
+        serde_yaml_ng::to_string(&patch).map_err(|error| GraphError::InvalidFormat(error.to_string()))
+    }
+}
+
+/// Unit tests.
+#[cfg(all(test, feature = "dsp", feature = "generators"))]
+mod tests {
+    use super::*;
+    use crate::{nodes, Connection, InputId, NodeId, OutputId};
+
+    #[test]
+    fn from_yaml() {
+        // Variable fed through two delays sharing a template.
+        let yaml = "
+            stage: &stage { type: delay, tags: [stage], meta: { owner: ops } }
+            connections:
+              - { source_node: 0, source_output: 0, target_input: 0, target_node: 1 }
+              - { source_node: 1, source_output: 0, target_input: 0, target_node: 2 }
+            nodes:
+              - { id: 0, label: input, params: [2.0], type: variable }
+              - { <<: *stage, id: 1 }
+              - { <<: *stage, id: 2, label: output }
+        ";
+        let mut graph = Graph::from_yaml(yaml, &NodeRegistry::builtin()).unwrap();
+        assert_eq!(graph.find_node("output"), Ok(NodeId(2)));
+        assert_eq!(graph.nodes_with_tag("stage"), [NodeId(1), NodeId(2)]);
+        assert_eq!(graph.get_meta(NodeId(2), "owner"), Ok(Some("ops")));
+        graph.process_n(2);
+        assert_eq!(graph.get_output(NodeId(2), OutputId(0)), Ok(2.0));
+
+        // Invalid patches.
+        assert!(matches!(Graph::from_yaml("nodes: [", &NodeRegistry::builtin()), Err(GraphError::InvalidFormat(_))));
+        assert!(matches!(
+            Graph::from_yaml("nodes: [{ <<: *missing, id: 0 }]", &NodeRegistry::builtin()),
+            Err(GraphError::InvalidFormat(_))
+        ));
+        assert_eq!(
+            Graph::from_yaml("nodes: [{ id: 0, type: multiplication }]", &NodeRegistry::builtin()).err(),
+            Some(GraphError::NodeTypeNotExists("multiplication".to_string()))
+        );
+    }
+
+    #[test]
+    fn to_yaml() {
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        let var0 = graph.add_node(Box::new(nodes::Variable::new(2.0)));
+        let del1 = graph.add_node(Box::new(nodes::Delay::new()));
+        graph.add_connection(Connection::new(var0, OutputId(0), del1, InputId(0))).unwrap();
+        graph.set_label(var0, Some("input")).unwrap();
+        graph.add_tag(del1, "stage").unwrap();
+        graph.process();
+
+        let yaml = graph.to_yaml(&NodeRegistry::builtin()).unwrap();
+        let restored = Graph::from_yaml(&yaml, &NodeRegistry::builtin()).unwrap();
+        assert_eq!(restored.find_node("input"), Ok(var0));
+        assert_eq!(restored.nodes_with_tag("stage"), [del1]);
+        assert_eq!(restored.to_yaml(&NodeRegistry::builtin()), Ok(yaml));
+    }
+}
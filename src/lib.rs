@@ -1,16 +1,125 @@
+// Lets derived nodes refer to `::flowing` inside this crate.
+#[cfg(all(test, feature = "derive"))]
+extern crate self as flowing;
+
+#[cfg(feature = "audio")]
+pub mod audio;
 mod connection;
+mod context;
+mod executor;
+pub mod executors;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(all(test, feature = "dsp", feature = "generators", feature = "math"))]
+mod fixtures;
 mod graph;
 mod node;
 pub mod nodes;
+#[cfg(feature = "osc")]
+pub mod osc;
+mod param;
+#[cfg(feature = "pd")]
+pub mod pd;
+mod registry;
+#[cfg(feature = "remote")]
+pub mod remote;
+#[cfg(any(test, feature = "rt-check"))]
+mod rt_check;
+mod run;
+#[cfg(any(feature = "autodiff", feature = "fixed", feature = "interval", feature = "uncertainty"))]
+mod scalar;
+#[doc(hidden)]
+pub mod static_graph;
+mod template;
+#[cfg(feature = "testing")]
+pub mod testing;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
 pub use connection::Connection;
-pub use graph::{Graph, GraphError};
-pub use node::{InputId, Node, NodeId, OutputId};
+pub use context::ProcessContext;
+pub use executor::Executor;
+#[cfg(feature = "derive")]
+pub use flowing_derive::Node;
+#[cfg(feature = "gpu")]
+pub use graph::GpuBatch;
+#[cfg(feature = "jit")]
+pub use graph::JitProgram;
+#[cfg(all(feature = "generators", feature = "math"))]
+pub use graph::OptimizationReport;
+#[cfg(any(feature = "autodiff", feature = "fixed", feature = "interval", feature = "uncertainty"))]
+pub use graph::ScalarProgram;
+pub use graph::{
+    DeadlineAction, DeadlineMiss, EditQueue, Graph, GraphCommand, GraphEdit, GraphError, GraphStats, NodeProfile,
+    NonFiniteValue, Overflow, ProbeId, Program, Visitor,
+};
+pub use node::{InputId, Node, NodeDescriptor, NodeId, OutputId, PortInfo, ProcessError, Value, ValueKind};
+pub use param::ParamHandle;
+pub use registry::{NodeRegistry, SavedNode};
+#[cfg(feature = "rt-check")]
+pub use rt_check::RtCheck;
+pub use run::Run;
+#[cfg(feature = "autodiff")]
+pub use scalar::Dual;
+#[cfg(feature = "interval")]
+pub use scalar::Interval;
+#[cfg(any(feature = "autodiff", feature = "fixed", feature = "interval", feature = "uncertainty"))]
+pub use scalar::Scalar;
+#[cfg(feature = "uncertainty")]
+pub use scalar::Uncertain;
+#[cfg(feature = "fixed")]
+pub use scalar::Q16;
+pub use template::GraphTemplate;
 
-#[cfg(test)]
+#[cfg(all(test, feature = "dsp"))]
 mod tests {
     use super::*;
 
+    #[cfg(feature = "derive")]
+    #[test]
+    fn derived_node() {
+        #[derive(Node)]
+        #[node(category = "dynamics", delayed, pure)]
+        struct Gain {
+            #[input]
+            signal: Value,
+            #[output(kind = Continuous)]
+            amplified: Value,
+            #[input(default = 1.0, min = 0.0, unit = "dB")]
+            gain: Value,
+            #[parameter]
+            inverted: Value,
+        }
+        impl Gain {
+            fn process(&mut self) {
+                self.amplified = self.signal * self.gain * (1.0 - 2.0 * self.inverted);
+            }
+        }
+
+        let mut gain = Gain { signal: 0.0, amplified: 0.0, gain: 1.0, inverted: 0.0 };
+        assert_eq!(gain.category(), "dynamics");
+        assert!(gain.delayed_processing() && gain.is_pure());
+        assert_eq!(gain.list_inputs(), &[InputId(0), InputId(1)]);
+        assert_eq!(gain.list_outputs(), &[OutputId(0)]);
+        assert_eq!(gain.input_name(InputId(1)), Some("gain"));
+        assert_eq!(gain.output_name(OutputId(0)), Some("amplified"));
+        assert_eq!(
+            gain.input_info(InputId(1)),
+            PortInfo { min: 0.0, default: 1.0, unit: Some("dB"), ..PortInfo::new(Some("gain")) }
+        );
+        assert_eq!(gain.output_info(OutputId(0)), PortInfo::new(Some("amplified")));
+        gain.set_input(InputId(0), 3.0);
+        gain.set_input(InputId(1), 2.0);
+        Node::process(&mut gain);
+        assert_eq!(gain.get_output(OutputId(0)), 6.0);
+
+        assert_eq!(gain.parameters(), &["inverted"]);
+        gain.set_parameter("inverted", 1.0);
+        assert_eq!(gain.get_parameter("inverted"), Some(1.0));
+        Node::process(&mut gain);
+        assert_eq!(gain.get_output(OutputId(0)), -6.0);
+    }
+
     #[test]
     fn multi_delay_processing() {
         let mut graph: Graph<Box<dyn Node>> = Graph::new();
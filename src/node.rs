@@ -1,52 +1,535 @@
+use crate::ProcessContext;
+use std::any::Any;
+
 /// Identifier for input (unique in node).
 #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct InputId(pub u32);
 
 /// Identifier for node (unique in graph).
 #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct NodeId(pub u32);
 
 /// Identifier for output (unique in node).
 #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct OutputId(pub u32);
+impl OutputId {
+    /// Status output provided by the graph for every node (0 if last processing succeeded, error code otherwise).
+    pub const STATUS: OutputId = OutputId(u32::MAX);
+}
+
+/// Value of ports, parameters and state (`f32` with the `f32` feature, e.g. for embedded or SIMD targets, `f64`
+/// otherwise).
+#[cfg(not(feature = "f32"))]
+pub type Value = f64;
+
+/// Value of ports, parameters and state (`f32` with the `f32` feature, e.g. for embedded or SIMD targets, `f64`
+/// otherwise).
+#[cfg(feature = "f32")]
+pub type Value = f32;
+
+/// Self-description of a node (see `Node::describe`), e.g. for editors, documentation or validation tooling.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NodeDescriptor<'a> {
+    /// Category of the node type (see `Node::category`).
+    pub category: &'static str,
+
+    /// Whether the node introduces processing delay.
+    pub delayed: bool,
+
+    /// Event inputs (see `Node::list_event_inputs`).
+    pub event_inputs: Vec<InputId>,
+
+    /// Event outputs (see `Node::list_event_outputs`).
+    pub event_outputs: Vec<OutputId>,
+
+    /// Inputs with their descriptors and number of channels.
+    pub inputs: Vec<(InputId, PortInfo<'a>, usize)>,
+
+    /// Outputs with their descriptors and number of channels.
+    pub outputs: Vec<(OutputId, PortInfo<'a>, usize)>,
+
+    /// Parameters with their current values.
+    pub parameters: Vec<(&'a str, Option<Value>)>,
+
+    /// Whether outputs are a function of the current inputs only (see `Node::is_pure`).
+    pub pure: bool,
+
+    /// Whether the node has runtime state (non-empty snapshot).
+    pub stateful: bool,
+
+    /// Name of the node type.
+    pub type_name: &'static str,
+}
+
+/// Descriptor of a port (e.g. for rendering controls in generic user interfaces).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PortInfo<'a> {
+    /// Initial value of the port.
+    pub default: Value,
+
+    /// Kind of values (connections between incompatible kinds are rejected).
+    pub kind: ValueKind,
+
+    /// Maximum meaningful value (may be infinite).
+    pub max: Value,
+
+    /// Minimum meaningful value (may be infinite).
+    pub min: Value,
+
+    /// Name of the port (`None` if unnamed).
+    pub name: Option<&'a str>,
+
+    /// Unit of the values (e.g. `Hz` or `dB`).
+    pub unit: Option<&'a str>,
+}
+impl<'a> PortInfo<'a> {
+    /// Creates new unbounded continuous port descriptor with default value 0 and without unit.
+    pub fn new(name: Option<&'a str>) -> Self {
+        PortInfo {
+            default: 0.0,
+            kind: ValueKind::Continuous,
+            max: Value::INFINITY,
+            min: Value::NEG_INFINITY,
+            name,
+            unit: None,
+        }
+    }
+}
+
+/// Kind of values carried by a port.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum ValueKind {
+    /// State that is either on (1) or off (0), e.g. a gate.
+    Boolean,
+
+    /// Complex number carried as two channels (real and imaginary part), e.g. a frequency-domain bin.
+    Complex,
+
+    /// Continuous value, e.g. a frequency.
+    #[default]
+    Continuous,
+
+    /// Integral value, e.g. a note number, index or error code (nodes round values received on integer inputs).
+    Integer,
+
+    /// Event that is non-zero for a single frame.
+    Trigger,
+}
+impl ValueKind {
+    /// Returns whether an input of this kind accepts values of a source kind (integers are accepted as continuous
+    /// values, other kinds must match).
+    pub fn accepts(self, source: ValueKind) -> bool {
+        self == source || (self == ValueKind::Continuous && source == ValueKind::Integer)
+    }
+
+    /// Returns whether values of a source kind can be converted to this kind with loss of information (continuous
+    /// values into integer inputs, which round them), see `Graph::set_lossy_conversions`.
+    pub fn is_lossy_from(self, source: ValueKind) -> bool {
+        self == ValueKind::Integer && source == ValueKind::Continuous
+    }
+}
+
+/// Error of fallible node processing, identified by a non-zero code (exposed on the status output).
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct ProcessError(pub u32);
+impl ProcessError {
+    /// Error of nodes with an input connected to a node that does not exist (reported by the graph).
+    pub const MISSING_SOURCE: ProcessError = ProcessError(u32::MAX);
+}
 
 /// Abstract processing node with inputs and outputs.
 pub trait Node {
+    /// Returns construction arguments that determine the ports of the node (e.g. the number of inputs), saved ahead
+    /// of the snapshot so registries can recreate the node (see `NodeRegistry::register`). Defaults to none.
+    fn arguments(&self) -> Vec<Value> {
+        Vec::new()
+    }
+
+    /// Returns node as `Any`, e.g. to downcast nodes of a `Graph<BuiltinNode>` to their concrete type (available for
+    /// sized `'static` nodes, boxed nodes return the box). Defaults to the node itself.
+    fn as_any(&self) -> &dyn Any
+    where
+        Self: Sized + 'static,
+    {
+        self
+    }
+
+    /// Returns node as mutable `Any`, e.g. to call type-specific methods like `nodes::Delay::reset_to`.
+    fn as_any_mut(&mut self) -> &mut dyn Any
+    where
+        Self: Sized + 'static,
+    {
+        self
+    }
+
+    /// Returns input passed through to an output while the node is bypassed in a graph (`None` if the output holds
+    /// its value). Defaults to passing input 0 through to output 0.
+    fn bypass_input(&self, output: OutputId) -> Option<InputId> {
+        (output == OutputId(0) && self.list_inputs().contains(&InputId(0))).then_some(InputId(0))
+    }
+
+    /// Returns category of node type for grouping in editor palettes (e.g. `"math"`). Defaults to `"other"`.
+    fn category(&self) -> &'static str {
+        "other"
+    }
+
     /// Returns whether node introduces processing delay.
     fn delayed_processing(&self) -> bool;
 
+    /// Returns self-description with type name, ports, parameters and processing properties. Defaults to
+    /// aggregating the respective methods.
+    fn describe(&self) -> NodeDescriptor<'_> {
+        NodeDescriptor {
+            category: self.category(),
+            delayed: self.delayed_processing(),
+            event_inputs: self.list_event_inputs().to_vec(),
+            event_outputs: self.list_event_outputs().to_vec(),
+            inputs: self.list_inputs().iter().map(|&id| (id, self.input_info(id), self.input_channels(id))).collect(),
+            outputs: self
+                .list_outputs()
+                .iter()
+                .map(|&id| (id, self.output_info(id), self.output_channels(id)))
+                .collect(),
+            parameters: self.parameters().iter().map(|&name| (name, self.get_parameter(name))).collect(),
+            pure: self.is_pure(),
+            stateful: !self.snapshot().is_empty(),
+            type_name: self.type_name(),
+        }
+    }
+
+    /// Returns payload of an event output if it fired in the last processing (`None` otherwise).
+    fn event_output(&self, _id: OutputId) -> Option<Value> {
+        None
+    }
+
     /// Returns output value.
-    fn get_output(&self, id: OutputId) -> f64;
+    fn get_output(&self, id: OutputId) -> Value;
+
+    /// Returns value of a parameter (`None` if it does not exist, see `parameters`).
+    fn get_parameter(&self, _name: &str) -> Option<Value> {
+        None
+    }
+
+    /// Returns value of a channel of a multichannel output (see `output_channels`). Defaults to `get_output`.
+    fn get_output_channel(&self, id: OutputId, _channel: usize) -> Value {
+        self.get_output(id)
+    }
+
+    /// Returns index of the graph input whose external value the node receives on input 0 (see `nodes::GraphInput`).
+    fn graph_input(&self) -> Option<usize> {
+        None
+    }
+
+    /// Returns index of the graph output the node exposes externally on output 0 (see `nodes::GraphOutput`).
+    fn graph_output(&self) -> Option<usize> {
+        None
+    }
+
+    /// Returns number of channels of an input (connected outputs must have the same number of channels).
+    fn input_channels(&self, _id: InputId) -> usize {
+        1
+    }
+
+    /// Returns descriptor of an input. Defaults to an unbounded port named by `input_name`.
+    fn input_info(&self, id: InputId) -> PortInfo<'_> {
+        PortInfo::new(self.input_name(id))
+    }
+
+    /// Returns name of an input (`None` if unnamed, it can then be referred to by its id).
+    fn input_name(&self, _id: InputId) -> Option<&str> {
+        None
+    }
+
+    /// Returns whether outputs are a function of the current input values (and parameters) only, without state or
+    /// timing, so a graph may skip processing while they are unchanged. Defaults to `false`.
+    fn is_pure(&self) -> bool {
+        false
+    }
+
+    /// Returns all available event inputs (separate from value inputs, see `receive_event`).
+    fn list_event_inputs(&self) -> &[InputId] {
+        &[]
+    }
+
+    /// Returns all available event outputs (separate from value outputs, see `event_output`).
+    fn list_event_outputs(&self) -> &[OutputId] {
+        &[]
+    }
 
-    /// Returns all available inputs.
+    /// Returns all available inputs (nodes with a number of inputs chosen at construction return a cached list).
     fn list_inputs(&self) -> &[InputId];
 
     /// Returns all available outputs.
     fn list_outputs(&self) -> &[OutputId];
 
-    /// Processes values.
-    fn process(&mut self);
+    /// Returns number of channels of an output (all channels are moved by one connection in every cycle, block
+    /// processing only moves the first channel).
+    fn output_channels(&self, _id: OutputId) -> usize {
+        1
+    }
+
+    /// Returns descriptor of an output. Defaults to an unbounded port named by `output_name`.
+    fn output_info(&self, id: OutputId) -> PortInfo<'_> {
+        PortInfo::new(self.output_name(id))
+    }
+
+    /// Returns name of an output (`None` if unnamed, it can then be referred to by its id).
+    fn output_name(&self, _id: OutputId) -> Option<&str> {
+        None
+    }
+
+    /// Returns names of parameters, i.e. configuration that is not a per-cycle signal (e.g. a filter mode or seed).
+    /// Parameters are saved with the node and can be edited while processing, but must not change its ports.
+    fn parameters(&self) -> &[&str] {
+        &[]
+    }
+
+    /// Processes values (nodes that need timing information implement `process_with_context` instead).
+    fn process(&mut self) {}
+
+    /// Processes a block of frames starting at the context frame, with one buffer per input (`None` if unconnected)
+    /// and per output. Returns the error of the last failed frame.
+    ///
+    /// Buffers are ordered like `list_inputs` and `list_outputs`. Input buffers may only be overwritten if
+    /// `writes_block_inputs` returns true. Defaults to calling `try_process` once per frame.
+    fn process_block(
+        &mut self,
+        context: &ProcessContext,
+        frames: usize,
+        inputs: &mut [Option<&mut [Value]>],
+        outputs: &mut [&mut [Value]],
+    ) -> Result<(), ProcessError> {
+        let delayed = self.delayed_processing();
+        let mut context = *context;
+        let mut result = Ok(());
+        for frame in 0..frames {
+            for (index, values) in inputs.iter().enumerate() {
+                if let Some(values) = values {
+                    let id = self.list_inputs()[index];
+                    self.set_input(id, values[frame]);
+                }
+            }
+
+            // Delayed nodes expose the previous value, so their outputs are read before processing.
+            if !delayed {
+                result = self.try_process(&context).and(result);
+            }
+            for (index, values) in outputs.iter_mut().enumerate() {
+                values[frame] = self.get_output(self.list_outputs()[index]);
+            }
+            if delayed {
+                result = self.try_process(&context).and(result);
+            }
+            context.frame += 1;
+        }
+        result
+    }
+
+    /// Processes values with timing information. Defaults to calling `process`.
+    fn process_with_context(&mut self, _context: &ProcessContext) {
+        self.process();
+    }
+
+    /// Receives an event on an event input (called before processing in the cycle the event is delivered).
+    fn receive_event(&mut self, _id: InputId, _payload: Value) {}
+
+    /// Resets runtime state (e.g. delayed values or integrators) to initial conditions, keeping inputs and
+    /// parameters. Defaults to doing nothing (for stateless nodes).
+    fn reset(&mut self) {}
+
+    /// Restores runtime state from a snapshot (see `snapshot`).
+    fn restore(&mut self, _snapshot: &[Value]) {}
 
     /// Sets input value.
-    fn set_input(&mut self, id: InputId, value: f64);
+    fn set_input(&mut self, id: InputId, value: Value);
+
+    /// Sets value of a channel of a multichannel input (see `input_channels`). Defaults to `set_input`.
+    fn set_input_channel(&mut self, id: InputId, _channel: usize, value: Value) {
+        self.set_input(id, value);
+    }
+
+    /// Sets value of a parameter (see `parameters`, unknown names are ignored).
+    fn set_parameter(&mut self, _name: &str, _value: Value) {}
+
+    /// Returns snapshot of runtime state (e.g. delayed values), empty for stateless nodes.
+    fn snapshot(&self) -> Vec<Value> {
+        Vec::new()
+    }
+
+    /// Processes values with timing information, reporting failures (status output of the node in a graph).
+    /// Defaults to calling `process_with_context`.
+    fn try_process(&mut self, context: &ProcessContext) -> Result<(), ProcessError> {
+        self.process_with_context(context);
+        Ok(())
+    }
+
+    /// Returns name of node type.
+    fn type_name(&self) -> &'static str {
+        std::any::type_name::<Self>()
+    }
+
+    /// Returns whether block processing overwrites input buffers (prevents sharing them with source outputs).
+    fn writes_block_inputs(&self) -> bool {
+        false
+    }
 }
 impl<N: Node + ?Sized> Node for Box<N> {
+    fn arguments(&self) -> Vec<Value> {
+        self.as_ref().arguments()
+    }
+    fn bypass_input(&self, output: OutputId) -> Option<InputId> {
+        self.as_ref().bypass_input(output)
+    }
+    fn category(&self) -> &'static str {
+        self.as_ref().category()
+    }
     fn delayed_processing(&self) -> bool {
         self.as_ref().delayed_processing()
     }
-    fn get_output(&self, id: OutputId) -> f64 {
+    fn describe(&self) -> NodeDescriptor<'_> {
+        self.as_ref().describe()
+    }
+    fn event_output(&self, id: OutputId) -> Option<Value> {
+        self.as_ref().event_output(id)
+    }
+    fn get_output(&self, id: OutputId) -> Value {
         self.as_ref().get_output(id)
     }
+    fn get_output_channel(&self, id: OutputId, channel: usize) -> Value {
+        self.as_ref().get_output_channel(id, channel)
+    }
+    fn get_parameter(&self, name: &str) -> Option<Value> {
+        self.as_ref().get_parameter(name)
+    }
+    fn graph_input(&self) -> Option<usize> {
+        self.as_ref().graph_input()
+    }
+    fn graph_output(&self) -> Option<usize> {
+        self.as_ref().graph_output()
+    }
+    fn input_channels(&self, id: InputId) -> usize {
+        self.as_ref().input_channels(id)
+    }
+    fn input_info(&self, id: InputId) -> PortInfo<'_> {
+        self.as_ref().input_info(id)
+    }
+    fn input_name(&self, id: InputId) -> Option<&str> {
+        self.as_ref().input_name(id)
+    }
+    fn is_pure(&self) -> bool {
+        self.as_ref().is_pure()
+    }
+    fn list_event_inputs(&self) -> &[InputId] {
+        self.as_ref().list_event_inputs()
+    }
+    fn list_event_outputs(&self) -> &[OutputId] {
+        self.as_ref().list_event_outputs()
+    }
     fn list_inputs(&self) -> &[InputId] {
         self.as_ref().list_inputs()
     }
     fn list_outputs(&self) -> &[OutputId] {
         self.as_ref().list_outputs()
     }
+    fn output_channels(&self, id: OutputId) -> usize {
+        self.as_ref().output_channels(id)
+    }
+    fn output_info(&self, id: OutputId) -> PortInfo<'_> {
+        self.as_ref().output_info(id)
+    }
+    fn output_name(&self, id: OutputId) -> Option<&str> {
+        self.as_ref().output_name(id)
+    }
+    fn parameters(&self) -> &[&str] {
+        self.as_ref().parameters()
+    }
     fn process(&mut self) {
         self.as_mut().process()
     }
-    fn set_input(&mut self, id: InputId, value: f64) {
+    fn process_block(
+        &mut self,
+        context: &ProcessContext,
+        frames: usize,
+        inputs: &mut [Option<&mut [Value]>],
+        outputs: &mut [&mut [Value]],
+    ) -> Result<(), ProcessError> {
+        self.as_mut().process_block(context, frames, inputs, outputs)
+    }
+    fn process_with_context(&mut self, context: &ProcessContext) {
+        self.as_mut().process_with_context(context)
+    }
+    fn receive_event(&mut self, id: InputId, payload: Value) {
+        self.as_mut().receive_event(id, payload)
+    }
+    fn reset(&mut self) {
+        self.as_mut().reset()
+    }
+    fn restore(&mut self, snapshot: &[Value]) {
+        self.as_mut().restore(snapshot)
+    }
+    fn set_input(&mut self, id: InputId, value: Value) {
         self.as_mut().set_input(id, value)
     }
+    fn set_input_channel(&mut self, id: InputId, channel: usize, value: Value) {
+        self.as_mut().set_input_channel(id, channel, value)
+    }
+    fn set_parameter(&mut self, name: &str, value: Value) {
+        self.as_mut().set_parameter(name, value)
+    }
+    fn snapshot(&self) -> Vec<Value> {
+        self.as_ref().snapshot()
+    }
+    fn try_process(&mut self, context: &ProcessContext) -> Result<(), ProcessError> {
+        self.as_mut().try_process(context)
+    }
+    fn type_name(&self) -> &'static str {
+        self.as_ref().type_name()
+    }
+    fn writes_block_inputs(&self) -> bool {
+        self.as_ref().writes_block_inputs()
+    }
+}
+
+/// Unit tests.
+#[cfg(all(test, feature = "dsp", feature = "math"))]
+mod tests {
+    use super::*;
+    use crate::nodes;
+
+    #[test]
+    fn downcasts_node() {
+        let mut node = nodes::BuiltinNode::from(nodes::Delay::new());
+        node.as_any_mut().downcast_mut::<nodes::Delay>().unwrap().reset_to(2.0);
+        assert_eq!(node.get_output(OutputId(0)), 2.0);
+        assert!(node.as_any().is::<nodes::Delay>());
+        assert!(node.as_any().downcast_ref::<nodes::Addition>().is_none());
+
+        // Boxed nodes return the box, nodes borrowing data are not required to be `'static`.
+        let node: Box<dyn Node> = Box::new(nodes::Delay::new());
+        assert!(node.as_any().is::<Box<dyn Node>>());
+        let scale = 2.0;
+        let mut node = nodes::FnNode::new(1, 1, |inputs, outputs| outputs[0] = inputs[0] * scale);
+        node.set_input(InputId(0), 3.0);
+        node.process();
+        assert_eq!(node.get_output(OutputId(0)), 6.0);
+    }
+
+    #[test]
+    fn describes_node() {
+        let node: Box<dyn Node> = Box::new(nodes::DelayN::new(4));
+        let descriptor = node.describe();
+        assert_eq!(descriptor.type_name, "flowing::nodes::delay_n::DelayN");
+        assert_eq!(descriptor.category, "time");
+        assert!(descriptor.delayed && descriptor.stateful && !descriptor.pure);
+        assert_eq!(descriptor.inputs.len(), 2);
+        assert_eq!(descriptor.inputs[1].0, InputId(1));
+        assert_eq!(descriptor.inputs[1].1.name, Some("length"));
+        assert_eq!(descriptor.inputs[1].1.max, 4.0);
+        assert_eq!(descriptor.outputs, [(OutputId(0), PortInfo::new(Some("delayed")), 1)]);
+        assert!(descriptor.event_inputs.is_empty() && descriptor.parameters.is_empty());
+    }
 }
@@ -1,7 +1,81 @@
+#[cfg(feature = "math")]
 mod addition;
+#[cfg(any(
+    feature = "dsp",
+    feature = "generators",
+    feature = "io",
+    feature = "logic",
+    feature = "math",
+    feature = "midi"
+))]
+mod builtin;
+#[cfg(feature = "math")]
+mod complex;
+#[cfg(feature = "generators")]
+mod constant;
+#[cfg(feature = "logic")]
+mod counter;
+#[cfg(feature = "dsp")]
 mod delay;
+#[cfg(feature = "dsp")]
+mod delay_n;
+#[cfg(feature = "math")]
+mod expression;
+mod function;
+#[cfg(feature = "dsp")]
+mod merge;
+#[cfg(feature = "midi")]
+mod midi;
+#[cfg(feature = "logic")]
+mod select;
+#[cfg(feature = "dsp")]
+mod smooth;
+#[cfg(feature = "dsp")]
+mod split;
+#[cfg(feature = "io")]
+mod terminal;
+#[cfg(feature = "generators")]
 mod variable;
+#[cfg(feature = "math")]
+mod variadic;
 
+#[cfg(feature = "math")]
 pub use addition::Addition;
+#[cfg(any(
+    feature = "dsp",
+    feature = "generators",
+    feature = "io",
+    feature = "logic",
+    feature = "math",
+    feature = "midi"
+))]
+pub use builtin::BuiltinNode;
+#[cfg(feature = "math")]
+pub use complex::{ComplexJoin, ComplexMultiply, ComplexSplit};
+#[cfg(feature = "generators")]
+pub use constant::Constant;
+#[cfg(feature = "logic")]
+pub use counter::Counter;
+#[cfg(feature = "dsp")]
 pub use delay::Delay;
+#[cfg(feature = "dsp")]
+pub use delay_n::DelayN;
+#[cfg(feature = "math")]
+pub use expression::{Expression, ExpressionError};
+pub use function::FnNode;
+#[cfg(feature = "dsp")]
+pub use merge::Merge;
+#[cfg(feature = "midi")]
+pub use midi::{MidiError, MidiInput};
+#[cfg(feature = "logic")]
+pub use select::SelectN;
+#[cfg(feature = "dsp")]
+pub use smooth::Smooth;
+#[cfg(feature = "dsp")]
+pub use split::Split;
+#[cfg(feature = "io")]
+pub use terminal::{GraphInput, GraphOutput};
+#[cfg(feature = "generators")]
 pub use variable::Variable;
+#[cfg(feature = "math")]
+pub use variadic::{AverageN, ProductN, SaturatingProductN, SaturatingSumN, SumN};
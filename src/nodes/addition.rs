@@ -1,9 +1,10 @@
-use crate::{InputId, Node, OutputId};
+use crate::{InputId, Node, OutputId, Value};
 
 /// Node that adds two values.
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Addition {
-    summands: (f64, f64),
-    sum: f64,
+    summands: (Value, Value),
+    sum: Value,
 }
 impl Addition {
     /// Creates new addition node.
@@ -11,18 +12,39 @@ impl Addition {
         Addition { summands: (0.0, 0.0), sum: 0.0 }
     }
 }
+impl Default for Addition {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 impl Node for Addition {
+    fn category(&self) -> &'static str {
+        "math"
+    }
+
     fn delayed_processing(&self) -> bool {
         false
     }
 
-    fn get_output(&self, id: OutputId) -> f64 {
+    fn get_output(&self, id: OutputId) -> Value {
         match id.0 {
             0 => self.sum,
             _ => panic!("Output with id {} does not exist.", id.0),
         }
     }
 
+    fn input_name(&self, id: InputId) -> Option<&str> {
+        match id.0 {
+            0 => Some("a"),
+            1 => Some("b"),
+            _ => None,
+        }
+    }
+
+    fn is_pure(&self) -> bool {
+        true
+    }
+
     fn list_inputs(&self) -> &[InputId] {
         // 0 -> 1st summand.
         // 1 -> 2nd summand.
@@ -34,17 +56,35 @@ impl Node for Addition {
         &[OutputId(0)]
     }
 
+    fn output_name(&self, id: OutputId) -> Option<&str> {
+        match id.0 {
+            0 => Some("sum"),
+            _ => None,
+        }
+    }
+
     fn process(&mut self) {
         self.sum = self.summands.0 + self.summands.1;
     }
 
-    fn set_input(&mut self, id: InputId, value: f64) {
+    fn restore(&mut self, snapshot: &[Value]) {
+        if let &[summand0, summand1, sum] = snapshot {
+            self.summands = (summand0, summand1);
+            self.sum = sum;
+        }
+    }
+
+    fn set_input(&mut self, id: InputId, value: Value) {
         match id.0 {
             0 => self.summands.0 = value,
             1 => self.summands.1 = value,
             _ => panic!("Input with id {} does not exist.", id.0),
         }
     }
+
+    fn snapshot(&self) -> Vec<Value> {
+        vec![self.summands.0, self.summands.1, self.sum]
+    }
 }
 
 /// Unit tests.
@@ -66,4 +106,16 @@ mod tests {
         add.process();
         assert_eq!(add.get_output(OutputId(0)), 36.0);
     }
+
+    #[test]
+    fn restores_snapshot() {
+        let mut add = Addition::new();
+        add.set_input(InputId(0), 2.0);
+        add.process();
+        assert_eq!(add.snapshot(), vec![2.0, 0.0, 2.0]);
+
+        let mut restored = Addition::new();
+        restored.restore(&add.snapshot());
+        assert_eq!(restored.get_output(OutputId(0)), 2.0);
+    }
 }
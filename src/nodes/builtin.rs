@@ -0,0 +1,273 @@
+use crate::{InputId, Node, NodeDescriptor, OutputId, PortInfo, ProcessContext, ProcessError, Value};
+use std::any::Any;
+
+/// Defines `BuiltinNode` with a variant per bundled node type (available with the feature of the type) and implements
+/// `Node` by matching on the variant.
+macro_rules! builtin_nodes {
+    ($(#[cfg($cfg:meta)] $variant:ident,)*) => {
+        /// Node of any bundled type, so graphs of standard nodes (`Graph<BuiltinNode>`) avoid boxing and dynamic
+        /// dispatch. Nodes are converted with `into()`, e.g. `graph.add_node(nodes::Addition::new().into())`.
+        pub enum BuiltinNode {
+            $(#[cfg($cfg)] $variant(super::$variant),)*
+        }
+        $(
+            #[cfg($cfg)]
+            impl From<super::$variant> for BuiltinNode {
+                fn from(node: super::$variant) -> Self {
+                    BuiltinNode::$variant(node)
+                }
+            }
+        )*
+        impl Node for BuiltinNode {
+            fn arguments(&self) -> Vec<Value> {
+                match self {
+                    $(#[cfg($cfg)] BuiltinNode::$variant(node) => node.arguments(),)*
+                }
+            }
+            fn as_any(&self) -> &dyn Any {
+                match self {
+                    $(#[cfg($cfg)] BuiltinNode::$variant(node) => node.as_any(),)*
+                }
+            }
+            fn as_any_mut(&mut self) -> &mut dyn Any {
+                match self {
+                    $(#[cfg($cfg)] BuiltinNode::$variant(node) => node.as_any_mut(),)*
+                }
+            }
+            fn bypass_input(&self, output: OutputId) -> Option<InputId> {
+                match self {
+                    $(#[cfg($cfg)] BuiltinNode::$variant(node) => node.bypass_input(output),)*
+                }
+            }
+            fn category(&self) -> &'static str {
+                match self {
+                    $(#[cfg($cfg)] BuiltinNode::$variant(node) => node.category(),)*
+                }
+            }
+            fn delayed_processing(&self) -> bool {
+                match self {
+                    $(#[cfg($cfg)] BuiltinNode::$variant(node) => node.delayed_processing(),)*
+                }
+            }
+            fn describe(&self) -> NodeDescriptor<'_> {
+                match self {
+                    $(#[cfg($cfg)] BuiltinNode::$variant(node) => node.describe(),)*
+                }
+            }
+            fn event_output(&self, id: OutputId) -> Option<Value> {
+                match self {
+                    $(#[cfg($cfg)] BuiltinNode::$variant(node) => node.event_output(id),)*
+                }
+            }
+            fn get_output(&self, id: OutputId) -> Value {
+                match self {
+                    $(#[cfg($cfg)] BuiltinNode::$variant(node) => node.get_output(id),)*
+                }
+            }
+            fn get_output_channel(&self, id: OutputId, channel: usize) -> Value {
+                match self {
+                    $(#[cfg($cfg)] BuiltinNode::$variant(node) => node.get_output_channel(id, channel),)*
+                }
+            }
+            fn get_parameter(&self, name: &str) -> Option<Value> {
+                match self {
+                    $(#[cfg($cfg)] BuiltinNode::$variant(node) => node.get_parameter(name),)*
+                }
+            }
+            fn graph_input(&self) -> Option<usize> {
+                match self {
+                    $(#[cfg($cfg)] BuiltinNode::$variant(node) => node.graph_input(),)*
+                }
+            }
+            fn graph_output(&self) -> Option<usize> {
+                match self {
+                    $(#[cfg($cfg)] BuiltinNode::$variant(node) => node.graph_output(),)*
+                }
+            }
+            fn input_channels(&self, id: InputId) -> usize {
+                match self {
+                    $(#[cfg($cfg)] BuiltinNode::$variant(node) => node.input_channels(id),)*
+                }
+            }
+            fn input_info(&self, id: InputId) -> PortInfo<'_> {
+                match self {
+                    $(#[cfg($cfg)] BuiltinNode::$variant(node) => node.input_info(id),)*
+                }
+            }
+            fn input_name(&self, id: InputId) -> Option<&str> {
+                match self {
+                    $(#[cfg($cfg)] BuiltinNode::$variant(node) => node.input_name(id),)*
+                }
+            }
+            fn is_pure(&self) -> bool {
+                match self {
+                    $(#[cfg($cfg)] BuiltinNode::$variant(node) => node.is_pure(),)*
+                }
+            }
+            fn list_event_inputs(&self) -> &[InputId] {
+                match self {
+                    $(#[cfg($cfg)] BuiltinNode::$variant(node) => node.list_event_inputs(),)*
+                }
+            }
+            fn list_event_outputs(&self) -> &[OutputId] {
+                match self {
+                    $(#[cfg($cfg)] BuiltinNode::$variant(node) => node.list_event_outputs(),)*
+                }
+            }
+            fn list_inputs(&self) -> &[InputId] {
+                match self {
+                    $(#[cfg($cfg)] BuiltinNode::$variant(node) => node.list_inputs(),)*
+                }
+            }
+            fn list_outputs(&self) -> &[OutputId] {
+                match self {
+                    $(#[cfg($cfg)] BuiltinNode::$variant(node) => node.list_outputs(),)*
+                }
+            }
+            fn output_channels(&self, id: OutputId) -> usize {
+                match self {
+                    $(#[cfg($cfg)] BuiltinNode::$variant(node) => node.output_channels(id),)*
+                }
+            }
+            fn output_info(&self, id: OutputId) -> PortInfo<'_> {
+                match self {
+                    $(#[cfg($cfg)] BuiltinNode::$variant(node) => node.output_info(id),)*
+                }
+            }
+            fn output_name(&self, id: OutputId) -> Option<&str> {
+                match self {
+                    $(#[cfg($cfg)] BuiltinNode::$variant(node) => node.output_name(id),)*
+                }
+            }
+            fn parameters(&self) -> &[&str] {
+                match self {
+                    $(#[cfg($cfg)] BuiltinNode::$variant(node) => node.parameters(),)*
+                }
+            }
+            fn process(&mut self) {
+                match self {
+                    $(#[cfg($cfg)] BuiltinNode::$variant(node) => node.process(),)*
+                }
+            }
+            fn process_block(
+                &mut self,
+                context: &ProcessContext,
+                frames: usize,
+                inputs: &mut [Option<&mut [Value]>],
+                outputs: &mut [&mut [Value]],
+            ) -> Result<(), ProcessError> {
+                match self {
+                    $(#[cfg($cfg)] BuiltinNode::$variant(node) => {
+                        node.process_block(context, frames, inputs, outputs)
+                    })*
+                }
+            }
+            fn process_with_context(&mut self, context: &ProcessContext) {
+                match self {
+                    $(#[cfg($cfg)] BuiltinNode::$variant(node) => node.process_with_context(context),)*
+                }
+            }
+            fn receive_event(&mut self, id: InputId, payload: Value) {
+                match self {
+                    $(#[cfg($cfg)] BuiltinNode::$variant(node) => node.receive_event(id, payload),)*
+                }
+            }
+            fn reset(&mut self) {
+                match self {
+                    $(#[cfg($cfg)] BuiltinNode::$variant(node) => node.reset(),)*
+                }
+            }
+            fn restore(&mut self, snapshot: &[Value]) {
+                match self {
+                    $(#[cfg($cfg)] BuiltinNode::$variant(node) => node.restore(snapshot),)*
+                }
+            }
+            fn set_input(&mut self, id: InputId, value: Value) {
+                match self {
+                    $(#[cfg($cfg)] BuiltinNode::$variant(node) => node.set_input(id, value),)*
+                }
+            }
+            fn set_input_channel(&mut self, id: InputId, channel: usize, value: Value) {
+                match self {
+                    $(#[cfg($cfg)] BuiltinNode::$variant(node) => node.set_input_channel(id, channel, value),)*
+                }
+            }
+            fn set_parameter(&mut self, name: &str, value: Value) {
+                match self {
+                    $(#[cfg($cfg)] BuiltinNode::$variant(node) => node.set_parameter(name, value),)*
+                }
+            }
+            fn snapshot(&self) -> Vec<Value> {
+                match self {
+                    $(#[cfg($cfg)] BuiltinNode::$variant(node) => node.snapshot(),)*
+                }
+            }
+            fn try_process(&mut self, context: &ProcessContext) -> Result<(), ProcessError> {
+                match self {
+                    $(#[cfg($cfg)] BuiltinNode::$variant(node) => node.try_process(context),)*
+                }
+            }
+            fn type_name(&self) -> &'static str {
+                match self {
+                    $(#[cfg($cfg)] BuiltinNode::$variant(node) => node.type_name(),)*
+                }
+            }
+            fn writes_block_inputs(&self) -> bool {
+                match self {
+                    $(#[cfg($cfg)] BuiltinNode::$variant(node) => node.writes_block_inputs(),)*
+                }
+            }
+        }
+    };
+}
+
+builtin_nodes! {
+    #[cfg(feature = "math")] Addition,
+    #[cfg(feature = "math")] AverageN,
+    #[cfg(feature = "math")] ComplexJoin,
+    #[cfg(feature = "math")] ComplexMultiply,
+    #[cfg(feature = "math")] ComplexSplit,
+    #[cfg(feature = "generators")] Constant,
+    #[cfg(feature = "logic")] Counter,
+    #[cfg(feature = "dsp")] Delay,
+    #[cfg(feature = "dsp")] DelayN,
+    #[cfg(feature = "math")] Expression,
+    #[cfg(feature = "io")] GraphInput,
+    #[cfg(feature = "io")] GraphOutput,
+    #[cfg(feature = "dsp")] Merge,
+    #[cfg(feature = "midi")] MidiInput,
+    #[cfg(feature = "math")] ProductN,
+    #[cfg(feature = "math")] SaturatingProductN,
+    #[cfg(feature = "math")] SaturatingSumN,
+    #[cfg(feature = "logic")] SelectN,
+    #[cfg(feature = "dsp")] Smooth,
+    #[cfg(feature = "dsp")] Split,
+    #[cfg(feature = "math")] SumN,
+    #[cfg(feature = "generators")] Variable,
+}
+
+/// Unit tests.
+#[cfg(all(test, feature = "dsp", feature = "generators", feature = "math"))]
+mod tests {
+    use super::*;
+    use crate::{nodes, Connection, Graph};
+
+    #[test]
+    fn processes_in_graph() {
+        let mut graph: Graph<BuiltinNode> = Graph::new();
+        let var0 = graph.add_node(nodes::Variable::new(2.0).into());
+        let add1 = graph.add_node(nodes::Addition::new().into());
+        let del2 = graph.add_node(nodes::Delay::new().into());
+        graph.add_connection(Connection::new(var0, OutputId(0), add1, InputId(0))).unwrap();
+        graph.add_connection(Connection::new(add1, OutputId(0), del2, InputId(0))).unwrap();
+        graph.add_connection(Connection::new(del2, OutputId(0), add1, InputId(1))).unwrap();
+        graph.process_n(3);
+        assert_eq!(graph.get_output(add1, OutputId(0)), Ok(6.0));
+
+        // Nodes keep their type name and can be downcast.
+        assert_eq!(graph.get_node(del2).unwrap().type_name(), "flowing::nodes::delay::Delay");
+        graph.get_node_as_mut::<nodes::Delay>(del2).unwrap().reset_to(0.0);
+        graph.process();
+        assert_eq!(graph.get_output(add1, OutputId(0)), Ok(2.0));
+    }
+}
@@ -0,0 +1,311 @@
+use crate::{InputId, Node, OutputId, PortInfo, Value, ValueKind};
+
+/// Returns descriptor of a complex port (two channels, real and imaginary part).
+fn complex_info(name: Option<&str>) -> PortInfo<'_> {
+    PortInfo { kind: ValueKind::Complex, ..PortInfo::new(name) }
+}
+
+/// Node that joins a real and an imaginary part into a complex value.
+pub struct ComplexJoin {
+    value: (Value, Value),
+}
+impl ComplexJoin {
+    /// Creates new complex join node.
+    pub fn new() -> Self {
+        ComplexJoin { value: (0.0, 0.0) }
+    }
+}
+impl Default for ComplexJoin {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl Node for ComplexJoin {
+    fn category(&self) -> &'static str {
+        "math"
+    }
+
+    fn delayed_processing(&self) -> bool {
+        false
+    }
+
+    fn get_output(&self, id: OutputId) -> Value {
+        self.get_output_channel(id, 0)
+    }
+
+    fn get_output_channel(&self, id: OutputId, channel: usize) -> Value {
+        match (id.0, channel) {
+            (0, 0) => self.value.0,
+            (0, 1) => self.value.1,
+            _ => panic!("Output with id {} and channel {} does not exist.", id.0, channel),
+        }
+    }
+
+    fn input_name(&self, id: InputId) -> Option<&str> {
+        match id.0 {
+            0 => Some("re"),
+            1 => Some("im"),
+            _ => None,
+        }
+    }
+
+    fn is_pure(&self) -> bool {
+        true
+    }
+
+    fn list_inputs(&self) -> &[InputId] {
+        // 0 -> real part.
+        // 1 -> imaginary part.
+        &[InputId(0), InputId(1)]
+    }
+
+    fn list_outputs(&self) -> &[OutputId] {
+        // 0 -> complex value.
+        &[OutputId(0)]
+    }
+
+    fn output_channels(&self, _id: OutputId) -> usize {
+        2
+    }
+
+    fn output_info(&self, id: OutputId) -> PortInfo<'_> {
+        complex_info(self.output_name(id))
+    }
+
+    fn output_name(&self, id: OutputId) -> Option<&str> {
+        match id.0 {
+            0 => Some("z"),
+            _ => None,
+        }
+    }
+
+    fn set_input(&mut self, id: InputId, value: Value) {
+        match id.0 {
+            0 => self.value.0 = value,
+            1 => self.value.1 = value,
+            _ => panic!("Input with id {} does not exist.", id.0),
+        }
+    }
+}
+
+/// Node that multiplies two complex values.
+pub struct ComplexMultiply {
+    factors: [(Value, Value); 2],
+    product: (Value, Value),
+}
+impl ComplexMultiply {
+    /// Creates new complex multiplication node.
+    pub fn new() -> Self {
+        ComplexMultiply { factors: [(0.0, 0.0); 2], product: (0.0, 0.0) }
+    }
+}
+impl Default for ComplexMultiply {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl Node for ComplexMultiply {
+    fn category(&self) -> &'static str {
+        "math"
+    }
+
+    fn delayed_processing(&self) -> bool {
+        false
+    }
+
+    fn get_output(&self, id: OutputId) -> Value {
+        self.get_output_channel(id, 0)
+    }
+
+    fn get_output_channel(&self, id: OutputId, channel: usize) -> Value {
+        match (id.0, channel) {
+            (0, 0) => self.product.0,
+            (0, 1) => self.product.1,
+            _ => panic!("Output with id {} and channel {} does not exist.", id.0, channel),
+        }
+    }
+
+    fn input_channels(&self, _id: InputId) -> usize {
+        2
+    }
+
+    fn input_info(&self, id: InputId) -> PortInfo<'_> {
+        complex_info(self.input_name(id))
+    }
+
+    fn input_name(&self, id: InputId) -> Option<&str> {
+        match id.0 {
+            0 => Some("a"),
+            1 => Some("b"),
+            _ => None,
+        }
+    }
+
+    fn is_pure(&self) -> bool {
+        true
+    }
+
+    fn list_inputs(&self) -> &[InputId] {
+        // 0 -> 1st factor.
+        // 1 -> 2nd factor.
+        &[InputId(0), InputId(1)]
+    }
+
+    fn list_outputs(&self) -> &[OutputId] {
+        // 0 -> product.
+        &[OutputId(0)]
+    }
+
+    fn output_channels(&self, _id: OutputId) -> usize {
+        2
+    }
+
+    fn output_info(&self, id: OutputId) -> PortInfo<'_> {
+        complex_info(self.output_name(id))
+    }
+
+    fn output_name(&self, id: OutputId) -> Option<&str> {
+        match id.0 {
+            0 => Some("product"),
+            _ => None,
+        }
+    }
+
+    fn process(&mut self) {
+        let [(a, b), (c, d)] = self.factors;
+        self.product = (a * c - b * d, a * d + b * c);
+    }
+
+    fn set_input(&mut self, id: InputId, value: Value) {
+        self.set_input_channel(id, 0, value);
+    }
+
+    fn set_input_channel(&mut self, id: InputId, channel: usize, value: Value) {
+        match (self.factors.get_mut(id.0 as usize), channel) {
+            (Some(factor), 0) => factor.0 = value,
+            (Some(factor), 1) => factor.1 = value,
+            _ => panic!("Input with id {} and channel {} does not exist.", id.0, channel),
+        }
+    }
+}
+
+/// Node that splits a complex value into its real and imaginary part, magnitude and phase (in radians).
+pub struct ComplexSplit {
+    value: (Value, Value),
+}
+impl ComplexSplit {
+    /// Creates new complex split node.
+    pub fn new() -> Self {
+        ComplexSplit { value: (0.0, 0.0) }
+    }
+}
+impl Default for ComplexSplit {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl Node for ComplexSplit {
+    fn category(&self) -> &'static str {
+        "math"
+    }
+
+    fn delayed_processing(&self) -> bool {
+        false
+    }
+
+    fn get_output(&self, id: OutputId) -> Value {
+        let (re, im) = self.value;
+        match id.0 {
+            0 => re,
+            1 => im,
+            2 => re.hypot(im),
+            3 => im.atan2(re),
+            _ => panic!("Output with id {} does not exist.", id.0),
+        }
+    }
+
+    fn input_channels(&self, _id: InputId) -> usize {
+        2
+    }
+
+    fn input_info(&self, id: InputId) -> PortInfo<'_> {
+        complex_info(self.input_name(id))
+    }
+
+    fn input_name(&self, id: InputId) -> Option<&str> {
+        match id.0 {
+            0 => Some("z"),
+            _ => None,
+        }
+    }
+
+    fn is_pure(&self) -> bool {
+        true
+    }
+
+    fn list_inputs(&self) -> &[InputId] {
+        // 0 -> complex value.
+        &[InputId(0)]
+    }
+
+    fn list_outputs(&self) -> &[OutputId] {
+        // 0 -> real part.
+        // 1 -> imaginary part.
+        // 2 -> magnitude.
+        // 3 -> phase.
+        &[OutputId(0), OutputId(1), OutputId(2), OutputId(3)]
+    }
+
+    fn output_name(&self, id: OutputId) -> Option<&str> {
+        match id.0 {
+            0 => Some("re"),
+            1 => Some("im"),
+            2 => Some("magnitude"),
+            3 => Some("phase"),
+            _ => None,
+        }
+    }
+
+    fn set_input(&mut self, id: InputId, value: Value) {
+        self.set_input_channel(id, 0, value);
+    }
+
+    fn set_input_channel(&mut self, id: InputId, channel: usize, value: Value) {
+        match (id.0, channel) {
+            (0, 0) => self.value.0 = value,
+            (0, 1) => self.value.1 = value,
+            _ => panic!("Input with id {} and channel {} does not exist.", id.0, channel),
+        }
+    }
+}
+
+/// Unit tests.
+#[cfg(all(test, feature = "generators"))]
+mod tests {
+    use super::*;
+    use crate::{nodes, Connection, Graph, GraphError};
+
+    #[test]
+    fn multiplies_in_graph() {
+        // (1 + 2i) * (3 - 1i) = 5 + 5i.
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        let join0 = graph.add_node(Box::new(ComplexJoin::new()));
+        let join1 = graph.add_node(Box::new(ComplexJoin::new()));
+        let mul2 = graph.add_node(Box::new(ComplexMultiply::new()));
+        let split3 = graph.add_node(Box::new(ComplexSplit::new()));
+        graph.add_connection(Connection::new(join0, OutputId(0), mul2, InputId(0))).unwrap();
+        graph.add_connection(Connection::new(join1, OutputId(0), mul2, InputId(1))).unwrap();
+        graph.add_connection(Connection::new(mul2, OutputId(0), split3, InputId(0))).unwrap();
+        for (node, input, value) in [(join0, 0, 1.0), (join0, 1, 2.0), (join1, 0, 3.0), (join1, 1, -1.0)] {
+            graph.set_input(node, InputId(input), value).unwrap();
+        }
+        graph.process();
+        let outputs: Vec<Value> = (0..4).map(|output| graph.get_output(split3, OutputId(output)).unwrap()).collect();
+        assert_eq!(outputs, [5.0, 5.0, (50.0 as Value).sqrt(), (1.0 as Value).atan2(1.0)]);
+
+        // Real values are not accepted as complex values.
+        let var4 = graph.add_node(Box::new(nodes::Variable::new(1.0)));
+        let connection = Connection::new(var4, OutputId(0), split3, InputId(0));
+        assert_eq!(graph.add_connection(connection), Err(GraphError::TypeMismatch(connection)));
+    }
+}
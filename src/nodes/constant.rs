@@ -0,0 +1,105 @@
+use crate::{InputId, Node, OutputId, PortInfo, Value, ValueKind};
+
+/// Value kinds by their code in the construction arguments of constants (in declaration order).
+const KINDS: [ValueKind; 5] =
+    [ValueKind::Boolean, ValueKind::Complex, ValueKind::Continuous, ValueKind::Integer, ValueKind::Trigger];
+
+/// Node with fixed output values (e.g. the result of constant folding, see `Graph::optimize`).
+pub struct Constant {
+    /// Value kinds of outputs.
+    kinds: Vec<ValueKind>,
+
+    /// Available outputs.
+    outputs: Vec<OutputId>,
+
+    /// Output values.
+    values: Vec<Value>,
+}
+impl Constant {
+    /// Creates new constant node with one continuous output per value.
+    pub fn new(values: Vec<Value>) -> Self {
+        let kinds = vec![ValueKind::Continuous; values.len()];
+        Self::with_kinds(values, kinds)
+    }
+
+    /// Creates new constant node with one output per value and given value kinds.
+    pub fn with_kinds(values: Vec<Value>, kinds: Vec<ValueKind>) -> Self {
+        assert_eq!(values.len(), kinds.len(), "Number of values and kinds must match.");
+        Constant { kinds, outputs: (0..values.len() as u32).map(OutputId).collect(), values }
+    }
+
+    /// Creates constant node from construction arguments (values followed by kind codes, see `Node::arguments`).
+    pub(crate) fn from_arguments(arguments: &[Value]) -> Self {
+        let (values, kinds) = arguments.split_at(arguments.len() / 2);
+        let kinds = kinds.iter().map(|&code| KINDS.get(code as usize).copied().unwrap_or_default()).collect();
+        Self::with_kinds(values.to_vec(), kinds)
+    }
+}
+impl Node for Constant {
+    fn arguments(&self) -> Vec<Value> {
+        self.values.iter().copied().chain(self.kinds.iter().map(|&kind| kind as u8 as Value)).collect()
+    }
+
+    fn category(&self) -> &'static str {
+        "sources"
+    }
+
+    fn delayed_processing(&self) -> bool {
+        false
+    }
+
+    fn get_output(&self, id: OutputId) -> Value {
+        match self.values.get(id.0 as usize) {
+            Some(&value) => value,
+            None => panic!("Output with id {} does not exist.", id.0),
+        }
+    }
+
+    fn is_pure(&self) -> bool {
+        true
+    }
+
+    fn list_inputs(&self) -> &[InputId] {
+        &[]
+    }
+
+    fn list_outputs(&self) -> &[OutputId] {
+        &self.outputs
+    }
+
+    fn output_info(&self, id: OutputId) -> PortInfo<'_> {
+        PortInfo { kind: self.kinds[id.0 as usize], ..PortInfo::new(None) }
+    }
+
+    fn process(&mut self) {
+        // Constant noop.
+    }
+
+    fn set_input(&mut self, id: InputId, _value: Value) {
+        panic!("Input with id {} does not exist.", id.0);
+    }
+}
+impl From<Constant> for Box<dyn Node> {
+    fn from(constant: Constant) -> Self {
+        Box::new(constant)
+    }
+}
+impl From<Constant> for Box<dyn Node + Send> {
+    fn from(constant: Constant) -> Self {
+        Box::new(constant)
+    }
+}
+
+/// Unit tests.
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn holds_values() {
+        let constant = Constant::with_kinds(vec![1.0, 2.0], vec![ValueKind::Continuous, ValueKind::Integer]);
+        assert_eq!(constant.list_outputs(), &[OutputId(0), OutputId(1)]);
+        assert_eq!(constant.get_output(OutputId(1)), 2.0);
+        assert_eq!(constant.output_info(OutputId(1)).kind, ValueKind::Integer);
+    }
+}
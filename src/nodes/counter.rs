@@ -0,0 +1,140 @@
+use crate::{InputId, Node, OutputId, PortInfo, Value, ValueKind};
+
+/// Node that counts triggers modulo a number of steps (unbounded if the modulus is 0), e.g. to step through a
+/// sequence. A reset restarts the count at 0 and takes precedence over a simultaneous trigger.
+pub struct Counter {
+    count: i64,
+    modulus: i64,
+    reset: bool,
+    trigger: bool,
+}
+impl Counter {
+    /// Creates new counter with a modulus (0 for an unbounded count).
+    pub fn new(modulus: u32) -> Self {
+        Counter { count: 0, modulus: modulus as i64, reset: false, trigger: false }
+    }
+}
+impl Node for Counter {
+    fn category(&self) -> &'static str {
+        "logic"
+    }
+
+    fn delayed_processing(&self) -> bool {
+        false
+    }
+
+    fn get_output(&self, id: OutputId) -> Value {
+        match id.0 {
+            0 => self.count as Value,
+            _ => panic!("Output with id {} does not exist.", id.0),
+        }
+    }
+
+    fn input_info(&self, id: InputId) -> PortInfo<'_> {
+        let name = self.input_name(id);
+        match id.0 {
+            2 => PortInfo { kind: ValueKind::Integer, min: 0.0, ..PortInfo::new(name) },
+            _ => PortInfo { kind: ValueKind::Trigger, max: 1.0, min: 0.0, ..PortInfo::new(name) },
+        }
+    }
+
+    fn input_name(&self, id: InputId) -> Option<&str> {
+        match id.0 {
+            0 => Some("trigger"),
+            1 => Some("reset"),
+            2 => Some("modulus"),
+            _ => None,
+        }
+    }
+
+    fn list_inputs(&self) -> &[InputId] {
+        // 0 -> trigger.
+        // 1 -> reset.
+        // 2 -> modulus.
+        &[InputId(0), InputId(1), InputId(2)]
+    }
+
+    fn list_outputs(&self) -> &[OutputId] {
+        // 0 -> count.
+        &[OutputId(0)]
+    }
+
+    fn output_info(&self, id: OutputId) -> PortInfo<'_> {
+        PortInfo { kind: ValueKind::Integer, min: 0.0, ..PortInfo::new(self.output_name(id)) }
+    }
+
+    fn output_name(&self, id: OutputId) -> Option<&str> {
+        match id.0 {
+            0 => Some("count"),
+            _ => None,
+        }
+    }
+
+    fn process(&mut self) {
+        if self.reset {
+            self.count = 0;
+        } else if self.trigger {
+            self.count += 1;
+        }
+        if self.modulus > 0 {
+            self.count = self.count.rem_euclid(self.modulus);
+        }
+    }
+
+    fn reset(&mut self) {
+        self.count = 0;
+    }
+
+    fn restore(&mut self, snapshot: &[Value]) {
+        if let &[count] = snapshot {
+            self.count = count as i64;
+        }
+    }
+
+    fn set_input(&mut self, id: InputId, value: Value) {
+        match id.0 {
+            0 => self.trigger = value != 0.0,
+            1 => self.reset = value != 0.0,
+            2 => self.modulus = value.round().max(0.0) as i64,
+            _ => panic!("Input with id {} does not exist.", id.0),
+        }
+    }
+
+    fn snapshot(&self) -> Vec<Value> {
+        vec![self.count as Value]
+    }
+}
+
+/// Unit tests.
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_modulo() {
+        let mut counter = Counter::new(3);
+        counter.set_input(InputId(0), 1.0);
+        let mut counts = Vec::new();
+        for _ in 0..4 {
+            counter.process();
+            counts.push(counter.get_output(OutputId(0)));
+        }
+        assert_eq!(counts, [1.0, 2.0, 0.0, 1.0]);
+
+        // Reset takes precedence, fractional moduli are rounded.
+        counter.set_input(InputId(1), 1.0);
+        counter.process();
+        assert_eq!(counter.get_output(OutputId(0)), 0.0);
+        counter.set_input(InputId(1), 0.0);
+        counter.set_input(InputId(2), 1.6);
+        for _ in 0..3 {
+            counter.process();
+        }
+        assert_eq!(counter.get_output(OutputId(0)), 1.0);
+        assert_eq!(counter.snapshot(), [1.0]);
+        assert_eq!(counter.input_info(InputId(2)).kind, ValueKind::Integer);
+
+        Node::reset(&mut counter);
+        assert_eq!(counter.snapshot(), [0.0]);
+    }
+}
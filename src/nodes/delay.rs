@@ -1,27 +1,59 @@
-use crate::{InputId, Node, OutputId};
+use crate::{InputId, Node, OutputId, Value};
 
 /// Node that delays the input by one processing cycle.
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Delay {
-    value: (f64, f64),
+    /// Output before the first processing cycle and after a reset (`None` for 0).
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    initial: Option<Value>,
+
+    /// Input and delayed output.
+    value: (Value, Value),
 }
 impl Delay {
     /// Creates new delay node.
     pub fn new() -> Self {
-        Delay { value: (0.0, 0.0) }
+        Delay { initial: None, value: (0.0, 0.0) }
+    }
+
+    /// Resets the delay to output a value until the next processing cycle (e.g. to re-seed an integrator).
+    pub fn reset_to(&mut self, value: Value) {
+        self.value = (value, value);
+    }
+
+    /// Creates new delay node with an initial value (output before the first processing cycle).
+    pub fn with_initial(value: Value) -> Self {
+        Delay { initial: Some(value), value: (value, value) }
+    }
+}
+impl Default for Delay {
+    fn default() -> Self {
+        Self::new()
     }
 }
 impl Node for Delay {
+    fn category(&self) -> &'static str {
+        "time"
+    }
+
     fn delayed_processing(&self) -> bool {
         true
     }
 
-    fn get_output(&self, id: OutputId) -> f64 {
+    fn get_output(&self, id: OutputId) -> Value {
         match id.0 {
             0 => self.value.1,
             _ => panic!("Output with id {} does not exist.", id.0),
         }
     }
 
+    fn input_name(&self, id: InputId) -> Option<&str> {
+        match id.0 {
+            0 => Some("value"),
+            _ => None,
+        }
+    }
+
     fn list_inputs(&self) -> &[InputId] {
         // 0 -> value.0 (input).
         &[InputId(0)]
@@ -32,17 +64,38 @@ impl Node for Delay {
         &[OutputId(0)]
     }
 
+    fn output_name(&self, id: OutputId) -> Option<&str> {
+        match id.0 {
+            0 => Some("delayed"),
+            _ => None,
+        }
+    }
+
     fn process(&mut self) {
         // Since delay nodes are processed last, output changes will be visible only in the next processing cycle.
         self.value.1 = self.value.0;
     }
 
-    fn set_input(&mut self, id: InputId, value: f64) {
+    fn reset(&mut self) {
+        self.reset_to(self.initial.unwrap_or(0.0));
+    }
+
+    fn restore(&mut self, snapshot: &[Value]) {
+        if let &[input, output] = snapshot {
+            self.value = (input, output);
+        }
+    }
+
+    fn set_input(&mut self, id: InputId, value: Value) {
         match id.0 {
             0 => self.value.0 = value,
             _ => panic!("Input with id {} does not exist.", id.0),
         }
     }
+
+    fn snapshot(&self) -> Vec<Value> {
+        vec![self.value.0, self.value.1]
+    }
 }
 
 /// Unit tests.
@@ -62,4 +115,37 @@ mod tests {
         del.process();
         assert_eq!(del.get_output(OutputId(0)), 2.0);
     }
+
+    #[test]
+    fn restores_snapshot() {
+        let mut del = Delay::new();
+        del.set_input(InputId(0), 2.0);
+        del.process();
+        del.set_input(InputId(0), 3.0);
+        assert_eq!(del.snapshot(), vec![3.0, 2.0]);
+
+        let mut restored = Delay::new();
+        restored.restore(&del.snapshot());
+        assert_eq!(restored.get_output(OutputId(0)), 2.0);
+        restored.process();
+        assert_eq!(restored.get_output(OutputId(0)), 3.0);
+    }
+
+    #[test]
+    fn starts_at_initial_value() {
+        let mut del = Delay::with_initial(1.5);
+        assert_eq!(del.get_output(OutputId(0)), 1.5);
+        del.process();
+        assert_eq!(del.get_output(OutputId(0)), 1.5);
+        del.set_input(InputId(0), 2.0);
+        del.process();
+        assert_eq!(del.get_output(OutputId(0)), 2.0);
+
+        del.reset_to(-1.0);
+        assert_eq!(del.get_output(OutputId(0)), -1.0);
+        assert_eq!(del.snapshot(), vec![-1.0, -1.0]);
+
+        del.reset();
+        assert_eq!(del.snapshot(), vec![1.5, 1.5]);
+    }
 }
@@ -0,0 +1,228 @@
+use crate::{InputId, Node, OutputId, PortInfo, Value};
+
+/// Node that delays the input by a number of processing cycles (delay line with a ring buffer of the maximum length).
+///
+/// The length can be modulated between 1 and the maximum length. Fractional lengths are rounded, or linearly
+/// interpolated between neighbouring cycles for interpolated delay lines.
+pub struct DelayN {
+    /// Past inputs (most recent at `position`).
+    buffer: Vec<Value>,
+
+    /// Current input.
+    input: Value,
+
+    /// Whether fractional lengths are interpolated.
+    interpolated: bool,
+
+    /// Current length in cycles.
+    length: Value,
+
+    /// Delayed output.
+    output: Value,
+
+    /// Index of the most recent input in the buffer.
+    position: usize,
+}
+impl DelayN {
+    /// Creates new delay line with given maximum length (at least 1 cycle), initially delaying by the maximum length.
+    pub fn new(length: usize) -> Self {
+        let length = length.max(1);
+        DelayN {
+            buffer: vec![0.0; length],
+            input: 0.0,
+            interpolated: false,
+            length: length as Value,
+            output: 0.0,
+            position: 0,
+        }
+    }
+
+    /// Creates new delay line with given maximum length that interpolates fractional lengths.
+    pub fn interpolated(length: usize) -> Self {
+        DelayN { interpolated: true, ..Self::new(length) }
+    }
+
+    /// Returns input from given number of cycles before the most recent one.
+    fn past(&self, cycles: usize) -> Value {
+        let len = self.buffer.len();
+        self.buffer[(self.position + len - cycles.min(len - 1)) % len]
+    }
+}
+impl Node for DelayN {
+    fn arguments(&self) -> Vec<Value> {
+        vec![self.buffer.len() as Value, self.interpolated as u8 as Value]
+    }
+
+    fn category(&self) -> &'static str {
+        "time"
+    }
+
+    fn delayed_processing(&self) -> bool {
+        true
+    }
+
+    fn get_output(&self, id: OutputId) -> Value {
+        match id.0 {
+            0 => self.output,
+            _ => panic!("Output with id {} does not exist.", id.0),
+        }
+    }
+
+    fn input_info(&self, id: InputId) -> PortInfo<'_> {
+        match id.0 {
+            1 => PortInfo { max: self.buffer.len() as Value, min: 1.0, ..PortInfo::new(self.input_name(id)) },
+            _ => PortInfo::new(self.input_name(id)),
+        }
+    }
+
+    fn input_name(&self, id: InputId) -> Option<&str> {
+        match id.0 {
+            0 => Some("value"),
+            1 => Some("length"),
+            _ => None,
+        }
+    }
+
+    fn list_inputs(&self) -> &[InputId] {
+        // 0 -> input.
+        // 1 -> length.
+        &[InputId(0), InputId(1)]
+    }
+
+    fn list_outputs(&self) -> &[OutputId] {
+        // 0 -> delayed output.
+        &[OutputId(0)]
+    }
+
+    fn output_name(&self, id: OutputId) -> Option<&str> {
+        match id.0 {
+            0 => Some("delayed"),
+            _ => None,
+        }
+    }
+
+    fn process(&mut self) {
+        // Since delay nodes are processed last, the output is the input of `length - 1` cycles before this one.
+        self.position = (self.position + 1) % self.buffer.len();
+        self.buffer[self.position] = self.input;
+        let offset = self.length.max(1.0).min(self.buffer.len() as Value) - 1.0;
+        self.output = match self.interpolated {
+            true => {
+                let (cycles, fraction) = (offset.floor(), offset - offset.floor());
+                let newer = self.past(cycles as usize);
+                newer + (self.past(cycles as usize + 1) - newer) * fraction
+            }
+            false => self.past(offset.round() as usize),
+        };
+    }
+
+    fn reset(&mut self) {
+        self.buffer.fill(0.0);
+        (self.input, self.output, self.position) = (0.0, 0.0, 0);
+    }
+
+    fn restore(&mut self, snapshot: &[Value]) {
+        if let [input, output, past @ ..] = snapshot {
+            if past.len() == self.buffer.len() {
+                // Past inputs are stored from the most recent one.
+                self.position = 0;
+                self.buffer[0] = past[0];
+                self.buffer[1..].iter_mut().rev().zip(&past[1..]).for_each(|(slot, &value)| *slot = value);
+                (self.input, self.output) = (*input, *output);
+            }
+        }
+    }
+
+    fn set_input(&mut self, id: InputId, value: Value) {
+        match id.0 {
+            0 => self.input = value,
+            1 => self.length = value,
+            _ => panic!("Input with id {} does not exist.", id.0),
+        }
+    }
+
+    fn snapshot(&self) -> Vec<Value> {
+        let mut snapshot = vec![self.input, self.output];
+        snapshot.extend((0..self.buffer.len()).map(|cycles| self.past(cycles)));
+        snapshot
+    }
+}
+
+/// Unit tests.
+#[cfg(all(test, feature = "math"))]
+mod tests {
+    use super::*;
+    use crate::{nodes, Connection, Graph};
+
+    #[test]
+    fn delays_by_length() {
+        let mut del = DelayN::new(3);
+        let mut outputs = Vec::new();
+        for input in 1..=5 {
+            del.set_input(InputId(0), input as Value);
+            outputs.push(del.get_output(OutputId(0)));
+            del.process();
+        }
+        assert_eq!(outputs, [0.0, 0.0, 0.0, 1.0, 2.0]);
+
+        // Shorter lengths read more recent inputs, lengths are clamped.
+        for (input, length, expected) in [(6.0, 1.0, 6.0), (7.0, 2.4, 6.0), (8.0, 0.0, 8.0), (9.0, 9.0, 7.0)] {
+            del.set_input(InputId(0), input);
+            del.set_input(InputId(1), length);
+            del.process();
+            assert_eq!(del.get_output(OutputId(0)), expected);
+        }
+    }
+
+    #[test]
+    fn interpolates_fractional_lengths() {
+        let mut del = DelayN::interpolated(4);
+        for input in [0.0, 4.0, 8.0] {
+            del.set_input(InputId(0), input);
+            del.process();
+        }
+        del.set_input(InputId(1), 1.25);
+        del.set_input(InputId(0), 12.0);
+        del.process();
+        assert_eq!(del.get_output(OutputId(0)), 11.0);
+        assert_eq!(del.input_info(InputId(1)).max, 4.0);
+    }
+
+    #[test]
+    fn restores_snapshot() {
+        let mut del = DelayN::new(3);
+        for input in [1.0, 2.0, 3.0, 4.0] {
+            del.set_input(InputId(0), input);
+            del.process();
+        }
+        assert_eq!(del.snapshot(), [4.0, 2.0, 4.0, 3.0, 2.0]);
+
+        let mut restored = DelayN::new(3);
+        restored.restore(&del.snapshot());
+        assert_eq!(restored.snapshot(), del.snapshot());
+        restored.process();
+        del.process();
+        assert_eq!(restored.get_output(OutputId(0)), del.get_output(OutputId(0)));
+
+        del.reset();
+        assert_eq!(del.snapshot(), [0.0; 5]);
+    }
+
+    #[test]
+    fn breaks_feedback_cycles() {
+        // Counter: output of the delay line plus 1, fed back with a delay of two cycles.
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        let add0 = graph.add_node(Box::new(nodes::Addition::new()));
+        let del1 = graph.add_node(Box::new(DelayN::new(2)));
+        graph.add_connection(Connection::new(add0, OutputId(0), del1, InputId(0))).unwrap();
+        graph.add_connection(Connection::new(del1, OutputId(0), add0, InputId(0))).unwrap();
+        graph.set_input(add0, InputId(1), 1.0).unwrap();
+        let outputs: Vec<Value> = (0..6)
+            .map(|_| {
+                graph.process();
+                graph.get_output(add0, OutputId(0)).unwrap()
+            })
+            .collect();
+        assert_eq!(outputs, [1.0, 1.0, 2.0, 2.0, 3.0, 3.0]);
+    }
+}
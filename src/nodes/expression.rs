@@ -0,0 +1,412 @@
+use crate::{InputId, Node, OutputId, Value};
+use std::fmt;
+
+/// Node that evaluates a math expression, with one input per free variable (ordered by name).
+///
+/// Expressions support numbers, `+`, `-`, `*`, `/`, `^` (power), parentheses, the constants `pi` and `e` and the
+/// functions `abs`, `cos`, `exp`, `ln`, `max`, `min`, `sin`, `sqrt` and `tan`.
+pub struct Expression {
+    /// Compiled expression in postfix order.
+    code: Vec<Operation>,
+
+    /// Parsed formula.
+    formula: String,
+
+    /// Available inputs.
+    inputs: Vec<InputId>,
+
+    /// Evaluation stack (sized to the maximum depth of the expression).
+    stack: Vec<Value>,
+
+    /// Result of the last evaluation.
+    value: Value,
+
+    /// Current values of the variables.
+    values: Vec<Value>,
+
+    /// Names of the free variables (sorted).
+    variables: Vec<String>,
+}
+impl Expression {
+    /// Creates new expression node by parsing a formula (e.g. `a * sin(b) + 2`).
+    pub fn new(formula: &str) -> Result<Self, ExpressionError> {
+        let mut parser = Parser { code: Vec::new(), formula, position: 0, variables: Vec::new() };
+        parser.parse_sum()?;
+        parser.skip_whitespace();
+        if let Some(character) = parser.peek() {
+            return Err(ExpressionError::UnexpectedCharacter(character, parser.position));
+        }
+
+        // Variables are numbered by first occurrence while parsing, inputs are ordered by name.
+        let mut variables = parser.variables;
+        let mut sorted: Vec<usize> = (0..variables.len()).collect();
+        sorted.sort_by(|&a, &b| variables[a].cmp(&variables[b]));
+        let mut code = parser.code;
+        for operation in code.iter_mut() {
+            if let Operation::Variable(index) = operation {
+                *index = sorted.iter().position(|&original| original == *index).unwrap();
+            }
+        }
+        variables.sort();
+
+        let mut stack_size: usize = 0;
+        let mut depth: isize = 0;
+        for operation in code.iter() {
+            depth += operation.stack_effect();
+            stack_size = stack_size.max(depth as usize);
+        }
+        Ok(Expression {
+            code,
+            formula: formula.to_string(),
+            inputs: (0..variables.len() as u32).map(InputId).collect(),
+            stack: Vec::with_capacity(stack_size),
+            value: 0.0,
+            values: vec![0.0; variables.len()],
+            variables,
+        })
+    }
+
+    /// Creates expression node from construction arguments (character codes of the formula, see `Node::arguments`),
+    /// evaluating to 0 if the formula is invalid.
+    pub(crate) fn from_arguments(arguments: &[Value]) -> Self {
+        let formula: String = arguments.iter().filter_map(|&code| char::from_u32(code as u32)).collect();
+        Self::new(&formula).or_else(|_| Self::new("0")).expect("0 is a valid formula")
+    }
+
+    /// Returns input of a variable.
+    pub fn input(&self, variable: &str) -> Option<InputId> {
+        self.variables.iter().position(|name| name == variable).map(|index| InputId(index as u32))
+    }
+
+    /// Returns names of the free variables (ordered like the inputs).
+    pub fn variables(&self) -> &[String] {
+        &self.variables
+    }
+}
+impl Node for Expression {
+    fn arguments(&self) -> Vec<Value> {
+        self.formula.chars().map(|character| character as u32 as Value).collect()
+    }
+
+    fn category(&self) -> &'static str {
+        "math"
+    }
+
+    fn delayed_processing(&self) -> bool {
+        false
+    }
+
+    fn get_output(&self, id: OutputId) -> Value {
+        match id.0 {
+            0 => self.value,
+            _ => panic!("Output with id {} does not exist.", id.0),
+        }
+    }
+
+    fn input_name(&self, id: InputId) -> Option<&str> {
+        self.variables.get(id.0 as usize).map(|name| name.as_str())
+    }
+
+    fn is_pure(&self) -> bool {
+        true
+    }
+
+    fn list_inputs(&self) -> &[InputId] {
+        // n -> n-th variable (by name).
+        &self.inputs
+    }
+
+    fn list_outputs(&self) -> &[OutputId] {
+        // 0 -> value.
+        &[OutputId(0)]
+    }
+
+    fn output_name(&self, id: OutputId) -> Option<&str> {
+        match id.0 {
+            0 => Some("value"),
+            _ => None,
+        }
+    }
+
+    fn process(&mut self) {
+        let stack = &mut self.stack;
+        for operation in self.code.iter() {
+            let value = match *operation {
+                Operation::Binary(function) => {
+                    let right = stack.pop().unwrap();
+                    let left = stack.pop().unwrap();
+                    function(left, right)
+                }
+                Operation::Constant(value) => value,
+                Operation::Unary(function) => {
+                    let argument = stack.pop().unwrap();
+                    function(argument)
+                }
+                Operation::Variable(index) => self.values[index],
+            };
+            stack.push(value);
+        }
+        self.value = stack.pop().unwrap();
+    }
+
+    fn set_input(&mut self, id: InputId, value: Value) {
+        match self.values.get_mut(id.0 as usize) {
+            Some(variable) => *variable = value,
+            None => panic!("Input with id {} does not exist.", id.0),
+        }
+    }
+}
+
+/// Expression parsing error type (with character positions).
+#[derive(PartialEq)]
+pub enum ExpressionError {
+    UnexpectedCharacter(char, usize),
+    UnexpectedEnd,
+    UnknownFunction(String, usize),
+}
+impl fmt::Debug for ExpressionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ExpressionError::UnexpectedCharacter(character, position) => {
+                write!(f, "Unexpected character '{}' at position {}.", character, position)
+            }
+            ExpressionError::UnexpectedEnd => write!(f, "Unexpected end of expression."),
+            ExpressionError::UnknownFunction(name, position) => {
+                write!(f, "Unknown function {} at position {}.", name, position)
+            }
+        }
+    }
+}
+
+/// Operation of a compiled expression.
+#[derive(Clone, Copy)]
+enum Operation {
+    /// Applies function to the two topmost values.
+    Binary(fn(Value, Value) -> Value),
+
+    /// Pushes a constant.
+    Constant(Value),
+
+    /// Applies function to the topmost value.
+    Unary(fn(Value) -> Value),
+
+    /// Pushes value of a variable.
+    Variable(usize),
+}
+impl Operation {
+    /// Returns change of stack depth.
+    fn stack_effect(&self) -> isize {
+        match self {
+            Operation::Binary(_) => -1,
+            Operation::Constant(_) | Operation::Variable(_) => 1,
+            Operation::Unary(_) => 0,
+        }
+    }
+}
+
+/// Recursive descent parser compiling an expression to postfix operations.
+struct Parser<'a> {
+    /// Compiled operations.
+    code: Vec<Operation>,
+
+    /// Parsed formula.
+    formula: &'a str,
+
+    /// Byte position of the next character.
+    position: usize,
+
+    /// Names of the variables (by first occurrence).
+    variables: Vec<String>,
+}
+impl<'a> Parser<'a> {
+    /// Consumes a character if it matches (after whitespace).
+    fn accept(&mut self, expected: char) -> bool {
+        self.skip_whitespace();
+        if self.peek() == Some(expected) {
+            self.position += expected.len_utf8();
+            return true;
+        }
+        false
+    }
+
+    /// Consumes a character that is required (after whitespace).
+    fn expect(&mut self, expected: char) -> Result<(), ExpressionError> {
+        if self.accept(expected) {
+            return Ok(());
+        }
+        match self.peek() {
+            Some(character) => Err(ExpressionError::UnexpectedCharacter(character, self.position)),
+            None => Err(ExpressionError::UnexpectedEnd),
+        }
+    }
+
+    /// Parses number, constant, variable, function call or parenthesized expression.
+    fn parse_atom(&mut self) -> Result<(), ExpressionError> {
+        self.skip_whitespace();
+        let start = self.position;
+        match self.peek() {
+            Some('(') => {
+                self.position += 1;
+                self.parse_sum()?;
+                self.expect(')')
+            }
+            Some(character) if character.is_ascii_digit() || character == '.' => {
+                let number = self.take_while(|c| c.is_ascii_digit() || c == '.');
+                let value = number.parse().map_err(|_| ExpressionError::UnexpectedCharacter(character, start))?;
+                self.code.push(Operation::Constant(value));
+                Ok(())
+            }
+            Some(character) if character.is_alphabetic() || character == '_' => {
+                let name = self.take_while(|c| c.is_alphanumeric() || c == '_');
+                if self.accept('(') {
+                    return self.parse_call(name, start);
+                }
+                let operation = match name {
+                    "e" => Operation::Constant(std::f64::consts::E as Value),
+                    "pi" => Operation::Constant(std::f64::consts::PI as Value),
+                    _ => match self.variables.iter().position(|variable| variable == name) {
+                        Some(index) => Operation::Variable(index),
+                        None => {
+                            self.variables.push(name.to_string());
+                            Operation::Variable(self.variables.len() - 1)
+                        }
+                    },
+                };
+                self.code.push(operation);
+                Ok(())
+            }
+            Some(character) => Err(ExpressionError::UnexpectedCharacter(character, start)),
+            None => Err(ExpressionError::UnexpectedEnd),
+        }
+    }
+
+    /// Parses arguments of a function call (after the opening parenthesis).
+    fn parse_call(&mut self, name: &'a str, start: usize) -> Result<(), ExpressionError> {
+        let operation = match name {
+            "max" | "min" => {
+                self.parse_sum()?;
+                self.expect(',')?;
+                Operation::Binary(if name == "max" { Value::max } else { Value::min })
+            }
+            "abs" => Operation::Unary(Value::abs),
+            "cos" => Operation::Unary(Value::cos),
+            "exp" => Operation::Unary(Value::exp),
+            "ln" => Operation::Unary(Value::ln),
+            "sin" => Operation::Unary(Value::sin),
+            "sqrt" => Operation::Unary(Value::sqrt),
+            "tan" => Operation::Unary(Value::tan),
+            _ => return Err(ExpressionError::UnknownFunction(name.to_string(), start)),
+        };
+        self.parse_sum()?;
+        self.expect(')')?;
+        self.code.push(operation);
+        Ok(())
+    }
+
+    /// Parses power (right associative) with optional sign.
+    fn parse_power(&mut self) -> Result<(), ExpressionError> {
+        if self.accept('-') {
+            self.parse_power()?;
+            self.code.push(Operation::Unary(|value| -value));
+            return Ok(());
+        }
+        self.accept('+');
+        self.parse_atom()?;
+        if self.accept('^') {
+            self.parse_power()?;
+            self.code.push(Operation::Binary(Value::powf));
+        }
+        Ok(())
+    }
+
+    /// Parses product or quotient.
+    fn parse_product(&mut self) -> Result<(), ExpressionError> {
+        self.parse_power()?;
+        loop {
+            let operation: fn(Value, Value) -> Value = if self.accept('*') {
+                |left, right| left * right
+            } else if self.accept('/') {
+                |left, right| left / right
+            } else {
+                return Ok(());
+            };
+            self.parse_power()?;
+            self.code.push(Operation::Binary(operation));
+        }
+    }
+
+    /// Parses sum or difference.
+    fn parse_sum(&mut self) -> Result<(), ExpressionError> {
+        self.parse_product()?;
+        loop {
+            let operation: fn(Value, Value) -> Value = if self.accept('+') {
+                |left, right| left + right
+            } else if self.accept('-') {
+                |left, right| left - right
+            } else {
+                return Ok(());
+            };
+            self.parse_product()?;
+            self.code.push(Operation::Binary(operation));
+        }
+    }
+
+    /// Returns next character.
+    fn peek(&self) -> Option<char> {
+        self.formula[self.position..].chars().next()
+    }
+
+    /// Skips whitespace.
+    fn skip_whitespace(&mut self) {
+        self.take_while(char::is_whitespace);
+    }
+
+    /// Consumes characters while they match.
+    fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> &'a str {
+        let rest = &self.formula[self.position..];
+        let length = rest.find(|c| !predicate(c)).unwrap_or(rest.len());
+        self.position += length;
+        &rest[..length]
+    }
+}
+
+/// Unit tests.
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Evaluates an expression with variable values (ordered by name).
+    fn evaluate(formula: &str, values: &[Value]) -> Value {
+        let mut expression = Expression::new(formula).unwrap();
+        for (&input, &value) in expression.list_inputs().to_vec().iter().zip(values) {
+            expression.set_input(input, value);
+        }
+        expression.process();
+        expression.get_output(OutputId(0))
+    }
+
+    #[test]
+    fn evaluates_formula() {
+        let expression = Expression::new("b * sin(a) + 2").unwrap();
+        assert_eq!(expression.variables(), &["a", "b"]);
+        assert_eq!(expression.input("b"), Some(InputId(1)));
+        assert_eq!(expression.list_inputs(), &[InputId(0), InputId(1)]);
+        assert_eq!(evaluate("b * sin(a) + 2", &[std::f64::consts::FRAC_PI_2 as Value, 3.0]), 5.0);
+
+        assert_eq!(evaluate("1 + 2 * 3 - 4 / 2", &[]), 5.0);
+        assert_eq!(evaluate("-2 ^ 2 + 2 ^ 3 ^ 2", &[]), 508.0);
+        assert_eq!(evaluate("(x + 1) * (x - 1)", &[3.0]), 8.0);
+        assert_eq!(evaluate("max(x, 2) + min(abs(-x), sqrt(16)) + ln(e)", &[-1.0]), 4.0);
+        assert_eq!(evaluate("cos(pi)", &[]), -1.0);
+    }
+
+    #[test]
+    fn rejects_invalid_formula() {
+        assert_eq!(Expression::new("a +").err(), Some(ExpressionError::UnexpectedEnd));
+        assert_eq!(Expression::new("a $ b").err(), Some(ExpressionError::UnexpectedCharacter('$', 2)));
+        assert_eq!(Expression::new("(a + b").err(), Some(ExpressionError::UnexpectedEnd));
+        assert_eq!(Expression::new("a b").err(), Some(ExpressionError::UnexpectedCharacter('b', 2)));
+        assert_eq!(Expression::new("1..2").err(), Some(ExpressionError::UnexpectedCharacter('1', 0)));
+        assert_eq!(Expression::new(" foo(1)").err(), Some(ExpressionError::UnknownFunction("foo".to_string(), 1)));
+    }
+}
@@ -0,0 +1,99 @@
+use crate::{InputId, Node, OutputId, Value};
+
+/// Node that processes values with a closure (taking input values and writing output values).
+pub struct FnNode<F: FnMut(&[Value], &mut [Value])> {
+    /// Processing closure.
+    function: F,
+
+    /// Input values.
+    input_values: Vec<Value>,
+
+    /// Available inputs.
+    inputs: Vec<InputId>,
+
+    /// Output values.
+    output_values: Vec<Value>,
+
+    /// Available outputs.
+    outputs: Vec<OutputId>,
+}
+impl<F: FnMut(&[Value], &mut [Value])> FnNode<F> {
+    /// Creates new closure node with given number of inputs and outputs.
+    pub fn new(inputs: u32, outputs: u32, function: F) -> Self {
+        FnNode {
+            function,
+            input_values: vec![0.0; inputs as usize],
+            inputs: (0..inputs).map(InputId).collect(),
+            output_values: vec![0.0; outputs as usize],
+            outputs: (0..outputs).map(OutputId).collect(),
+        }
+    }
+}
+impl<F: FnMut(&[Value], &mut [Value])> Node for FnNode<F> {
+    fn delayed_processing(&self) -> bool {
+        false
+    }
+
+    fn get_output(&self, id: OutputId) -> Value {
+        match self.output_values.get(id.0 as usize) {
+            Some(&value) => value,
+            None => panic!("Output with id {} does not exist.", id.0),
+        }
+    }
+
+    fn list_inputs(&self) -> &[InputId] {
+        &self.inputs
+    }
+
+    fn list_outputs(&self) -> &[OutputId] {
+        &self.outputs
+    }
+
+    fn process(&mut self) {
+        (self.function)(&self.input_values, &mut self.output_values);
+    }
+
+    fn set_input(&mut self, id: InputId, value: Value) {
+        match self.input_values.get_mut(id.0 as usize) {
+            Some(input) => *input = value,
+            None => panic!("Input with id {} does not exist.", id.0),
+        }
+    }
+}
+
+/// Unit tests.
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Connection, Graph};
+
+    #[test]
+    fn calls_closure() {
+        let mut node = FnNode::new(2, 2, |inputs, outputs| {
+            outputs[0] = inputs[0] * inputs[1];
+            outputs[1] = inputs[0] - inputs[1];
+        });
+        assert_eq!(node.list_inputs(), &[InputId(0), InputId(1)]);
+        assert_eq!(node.list_outputs(), &[OutputId(0), OutputId(1)]);
+
+        node.set_input(InputId(0), 3.0);
+        node.set_input(InputId(1), 2.0);
+        node.process();
+        assert_eq!(node.get_output(OutputId(0)), 6.0);
+        assert_eq!(node.get_output(OutputId(1)), 1.0);
+    }
+
+    #[test]
+    fn processes_in_graph() {
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        let mut count = 0.0;
+        let counter = graph.add_node(Box::new(FnNode::new(0, 1, move |_, outputs| {
+            count += 1.0;
+            outputs[0] = count;
+        })));
+        let square = graph.add_node(Box::new(FnNode::new(1, 1, |inputs, outputs| outputs[0] = inputs[0] * inputs[0])));
+        graph.add_connection(Connection::new(counter, OutputId(0), square, InputId(0))).unwrap();
+        graph.process_n(3);
+        assert_eq!(graph.get_output(square, OutputId(0)), Ok(9.0));
+    }
+}
@@ -0,0 +1,113 @@
+use crate::{InputId, Node, OutputId, Value};
+
+/// Node that merges mono inputs (one per channel) into a multichannel output.
+pub struct Merge {
+    /// Available inputs.
+    inputs: Vec<InputId>,
+
+    /// Channel values.
+    values: Vec<Value>,
+}
+impl Merge {
+    /// Creates new merge node with given number of channels.
+    pub fn new(channels: u32) -> Self {
+        Merge { inputs: (0..channels).map(InputId).collect(), values: vec![0.0; channels as usize] }
+    }
+}
+impl Node for Merge {
+    fn arguments(&self) -> Vec<Value> {
+        vec![self.values.len() as Value]
+    }
+
+    fn category(&self) -> &'static str {
+        "routing"
+    }
+
+    fn delayed_processing(&self) -> bool {
+        false
+    }
+
+    fn get_output(&self, id: OutputId) -> Value {
+        self.get_output_channel(id, 0)
+    }
+
+    fn get_output_channel(&self, id: OutputId, channel: usize) -> Value {
+        match (id.0, self.values.get(channel)) {
+            (0, Some(&value)) => value,
+            _ => panic!("Output with id {} and channel {} does not exist.", id.0, channel),
+        }
+    }
+
+    fn is_pure(&self) -> bool {
+        true
+    }
+
+    fn list_inputs(&self) -> &[InputId] {
+        &self.inputs
+    }
+
+    fn list_outputs(&self) -> &[OutputId] {
+        // 0 -> channels.
+        &[OutputId(0)]
+    }
+
+    fn output_channels(&self, _id: OutputId) -> usize {
+        self.values.len()
+    }
+
+    fn output_name(&self, id: OutputId) -> Option<&str> {
+        match id.0 {
+            0 => Some("channels"),
+            _ => None,
+        }
+    }
+
+    fn set_input(&mut self, id: InputId, value: Value) {
+        match self.values.get_mut(id.0 as usize) {
+            Some(input) => *input = value,
+            None => panic!("Input with id {} does not exist.", id.0),
+        }
+    }
+}
+
+/// Unit tests.
+#[cfg(all(test, feature = "generators"))]
+mod tests {
+    use super::*;
+    use crate::{nodes, Connection, Graph, GraphError};
+
+    #[test]
+    fn merges_channels() {
+        let mut merge = Merge::new(2);
+        assert_eq!(merge.output_channels(OutputId(0)), 2);
+        merge.set_input(InputId(0), 1.0);
+        merge.set_input(InputId(1), 2.0);
+        merge.process();
+        assert_eq!(merge.get_output_channel(OutputId(0), 0), 1.0);
+        assert_eq!(merge.get_output_channel(OutputId(0), 1), 2.0);
+    }
+
+    #[test]
+    fn moves_channels_in_graph() {
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        let var0 = graph.add_node(Box::new(nodes::Variable::new(1.0)));
+        let var1 = graph.add_node(Box::new(nodes::Variable::new(2.0)));
+        let merge2 = graph.add_node(Box::new(Merge::new(2)));
+        let split3 = graph.add_node(Box::new(nodes::Split::new(2)));
+        graph.add_connection(Connection::new(var0, OutputId(0), merge2, InputId(0))).unwrap();
+        graph.add_connection(Connection::new(var1, OutputId(0), merge2, InputId(1))).unwrap();
+        graph.add_connection(Connection::new(merge2, OutputId(0), split3, InputId(0))).unwrap();
+        graph.process();
+        assert_eq!(graph.get_output(split3, OutputId(0)), Ok(1.0));
+        assert_eq!(graph.get_output(split3, OutputId(1)), Ok(2.0));
+
+        // Channel counts of connected ports must match.
+        let split4 = graph.add_node(Box::new(nodes::Split::new(3)));
+        let connection = Connection::new(merge2, OutputId(0), split4, InputId(0));
+        assert_eq!(graph.add_connection(connection), Err(GraphError::ChannelMismatch(connection)));
+        let connection = Connection::new(var0, OutputId(0), split3, InputId(0));
+        graph.remove_node(split4).unwrap();
+        graph.remove_connection(Connection::new(merge2, OutputId(0), split3, InputId(0))).unwrap();
+        assert_eq!(graph.add_connection(connection), Err(GraphError::ChannelMismatch(connection)));
+    }
+}
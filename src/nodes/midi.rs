@@ -0,0 +1,233 @@
+use crate::{InputId, Node, OutputId, PortInfo, Value, ValueKind};
+use std::{
+    fmt,
+    sync::{
+        atomic::{AtomicU8, Ordering},
+        Arc,
+    },
+};
+
+/// Node that receives MIDI messages from an input port (on all channels).
+///
+/// Outputs are updated at the start of processing: 0 -> note number, 1 -> velocity (0 to 1), 2 -> gate (1 while a
+/// note is held, 0 otherwise), followed by one output per requested control change number (0 to 1).
+pub struct MidiInput {
+    /// Open port connection (closed when dropped), `None` if only fed by `receive`.
+    connection: Option<midir::MidiInputConnection<()>>,
+
+    /// Requested control change numbers.
+    controls: Vec<u8>,
+
+    /// Names of the outputs (`cc<number>` for control changes).
+    output_names: Vec<String>,
+
+    /// Available outputs.
+    outputs: Vec<OutputId>,
+
+    /// State shared with the MIDI thread.
+    state: Arc<MidiState>,
+
+    /// Output values.
+    values: Vec<Value>,
+}
+impl MidiInput {
+    /// Creates new MIDI input node that is not connected to a port (exposing given control change numbers).
+    pub fn new(controls: &[u8]) -> Self {
+        let outputs = (0..controls.len() as u32 + 3).map(OutputId).collect::<Vec<_>>();
+        MidiInput {
+            connection: None,
+            controls: controls.to_vec(),
+            output_names: ["note", "velocity", "gate"]
+                .into_iter()
+                .map(String::from)
+                .chain(controls.iter().map(|control| format!("cc{}", control)))
+                .collect(),
+            values: vec![0.0; outputs.len()],
+            outputs,
+            state: Arc::new(MidiState::new()),
+        }
+    }
+
+    /// Creates new MIDI input node connected to the first port whose name contains `port` (or the first port if
+    /// `None`), exposing given control change numbers.
+    pub fn open(port: Option<&str>, controls: &[u8]) -> Result<Self, MidiError> {
+        let input = midir::MidiInput::new("flowing").map_err(|error| MidiError::Connect(error.to_string()))?;
+        let selected = input
+            .ports()
+            .into_iter()
+            .find(|candidate| match port {
+                Some(name) => input.port_name(candidate).is_ok_and(|candidate| candidate.contains(name)),
+                None => true,
+            })
+            .ok_or(MidiError::NoPort)?;
+
+        let mut node = MidiInput::new(controls);
+        let state = node.state.clone();
+        let connection = input
+            .connect(&selected, "flowing-input", move |_, message, _| state.receive(message), ())
+            .map_err(|error| MidiError::Connect(error.to_string()))?;
+        node.connection = Some(connection);
+        Ok(node)
+    }
+
+    /// Handles a raw MIDI message (as if received from the port).
+    pub fn receive(&self, message: &[u8]) {
+        self.state.receive(message);
+    }
+}
+impl Node for MidiInput {
+    fn arguments(&self) -> Vec<Value> {
+        self.controls.iter().map(|&control| control as Value).collect()
+    }
+
+    fn category(&self) -> &'static str {
+        "sources"
+    }
+
+    fn delayed_processing(&self) -> bool {
+        false
+    }
+
+    fn get_output(&self, id: OutputId) -> Value {
+        match self.values.get(id.0 as usize) {
+            Some(&value) => value,
+            None => panic!("Output with id {} does not exist.", id.0),
+        }
+    }
+
+    fn list_inputs(&self) -> &[InputId] {
+        &[]
+    }
+
+    fn list_outputs(&self) -> &[OutputId] {
+        // 0 -> note, 1 -> velocity, 2 -> gate, 3.. -> control changes.
+        &self.outputs
+    }
+
+    fn output_info(&self, id: OutputId) -> PortInfo<'_> {
+        let (kind, max) = match id.0 {
+            0 => (ValueKind::Integer, 127.0),
+            2 => (ValueKind::Boolean, 1.0),
+            _ => (ValueKind::Continuous, 1.0),
+        };
+        PortInfo { kind, max, min: 0.0, ..PortInfo::new(self.output_name(id)) }
+    }
+
+    fn output_name(&self, id: OutputId) -> Option<&str> {
+        self.output_names.get(id.0 as usize).map(|name| name.as_str())
+    }
+
+    fn process(&mut self) {
+        let velocity = self.state.velocity.load(Ordering::Acquire);
+        self.values[0] = self.state.note.load(Ordering::Acquire) as Value;
+        self.values[1] = velocity as Value / 127.0;
+        self.values[2] = if velocity > 0 { 1.0 } else { 0.0 };
+        for (value, &control) in self.values[3..].iter_mut().zip(self.controls.iter()) {
+            *value = self.state.controls[control as usize & 0x7f].load(Ordering::Acquire) as Value / 127.0;
+        }
+    }
+
+    fn set_input(&mut self, id: InputId, _value: Value) {
+        panic!("Input with id {} does not exist.", id.0);
+    }
+}
+
+/// MIDI error type.
+#[derive(PartialEq)]
+pub enum MidiError {
+    Connect(String),
+    NoPort,
+}
+impl fmt::Debug for MidiError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MidiError::Connect(message) => write!(f, "MIDI input could not be connected: {}", message),
+            MidiError::NoPort => write!(f, "No matching MIDI input port available."),
+        }
+    }
+}
+
+/// Last received MIDI values (shared between MIDI thread and node).
+struct MidiState {
+    /// Control change values (indexed by controller number).
+    controls: [AtomicU8; 128],
+
+    /// Last pressed note number.
+    note: AtomicU8,
+
+    /// Velocity of the held note (0 if released).
+    velocity: AtomicU8,
+}
+impl MidiState {
+    /// Creates new state without held note.
+    fn new() -> Self {
+        MidiState {
+            controls: std::array::from_fn(|_| AtomicU8::new(0)),
+            note: AtomicU8::new(0),
+            velocity: AtomicU8::new(0),
+        }
+    }
+
+    /// Handles a raw MIDI message (note on, note off and control change, others are ignored).
+    fn receive(&self, message: &[u8]) {
+        match *message {
+            [status, note, velocity] if status & 0xf0 == 0x90 && velocity > 0 => {
+                self.note.store(note & 0x7f, Ordering::Release);
+                self.velocity.store(velocity & 0x7f, Ordering::Release);
+            }
+            // Releasing an earlier note keeps the last pressed one held.
+            [status, note, _]
+                if (status & 0xf0 == 0x80 || status & 0xf0 == 0x90)
+                    && self.note.load(Ordering::Acquire) == note & 0x7f =>
+            {
+                self.velocity.store(0, Ordering::Release);
+            }
+            [status, control, value] if status & 0xf0 == 0xb0 => {
+                self.controls[control as usize & 0x7f].store(value & 0x7f, Ordering::Release);
+            }
+            _ => (),
+        }
+    }
+}
+
+/// Unit tests.
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn receives_notes() {
+        let mut midi = MidiInput::new(&[]);
+        assert_eq!(midi.list_outputs(), &[OutputId(0), OutputId(1), OutputId(2)]);
+
+        midi.receive(&[0x90, 60, 127]);
+        midi.receive(&[0x91, 64, 127]);
+        midi.receive(&[0x80, 60, 0]);
+        midi.process();
+        assert_eq!(midi.get_output(OutputId(0)), 64.0);
+        assert_eq!(midi.get_output(OutputId(1)), 1.0);
+        assert_eq!(midi.get_output(OutputId(2)), 1.0);
+
+        // Note on with zero velocity releases the note.
+        midi.receive(&[0x91, 64, 0]);
+        midi.process();
+        assert_eq!(midi.get_output(OutputId(0)), 64.0);
+        assert_eq!(midi.get_output(OutputId(2)), 0.0);
+    }
+
+    #[test]
+    fn receives_control_changes() {
+        let mut midi = MidiInput::new(&[1, 74]);
+        assert_eq!(midi.list_outputs().len(), 5);
+        assert_eq!(midi.output_name(OutputId(4)), Some("cc74"));
+        assert_eq!(midi.output_info(OutputId(4)).max, 1.0);
+        assert_eq!(midi.output_info(OutputId(0)).kind, ValueKind::Integer);
+
+        midi.receive(&[0xb0, 74, 127]);
+        midi.receive(&[0xb3, 7, 127]);
+        midi.receive(&[0xf8]);
+        midi.process();
+        assert_eq!(midi.get_output(OutputId(3)), 0.0);
+        assert_eq!(midi.get_output(OutputId(4)), 1.0);
+    }
+}
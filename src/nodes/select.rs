@@ -0,0 +1,134 @@
+use crate::{InputId, Node, OutputId, PortInfo, Value, ValueKind};
+
+/// Node that outputs one of a number of values chosen by an index (rounded and clamped to the available values, 0
+/// without values).
+pub struct SelectN {
+    /// Index of the selected value.
+    index: i64,
+
+    /// Available inputs (index first, then values).
+    inputs: Vec<InputId>,
+
+    /// Selected value.
+    selected: Value,
+
+    /// Values to choose from.
+    values: Vec<Value>,
+}
+impl SelectN {
+    /// Creates new select node with given number of values.
+    pub fn new(values: u32) -> Self {
+        SelectN {
+            index: 0,
+            inputs: (0..=values).map(InputId).collect(),
+            selected: 0.0,
+            values: vec![0.0; values as usize],
+        }
+    }
+}
+impl Node for SelectN {
+    fn arguments(&self) -> Vec<Value> {
+        vec![self.values.len() as Value]
+    }
+
+    fn category(&self) -> &'static str {
+        "logic"
+    }
+
+    fn delayed_processing(&self) -> bool {
+        false
+    }
+
+    fn get_output(&self, id: OutputId) -> Value {
+        match id.0 {
+            0 => self.selected,
+            _ => panic!("Output with id {} does not exist.", id.0),
+        }
+    }
+
+    fn input_info(&self, id: InputId) -> PortInfo<'_> {
+        match id.0 {
+            0 => PortInfo {
+                kind: ValueKind::Integer,
+                max: self.values.len().saturating_sub(1) as Value,
+                min: 0.0,
+                ..PortInfo::new(self.input_name(id))
+            },
+            _ => PortInfo::new(None),
+        }
+    }
+
+    fn input_name(&self, id: InputId) -> Option<&str> {
+        match id.0 {
+            0 => Some("index"),
+            _ => None,
+        }
+    }
+
+    fn is_pure(&self) -> bool {
+        true
+    }
+
+    fn list_inputs(&self) -> &[InputId] {
+        // 0 -> index.
+        // 1.. -> values.
+        &self.inputs
+    }
+
+    fn list_outputs(&self) -> &[OutputId] {
+        // 0 -> selected value.
+        &[OutputId(0)]
+    }
+
+    fn output_name(&self, id: OutputId) -> Option<&str> {
+        match id.0 {
+            0 => Some("selected"),
+            _ => None,
+        }
+    }
+
+    fn process(&mut self) {
+        let last = self.values.len() as i64 - 1;
+        self.selected = match last {
+            -1 => 0.0,
+            last => self.values[self.index.clamp(0, last) as usize],
+        };
+    }
+
+    fn set_input(&mut self, id: InputId, value: Value) {
+        match id.0 {
+            0 => self.index = value.round() as i64,
+            index => match self.values.get_mut(index as usize - 1) {
+                Some(input) => *input = value,
+                None => panic!("Input with id {} does not exist.", id.0),
+            },
+        }
+    }
+}
+
+/// Unit tests.
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selects_by_index() {
+        let mut select = SelectN::new(3);
+        for (input, value) in [(1, 10.0), (2, 20.0), (3, 30.0)] {
+            select.set_input(InputId(input), value);
+        }
+        let mut selected = Vec::new();
+        for index in [0.0, 1.4, 1.6, -1.0, 5.0] {
+            select.set_input(InputId(0), index);
+            select.process();
+            selected.push(select.get_output(OutputId(0)));
+        }
+        assert_eq!(selected, [10.0, 20.0, 30.0, 10.0, 30.0]);
+        assert_eq!(select.input_info(InputId(0)).max, 2.0);
+
+        let mut empty = SelectN::new(0);
+        empty.process();
+        assert_eq!(empty.get_output(OutputId(0)), 0.0);
+        assert_eq!(empty.list_inputs(), [InputId(0)]);
+    }
+}
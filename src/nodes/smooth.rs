@@ -0,0 +1,127 @@
+use crate::{InputId, Node, OutputId, PortInfo, ProcessContext, Value};
+
+/// Node that slews towards its input with a time constant in seconds (one-pole lowpass), e.g. to avoid zipper noise
+/// or control loop kicks when parameters are stepped. A time constant of 0 follows the input immediately.
+pub struct Smooth {
+    initial: Value,
+    target: Value,
+    time_constant: Value,
+    value: Value,
+}
+impl Smooth {
+    /// Creates new smoothing node starting at a value with given time constant in seconds.
+    pub fn new(value: Value, time_constant: Value) -> Self {
+        Smooth { initial: value, target: value, time_constant, value }
+    }
+}
+impl Node for Smooth {
+    fn arguments(&self) -> Vec<Value> {
+        vec![self.initial, self.time_constant]
+    }
+
+    fn category(&self) -> &'static str {
+        "time"
+    }
+
+    fn delayed_processing(&self) -> bool {
+        false
+    }
+
+    fn get_output(&self, id: OutputId) -> Value {
+        match id.0 {
+            0 => self.value,
+            _ => panic!("Output with id {} does not exist.", id.0),
+        }
+    }
+
+    fn input_info(&self, id: InputId) -> PortInfo<'_> {
+        match id.0 {
+            1 => PortInfo { min: 0.0, unit: Some("s"), ..PortInfo::new(self.input_name(id)) },
+            _ => PortInfo::new(self.input_name(id)),
+        }
+    }
+
+    fn input_name(&self, id: InputId) -> Option<&str> {
+        match id.0 {
+            0 => Some("target"),
+            1 => Some("time_constant"),
+            _ => None,
+        }
+    }
+
+    fn list_inputs(&self) -> &[InputId] {
+        // 0 -> target.
+        // 1 -> time constant.
+        &[InputId(0), InputId(1)]
+    }
+
+    fn list_outputs(&self) -> &[OutputId] {
+        // 0 -> smoothed value.
+        &[OutputId(0)]
+    }
+
+    fn output_name(&self, id: OutputId) -> Option<&str> {
+        match id.0 {
+            0 => Some("smoothed"),
+            _ => None,
+        }
+    }
+
+    fn process_with_context(&mut self, context: &ProcessContext) {
+        let coefficient = match self.time_constant > 0.0 {
+            true => 1.0 - (-(context.dt as Value) / self.time_constant).exp(),
+            false => 1.0,
+        };
+        self.value += (self.target - self.value) * coefficient;
+    }
+
+    fn reset(&mut self) {
+        self.value = self.initial;
+    }
+
+    fn restore(&mut self, snapshot: &[Value]) {
+        if let &[value] = snapshot {
+            self.value = value;
+        }
+    }
+
+    fn set_input(&mut self, id: InputId, value: Value) {
+        match id.0 {
+            0 => self.target = value,
+            1 => self.time_constant = value,
+            _ => panic!("Input with id {} does not exist.", id.0),
+        }
+    }
+
+    fn snapshot(&self) -> Vec<Value> {
+        vec![self.value]
+    }
+}
+
+/// Unit tests.
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slews_towards_target() {
+        let context = ProcessContext::new(100.0);
+        let mut smooth = Smooth::new(0.0, 0.1);
+        smooth.set_input(InputId(0), 1.0);
+        for _ in 0..10 {
+            smooth.process_with_context(&context);
+        }
+
+        // After one time constant, 1 - 1/e of the step is reached.
+        let expected = 1.0 - (-1.0 as Value).exp();
+        assert!((smooth.get_output(OutputId(0)) - expected).abs() < 1e-6);
+        assert_eq!(smooth.snapshot().len(), 1);
+
+        smooth.set_input(InputId(1), 0.0);
+        smooth.process_with_context(&context);
+        assert_eq!(smooth.get_output(OutputId(0)), 1.0);
+
+        smooth.reset();
+        assert_eq!(smooth.get_output(OutputId(0)), 0.0);
+    }
+}
@@ -0,0 +1,90 @@
+use crate::{InputId, Node, OutputId, Value};
+
+/// Node that splits a multichannel input into mono outputs (one per channel).
+pub struct Split {
+    /// Available outputs.
+    outputs: Vec<OutputId>,
+
+    /// Channel values.
+    values: Vec<Value>,
+}
+impl Split {
+    /// Creates new split node with given number of channels.
+    pub fn new(channels: u32) -> Self {
+        Split { outputs: (0..channels).map(OutputId).collect(), values: vec![0.0; channels as usize] }
+    }
+}
+impl Node for Split {
+    fn arguments(&self) -> Vec<Value> {
+        vec![self.values.len() as Value]
+    }
+
+    fn category(&self) -> &'static str {
+        "routing"
+    }
+
+    fn delayed_processing(&self) -> bool {
+        false
+    }
+
+    fn get_output(&self, id: OutputId) -> Value {
+        match self.values.get(id.0 as usize) {
+            Some(&value) => value,
+            None => panic!("Output with id {} does not exist.", id.0),
+        }
+    }
+
+    fn input_channels(&self, _id: InputId) -> usize {
+        self.values.len()
+    }
+
+    fn input_name(&self, id: InputId) -> Option<&str> {
+        match id.0 {
+            0 => Some("channels"),
+            _ => None,
+        }
+    }
+
+    fn is_pure(&self) -> bool {
+        true
+    }
+
+    fn list_inputs(&self) -> &[InputId] {
+        // 0 -> channels.
+        &[InputId(0)]
+    }
+
+    fn list_outputs(&self) -> &[OutputId] {
+        &self.outputs
+    }
+
+    fn set_input(&mut self, id: InputId, value: Value) {
+        self.set_input_channel(id, 0, value);
+    }
+
+    fn set_input_channel(&mut self, id: InputId, channel: usize, value: Value) {
+        match (id.0, self.values.get_mut(channel)) {
+            (0, Some(input)) => *input = value,
+            _ => panic!("Input with id {} and channel {} does not exist.", id.0, channel),
+        }
+    }
+}
+
+/// Unit tests.
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_channels() {
+        let mut split = Split::new(2);
+        assert_eq!(split.input_channels(InputId(0)), 2);
+        assert_eq!(split.list_outputs(), &[OutputId(0), OutputId(1)]);
+
+        split.set_input_channel(InputId(0), 0, 1.0);
+        split.set_input_channel(InputId(0), 1, 2.0);
+        split.process();
+        assert_eq!(split.get_output(OutputId(0)), 1.0);
+        assert_eq!(split.get_output(OutputId(1)), 2.0);
+    }
+}
@@ -0,0 +1,165 @@
+use crate::{InputId, Node, OutputId, Value};
+
+/// Node that receives an external value of a graph by index (see `Graph::set_external_input`).
+pub struct GraphInput {
+    index: usize,
+    value: Value,
+}
+impl GraphInput {
+    /// Creates new graph input node with index.
+    pub fn new(index: usize) -> Self {
+        GraphInput { index, value: 0.0 }
+    }
+}
+impl Node for GraphInput {
+    fn arguments(&self) -> Vec<Value> {
+        vec![self.index as Value]
+    }
+
+    fn category(&self) -> &'static str {
+        "terminals"
+    }
+
+    fn delayed_processing(&self) -> bool {
+        false
+    }
+
+    fn get_output(&self, id: OutputId) -> Value {
+        match id.0 {
+            0 => self.value,
+            _ => panic!("Output with id {} does not exist.", id.0),
+        }
+    }
+
+    fn graph_input(&self) -> Option<usize> {
+        Some(self.index)
+    }
+
+    fn input_name(&self, id: InputId) -> Option<&str> {
+        match id.0 {
+            0 => Some("value"),
+            _ => None,
+        }
+    }
+
+    fn list_inputs(&self) -> &[InputId] {
+        // 0 -> value.
+        &[InputId(0)]
+    }
+
+    fn list_outputs(&self) -> &[OutputId] {
+        // 0 -> value.
+        &[OutputId(0)]
+    }
+
+    fn output_name(&self, id: OutputId) -> Option<&str> {
+        match id.0 {
+            0 => Some("value"),
+            _ => None,
+        }
+    }
+
+    fn process(&mut self) {
+        // Passthrough noop.
+    }
+
+    fn set_input(&mut self, id: InputId, value: Value) {
+        match id.0 {
+            0 => self.value = value,
+            _ => panic!("Input with id {} does not exist.", id.0),
+        }
+    }
+}
+
+/// Node that exposes a value of a graph externally by index (see `Graph::external_output`).
+pub struct GraphOutput {
+    index: usize,
+    input: Value,
+    value: Value,
+}
+impl GraphOutput {
+    /// Creates new graph output node with index.
+    pub fn new(index: usize) -> Self {
+        GraphOutput { index, input: 0.0, value: 0.0 }
+    }
+}
+impl Node for GraphOutput {
+    fn arguments(&self) -> Vec<Value> {
+        vec![self.index as Value]
+    }
+
+    fn category(&self) -> &'static str {
+        "terminals"
+    }
+
+    fn delayed_processing(&self) -> bool {
+        false
+    }
+
+    fn get_output(&self, id: OutputId) -> Value {
+        match id.0 {
+            0 => self.value,
+            _ => panic!("Output with id {} does not exist.", id.0),
+        }
+    }
+
+    fn graph_output(&self) -> Option<usize> {
+        Some(self.index)
+    }
+
+    fn input_name(&self, id: InputId) -> Option<&str> {
+        match id.0 {
+            0 => Some("value"),
+            _ => None,
+        }
+    }
+
+    fn list_inputs(&self) -> &[InputId] {
+        // 0 -> value.
+        &[InputId(0)]
+    }
+
+    fn list_outputs(&self) -> &[OutputId] {
+        // 0 -> value.
+        &[OutputId(0)]
+    }
+
+    fn output_name(&self, id: OutputId) -> Option<&str> {
+        match id.0 {
+            0 => Some("value"),
+            _ => None,
+        }
+    }
+
+    fn process(&mut self) {
+        self.value = self.input;
+    }
+
+    fn set_input(&mut self, id: InputId, value: Value) {
+        match id.0 {
+            0 => self.input = value,
+            _ => panic!("Input with id {} does not exist.", id.0),
+        }
+    }
+}
+
+/// Unit tests.
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn passes_values() {
+        let mut input = GraphInput::new(1);
+        input.set_input(InputId(0), 2.0);
+        assert_eq!(input.get_output(OutputId(0)), 2.0);
+        assert_eq!((input.graph_input(), input.graph_output()), (Some(1), None));
+
+        let mut output = GraphOutput::new(0);
+        output.set_input(InputId(0), 3.0);
+        assert_eq!(output.get_output(OutputId(0)), 0.0);
+        output.process();
+        assert_eq!(output.get_output(OutputId(0)), 3.0);
+        assert_eq!((output.graph_input(), output.graph_output()), (None, Some(0)));
+    }
+}
@@ -1,27 +1,60 @@
-use crate::{InputId, Node, OutputId};
+use crate::{InputId, Node, OutputId, PortInfo, Value};
 
-/// Node that holds a variable value.
+/// Node that holds a variable value, optionally clamped to a range (e.g. bound to a slider).
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Variable {
-    value: f64,
+    /// Range (minimum, maximum) that set values are clamped to.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    range: Option<(Value, Value)>,
+
+    value: Value,
 }
 impl Variable {
     /// Creates new variable node with initial value.
-    pub fn new(value: f64) -> Self {
-        Variable { value }
+    pub fn new(value: Value) -> Self {
+        Variable { range: None, value }
+    }
+
+    /// Creates new variable node with initial value that clamps set values to a range (exposed as port metadata).
+    pub fn with_range(value: Value, min: Value, max: Value) -> Self {
+        let mut variable = Variable { range: Some((min, max)), value: 0.0 };
+        variable.set_input(InputId(0), value);
+        variable
+    }
+
+    /// Returns port metadata with the range of the variable.
+    fn info<'a>(&self, name: Option<&'a str>) -> PortInfo<'a> {
+        let (min, max) = self.range.unwrap_or((Value::NEG_INFINITY, Value::INFINITY));
+        PortInfo { max, min, ..PortInfo::new(name) }
     }
 }
 impl Node for Variable {
+    fn category(&self) -> &'static str {
+        "sources"
+    }
+
     fn delayed_processing(&self) -> bool {
         false
     }
 
-    fn get_output(&self, id: OutputId) -> f64 {
+    fn get_output(&self, id: OutputId) -> Value {
         match id.0 {
             0 => self.value,
             _ => panic!("Output with id {} does not exist.", id.0),
         }
     }
 
+    fn input_info(&self, id: InputId) -> PortInfo<'_> {
+        self.info(self.input_name(id))
+    }
+
+    fn input_name(&self, id: InputId) -> Option<&str> {
+        match id.0 {
+            0 => Some("value"),
+            _ => None,
+        }
+    }
+
     fn list_inputs(&self) -> &[InputId] {
         // 0 -> value.
         &[InputId(0)]
@@ -32,16 +65,42 @@ impl Node for Variable {
         &[OutputId(0)]
     }
 
+    fn output_info(&self, id: OutputId) -> PortInfo<'_> {
+        self.info(self.output_name(id))
+    }
+
+    fn output_name(&self, id: OutputId) -> Option<&str> {
+        match id.0 {
+            0 => Some("value"),
+            _ => None,
+        }
+    }
+
     fn process(&mut self) {
         // Passthrough noop.
     }
 
-    fn set_input(&mut self, id: InputId, value: f64) {
+    fn restore(&mut self, snapshot: &[Value]) {
+        if let &[value] = snapshot {
+            self.value = value;
+        }
+    }
+
+    fn set_input(&mut self, id: InputId, value: Value) {
         match id.0 {
-            0 => self.value = value,
+            0 => {
+                self.value = match self.range {
+                    Some((min, max)) => value.max(min).min(max),
+                    None => value,
+                }
+            }
             _ => panic!("Input with id {} does not exist.", id.0),
         }
     }
+
+    fn snapshot(&self) -> Vec<Value> {
+        vec![self.value]
+    }
 }
 
 /// Unit tests.
@@ -57,4 +116,27 @@ mod tests {
         var.set_input(InputId(0), 2.0);
         assert_eq!(var.get_output(OutputId(0)), 2.0);
     }
+
+    #[test]
+    fn clamps_to_range() {
+        let mut var = Variable::with_range(5.0, -1.0, 1.0);
+        assert_eq!(var.get_output(OutputId(0)), 1.0);
+        var.set_input(InputId(0), -0.5);
+        assert_eq!(var.get_output(OutputId(0)), -0.5);
+        var.set_input(InputId(0), -3.0);
+        assert_eq!(var.get_output(OutputId(0)), -1.0);
+        assert_eq!(var.input_info(InputId(0)).max, 1.0);
+        assert_eq!(var.output_info(OutputId(0)).min, -1.0);
+        assert_eq!(Variable::new(0.0).input_info(InputId(0)).max, Value::INFINITY);
+    }
+
+    #[test]
+    fn restores_snapshot() {
+        let var = Variable::new(42.0);
+        assert_eq!(var.snapshot(), vec![42.0]);
+
+        let mut restored = Variable::new(0.0);
+        restored.restore(&var.snapshot());
+        assert_eq!(restored.get_output(OutputId(0)), 42.0);
+    }
 }
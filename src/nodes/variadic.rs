@@ -0,0 +1,423 @@
+use crate::{InputId, Node, OutputId, PortInfo, Value};
+
+/// Inputs of a variadic node (count chosen at construction, ids are cached for `list_inputs`).
+struct Inputs {
+    /// Available inputs.
+    ids: Vec<InputId>,
+
+    /// Input values.
+    values: Vec<Value>,
+}
+impl Inputs {
+    /// Creates given number of inputs with value 0.
+    fn new(count: u32) -> Self {
+        Inputs { ids: (0..count).map(InputId).collect(), values: vec![0.0; count as usize] }
+    }
+
+    /// Sets input value.
+    fn set(&mut self, id: InputId, value: Value) {
+        match self.values.get_mut(id.0 as usize) {
+            Some(input) => *input = value,
+            None => panic!("Input with id {} does not exist.", id.0),
+        }
+    }
+}
+
+/// Node that averages a number of values (0 without inputs).
+pub struct AverageN {
+    average: Value,
+    inputs: Inputs,
+}
+impl AverageN {
+    /// Creates new average node with given number of inputs.
+    pub fn new(inputs: u32) -> Self {
+        AverageN { average: 0.0, inputs: Inputs::new(inputs) }
+    }
+}
+impl Node for AverageN {
+    fn arguments(&self) -> Vec<Value> {
+        vec![self.inputs.ids.len() as Value]
+    }
+
+    fn category(&self) -> &'static str {
+        "math"
+    }
+
+    fn delayed_processing(&self) -> bool {
+        false
+    }
+
+    fn get_output(&self, id: OutputId) -> Value {
+        match id.0 {
+            0 => self.average,
+            _ => panic!("Output with id {} does not exist.", id.0),
+        }
+    }
+
+    fn is_pure(&self) -> bool {
+        true
+    }
+
+    fn list_inputs(&self) -> &[InputId] {
+        &self.inputs.ids
+    }
+
+    fn list_outputs(&self) -> &[OutputId] {
+        // 0 -> average.
+        &[OutputId(0)]
+    }
+
+    fn output_name(&self, id: OutputId) -> Option<&str> {
+        match id.0 {
+            0 => Some("average"),
+            _ => None,
+        }
+    }
+
+    fn process(&mut self) {
+        let values = &self.inputs.values;
+        self.average = match values.len() {
+            0 => 0.0,
+            count => values.iter().sum::<Value>() / count as Value,
+        };
+    }
+
+    fn set_input(&mut self, id: InputId, value: Value) {
+        self.inputs.set(id, value);
+    }
+}
+
+/// Node that multiplies a number of values (1 without inputs).
+pub struct ProductN {
+    inputs: Inputs,
+    product: Value,
+}
+impl ProductN {
+    /// Creates new product node with given number of inputs.
+    pub fn new(inputs: u32) -> Self {
+        ProductN { inputs: Inputs::new(inputs), product: 0.0 }
+    }
+}
+impl Node for ProductN {
+    fn arguments(&self) -> Vec<Value> {
+        vec![self.inputs.ids.len() as Value]
+    }
+
+    fn category(&self) -> &'static str {
+        "math"
+    }
+
+    fn delayed_processing(&self) -> bool {
+        false
+    }
+
+    fn get_output(&self, id: OutputId) -> Value {
+        match id.0 {
+            0 => self.product,
+            _ => panic!("Output with id {} does not exist.", id.0),
+        }
+    }
+
+    fn is_pure(&self) -> bool {
+        true
+    }
+
+    fn list_inputs(&self) -> &[InputId] {
+        &self.inputs.ids
+    }
+
+    fn list_outputs(&self) -> &[OutputId] {
+        // 0 -> product.
+        &[OutputId(0)]
+    }
+
+    fn output_name(&self, id: OutputId) -> Option<&str> {
+        match id.0 {
+            0 => Some("product"),
+            _ => None,
+        }
+    }
+
+    fn process(&mut self) {
+        self.product = self.inputs.values.iter().product();
+    }
+
+    fn set_input(&mut self, id: InputId, value: Value) {
+        self.inputs.set(id, value);
+    }
+}
+
+/// Node that multiplies a number of values and clamps the product to a range (e.g. an actuator command path).
+pub struct SaturatingProductN {
+    inputs: Inputs,
+    max: Value,
+    min: Value,
+    product: Value,
+}
+impl SaturatingProductN {
+    /// Creates new saturating product node with given number of inputs and output range.
+    pub fn new(inputs: u32, min: Value, max: Value) -> Self {
+        SaturatingProductN { inputs: Inputs::new(inputs), max, min, product: 0.0 }
+    }
+}
+impl Node for SaturatingProductN {
+    fn arguments(&self) -> Vec<Value> {
+        vec![self.inputs.ids.len() as Value, self.min, self.max]
+    }
+
+    fn category(&self) -> &'static str {
+        "math"
+    }
+
+    fn delayed_processing(&self) -> bool {
+        false
+    }
+
+    fn get_output(&self, id: OutputId) -> Value {
+        match id.0 {
+            0 => self.product,
+            _ => panic!("Output with id {} does not exist.", id.0),
+        }
+    }
+
+    fn is_pure(&self) -> bool {
+        true
+    }
+
+    fn list_inputs(&self) -> &[InputId] {
+        &self.inputs.ids
+    }
+
+    fn list_outputs(&self) -> &[OutputId] {
+        // 0 -> product.
+        &[OutputId(0)]
+    }
+
+    fn output_info(&self, id: OutputId) -> PortInfo<'_> {
+        PortInfo { max: self.max, min: self.min, ..PortInfo::new(self.output_name(id)) }
+    }
+
+    fn output_name(&self, id: OutputId) -> Option<&str> {
+        match id.0 {
+            0 => Some("product"),
+            _ => None,
+        }
+    }
+
+    fn process(&mut self) {
+        self.product = saturate(self.inputs.values.iter().product(), self.min, self.max);
+    }
+
+    fn set_input(&mut self, id: InputId, value: Value) {
+        self.inputs.set(id, value);
+    }
+}
+
+/// Node that adds a number of values and clamps the sum to a range (e.g. a limiting mixer).
+pub struct SaturatingSumN {
+    inputs: Inputs,
+    max: Value,
+    min: Value,
+    sum: Value,
+}
+impl SaturatingSumN {
+    /// Creates new saturating sum node with given number of inputs and output range.
+    pub fn new(inputs: u32, min: Value, max: Value) -> Self {
+        SaturatingSumN { inputs: Inputs::new(inputs), max, min, sum: 0.0 }
+    }
+}
+impl Node for SaturatingSumN {
+    fn arguments(&self) -> Vec<Value> {
+        vec![self.inputs.ids.len() as Value, self.min, self.max]
+    }
+
+    fn category(&self) -> &'static str {
+        "math"
+    }
+
+    fn delayed_processing(&self) -> bool {
+        false
+    }
+
+    fn get_output(&self, id: OutputId) -> Value {
+        match id.0 {
+            0 => self.sum,
+            _ => panic!("Output with id {} does not exist.", id.0),
+        }
+    }
+
+    fn is_pure(&self) -> bool {
+        true
+    }
+
+    fn list_inputs(&self) -> &[InputId] {
+        &self.inputs.ids
+    }
+
+    fn list_outputs(&self) -> &[OutputId] {
+        // 0 -> sum.
+        &[OutputId(0)]
+    }
+
+    fn output_info(&self, id: OutputId) -> PortInfo<'_> {
+        PortInfo { max: self.max, min: self.min, ..PortInfo::new(self.output_name(id)) }
+    }
+
+    fn output_name(&self, id: OutputId) -> Option<&str> {
+        match id.0 {
+            0 => Some("sum"),
+            _ => None,
+        }
+    }
+
+    fn process(&mut self) {
+        self.sum = saturate(self.inputs.values.iter().sum(), self.min, self.max);
+    }
+
+    fn set_input(&mut self, id: InputId, value: Value) {
+        self.inputs.set(id, value);
+    }
+}
+
+/// Node that adds a number of values (e.g. a mixer of many sources).
+pub struct SumN {
+    inputs: Inputs,
+    sum: Value,
+}
+impl SumN {
+    /// Creates new sum node with given number of inputs.
+    pub fn new(inputs: u32) -> Self {
+        SumN { inputs: Inputs::new(inputs), sum: 0.0 }
+    }
+}
+impl Node for SumN {
+    fn arguments(&self) -> Vec<Value> {
+        vec![self.inputs.ids.len() as Value]
+    }
+
+    fn category(&self) -> &'static str {
+        "math"
+    }
+
+    fn delayed_processing(&self) -> bool {
+        false
+    }
+
+    fn get_output(&self, id: OutputId) -> Value {
+        match id.0 {
+            0 => self.sum,
+            _ => panic!("Output with id {} does not exist.", id.0),
+        }
+    }
+
+    fn is_pure(&self) -> bool {
+        true
+    }
+
+    fn list_inputs(&self) -> &[InputId] {
+        &self.inputs.ids
+    }
+
+    fn list_outputs(&self) -> &[OutputId] {
+        // 0 -> sum.
+        &[OutputId(0)]
+    }
+
+    fn output_name(&self, id: OutputId) -> Option<&str> {
+        match id.0 {
+            0 => Some("sum"),
+            _ => None,
+        }
+    }
+
+    fn process(&mut self) {
+        self.sum = self.inputs.values.iter().sum();
+    }
+
+    fn set_input(&mut self, id: InputId, value: Value) {
+        self.inputs.set(id, value);
+    }
+}
+
+/// Clamps a value to a range without panicking (NaN saturates to the minimum).
+fn saturate(value: Value, min: Value, max: Value) -> Value {
+    value.max(min).min(max)
+}
+
+impl From<ProductN> for Box<dyn Node> {
+    fn from(node: ProductN) -> Self {
+        Box::new(node)
+    }
+}
+impl From<ProductN> for Box<dyn Node + Send> {
+    fn from(node: ProductN) -> Self {
+        Box::new(node)
+    }
+}
+impl From<SumN> for Box<dyn Node> {
+    fn from(node: SumN) -> Self {
+        Box::new(node)
+    }
+}
+impl From<SumN> for Box<dyn Node + Send> {
+    fn from(node: SumN) -> Self {
+        Box::new(node)
+    }
+}
+
+/// Unit tests.
+#[cfg(all(test, feature = "generators"))]
+mod tests {
+    use super::*;
+    use crate::{nodes, Connection, Graph};
+
+    #[test]
+    fn reduces_inputs() {
+        let (mut average, mut product, mut sum) = (AverageN::new(3), ProductN::new(3), SumN::new(3));
+        assert_eq!(sum.list_inputs(), &[InputId(0), InputId(1), InputId(2)]);
+        for (index, value) in [1.0, 2.0, 6.0].into_iter().enumerate() {
+            average.set_input(InputId(index as u32), value);
+            product.set_input(InputId(index as u32), value);
+            sum.set_input(InputId(index as u32), value);
+        }
+        average.process();
+        product.process();
+        sum.process();
+        assert_eq!(average.get_output(OutputId(0)), 3.0);
+        assert_eq!(product.get_output(OutputId(0)), 12.0);
+        assert_eq!(sum.get_output(OutputId(0)), 9.0);
+
+        // Without inputs.
+        let mut average = AverageN::new(0);
+        average.process();
+        assert_eq!(average.get_output(OutputId(0)), 0.0);
+    }
+
+    #[test]
+    fn saturates() {
+        let (mut product, mut sum) = (SaturatingProductN::new(2, -1.0, 1.0), SaturatingSumN::new(2, -1.0, 1.0));
+        for (inputs, expected) in
+            [((0.5, 0.5), (0.25, 1.0)), ((-4.0, 2.0), (-1.0, -1.0)), ((Value::NAN, 0.0), (-1.0, -1.0))]
+        {
+            for node in [&mut product as &mut dyn Node, &mut sum] {
+                node.set_input(InputId(0), inputs.0);
+                node.set_input(InputId(1), inputs.1);
+                node.process();
+            }
+            assert_eq!((product.get_output(OutputId(0)), sum.get_output(OutputId(0))), expected);
+        }
+        assert_eq!(sum.output_info(OutputId(0)).max, 1.0);
+    }
+
+    #[test]
+    fn mixes_in_graph() {
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        let sum0 = graph.add_node(Box::new(SumN::new(8)));
+        for input in 0..8 {
+            let var = graph.add_node(Box::new(nodes::Variable::new(input as Value)));
+            graph.add_connection(Connection::new(var, OutputId(0), sum0, InputId(input))).unwrap();
+        }
+        graph.process();
+        assert_eq!(graph.get_output(sum0, OutputId(0)), Ok(28.0));
+    }
+}
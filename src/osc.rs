@@ -0,0 +1,228 @@
+use crate::{Graph, GraphError, InputId, Node, NodeId, Value};
+use std::{
+    fmt, io,
+    net::{SocketAddr, ToSocketAddrs, UdpSocket},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver, Sender},
+        Arc,
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+/// Server that receives OSC messages over UDP and maps them to node inputs.
+///
+/// Messages are addressed as `/node/<label or id>/<input name or id>` with a single numeric (`f`, `d`, `i`, `h`) or
+/// boolean (`T`, `F`) argument. Received values are queued and applied to a graph with `apply` between cycles.
+pub struct OscServer {
+    /// Local address of the socket.
+    address: SocketAddr,
+
+    /// Queue of received messages (address and value).
+    messages: Receiver<(String, f64)>,
+
+    /// Whether the receiving thread keeps running.
+    running: Arc<AtomicBool>,
+
+    /// Receiving thread (joined when dropped).
+    thread: Option<JoinHandle<()>>,
+}
+impl OscServer {
+    /// Binds new server to an address and starts receiving.
+    pub fn bind(address: impl ToSocketAddrs) -> io::Result<Self> {
+        let socket = UdpSocket::bind(address)?;
+        socket.set_read_timeout(Some(Duration::from_millis(50)))?;
+        let address = socket.local_addr()?;
+        let running = Arc::new(AtomicBool::new(true));
+        let (sender, messages) = mpsc::channel();
+        let thread = {
+            let running = running.clone();
+            thread::spawn(move || receive(socket, sender, running))
+        };
+        Ok(OscServer { address, messages, running, thread: Some(thread) })
+    }
+
+    /// Applies all received messages to node inputs of a graph (call between cycles). Returns errors of messages
+    /// that could not be applied.
+    pub fn apply<N: Node>(&self, graph: &mut Graph<N>) -> Vec<OscError> {
+        let mut errors = Vec::new();
+        for (address, value) in self.messages.try_iter() {
+            let result = match resolve(graph, &address) {
+                Some(Ok((node, input))) => graph.set_input(node, input, value as Value).map_err(OscError::Graph),
+                Some(Err(error)) => Err(OscError::Graph(error)),
+                None => Err(OscError::AddressNotMapped(address)),
+            };
+            if let Err(error) = result {
+                errors.push(error);
+            }
+        }
+        errors
+    }
+
+    /// Returns local address the server is bound to.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.address
+    }
+}
+impl Drop for OscServer {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// OSC error type.
+#[derive(PartialEq)]
+pub enum OscError {
+    AddressNotMapped(String),
+    Graph(GraphError),
+}
+impl fmt::Debug for OscError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OscError::AddressNotMapped(address) => write!(f, "OSC address {} is not mapped to a node input.", address),
+            OscError::Graph(error) => write!(f, "{:?}", error),
+        }
+    }
+}
+
+/// Receives packets until the server is dropped.
+fn receive(socket: UdpSocket, sender: Sender<(String, f64)>, running: Arc<AtomicBool>) {
+    let mut packet = [0; 65536];
+    let mut messages = Vec::new();
+    while running.load(Ordering::Relaxed) {
+        if let Ok(length) = socket.recv(&mut packet) {
+            // Malformed packets are dropped.
+            decode(&packet[..length], &mut messages);
+            for message in messages.drain(..) {
+                if sender.send(message).is_err() {
+                    return;
+                }
+            }
+        }
+    }
+}
+
+/// Resolves node and input of an address (`None` if not a node address).
+fn resolve<N: Node>(graph: &Graph<N>, address: &str) -> Option<Result<(NodeId, InputId), GraphError>> {
+    let mut parts = address.strip_prefix("/node/")?.split('/');
+    let (node, input) = (parts.next()?, parts.next()?);
+    if parts.next().is_some() {
+        return None;
+    }
+    let node = match graph.find_node(node) {
+        Ok(id) => Ok(id),
+        Err(error) => node.parse().map(NodeId).map_err(|_| error),
+    };
+    Some(node.and_then(|node| Ok((node, graph.find_input(node, input)?))))
+}
+
+/// Decodes messages (address and first argument) of an OSC packet, returns `None` if malformed.
+fn decode(packet: &[u8], messages: &mut Vec<(String, f64)>) -> Option<()> {
+    if let Some(mut elements) = packet.strip_prefix(b"#bundle\0") {
+        // Time tags are ignored, bundled messages are applied immediately.
+        elements = elements.get(8..)?;
+        while !elements.is_empty() {
+            let size = u32::from_be_bytes(elements.get(..4)?.try_into().ok()?) as usize;
+            let (element, rest) = elements[4..].split_at_checked(size)?;
+            decode(element, messages)?;
+            elements = rest;
+        }
+        return Some(());
+    }
+
+    let mut position = 0;
+    let address = read_string(packet, &mut position)?;
+    let tags = read_string(packet, &mut position)?.strip_prefix(',')?;
+    let argument = packet.get(position..).unwrap_or(&[]);
+    let value = match tags.chars().next()? {
+        'd' => f64::from_be_bytes(argument.get(..8)?.try_into().ok()?),
+        'f' => f32::from_be_bytes(argument.get(..4)?.try_into().ok()?) as f64,
+        'h' => i64::from_be_bytes(argument.get(..8)?.try_into().ok()?) as f64,
+        'i' => i32::from_be_bytes(argument.get(..4)?.try_into().ok()?) as f64,
+        'F' => 0.0,
+        'T' => 1.0,
+        _ => return None,
+    };
+    messages.push((address.to_string(), value));
+    Some(())
+}
+
+/// Reads a null-terminated string padded to 4 bytes.
+fn read_string<'a>(packet: &'a [u8], position: &mut usize) -> Option<&'a str> {
+    let data = packet.get(*position..)?;
+    let length = data.iter().position(|&byte| byte == 0)?;
+    *position += (length + 4) & !3;
+    std::str::from_utf8(&data[..length]).ok()
+}
+
+/// Unit tests.
+#[cfg(all(test, feature = "generators"))]
+mod tests {
+    use super::*;
+    use crate::{nodes, OutputId};
+    use std::time::Instant;
+
+    /// Encodes an OSC message with a float argument.
+    fn encode(address: &str, value: f32) -> Vec<u8> {
+        let mut packet = Vec::new();
+        for string in [address, ",f"] {
+            packet.extend_from_slice(string.as_bytes());
+            packet.resize((packet.len() + 4) & !3, 0);
+        }
+        packet.extend_from_slice(&value.to_be_bytes());
+        packet
+    }
+
+    #[test]
+    fn decodes_packets() {
+        let mut messages = Vec::new();
+        assert_eq!(decode(&encode("/node/gain/0", 0.5), &mut messages), Some(()));
+        assert_eq!(messages, vec![("/node/gain/0".to_string(), 0.5)]);
+
+        let message = encode("/node/1/0", 2.0);
+        let mut bundle = b"#bundle\0\0\0\0\0\0\0\0\x01".to_vec();
+        bundle.extend_from_slice(&(message.len() as i32).to_be_bytes());
+        bundle.extend_from_slice(&message);
+        messages.clear();
+        assert_eq!(decode(&bundle, &mut messages), Some(()));
+        assert_eq!(messages, vec![("/node/1/0".to_string(), 2.0)]);
+
+        assert_eq!(decode(b"/node", &mut messages), None);
+        assert_eq!(decode(&encode("/node/gain/0", 0.5)[..16], &mut messages), None);
+    }
+
+    #[test]
+    fn applies_messages() {
+        let mut graph: Graph<nodes::Variable> = Graph::new();
+        let var0 = graph.add_node(nodes::Variable::new(0.0));
+        let var1 = graph.add_node(nodes::Variable::new(0.0));
+        graph.set_label(var0, Some("gain")).unwrap();
+
+        let server = OscServer::bind("127.0.0.1:0").unwrap();
+        let client = UdpSocket::bind("127.0.0.1:0").unwrap();
+        for (address, value) in [("/node/gain/value", 0.5), ("/node/1/value", 2.0), ("/node/1/3", 1.0), ("/lfo", 1.0)] {
+            client.send_to(&encode(address, value), server.local_addr()).unwrap();
+        }
+
+        let mut errors = Vec::new();
+        let start = Instant::now();
+        while errors.len() < 2 && start.elapsed() < Duration::from_secs(5) {
+            errors.extend(server.apply(&mut graph));
+            thread::sleep(Duration::from_millis(1));
+        }
+        assert_eq!(
+            errors,
+            vec![
+                OscError::Graph(GraphError::PortNotExists(var1, "3".into())),
+                OscError::AddressNotMapped("/lfo".into())
+            ]
+        );
+        graph.process();
+        assert_eq!(graph.get_output(var0, OutputId(0)), Ok(0.5));
+        assert_eq!(graph.get_output(var1, OutputId(0)), Ok(2.0));
+    }
+}
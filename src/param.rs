@@ -0,0 +1,94 @@
+use crate::Value;
+#[cfg(feature = "f32")]
+use std::sync::atomic::AtomicU32 as AtomicBits;
+#[cfg(not(feature = "f32"))]
+use std::sync::atomic::AtomicU64 as AtomicBits;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+/// Thread-safe handle for updating a node input while the graph is processed on another thread.
+///
+/// Values are applied at the start of the next cycle (or block), only the latest value is applied.
+#[derive(Clone)]
+pub struct ParamHandle {
+    /// Value shared with the graph.
+    shared: Arc<SharedParam>,
+}
+impl ParamHandle {
+    /// Creates new handle with value shared with the graph.
+    pub(crate) fn new(shared: Arc<SharedParam>) -> Self {
+        ParamHandle { shared }
+    }
+
+    /// Returns the latest value set through any handle of the input.
+    pub fn get(&self) -> Value {
+        Value::from_bits(self.shared.value.load(Ordering::Acquire))
+    }
+
+    /// Sets value to be applied at the start of the next cycle.
+    pub fn set(&self, value: Value) {
+        self.shared.value.store(value.to_bits(), Ordering::Release);
+        self.shared.pending.store(true, Ordering::Release);
+    }
+}
+
+/// Lock-free mailbox for an input value.
+#[derive(Default)]
+pub(crate) struct SharedParam {
+    /// Whether value was set since it was last taken.
+    pending: AtomicBool,
+
+    /// Bits of latest value.
+    value: AtomicBits,
+}
+impl SharedParam {
+    /// Returns the latest value if it was set since it was last taken.
+    pub(crate) fn take(&self) -> Option<Value> {
+        if self.pending.swap(false, Ordering::AcqRel) {
+            Some(Value::from_bits(self.value.load(Ordering::Acquire)))
+        } else {
+            None
+        }
+    }
+}
+
+/// Unit tests.
+#[cfg(all(test, feature = "generators"))]
+mod tests {
+    use super::*;
+    use crate::{nodes, Graph, GraphError, InputId, Node, NodeId, OutputId};
+    use std::thread;
+
+    #[test]
+    fn takes_latest_value() {
+        let handle = ParamHandle::new(Arc::new(SharedParam::default()));
+        assert_eq!(handle.shared.take(), None);
+        handle.set(1.0);
+        handle.clone().set(2.0);
+        assert_eq!(handle.get(), 2.0);
+        assert_eq!(handle.shared.take(), Some(2.0));
+        assert_eq!(handle.shared.take(), None);
+    }
+
+    #[test]
+    fn updates_graph_from_other_thread() {
+        let mut graph: Graph<Box<dyn Node + Send>> = Graph::new();
+        let var0 = graph.add_node(Box::from(nodes::Variable::new(1.0)));
+        let handle = graph.param_handle(var0, InputId(0)).unwrap();
+        assert_eq!(graph.param_handle(var0, InputId(1)).err(), Some(GraphError::InputNotExists(var0, InputId(1))));
+        assert_eq!(graph.param_handle(NodeId(1), InputId(0)).err(), Some(GraphError::NodeNotExists(NodeId(1))));
+
+        // Value is applied at the start of the next cycle on the processing thread.
+        thread::spawn(move || handle.set(3.0)).join().unwrap();
+        let graph = thread::spawn(move || {
+            assert_eq!(graph.get_output(var0, OutputId(0)), Ok(1.0));
+            graph.process();
+            graph
+        })
+        .join()
+        .unwrap();
+        assert_eq!(graph.get_output(var0, OutputId(0)), Ok(3.0));
+    }
+}
@@ -0,0 +1,210 @@
+use crate::{nodes, Connection, Graph, GraphError, InputId, Node, NodeId, OutputId, Value};
+
+/// Input of a node set by the creation argument of its object.
+type Argument = Option<(InputId, Value)>;
+
+/// Result of importing a Pure Data patch.
+pub struct PdImport {
+    /// Graph with the supported objects of the patch.
+    pub graph: Graph<Box<dyn Node>>,
+
+    /// Nodes of the objects of the patch, indexed like the objects (`None` for unsupported objects and comments).
+    pub nodes: Vec<Option<NodeId>>,
+
+    /// Connections of the patch that were skipped (source object and outlet, target object and inlet), as they
+    /// involve unsupported objects or ports or target an inlet that is already connected.
+    pub skipped_connections: Vec<(usize, u32, usize, u32)>,
+
+    /// Unsupported objects of the patch (object index and text, e.g. `(3, "line~ 0 50")`).
+    pub unsupported: Vec<(usize, String)>,
+}
+
+/// Imports a Pure Data patch (contents of a `.pd` file) on a best-effort basis, processing oscillators at a sample
+/// rate.
+///
+/// Supported objects of the main canvas are mapped to nodes (with creation arguments as input values):
+///
+/// - `+`, `-`, `*`, `/` and their signal versions (argument as input 1)
+/// - `f`, `float`, `sig~`, `floatatom` and messages with a single number as variables
+/// - `z~` as delay of one cycle (`delay` and `del` schedule bangs in milliseconds and are unsupported)
+/// - `osc~` as cosine oscillator (frequency as input 0)
+///
+/// Node metadata holds the object text (`pd`) and the canvas position (`x`, `y`). Subpatches and other objects are
+/// reported as unsupported. Pd sums multiple connections into an inlet, which is not supported either.
+pub fn import(patch: &str, sample_rate: f64) -> Result<PdImport, GraphError> {
+    let mut import =
+        PdImport { graph: Graph::new(), nodes: Vec::new(), skipped_connections: Vec::new(), unsupported: Vec::new() };
+    import.graph.set_sample_rate(sample_rate);
+    let mut depth = 0;
+    for record in records(patch) {
+        // Width suffixes (e.g. `, f 10`) and further messages of message boxes are dropped.
+        let atoms: Vec<&str> = record.split(',').next().unwrap_or_default().split_whitespace().collect();
+        match atoms.as_slice() {
+            ["#N", "canvas", ..] => depth += 1,
+            ["#X", "restore", _, _, text @ ..] if depth == 2 => {
+                depth -= 1;
+                import.unsupported.push((import.nodes.len(), text.join(" ")));
+                import.nodes.push(None);
+            }
+            ["#X", "restore", ..] => depth -= 1,
+            _ if depth != 1 => {}
+            ["#X", "connect", source, outlet, target, inlet] => {
+                let connection = (parse(source)?, parse(outlet)?, parse(target)?, parse(inlet)?);
+                if !import.connect(connection) {
+                    import.skipped_connections.push(connection);
+                }
+            }
+            ["#X", "text", _, _, ..] => import.nodes.push(None),
+            ["#X", kind @ ("obj" | "msg" | "floatatom" | "symbolatom"), x, y, text @ ..] => {
+                let node = match create(kind, text, sample_rate) {
+                    Some((node, argument)) => Some(import.add(node, argument, x, y, text)),
+                    None if *kind == "obj" => {
+                        import.unsupported.push((import.nodes.len(), text.join(" ")));
+                        None
+                    }
+                    None => {
+                        import.unsupported.push((import.nodes.len(), [&[*kind][..], text].concat().join(" ")));
+                        None
+                    }
+                };
+                import.nodes.push(node);
+            }
+            ["#X", "obj" | "msg" | "floatatom" | "symbolatom" | "text", ..] => {
+                return Err(GraphError::InvalidFormat(format!("Invalid object {}.", record.trim())));
+            }
+            _ => {}
+        }
+    }
+    Ok(import)
+}
+
+impl PdImport {
+    /// Adds node of an object, setting an input to the creation argument.
+    fn add(&mut self, node: Box<dyn Node>, argument: Argument, x: &str, y: &str, text: &[&str]) -> NodeId {
+        let id = self.graph.add_node(node);
+        if let Some((input, value)) = argument {
+            self.graph.set_input(id, input, value).unwrap();
+        }
+        self.graph.set_meta(id, "pd", &text.join(" ")).unwrap();
+        self.graph.set_meta(id, "x", x).unwrap();
+        self.graph.set_meta(id, "y", y).unwrap();
+        id
+    }
+
+    /// Connects nodes of objects and returns whether it succeeded.
+    fn connect(&mut self, (source, outlet, target, inlet): (usize, u32, usize, u32)) -> bool {
+        match (self.nodes.get(source), self.nodes.get(target)) {
+            (Some(&Some(source)), Some(&Some(target))) => {
+                let connection = Connection::new(source, OutputId(outlet), target, InputId(inlet));
+                self.graph.add_connection(connection).is_ok()
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Creates node of a supported object, with the input set by its creation argument.
+fn create(kind: &str, text: &[&str], sample_rate: f64) -> Option<(Box<dyn Node>, Argument)> {
+    let argument: Option<Value> = match text.get(1) {
+        Some(argument) => Some(argument.parse().ok()?),
+        None => None,
+    };
+    let (node, input): (Box<dyn Node>, _) = match (kind, text.first().copied().unwrap_or_default()) {
+        ("floatatom", _) => (Box::new(nodes::Variable::new(0.0)), None),
+        ("msg", value) if text.len() == 1 => (Box::new(nodes::Variable::new(value.parse().ok()?)), None),
+        ("obj", "+" | "+~") => (Box::new(nodes::Addition::new()), Some(InputId(1))),
+        ("obj", "-" | "-~") => (Box::new(nodes::Expression::new("a - b").unwrap()), Some(InputId(1))),
+        ("obj", "*" | "*~") => (Box::new(nodes::ProductN::new(2)), Some(InputId(1))),
+        ("obj", "/" | "/~") => (Box::new(nodes::Expression::new("a / b").unwrap()), Some(InputId(1))),
+        ("obj", "z~") => (Box::new(nodes::Delay::new()), None),
+        ("obj", "f" | "float" | "sig~") => (Box::new(nodes::Variable::new(argument.unwrap_or(0.0))), None),
+        ("obj", "osc~") => {
+            let mut phase: Value = 0.0;
+            let oscillator = nodes::FnNode::new(1, 1, move |inputs, outputs| {
+                outputs[0] = (phase * std::f64::consts::TAU as Value).cos();
+                phase += inputs[0] / sample_rate as Value;
+                phase -= phase.floor();
+            });
+            (Box::new(oscillator), Some(InputId(0)))
+        }
+        _ => return None,
+    };
+    Some((node, input.zip(argument)))
+}
+
+/// Parses a number of a record.
+fn parse<T: std::str::FromStr>(atom: &str) -> Result<T, GraphError> {
+    atom.parse().map_err(|_| GraphError::InvalidFormat(format!("Invalid number {}.", atom)))
+}
+
+/// Returns records of a patch (separated by semicolons that are not escaped).
+fn records(patch: &str) -> Vec<&str> {
+    let mut records = Vec::new();
+    let mut start = 0;
+    let mut escaped = false;
+    for (index, character) in patch.char_indices() {
+        if character == ';' && !escaped {
+            records.push(&patch[start..index]);
+            start = index + 1;
+        }
+        escaped = character == '\\' && !escaped;
+    }
+    records
+}
+
+/// Unit tests.
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn import() {
+        let patch = "#N canvas 0 50 450 300 12;
+            #X obj 30 20 osc~ 0.25;
+            #X obj 30 60 *~ 0.5, f 8;
+            #X obj 30 100 dac~;
+            #X text 120 20 comment \\; with semicolon;
+            #X obj 200 20 f 2;
+            #X obj 200 60 + 1;
+            #N canvas 0 0 450 300 sub 0;
+            #X obj 10 10 inlet;
+            #X restore 200 140 pd sub;
+            #X msg 300 20 3;
+            #X connect 0 0 1 0;
+            #X connect 1 0 2 0;
+            #X connect 1 0 2 1;
+            #X connect 4 0 5 0;
+            #X connect 7 0 5 1;
+            #X connect 5 0 6 0;
+        ";
+        let mut import = super::import(patch, 1.0).unwrap();
+        let ids =
+            [Some(NodeId(0)), Some(NodeId(1)), None, None, Some(NodeId(2)), Some(NodeId(3)), None, Some(NodeId(4))];
+        assert_eq!(import.nodes, ids);
+        assert_eq!(import.unsupported, [(2, "dac~".to_string()), (6, "pd sub".to_string())]);
+        assert_eq!(import.skipped_connections, [(1, 0, 2, 0), (1, 0, 2, 1), (5, 0, 6, 0)]);
+        assert_eq!(import.graph.get_meta(NodeId(1), "pd"), Ok(Some("*~ 0.5")));
+        assert_eq!(import.graph.get_meta(NodeId(3), "x"), Ok(Some("200")));
+
+        // Oscillator at a quarter of the sample rate, scaled by a half.
+        let mut outputs = Vec::new();
+        for _ in 0..3 {
+            import.graph.process();
+            outputs.push(import.graph.get_output(NodeId(1), OutputId(0)).unwrap());
+        }
+        assert!((outputs[0] - 0.5).abs() < 1e-6 && outputs[1].abs() < 1e-6 && (outputs[2] + 0.5).abs() < 1e-6);
+        assert_eq!(import.graph.get_output(NodeId(3), OutputId(0)), Ok(5.0));
+
+        // Bang schedulers are no delays of one cycle.
+        let import = super::import("#N canvas 0 0 450 300 12;\n#X obj 10 10 del 100;\n#X obj 10 50 z~;", 1.0).unwrap();
+        assert_eq!(import.nodes, [None, Some(NodeId(0))]);
+        assert_eq!(import.unsupported, [(0, "del 100".to_string())]);
+
+        // Invalid patches.
+        assert_eq!(
+            super::import("#N canvas 0 0 450 300 12;\n#X connect 0 x 1 0;", 1.0).err(),
+            Some(GraphError::InvalidFormat("Invalid number x.".to_string()))
+        );
+        assert!(matches!(super::import("#N canvas;\n#X obj 30;", 1.0), Err(GraphError::InvalidFormat(_))));
+    }
+}
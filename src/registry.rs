@@ -0,0 +1,578 @@
+#[cfg(any(
+    feature = "dsp",
+    feature = "generators",
+    feature = "io",
+    feature = "logic",
+    feature = "math",
+    feature = "midi"
+))]
+use crate::nodes;
+#[cfg(feature = "serde")]
+use crate::{graph::serialization::Topology, Graph};
+use crate::{GraphError, Node, Value};
+#[cfg(feature = "json")]
+use crate::{InputId, OutputId, PortInfo, ValueKind};
+#[cfg(feature = "serde")]
+use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::{BTreeMap, HashMap};
+
+/// Constructor of a registered node type (taking optional parameters).
+type Constructor<N> = Box<dyn Fn(&[Value]) -> N + Send + Sync>;
+
+/// Migration of saved nodes from an older version of a registered node type (taking the saved version).
+type Migration = Box<dyn Fn(u32, &mut SavedNode) + Send + Sync>;
+
+/// Registered node type.
+struct NodeType<N> {
+    /// Category of the node type (see `Node::category`).
+    category: &'static str,
+
+    /// Constructor of nodes.
+    constructor: Constructor<N>,
+
+    /// Migration of nodes saved with older versions.
+    migration: Option<Migration>,
+
+    /// Rust type name of nodes.
+    type_name: &'static str,
+
+    /// Current version of the node type.
+    version: u32,
+}
+
+/// Saved parameters and state of a node of a registered type, as passed to migrations (see
+/// `NodeRegistry::set_version`).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SavedNode {
+    /// Parameters of the node by name (see `Node::parameters`).
+    pub parameters: Vec<(String, Value)>,
+
+    /// Construction arguments followed by the snapshot of node state (see `Node::arguments` and `Node::snapshot`).
+    pub state: Vec<Value>,
+}
+
+/// Registry of node types that constructs nodes by name (e.g. for deserialization or editor palettes).
+pub struct NodeRegistry<N: Node> {
+    /// Node types, indexed by registered name.
+    types: HashMap<String, NodeType<N>>,
+}
+impl<N: Node> NodeRegistry<N> {
+    /// Creates new empty registry.
+    pub fn new() -> Self {
+        NodeRegistry { types: HashMap::new() }
+    }
+
+    /// Returns registered names grouped by category (both sorted), e.g. for an "add node" palette.
+    pub fn categories(&self) -> BTreeMap<&str, Vec<&str>> {
+        let mut categories: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+        for name in self.names() {
+            categories.entry(self.types[name].category).or_default().push(name);
+        }
+        categories
+    }
+
+    /// Returns category of a registered type.
+    pub fn category_of(&self, name: &str) -> Option<&str> {
+        self.types.get(name).map(|node_type| node_type.category)
+    }
+
+    /// Creates node of a registered type with parameters (empty for defaults).
+    pub fn create(&self, name: &str, params: &[Value]) -> Result<N, GraphError> {
+        let node_type = self.types.get(name).ok_or_else(|| GraphError::NodeTypeNotExists(name.to_string()))?;
+        Ok((node_type.constructor)(params))
+    }
+
+    /// Returns registered name of the type of a node.
+    pub fn name_of(&self, node: &N) -> Option<&str> {
+        let type_name = node.type_name();
+        self.types.iter().find(|(_, node_type)| node_type.type_name == type_name).map(|(name, _)| name.as_str())
+    }
+
+    /// Returns registered names (sorted).
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.types.keys().map(|name| name.as_str()).collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// Registers (or replaces) a node type with a constructor taking parameters.
+    ///
+    /// The constructor is called once without parameters to determine the type and category of its nodes, so
+    /// parameters must be optional. Saved nodes are constructed with their arguments followed by their snapshot (see
+    /// `Node::arguments`).
+    pub fn register<F: Fn(&[Value]) -> N + Send + Sync + 'static>(&mut self, name: &str, constructor: F) {
+        let node = constructor(&[]);
+        let (category, type_name) = (node.category(), node.type_name());
+        let constructor = Box::new(constructor);
+        let node_type = NodeType { category, constructor, migration: None, type_name, version: 0 };
+        self.types.insert(name.to_string(), node_type);
+    }
+
+    /// Sets current version of a registered type (initially 0) together with a migration that upgrades nodes saved
+    /// with an older version (e.g. after renaming parameters or changing the state layout). The migration is called
+    /// with the saved version when graphs are deserialized.
+    pub fn set_version<F: Fn(u32, &mut SavedNode) + Send + Sync + 'static>(
+        &mut self,
+        name: &str,
+        version: u32,
+        migration: F,
+    ) -> Result<(), GraphError> {
+        let node_type = self.types.get_mut(name).ok_or_else(|| GraphError::NodeTypeNotExists(name.to_string()))?;
+        node_type.migration = Some(Box::new(migration));
+        node_type.version = version;
+        Ok(())
+    }
+
+    /// Returns current version of a registered type.
+    pub fn version_of(&self, name: &str) -> Option<u32> {
+        self.types.get(name).map(|node_type| node_type.version)
+    }
+}
+impl NodeRegistry<Box<dyn Node>> {
+    /// Creates registry of the bundled nodes whose features are enabled, constructed from their arguments (see
+    /// `Node::arguments`) or with defaults (e.g. two inputs for `sum`, an initial value of 0 for `variable`):
+    ///
+    /// - `math`: `addition`, `average`, `complex_join`, `complex_multiply`, `complex_split`, `expression` (formula
+    ///   as character codes), `product`, `saturating_product`, `saturating_sum` and `sum`
+    /// - `generators`: `constant` and `variable`
+    /// - `logic`: `counter` and `select`
+    /// - `dsp`: `delay`, `delay_line`, `merge`, `smooth` and `split`
+    /// - `midi`: `midi_input` (not connected to a port)
+    /// - `io`: `graph_input` and `graph_output`
+    pub fn builtin() -> Self {
+        #[allow(unused_mut)]
+        let mut registry = Self::new();
+        #[cfg(feature = "math")]
+        {
+            registry.register("addition", |_| Box::new(nodes::Addition::new()));
+            registry.register("average", |params| Box::new(nodes::AverageN::new(count(params, 0, 2))));
+            registry.register("complex_join", |_| Box::new(nodes::ComplexJoin::new()));
+            registry.register("complex_multiply", |_| Box::new(nodes::ComplexMultiply::new()));
+            registry.register("complex_split", |_| Box::new(nodes::ComplexSplit::new()));
+            registry.register("expression", |params| Box::new(nodes::Expression::from_arguments(params)));
+            registry.register("product", |params| Box::new(nodes::ProductN::new(count(params, 0, 2))));
+            registry.register("saturating_product", |params| {
+                let (min, max) = (value(params, 1, -1.0), value(params, 2, 1.0));
+                Box::new(nodes::SaturatingProductN::new(count(params, 0, 2), min, max))
+            });
+            registry.register("saturating_sum", |params| {
+                let (min, max) = (value(params, 1, -1.0), value(params, 2, 1.0));
+                Box::new(nodes::SaturatingSumN::new(count(params, 0, 2), min, max))
+            });
+            registry.register("sum", |params| Box::new(nodes::SumN::new(count(params, 0, 2))));
+        }
+        #[cfg(feature = "generators")]
+        {
+            registry.register("constant", |params| Box::new(nodes::Constant::from_arguments(params)));
+            registry.register("variable", |params| Box::new(nodes::Variable::new(value(params, 0, 0.0))));
+        }
+        #[cfg(feature = "logic")]
+        {
+            registry.register("counter", |_| Box::new(nodes::Counter::new(0)));
+            registry.register("select", |params| Box::new(nodes::SelectN::new(count(params, 0, 2))));
+        }
+        #[cfg(feature = "dsp")]
+        {
+            registry.register("delay", |params| Box::new(nodes::Delay::with_initial(value(params, 0, 0.0))));
+            registry.register("delay_line", |params| match count(params, 1, 0) {
+                0 => Box::new(nodes::DelayN::new(count(params, 0, 1) as usize)),
+                _ => Box::new(nodes::DelayN::interpolated(count(params, 0, 1) as usize)),
+            });
+            registry.register("merge", |params| Box::new(nodes::Merge::new(count(params, 0, 2))));
+            registry.register("smooth", |params| {
+                Box::new(nodes::Smooth::new(value(params, 0, 0.0), value(params, 1, 0.0)))
+            });
+            registry.register("split", |params| Box::new(nodes::Split::new(count(params, 0, 2))));
+        }
+        #[cfg(feature = "midi")]
+        registry.register("midi_input", |params| {
+            let controls: Vec<u8> = params.iter().map(|&control| control as u8).collect();
+            Box::new(nodes::MidiInput::new(&controls))
+        });
+        #[cfg(feature = "io")]
+        {
+            registry.register("graph_input", |params| Box::new(nodes::GraphInput::new(count(params, 0, 0) as usize)));
+            registry.register("graph_output", |params| Box::new(nodes::GraphOutput::new(count(params, 0, 0) as usize)));
+        }
+        registry
+    }
+}
+
+/// Returns constructor parameter at an index as a count (or a default if missing).
+#[cfg(any(feature = "dsp", feature = "io", feature = "logic", feature = "math"))]
+fn count(params: &[Value], index: usize, default: u32) -> u32 {
+    params.get(index).map_or(default, |&count| count.round().max(0.0) as u32)
+}
+
+/// Returns constructor parameter at an index (or a default if missing).
+#[cfg(any(feature = "dsp", feature = "generators", feature = "math"))]
+fn value(params: &[Value], index: usize, default: Value) -> Value {
+    params.get(index).copied().unwrap_or(default)
+}
+
+impl<N: Node> Default for NodeRegistry<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Serialized node of a registered type.
+#[cfg(feature = "serde")]
+#[derive(Deserialize, Serialize)]
+struct RegisteredNode {
+    /// Parameters of the node by name (see `Node::parameters`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    parameters: Vec<(String, Value)>,
+
+    /// Construction arguments followed by the snapshot of node state.
+    state: Vec<Value>,
+
+    /// Registered name of node type.
+    #[serde(rename = "type")]
+    type_name: String,
+
+    /// Version of node type the node was saved with (0 if missing).
+    #[serde(default, skip_serializing_if = "is_zero")]
+    version: u32,
+}
+
+/// Returns whether a version is the initial one (omitted when serializing).
+#[cfg(feature = "serde")]
+fn is_zero(version: &u32) -> bool {
+    *version == 0
+}
+
+#[cfg(feature = "serde")]
+impl<N: Node> NodeRegistry<N> {
+    /// Deserializes graph whose nodes are stored by registered type name, arguments and state snapshot. Nodes saved
+    /// with an older version of their type are migrated first (see `set_version`), newer versions are rejected.
+    pub fn deserialize_graph<'de, D: Deserializer<'de>>(&self, deserializer: D) -> Result<Graph<N>, D::Error> {
+        let topology = Topology::<RegisteredNode>::deserialize(deserializer)?;
+        for (_, registered) in topology.nodes.iter() {
+            let version = self.version_of(&registered.type_name).unwrap_or(0);
+            if registered.version > version {
+                return Err(de::Error::custom(format!(
+                    "Node type {} has version {}, which is newer than the registered version {}.",
+                    registered.type_name, registered.version, version
+                )));
+            }
+        }
+        topology
+            .map_nodes(|registered| {
+                let mut saved = SavedNode { parameters: registered.parameters, state: registered.state };
+                self.migrate(&registered.type_name, registered.version, &mut saved)?;
+                let mut node = (self.types[&registered.type_name].constructor)(&saved.state);
+                node.restore(saved.state.get(node.arguments().len()..).unwrap_or_default());
+                for (name, value) in saved.parameters {
+                    node.set_parameter(&name, value);
+                }
+                Ok(node)
+            })
+            .and_then(Topology::build)
+            .map_err(|error| de::Error::custom(format!("{:?}", error)))
+    }
+
+    /// Migrates a node saved with an older version of its registered type (see `set_version`), newer versions are
+    /// rejected.
+    pub(crate) fn migrate(&self, name: &str, version: u32, saved: &mut SavedNode) -> Result<(), GraphError> {
+        let node_type = self.types.get(name).ok_or_else(|| GraphError::NodeTypeNotExists(name.to_string()))?;
+        if version > node_type.version {
+            return Err(GraphError::InvalidFormat(format!(
+                "Node type {} has version {}, which is newer than the registered version {}.",
+                name, version, node_type.version
+            )));
+        }
+        if let Some(migration) = node_type.migration.as_ref().filter(|_| version < node_type.version) {
+            migration(version, saved);
+        }
+        Ok(())
+    }
+
+    /// Serializes graph of registered node types (by registered type name, version, arguments and state snapshot).
+    pub fn serialize_graph<S: Serializer>(&self, graph: &Graph<N>, serializer: S) -> Result<S::Ok, S::Error> {
+        Topology::from_graph(graph, |node| {
+            let type_name =
+                self.name_of(node).ok_or_else(|| GraphError::NodeTypeNotExists(node.type_name().to_string()))?;
+            let parameters = node
+                .parameters()
+                .iter()
+                .filter_map(|&name| Some((name.to_string(), node.get_parameter(name)?)))
+                .collect();
+            let version = self.types[type_name].version;
+            let state = node.arguments().into_iter().chain(node.snapshot()).collect();
+            Ok(RegisteredNode { parameters, state, type_name: type_name.to_string(), version })
+        })
+        .map_err(|error| ser::Error::custom(format!("{:?}", error)))?
+        .serialize(serializer)
+    }
+}
+
+/// Schema of a registered node type (see `NodeRegistry::schema`).
+#[cfg(feature = "json")]
+#[derive(Serialize)]
+struct TypeSchema<'a> {
+    /// Category of the node type.
+    category: &'a str,
+
+    /// Identifiers of event inputs.
+    event_inputs: &'a [InputId],
+
+    /// Identifiers of event outputs.
+    event_outputs: &'a [OutputId],
+
+    /// Value inputs.
+    inputs: Vec<PortSchema<'a>>,
+
+    /// Value outputs.
+    outputs: Vec<PortSchema<'a>>,
+
+    /// Names of parameters with their initial values.
+    parameters: BTreeMap<&'a str, Option<Value>>,
+
+    /// Current version of the node type.
+    version: u32,
+}
+
+/// Schema of a port of a registered node type.
+#[cfg(feature = "json")]
+#[derive(Serialize)]
+struct PortSchema<'a> {
+    /// Number of channels of the port.
+    channels: usize,
+
+    /// Initial value of the port.
+    default: Value,
+
+    /// Identifier of the port.
+    id: u32,
+
+    /// Kind of values.
+    kind: ValueKind,
+
+    /// Maximum meaningful value (`None` if unbounded).
+    max: Option<Value>,
+
+    /// Minimum meaningful value (`None` if unbounded).
+    min: Option<Value>,
+
+    /// Name of the port.
+    name: Option<&'a str>,
+
+    /// Unit of the values.
+    unit: Option<&'a str>,
+}
+#[cfg(feature = "json")]
+impl<'a> PortSchema<'a> {
+    /// Creates schema of a port from its descriptor.
+    fn new(id: u32, channels: usize, info: PortInfo<'a>) -> Self {
+        let (max, min) = (Some(info.max).filter(|max| max.is_finite()), Some(info.min).filter(|min| min.is_finite()));
+        PortSchema { channels, default: info.default, id, kind: info.kind, max, min, name: info.name, unit: info.unit }
+    }
+}
+
+#[cfg(feature = "json")]
+impl<N: Node> NodeRegistry<N> {
+    /// Returns JSON object describing every registered node type by name (category, ports with names, kinds,
+    /// defaults and ranges, parameters and version), e.g. for external editors rendering unknown node types.
+    ///
+    /// Node types are described by a node constructed without parameters.
+    pub fn schema(&self) -> String {
+        let nodes: Vec<(&str, &NodeType<N>, N)> = self
+            .names()
+            .into_iter()
+            .map(|name| (name, &self.types[name], (self.types[name].constructor)(&[])))
+            .collect();
+        let mut schema = BTreeMap::new();
+        for (name, node_type, node) in nodes.iter() {
+            let input = |&id: &InputId| PortSchema::new(id.0, node.input_channels(id), node.input_info(id));
+            let output = |&id: &OutputId| PortSchema::new(id.0, node.output_channels(id), node.output_info(id));
+            let parameters = node.parameters().iter().map(|&name| (name, node.get_parameter(name))).collect();
+            schema.insert(
+                *name,
+                TypeSchema {
+                    category: node_type.category,
+                    event_inputs: node.list_event_inputs(),
+                    event_outputs: node.list_event_outputs(),
+                    inputs: node.list_inputs().iter().map(input).collect(),
+                    outputs: node.list_outputs().iter().map(output).collect(),
+                    parameters,
+                    version: node_type.version,
+                },
+            );
+        }
+        serde_json::to_string_pretty(&schema).expect("schema is serializable")
+    }
+}
+
+/// Unit tests.
+#[cfg(all(test, feature = "dsp", feature = "generators", feature = "io", feature = "logic", feature = "math"))]
+mod tests {
+    use super::*;
+    use crate::{OutputId, ValueKind};
+
+    #[test]
+    fn create() {
+        let registry = NodeRegistry::builtin();
+        let mut names = vec![
+            "addition",
+            "average",
+            "complex_join",
+            "complex_multiply",
+            "complex_split",
+            "constant",
+            "counter",
+            "delay",
+            "delay_line",
+            "expression",
+            "graph_input",
+            "graph_output",
+            "merge",
+            "product",
+            "saturating_product",
+            "saturating_sum",
+            "select",
+            "smooth",
+            "split",
+            "sum",
+            "variable",
+        ];
+        if cfg!(feature = "midi") {
+            names.insert(13, "midi_input");
+        }
+        assert_eq!(registry.names(), names);
+
+        let var = registry.create("variable", &[2.0]).unwrap();
+        assert_eq!(var.get_output(OutputId(0)), 2.0);
+        assert_eq!(registry.name_of(&var), Some("variable"));
+        assert_eq!(registry.create("variable", &[]).unwrap().get_output(OutputId(0)), 0.0);
+        assert_eq!(
+            registry.create("multiplication", &[]).err(),
+            Some(GraphError::NodeTypeNotExists("multiplication".to_string()))
+        );
+        assert_eq!(NodeRegistry::<Box<dyn Node>>::new().name_of(&var), None);
+    }
+
+    #[test]
+    fn arguments() {
+        // Nodes are recreated from their arguments.
+        let registry = NodeRegistry::builtin();
+        for node in [
+            Box::new(nodes::Constant::with_kinds(vec![1.0, 2.0], vec![ValueKind::Integer, ValueKind::Trigger])),
+            Box::new(nodes::DelayN::interpolated(4)),
+            Box::new(nodes::Expression::new("a * sin(b)").unwrap()),
+            Box::new(nodes::GraphOutput::new(3)),
+            Box::new(nodes::SaturatingSumN::new(3, 0.0, 2.0)),
+            Box::new(nodes::Smooth::new(1.0, 0.5)),
+            Box::new(nodes::SumN::new(5)),
+        ] as [Box<dyn Node>; 7]
+        {
+            let name = registry.name_of(&node).unwrap();
+            let created = registry.create(name, &node.arguments()).unwrap();
+            assert_eq!(created.arguments(), node.arguments(), "{}", name);
+            assert_eq!(created.describe(), node.describe(), "{}", name);
+        }
+
+        // Missing or invalid arguments fall back to defaults.
+        assert_eq!(registry.create("sum", &[]).unwrap().list_inputs().len(), 2);
+        let expression = registry.create("expression", &[]).unwrap();
+        assert_eq!(expression.arguments(), ['0' as u32 as Value]);
+        assert_eq!(registry.create("expression", &[Value::from(b'+')]).unwrap().arguments(), expression.arguments());
+    }
+
+    #[test]
+    fn categories() {
+        let registry = NodeRegistry::builtin();
+        assert_eq!(registry.category_of("delay"), Some("time"));
+        assert_eq!(registry.category_of("multiplication"), None);
+        let categories = registry.categories();
+        assert_eq!(
+            categories.keys().copied().collect::<Vec<_>>(),
+            ["logic", "math", "routing", "sources", "terminals", "time"]
+        );
+        assert_eq!(categories["time"], ["delay", "delay_line", "smooth"]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serialize_graph() {
+        use crate::{Connection, InputId};
+
+        let registry = NodeRegistry::builtin();
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        let var0 = graph.add_node(registry.create("variable", &[1.0]).unwrap());
+        let add1 = graph.add_node(registry.create("addition", &[]).unwrap());
+        let del2 = graph.add_node(registry.create("delay", &[]).unwrap());
+        graph.add_connection(Connection::new(var0, OutputId(0), add1, InputId(0))).unwrap();
+        graph.add_connection(Connection::new(add1, OutputId(0), del2, InputId(0))).unwrap();
+        graph.add_connection(Connection::new(del2, OutputId(0), add1, InputId(1))).unwrap();
+        graph.process_n(2);
+
+        let mut json = Vec::new();
+        registry.serialize_graph(&graph, &mut serde_json::Serializer::new(&mut json)).unwrap();
+        let mut restored = registry.deserialize_graph(&mut serde_json::Deserializer::from_slice(&json)).unwrap();
+        restored.process();
+        graph.process();
+        assert_eq!(restored.get_output(add1, OutputId(0)), Ok(3.0));
+        assert_eq!(restored.get_output(add1, OutputId(0)), graph.get_output(add1, OutputId(0)));
+
+        // Unregistered node types cannot be serialized.
+        graph.add_node(Box::new(nodes::Variable::new(1.0)));
+        let mut registry = NodeRegistry::new();
+        #[cfg(feature = "dsp")]
+        registry.register("delay", |_| -> Box<dyn Node> { Box::new(nodes::Delay::new()) });
+        let error = registry.serialize_graph(&graph, &mut serde_json::Serializer::new(Vec::new())).unwrap_err();
+        assert_eq!(error.to_string(), "Node type flowing::nodes::variable::Variable is not registered.");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn migrate_versions() {
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        let var0 = graph.add_node(Box::new(nodes::Variable::new(250.0)));
+        let mut json = Vec::new();
+        NodeRegistry::builtin().serialize_graph(&graph, &mut serde_json::Serializer::new(&mut json)).unwrap();
+
+        // Variables now store their value in units instead of cents.
+        let mut registry = NodeRegistry::builtin();
+        registry
+            .set_version("variable", 1, |version, saved| {
+                if version < 1 {
+                    saved.state.iter_mut().for_each(|value| *value /= 100.0);
+                }
+            })
+            .unwrap();
+        assert_eq!(registry.version_of("variable"), Some(1));
+        assert_eq!(registry.version_of("delay"), Some(0));
+        let migrated = registry.deserialize_graph(&mut serde_json::Deserializer::from_slice(&json)).unwrap();
+        assert_eq!(migrated.get_node(var0).unwrap().snapshot(), vec![2.5]);
+
+        // Current versions are stored and not migrated again, newer versions are rejected.
+        let mut json = Vec::new();
+        registry.serialize_graph(&migrated, &mut serde_json::Serializer::new(&mut json)).unwrap();
+        assert!(String::from_utf8_lossy(&json).contains(r#""version":1"#));
+        let restored = registry.deserialize_graph(&mut serde_json::Deserializer::from_slice(&json)).unwrap();
+        assert_eq!(restored.get_node(var0).unwrap().snapshot(), vec![2.5]);
+        let error = NodeRegistry::builtin().deserialize_graph(&mut serde_json::Deserializer::from_slice(&json));
+        assert_eq!(
+            error.err().unwrap().to_string(),
+            "Node type variable has version 1, which is newer than the registered version 0."
+        );
+        assert_eq!(registry.set_version("lfo", 1, |_, _| {}), Err(GraphError::NodeTypeNotExists("lfo".to_string())));
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn schema() {
+        let schema: serde_json::Value = serde_json::from_str(&NodeRegistry::builtin().schema()).unwrap();
+        assert_eq!(schema.as_object().unwrap().keys().collect::<Vec<_>>(), NodeRegistry::builtin().names());
+        assert_eq!(schema["delay"]["category"], "time");
+        assert_eq!(schema["addition"]["inputs"].as_array().unwrap().len(), 2);
+        assert_eq!(
+            schema["variable"]["outputs"][0],
+            serde_json::json!({
+                "channels": 1, "default": 0.0, "id": 0, "kind": "Continuous", "max": null, "min": null, "name": "value",
+                "unit": null
+            })
+        );
+    }
+}
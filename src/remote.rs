@@ -0,0 +1,237 @@
+use crate::{Connection, Graph, GraphCommand, GraphError, InputId, Node, NodeId, NodeRegistry, Value};
+use serde::{Deserialize, Serialize};
+use std::{
+    io::{self, BufRead, BufReader, ErrorKind, Read},
+    net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver, Sender},
+        Arc,
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+/// Maximum length of a command line in bytes (longer lines close the connection).
+const MAX_LINE_LENGTH: usize = 64 * 1024;
+
+/// Serializable graph command (see `GraphCommand`), sent as one JSON object per line, e.g.
+/// `{"command":"set_input","node":0,"input":0,"value":0.5}`. Added nodes are created by registered type name.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case", tag = "command")]
+pub enum WireCommand {
+    AddNode {
+        node_type: String,
+        #[serde(default)]
+        params: Vec<Value>,
+    },
+    Connect {
+        connection: Connection,
+    },
+    Disconnect {
+        connection: Connection,
+    },
+    RemoveNode {
+        node: NodeId,
+    },
+    SetInput {
+        input: InputId,
+        node: NodeId,
+        value: Value,
+    },
+    SetParam {
+        name: String,
+        node: NodeId,
+        value: Value,
+    },
+}
+impl WireCommand {
+    /// Converts into a graph command, creating added nodes with a registry.
+    pub fn into_command<N: Node>(self, registry: &NodeRegistry<N>) -> Result<GraphCommand<N>, GraphError> {
+        Ok(match self {
+            WireCommand::AddNode { node_type, params } => GraphCommand::AddNode(registry.create(&node_type, &params)?),
+            WireCommand::Connect { connection } => GraphCommand::Connect(connection),
+            WireCommand::Disconnect { connection } => GraphCommand::Disconnect(connection),
+            WireCommand::RemoveNode { node } => GraphCommand::RemoveNode(node),
+            WireCommand::SetInput { input, node, value } => GraphCommand::SetInput(node, input, value),
+            WireCommand::SetParam { name, node, value } => GraphCommand::SetParam(node, name, value),
+        })
+    }
+}
+
+/// Server that receives graph commands from channels or TCP connections, e.g. to drive a headless engine from a
+/// separate editor process. Received commands are queued and applied to a graph with `apply` between cycles.
+pub struct GraphServer {
+    /// Local address of the TCP listener (if bound).
+    address: Option<SocketAddr>,
+
+    /// Queue of received commands.
+    commands: Receiver<WireCommand>,
+
+    /// Whether the listening thread keeps running.
+    running: Arc<AtomicBool>,
+
+    /// Sending side of the queue, handed out to local clients.
+    sender: Sender<WireCommand>,
+
+    /// Listening thread (joined when dropped).
+    thread: Option<JoinHandle<()>>,
+}
+impl GraphServer {
+    /// Creates new server receiving commands only from local channels (see `sender`).
+    pub fn new() -> Self {
+        let (sender, commands) = mpsc::channel();
+        GraphServer { address: None, commands, running: Arc::new(AtomicBool::new(true)), sender, thread: None }
+    }
+
+    /// Binds new server to an address and starts accepting TCP connections that send line-delimited JSON commands
+    /// (malformed lines are dropped, connections sending lines longer than 64 KiB are closed).
+    pub fn bind(address: impl ToSocketAddrs) -> io::Result<Self> {
+        let listener = TcpListener::bind(address)?;
+        listener.set_nonblocking(true)?;
+        let mut server = Self::new();
+        server.address = Some(listener.local_addr()?);
+        server.thread = {
+            let (sender, running) = (server.sender.clone(), server.running.clone());
+            Some(thread::spawn(move || listen(listener, sender, running)))
+        };
+        Ok(server)
+    }
+
+    /// Applies all received commands to a graph (call between cycles), creating added nodes with a registry. Returns
+    /// errors of commands that could not be applied.
+    pub fn apply<N: Node>(&self, graph: &mut Graph<N>, registry: &NodeRegistry<N>) -> Vec<GraphError> {
+        let mut errors = Vec::new();
+        for command in self.commands.try_iter() {
+            if let Err(error) = command.into_command(registry).and_then(|command| graph.apply(command)) {
+                errors.push(error);
+            }
+        }
+        errors
+    }
+
+    /// Returns local address of the TCP listener (`None` if not bound).
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.address
+    }
+
+    /// Returns sender for queueing commands from the same process (e.g. a UI thread).
+    pub fn sender(&self) -> Sender<WireCommand> {
+        self.sender.clone()
+    }
+}
+impl Default for GraphServer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl Drop for GraphServer {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Accepts connections until the server is dropped, releasing threads of closed connections.
+fn listen(listener: TcpListener, sender: Sender<WireCommand>, running: Arc<AtomicBool>) {
+    let mut connections: Vec<JoinHandle<()>> = Vec::new();
+    while running.load(Ordering::Relaxed) {
+        connections.retain(|connection| !connection.is_finished());
+        match listener.accept() {
+            Ok((stream, _)) => {
+                let (sender, running) = (sender.clone(), running.clone());
+                connections.push(thread::spawn(move || receive(stream, sender, running)));
+            }
+            Err(_) => thread::sleep(Duration::from_millis(10)),
+        }
+    }
+    for connection in connections {
+        let _ = connection.join();
+    }
+}
+
+/// Receives commands of a connection until it is closed, exceeds the maximum line length or the server is dropped.
+fn receive(stream: TcpStream, sender: Sender<WireCommand>, running: Arc<AtomicBool>) {
+    if stream.set_nonblocking(false).and_then(|_| stream.set_read_timeout(Some(Duration::from_millis(50)))).is_err() {
+        return;
+    }
+    let mut reader = BufReader::new(stream);
+    let mut line = Vec::new();
+    while running.load(Ordering::Relaxed) {
+        let limit = (MAX_LINE_LENGTH + 1 - line.len()) as u64;
+        match reader.by_ref().take(limit).read_until(b'\n', &mut line) {
+            Ok(0) => return,
+            Ok(_) if line.ends_with(b"\n") => {
+                if let Ok(command) = serde_json::from_slice(&line) {
+                    if sender.send(command).is_err() {
+                        return;
+                    }
+                }
+                line.clear();
+            }
+            Ok(_) if line.len() > MAX_LINE_LENGTH => return,
+            Ok(_) => {}
+            Err(error) if matches!(error.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {}
+            Err(_) => return,
+        }
+    }
+}
+
+/// Unit tests.
+#[cfg(all(test, feature = "dsp"))]
+mod tests {
+    use super::*;
+    use crate::{nodes, OutputId};
+    use std::{io::Write, time::Instant};
+
+    #[test]
+    fn applies_commands() {
+        let registry = NodeRegistry::builtin();
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        let server = GraphServer::bind("127.0.0.1:0").unwrap();
+        let mut client = TcpStream::connect(server.local_addr().unwrap()).unwrap();
+        let commands = [
+            r#"{"command":"add_node","node_type":"variable","params":[2.0]}"#,
+            r#"{"command":"add_node","node_type":"addition"}"#,
+            "malformed",
+            r#"{"command":"add_node","node_type":"lfo"}"#,
+        ];
+        for command in commands {
+            writeln!(client, "{}", command).unwrap();
+        }
+
+        let mut errors = Vec::new();
+        let start = Instant::now();
+        while errors.is_empty() && start.elapsed() < Duration::from_secs(5) {
+            errors.extend(server.apply(&mut graph, &registry));
+            thread::sleep(Duration::from_millis(1));
+        }
+        assert_eq!(errors, [GraphError::NodeTypeNotExists("lfo".into())]);
+
+        // Commands from the same process.
+        let connection = Connection::new(NodeId(0), OutputId(0), NodeId(1), InputId(0));
+        server.sender().send(WireCommand::Connect { connection }).unwrap();
+        server.sender().send(WireCommand::SetInput { input: InputId(1), node: NodeId(1), value: 0.5 }).unwrap();
+        assert_eq!(server.apply(&mut graph, &registry), []);
+        graph.process();
+        assert_eq!(graph.get_output(NodeId(1), OutputId(0)), Ok(2.5));
+
+        let json = serde_json::to_string(&WireCommand::RemoveNode { node: NodeId(1) }).unwrap();
+        assert_eq!(json, r#"{"command":"remove_node","node":1}"#);
+        assert!(matches!(
+            WireCommand::AddNode { node_type: "delay".into(), params: vec![] }.into_command(&registry),
+            Ok(GraphCommand::AddNode(node)) if node.type_name() == std::any::type_name::<nodes::Delay>()
+        ));
+    }
+
+    #[test]
+    fn closes_long_lines() {
+        let server = GraphServer::bind("127.0.0.1:0").unwrap();
+        let mut client = TcpStream::connect(server.local_addr().unwrap()).unwrap();
+        client.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+        client.write_all(&vec![b' '; MAX_LINE_LENGTH + 1]).unwrap();
+        assert_eq!(client.read(&mut [0; 1]).unwrap(), 0);
+    }
+}
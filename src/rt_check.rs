@@ -0,0 +1,177 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+};
+
+thread_local! {
+    /// Whether the current thread is in a real-time section.
+    static ACTIVE: Cell<bool> = const { Cell::new(false) };
+
+    /// Number of heap operations in the current real-time section.
+    static VIOLATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Allocator that detects heap allocation during real-time safe processing (install as `#[global_allocator]` to
+/// assert that `Graph::process` is real-time safe).
+pub struct RtCheck;
+unsafe impl GlobalAlloc for RtCheck {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        record();
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        record();
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        record();
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        record();
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+/// Real-time section of the current thread, heap operations are violations.
+pub(crate) struct Section {
+    /// Whether the section is checked.
+    active: bool,
+}
+impl Section {
+    /// Enters (checked or unchecked) real-time section.
+    pub(crate) fn enter(active: bool) -> Self {
+        if active {
+            VIOLATIONS.with(|violations| violations.set(0));
+            ACTIVE.with(|flag| flag.set(true));
+        }
+        Section { active }
+    }
+
+    /// Leaves real-time section, panics if heap operations were detected.
+    pub(crate) fn finish(self) {
+        if self.active {
+            ACTIVE.with(|flag| flag.set(false));
+            let violations = VIOLATIONS.with(|violations| violations.get());
+            assert_eq!(violations, 0, "Real-time section performed {} heap operations.", violations);
+        }
+    }
+}
+impl Drop for Section {
+    fn drop(&mut self) {
+        if self.active {
+            ACTIVE.with(|flag| flag.set(false));
+        }
+    }
+}
+
+/// Runs a closure that is allowed to allocate within a real-time section (e.g. preparing for a topology change).
+pub(crate) fn permit<R>(f: impl FnOnce() -> R) -> R {
+    let active = ACTIVE.with(|flag| flag.replace(false));
+    let result = f();
+    ACTIVE.with(|flag| flag.set(active));
+    result
+}
+
+/// Records a heap operation (violation if in a real-time section).
+fn record() {
+    // Thread locals may be unavailable during thread teardown.
+    let _ = ACTIVE.try_with(|flag| {
+        if flag.get() {
+            VIOLATIONS.with(|violations| violations.set(violations.get() + 1));
+        }
+    });
+}
+
+/// Allocator of unit tests (asserts real-time safety of every processed cycle).
+#[cfg(test)]
+#[global_allocator]
+static ALLOCATOR: RtCheck = RtCheck;
+
+/// Unit tests.
+#[cfg(all(test, feature = "dsp", feature = "generators", feature = "math"))]
+mod tests {
+    use super::*;
+    use crate::fixtures::accumulator;
+    use crate::{executors, nodes, Graph, InputId, Node, NodeId, OutputId, Value};
+    use std::time::Duration;
+
+    /// Node that allocates while processing.
+    struct Allocating {
+        values: Vec<Value>,
+    }
+    impl Node for Allocating {
+        fn delayed_processing(&self) -> bool {
+            false
+        }
+
+        fn get_output(&self, _id: OutputId) -> Value {
+            self.values.len() as Value
+        }
+
+        fn list_inputs(&self) -> &[InputId] {
+            &[]
+        }
+
+        fn list_outputs(&self) -> &[OutputId] {
+            &[OutputId(0)]
+        }
+
+        fn process(&mut self) {
+            self.values.push(0.0);
+        }
+
+        fn set_input(&mut self, _id: InputId, _value: Value) {}
+    }
+
+    /// Builds graph using all graph features that are active while processing.
+    fn build() -> Graph<Box<dyn Node + Send>> {
+        let (mut graph, [var0, _, del2]) = accumulator(1.0, |node| node);
+        graph.set_rate_divisor(del2, 2).unwrap();
+        graph.set_priority(var0, 1).unwrap();
+        graph.set_cycle_budget(Duration::from_secs(1), 0);
+        graph
+    }
+
+    #[test]
+    fn processes_without_allocation() {
+        let mut graph = build();
+        let handle = graph.param_handle(NodeId(0), InputId(0)).unwrap();
+        for value in 0..8 {
+            handle.set(value as Value);
+            graph.process();
+        }
+
+        // Compiling is permitted once per topology change.
+        for executor in 0..2 {
+            let mut graph = build();
+            match executor {
+                0 => graph.set_executor(executors::Compiled::new()),
+                _ => graph.set_executor(executors::Flat::new()),
+            }
+            graph.process_n(4);
+            graph.add_node(Box::from(nodes::Addition::new()));
+            graph.process_n(4);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Real-time section performed")]
+    fn detects_allocation() {
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        graph.add_node(Box::from(Allocating { values: Vec::new() }));
+        graph.process_n(8);
+    }
+
+    #[test]
+    fn ignores_unsafe_executors() {
+        let mut graph: Graph<Box<dyn Node + Send>> = Graph::new();
+        graph.add_node(Box::from(Allocating { values: Vec::new() }));
+        graph.set_executor(executors::Parallel::new(2));
+        graph.process_n(2);
+        assert_eq!(permit(|| vec![1.0]), vec![1.0]);
+    }
+}
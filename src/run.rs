@@ -0,0 +1,60 @@
+use crate::{Graph, GraphError, Node, NodeId, OutputId, Value};
+
+/// Iterator that processes a graph once per step and yields the values of selected outputs.
+pub struct Run<'a, N: Node> {
+    /// Graph being processed.
+    graph: &'a mut Graph<N>,
+
+    /// Outputs whose values are yielded after each cycle.
+    outputs: Vec<(NodeId, OutputId)>,
+}
+impl<'a, N: Node> Run<'a, N> {
+    /// Creates new run over graph (without selected outputs).
+    pub(crate) fn new(graph: &'a mut Graph<N>) -> Self {
+        Run { graph, outputs: Vec::new() }
+    }
+
+    /// Selects an output whose value is yielded after each cycle (in order of selection).
+    pub fn select(mut self, node: NodeId, output: OutputId) -> Result<Self, GraphError> {
+        self.graph.get_output(node, output)?;
+        self.outputs.push((node, output));
+        Ok(self)
+    }
+}
+impl<'a, N: Node> Iterator for Run<'a, N> {
+    type Item = Vec<Value>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.graph.process();
+        Some(self.outputs.iter().map(|&(node, output)| self.graph.get_output(node, output).unwrap()).collect())
+    }
+}
+
+/// Unit tests.
+#[cfg(all(test, feature = "dsp", feature = "generators", feature = "math"))]
+mod tests {
+    use super::*;
+    use crate::fixtures::accumulator;
+    use crate::nodes;
+
+    #[test]
+    fn yields_selected_outputs() {
+        let (mut graph, [_, add1, del2]) = accumulator(1.0, |node| node as Box<dyn Node>);
+
+        let values: Vec<Vec<Value>> =
+            graph.run().select(add1, OutputId(0)).unwrap().select(del2, OutputId(0)).unwrap().take(3).collect();
+        assert_eq!(values, vec![vec![1.0, 1.0], vec![2.0, 2.0], vec![3.0, 3.0]]);
+
+        // Without selection, empty values are yielded.
+        assert_eq!(graph.run().next(), Some(vec![]));
+        assert_eq!(graph.get_node(add1).unwrap().get_output(OutputId(0)), 4.0);
+    }
+
+    #[test]
+    fn rejects_invalid_selection() {
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        let var0 = graph.add_node(Box::from(nodes::Variable::new(1.0)));
+        assert_eq!(graph.run().select(var0, OutputId(1)).err(), Some(GraphError::OutputNotExists(var0, OutputId(1))));
+        assert_eq!(graph.run().select(NodeId(1), OutputId(0)).err(), Some(GraphError::NodeNotExists(NodeId(1))));
+    }
+}
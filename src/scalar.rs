@@ -0,0 +1,386 @@
+use crate::Value;
+#[cfg(feature = "fixed")]
+use std::fmt;
+use std::ops::{Add, Div, Mul};
+
+/// Number type that built-in arithmetic kernels are evaluated with (see `Graph::compile_scalar`), e.g. `Value` for
+/// floating point or `Q16` for fixed point.
+pub trait Scalar: Add<Output = Self> + Copy + Div<Output = Self> + Mul<Output = Self> + PartialEq {
+    /// Additive identity.
+    const ZERO: Self;
+
+    /// Multiplicative identity.
+    const ONE: Self;
+
+    /// Converts from a count (e.g. the number of averaged inputs).
+    fn from_count(count: usize) -> Self;
+
+    /// Converts from a value (rounding to the nearest representable number).
+    fn from_value(value: Value) -> Self;
+
+    /// Converts to a value.
+    fn to_value(self) -> Value;
+}
+impl Scalar for Value {
+    const ZERO: Self = 0.0;
+    const ONE: Self = 1.0;
+
+    fn from_count(count: usize) -> Self {
+        count as Value
+    }
+
+    fn from_value(value: Value) -> Self {
+        value
+    }
+
+    fn to_value(self) -> Value {
+        self
+    }
+}
+
+/// Dual number carrying a value and its partial derivatives with respect to `N` chosen inputs (forward-mode automatic
+/// differentiation), e.g. to obtain sensitivities of outputs to controller gains.
+///
+/// Inputs of interest are seeded with `Dual::variable`, all other values have zero partials.
+#[cfg(feature = "autodiff")]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Dual<const N: usize> {
+    /// Partial derivatives with respect to the chosen inputs.
+    pub partials: [Value; N],
+
+    /// Value.
+    pub value: Value,
+}
+#[cfg(feature = "autodiff")]
+impl<const N: usize> Dual<N> {
+    /// Creates new dual number with zero partials.
+    pub fn constant(value: Value) -> Self {
+        Dual { partials: [0.0; N], value }
+    }
+
+    /// Creates new dual number for the chosen input with given index (partial of 1 with respect to itself).
+    pub fn variable(value: Value, index: usize) -> Self {
+        let mut partials = [0.0; N];
+        partials[index] = 1.0;
+        Dual { partials, value }
+    }
+}
+#[cfg(feature = "autodiff")]
+impl<const N: usize> Add for Dual<N> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Dual {
+            partials: std::array::from_fn(|i| self.partials[i] + other.partials[i]),
+            value: self.value + other.value,
+        }
+    }
+}
+#[cfg(feature = "autodiff")]
+impl<const N: usize> Div for Dual<N> {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, other: Self) -> Self {
+        let value = self.value / other.value;
+        Dual { partials: std::array::from_fn(|i| (self.partials[i] - value * other.partials[i]) / other.value), value }
+    }
+}
+#[cfg(feature = "autodiff")]
+impl<const N: usize> Mul for Dual<N> {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn mul(self, other: Self) -> Self {
+        Dual {
+            partials: std::array::from_fn(|i| self.partials[i] * other.value + self.value * other.partials[i]),
+            value: self.value * other.value,
+        }
+    }
+}
+#[cfg(feature = "autodiff")]
+impl<const N: usize> Scalar for Dual<N> {
+    const ZERO: Self = Dual { partials: [0.0; N], value: 0.0 };
+    const ONE: Self = Dual { partials: [0.0; N], value: 1.0 };
+
+    fn from_count(count: usize) -> Self {
+        Self::constant(count as Value)
+    }
+
+    fn from_value(value: Value) -> Self {
+        Self::constant(value)
+    }
+
+    fn to_value(self) -> Value {
+        self.value
+    }
+}
+
+/// Closed interval of values for worst-case range analysis, e.g. to verify that a graph cannot leave actuator limits
+/// for given input ranges.
+///
+/// Dividing by an interval containing 0 yields the unbounded interval. Intervals are not tracked through correlations,
+/// so the bounds may be wider than the actual range (but never narrower).
+#[cfg(feature = "interval")]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Interval {
+    /// Upper bound.
+    pub hi: Value,
+
+    /// Lower bound.
+    pub lo: Value,
+}
+#[cfg(feature = "interval")]
+impl Interval {
+    /// Creates new interval from its bounds (in any order).
+    pub fn new(a: Value, b: Value) -> Self {
+        Interval { hi: a.max(b), lo: a.min(b) }
+    }
+
+    /// Returns whether the interval lies within given bounds.
+    pub fn within(self, lo: Value, hi: Value) -> bool {
+        lo <= self.lo && self.hi <= hi
+    }
+}
+#[cfg(feature = "interval")]
+impl Add for Interval {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Interval { hi: self.hi + other.hi, lo: self.lo + other.lo }
+    }
+}
+#[cfg(feature = "interval")]
+impl Div for Interval {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, other: Self) -> Self {
+        match other.lo <= 0.0 && other.hi >= 0.0 {
+            true => Interval { hi: Value::INFINITY, lo: Value::NEG_INFINITY },
+            false => self * Interval::new(1.0 / other.lo, 1.0 / other.hi),
+        }
+    }
+}
+#[cfg(feature = "interval")]
+impl Mul for Interval {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        let products = [self.lo * other.lo, self.lo * other.hi, self.hi * other.lo, self.hi * other.hi];
+        Interval {
+            hi: products.into_iter().fold(Value::NEG_INFINITY, Value::max),
+            lo: products.into_iter().fold(Value::INFINITY, Value::min),
+        }
+    }
+}
+#[cfg(feature = "interval")]
+impl Scalar for Interval {
+    const ZERO: Self = Interval { hi: 0.0, lo: 0.0 };
+    const ONE: Self = Interval { hi: 1.0, lo: 1.0 };
+
+    fn from_count(count: usize) -> Self {
+        Self::from_value(count as Value)
+    }
+
+    fn from_value(value: Value) -> Self {
+        Interval { hi: value, lo: value }
+    }
+
+    fn to_value(self) -> Value {
+        (self.lo + self.hi) / 2.0
+    }
+}
+
+/// Signed Q16.16 fixed-point number (16 integer and 16 fractional bits) for targets without floating-point unit.
+///
+/// Addition wraps around on overflow, multiplication and division truncate towards negative infinity. Division by
+/// zero saturates to the bound with the sign of the dividend (0 for 0 / 0).
+#[cfg(feature = "fixed")]
+#[derive(Clone, Copy, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Q16(pub i32);
+#[cfg(feature = "fixed")]
+impl Q16 {
+    /// Number of fractional bits.
+    pub const FRACTIONAL_BITS: u32 = 16;
+}
+#[cfg(feature = "fixed")]
+impl Add for Q16 {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Q16(self.0.wrapping_add(other.0))
+    }
+}
+#[cfg(feature = "fixed")]
+impl fmt::Debug for Q16 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Q16({})", self.to_value())
+    }
+}
+#[cfg(feature = "fixed")]
+impl Div for Q16 {
+    type Output = Self;
+
+    fn div(self, other: Self) -> Self {
+        match (self.0, other.0) {
+            (0, 0) => Q16(0),
+            (dividend, 0) => Q16(if dividend > 0 { i32::MAX } else { i32::MIN }),
+            (dividend, divisor) => Q16((((dividend as i64) << Self::FRACTIONAL_BITS) / divisor as i64) as i32),
+        }
+    }
+}
+#[cfg(feature = "fixed")]
+impl Mul for Q16 {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        Q16(((self.0 as i64 * other.0 as i64) >> Self::FRACTIONAL_BITS) as i32)
+    }
+}
+#[cfg(feature = "fixed")]
+impl Scalar for Q16 {
+    const ZERO: Self = Q16(0);
+    const ONE: Self = Q16(1 << Self::FRACTIONAL_BITS);
+
+    fn from_count(count: usize) -> Self {
+        Q16((count as i32) << Self::FRACTIONAL_BITS)
+    }
+
+    fn from_value(value: Value) -> Self {
+        Q16((value * (1 << Self::FRACTIONAL_BITS) as Value).round() as i32)
+    }
+
+    fn to_value(self) -> Value {
+        self.0 as Value / (1 << Self::FRACTIONAL_BITS) as Value
+    }
+}
+
+/// Uncertain value given by mean and variance, propagated with first-order (linearized) rules for independent
+/// operands, e.g. to track the uncertainty of fused sensor readings.
+///
+/// Correlations are not tracked, so a value combined with itself (e.g. through a feedback path) is treated as an
+/// independent operand.
+#[cfg(feature = "uncertainty")]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Uncertain {
+    /// Mean (expected value).
+    pub mean: Value,
+
+    /// Variance (squared standard deviation).
+    pub variance: Value,
+}
+#[cfg(feature = "uncertainty")]
+impl Uncertain {
+    /// Creates new uncertain value.
+    pub fn new(mean: Value, variance: Value) -> Self {
+        Uncertain { mean, variance }
+    }
+
+    /// Returns standard deviation.
+    pub fn std_dev(self) -> Value {
+        self.variance.sqrt()
+    }
+}
+#[cfg(feature = "uncertainty")]
+impl Add for Uncertain {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Uncertain::new(self.mean + other.mean, self.variance + other.variance)
+    }
+}
+#[cfg(feature = "uncertainty")]
+impl Div for Uncertain {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, other: Self) -> Self {
+        let mean = self.mean / other.mean;
+        let divisor = other.mean * other.mean;
+        Uncertain::new(mean, (self.variance + mean * mean * other.variance) / divisor)
+    }
+}
+#[cfg(feature = "uncertainty")]
+impl Mul for Uncertain {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn mul(self, other: Self) -> Self {
+        let variance = other.mean * other.mean * self.variance + self.mean * self.mean * other.variance;
+        Uncertain::new(self.mean * other.mean, variance)
+    }
+}
+#[cfg(feature = "uncertainty")]
+impl Scalar for Uncertain {
+    const ZERO: Self = Uncertain { mean: 0.0, variance: 0.0 };
+    const ONE: Self = Uncertain { mean: 1.0, variance: 0.0 };
+
+    fn from_count(count: usize) -> Self {
+        Uncertain::new(count as Value, 0.0)
+    }
+
+    fn from_value(value: Value) -> Self {
+        Uncertain::new(value, 0.0)
+    }
+
+    fn to_value(self) -> Value {
+        self.mean
+    }
+}
+
+/// Unit tests.
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "autodiff")]
+    #[test]
+    fn dual_arithmetic() {
+        // f(x, y) = x * y / (x + y) at (2, 3).
+        let (x, y) = (Dual::<2>::variable(2.0, 0), Dual::<2>::variable(3.0, 1));
+        let f = x * y / (x + y);
+        assert_eq!(f.value, 1.2);
+        assert!((f.partials[0] - 0.36).abs() < 1e-6);
+        assert!((f.partials[1] - 0.16).abs() < 1e-6);
+        assert_eq!(Dual::<2>::from_count(3), Dual { partials: [0.0; 2], value: 3.0 });
+    }
+
+    #[cfg(feature = "fixed")]
+    #[test]
+    fn fixed_point_arithmetic() {
+        let (a, b) = (Q16::from_value(1.5), Q16::from_value(-0.25));
+        assert_eq!(a, Q16(0x18000));
+        assert_eq!((a + b).to_value(), 1.25);
+        assert_eq!((a * b).to_value(), -0.375);
+        assert_eq!((a / b).to_value(), -6.0);
+        assert_eq!(Q16::from_count(3) * Q16::ONE, Q16::from_value(3.0));
+        assert_eq!(a / Q16::ZERO, Q16(i32::MAX));
+        assert_eq!(Q16::ZERO / Q16::ZERO, Q16::ZERO);
+        assert_eq!(format!("{:?}", b), "Q16(-0.25)");
+    }
+
+    #[cfg(feature = "interval")]
+    #[test]
+    fn interval_arithmetic() {
+        let (a, b) = (Interval::new(-1.0, 2.0), Interval::new(4.0, 0.5));
+        assert_eq!(a, Interval { hi: 2.0, lo: -1.0 });
+        assert_eq!(a + b, Interval::new(-0.5, 6.0));
+        assert_eq!(a * b, Interval::new(-4.0, 8.0));
+        assert_eq!(a / b, Interval::new(-2.0, 4.0));
+        assert_eq!(b / a, Interval::new(Value::NEG_INFINITY, Value::INFINITY));
+        assert_eq!(Interval::from_count(2).to_value(), 2.0);
+        assert!(a.within(-1.0, 2.0) && !a.within(0.0, 2.0));
+    }
+
+    #[cfg(feature = "uncertainty")]
+    #[test]
+    fn uncertainty_propagation() {
+        let (a, b) = (Uncertain::new(2.0, 0.25), Uncertain::new(4.0, 1.0));
+        assert_eq!(a + b, Uncertain::new(6.0, 1.25));
+        assert_eq!(a * b, Uncertain::new(8.0, 8.0));
+        assert_eq!(a / b, Uncertain::new(0.5, 0.03125));
+        assert_eq!((a + b).std_dev(), (1.25 as Value).sqrt());
+        assert_eq!(Uncertain::from_value(3.0) * a, Uncertain::new(6.0, 2.25));
+    }
+}
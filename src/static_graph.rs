@@ -0,0 +1,132 @@
+use crate::{InputId, Node, OutputId};
+
+/// Defines a struct for a graph of fixed topology, with a field per node and a `process` method that calls the
+/// nodes directly (without boxing, dynamic dispatch or lookups), e.g. for hot inner loops like a fixed filter chain.
+///
+/// Nodes are declared in processing order (nodes only depend on previous nodes or on nodes that introduce delay)
+/// with their type and initial value. Inputs are fed by outputs of other nodes as `input: source.output`. Like in a
+/// `Graph`, nodes that introduce delay are processed last and their outputs reach other nodes one cycle later.
+///
+#[cfg_attr(all(feature = "dsp", feature = "generators", feature = "math"), doc = "```")]
+#[cfg_attr(not(all(feature = "dsp", feature = "generators", feature = "math")), doc = "```ignore")]
+/// use flowing::{nodes, static_graph, InputId, Node, OutputId, ProcessContext};
+///
+/// static_graph! {
+///     /// Accumulator of a variable.
+///     struct Accumulator {
+///         var: nodes::Variable = nodes::Variable::new(2.0),
+///         sum: nodes::Addition = nodes::Addition::new() => { 0: var.0, 1: feedback.0 },
+///         feedback: nodes::Delay = nodes::Delay::new() => { 0: sum.0 },
+///     }
+/// }
+///
+/// let mut acc = Accumulator::new();
+/// let context = ProcessContext::new(1.0);
+/// acc.process(&context).unwrap();
+/// acc.process(&context).unwrap();
+/// assert_eq!(acc.sum.get_output(OutputId(0)), 4.0);
+/// ```
+#[macro_export]
+macro_rules! static_graph {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident {
+            $(
+                $node:ident: $type:ty = $init:expr
+                $(=> { $($input:literal: $source:ident.$output:literal),* $(,)? })?
+            ),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis struct $name {
+            $($vis $node: $type,)*
+        }
+        impl $name {
+            /// Creates graph with nodes at their initial values.
+            $vis fn new() -> Self {
+                $name { $($node: $init,)* }
+            }
+
+            /// Processes one cycle (returns the error of the last failed node).
+            $vis fn process(&mut self, context: &$crate::ProcessContext) -> Result<(), $crate::ProcessError> {
+                use $crate::Node as _;
+                let mut result = Ok(());
+
+                // First pass (inputs of all nodes are populated).
+                $(
+                    $($(
+                        $crate::static_graph::transfer(
+                            &self.$source,
+                            $crate::OutputId($output),
+                            &mut self.$node,
+                            $crate::InputId($input),
+                        );
+                    )*)?
+                    if !self.$node.delayed_processing() {
+                        result = self.$node.try_process(context).and(result);
+                    }
+                )*
+
+                // Second pass.
+                $(
+                    if self.$node.delayed_processing() {
+                        result = self.$node.try_process(context).and(result);
+                    }
+                )*
+                result
+            }
+        }
+        impl Default for $name {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+    };
+}
+
+/// Copies the value (all channels) of an output of a node to an input of another node (see `static_graph!`).
+pub fn transfer<S: Node, T: Node>(source: &S, output: OutputId, target: &mut T, input: InputId) {
+    match source.output_channels(output) {
+        1 => target.set_input(input, source.get_output(output)),
+        channels => {
+            for channel in 0..channels {
+                target.set_input_channel(input, channel, source.get_output_channel(output, channel));
+            }
+        }
+    }
+}
+
+/// Unit tests.
+#[cfg(all(test, feature = "dsp", feature = "generators", feature = "math"))]
+mod tests {
+    use crate::fixtures::accumulator;
+    use crate::{nodes, Connection, InputId, Node, OutputId, ProcessContext};
+
+    crate::static_graph! {
+        /// Smoothed accumulator of a variable.
+        struct Accumulator {
+            var: nodes::Variable = nodes::Variable::new(2.0),
+            sum: nodes::Addition = nodes::Addition::new() => { 0: var.0, 1: feedback.0 },
+            feedback: nodes::Delay = nodes::Delay::new() => { 0: sum.0 },
+            smooth: nodes::Smooth = nodes::Smooth::new(0.0, 0.5) => { 0: sum.0 },
+        }
+    }
+
+    #[test]
+    fn processes_like_graph() {
+        let (mut graph, [_, add1, _]) = accumulator(2.0, |node| node as Box<dyn Node>);
+        let smooth3 = graph.add_node(Box::new(nodes::Smooth::new(0.0, 0.5)));
+        graph.add_connection(Connection::new(add1, OutputId(0), smooth3, InputId(0))).unwrap();
+
+        let mut acc = Accumulator::new();
+        let mut context = ProcessContext::new(graph.context().sample_rate);
+        for _ in 0..5 {
+            graph.process();
+            acc.process(&context).unwrap();
+            context.frame += 1;
+            assert_eq!(acc.sum.get_output(OutputId(0)), graph.get_output(add1, OutputId(0)).unwrap());
+            assert_eq!(acc.smooth.get_output(OutputId(0)), graph.get_output(smooth3, OutputId(0)).unwrap());
+        }
+        assert_eq!(acc.sum.get_output(OutputId(0)), 10.0);
+    }
+}
@@ -0,0 +1,141 @@
+use crate::{Connection, Graph, GraphError, Node, NodeId, Value};
+
+/// Constructor of a template node (taking instance parameters).
+type Constructor<N> = Box<dyn Fn(&[Value]) -> N + Send + Sync>;
+
+/// Parameterized graph description that can be instantiated multiple times into a graph (e.g. identical voices).
+///
+/// Nodes are identified by template ids (numbered from 0 in the order they are added), which are mapped to graph ids
+/// when instantiating.
+pub struct GraphTemplate<N: Node> {
+    /// Connections between template nodes.
+    connections: Vec<Connection>,
+
+    /// Event connections between template nodes.
+    event_connections: Vec<Connection>,
+
+    /// Constructors of template nodes, indexed by template id.
+    nodes: Vec<Constructor<N>>,
+}
+impl<N: Node> GraphTemplate<N> {
+    /// Creates new empty template.
+    pub fn new() -> Self {
+        GraphTemplate { connections: Vec::new(), event_connections: Vec::new(), nodes: Vec::new() }
+    }
+
+    /// Adds a connection between template nodes (ports are validated when instantiating).
+    pub fn add_connection(&mut self, connection: Connection) -> Result<Connection, GraphError> {
+        self.validate_nodes(connection)?;
+        self.connections.push(connection);
+        Ok(connection)
+    }
+
+    /// Adds an event connection between template nodes (ports are validated when instantiating).
+    pub fn add_event_connection(&mut self, connection: Connection) -> Result<Connection, GraphError> {
+        self.validate_nodes(connection)?;
+        self.event_connections.push(connection);
+        Ok(connection)
+    }
+
+    /// Adds a node constructed from instance parameters and returns its template id.
+    pub fn add_node<F: Fn(&[Value]) -> N + Send + Sync + 'static>(&mut self, constructor: F) -> NodeId {
+        self.nodes.push(Box::new(constructor));
+        NodeId(self.nodes.len() as u32 - 1)
+    }
+
+    /// Returns whether template has no nodes.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Returns number of nodes in template.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Checks whether both nodes of a connection exist in the template.
+    fn validate_nodes(&self, connection: Connection) -> Result<(), GraphError> {
+        for node in [connection.source_node, connection.target_node] {
+            if node.0 as usize >= self.nodes.len() {
+                return Err(GraphError::NodeNotExists(node));
+            }
+        }
+        Ok(())
+    }
+}
+impl<N: Node> Default for GraphTemplate<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<N: Node> Graph<N> {
+    /// Instantiates a template with parameters (passed to all node constructors) and returns the graph ids of its
+    /// nodes, indexed by template id. Added nodes are removed again if a connection of the template is invalid.
+    pub fn instantiate(&mut self, template: &GraphTemplate<N>, params: &[Value]) -> Result<Vec<NodeId>, GraphError> {
+        let ids = self.add_nodes(template.nodes.iter().map(|constructor| constructor(params)));
+        let map = |connection: &Connection| {
+            Connection::new(
+                ids[connection.source_node.0 as usize],
+                connection.source_output,
+                ids[connection.target_node.0 as usize],
+                connection.target_input,
+            )
+        };
+        let mut result = self.add_connections(template.connections.iter().map(map)).map(|_| ());
+        if result.is_ok() {
+            result = template.event_connections.iter().try_for_each(|c| self.add_event_connection(map(c)).map(|_| ()));
+        }
+        if let Err(error) = result {
+            self.remove_nodes(&ids).unwrap();
+            return Err(error);
+        }
+        Ok(ids)
+    }
+}
+
+/// Unit tests.
+#[cfg(all(test, feature = "generators", feature = "math"))]
+mod tests {
+    use super::*;
+    use crate::{nodes, InputId, OutputId};
+
+    /// Builds template of a voice (oscillator frequency parameter added to a shared input).
+    fn voice() -> GraphTemplate<Box<dyn Node>> {
+        let mut template: GraphTemplate<Box<dyn Node>> = GraphTemplate::new();
+        let var0 = template.add_node(|params| Box::new(nodes::Variable::new(params[0])));
+        let add1 = template.add_node(|_| Box::new(nodes::Addition::new()));
+        template.add_connection(Connection::new(var0, OutputId(0), add1, InputId(0))).unwrap();
+        template
+    }
+
+    #[test]
+    fn instantiate() {
+        let template = voice();
+        assert_eq!(template.len(), 2);
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        let input = graph.add_node(Box::new(nodes::Variable::new(1.0)));
+        let voices: Vec<Vec<NodeId>> =
+            [10.0, 20.0].iter().map(|&frequency| graph.instantiate(&template, &[frequency]).unwrap()).collect();
+        assert_eq!(voices, vec![vec![NodeId(1), NodeId(2)], vec![NodeId(3), NodeId(4)]]);
+        for voice in voices.iter() {
+            graph.add_connection(Connection::new(input, OutputId(0), voice[1], InputId(1))).unwrap();
+        }
+        graph.process();
+        assert_eq!(graph.get_output(voices[0][1], OutputId(0)), Ok(11.0));
+        assert_eq!(graph.get_output(voices[1][1], OutputId(0)), Ok(21.0));
+    }
+
+    #[test]
+    fn rejects_invalid_connections() {
+        let mut template = voice();
+        assert_eq!(
+            template.add_connection(Connection::new(NodeId(0), OutputId(0), NodeId(2), InputId(0))),
+            Err(GraphError::NodeNotExists(NodeId(2)))
+        );
+        template.add_connection(Connection::new(NodeId(1), OutputId(0), NodeId(1), InputId(1))).unwrap();
+        let mut graph = Graph::new();
+        assert_eq!(graph.instantiate(&template, &[0.0]), Err(GraphError::CycleWithoutDelay));
+        assert!(graph.processing_order().is_empty());
+    }
+}
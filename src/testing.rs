@@ -0,0 +1,307 @@
+//! Utilities for testing graphs (enable with the `testing` feature): a `Harness` for scripted input and expected
+//! output sequences and proptest strategies for property testing.
+//!
+//! ```ignore
+//! proptest! {
+//!     #[test]
+//!     fn order_is_topological(spec in testing::graphs(8, true), edits in testing::edits(16)) {
+//!         let mut graph = spec.build();
+//!         for edit in edits.iter() {
+//!             let _ = edit.apply(&mut graph);
+//!             prop_assert!(testing::is_topological_order(&graph));
+//!         }
+//!     }
+//! }
+//! ```
+
+use crate::{nodes, Connection, Graph, GraphError, InputId, Node, NodeId, OutputId, Value};
+use proptest::{collection, prelude::*};
+use std::{collections::HashMap, fmt::Write};
+
+/// Graph type produced by the strategies.
+pub type TestGraph = Graph<Box<dyn Node + Send>>;
+
+/// Random edit of a graph. Nodes and connections are referred to by index (modulo their count, in id and insertion
+/// order), so edits stay meaningful whatever the graph.
+#[derive(Clone, Debug)]
+pub enum Edit {
+    /// Connects output of a node to an input of another node.
+    AddConnection { input: u32, source: usize, target: usize },
+
+    /// Adds a built-in node.
+    AddNode(NodeKind),
+
+    /// Removes a connection.
+    RemoveConnection(usize),
+
+    /// Removes a node (and its connections).
+    RemoveNode(usize),
+}
+impl Edit {
+    /// Applies the edit to a graph (edits referring to an empty graph do nothing).
+    pub fn apply(&self, graph: &mut TestGraph) -> Result<(), GraphError> {
+        let mut ids: Vec<NodeId> = graph.iter_nodes().map(|(&id, _)| id).collect();
+        ids.sort_unstable();
+        match *self {
+            Edit::AddConnection { input, source, target } if !ids.is_empty() => {
+                let (source, target) = (ids[source % ids.len()], ids[target % ids.len()]);
+                let inputs = graph.get_node(target)?.list_inputs().len() as u32;
+                let connection = Connection::new(source, OutputId(0), target, InputId(input % inputs.max(1)));
+                graph.add_connection(connection).map(|_| ())
+            }
+            Edit::AddNode(kind) => {
+                graph.add_node(kind.build());
+                Ok(())
+            }
+            Edit::RemoveConnection(index) if !graph.connections().is_empty() => {
+                let connection = graph.connections()[index % graph.connections().len()];
+                graph.remove_connection(connection).map(|_| ())
+            }
+            Edit::RemoveNode(index) if !ids.is_empty() => graph.remove_node(ids[index % ids.len()]).map(|_| ()),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Description of a random graph, built with `GraphSpec::build`.
+#[derive(Clone, Debug)]
+pub struct GraphSpec {
+    /// Connections added after the nodes (invalid ones, e.g. undelayed cycles or connected inputs, are skipped).
+    pub connections: Vec<Edit>,
+
+    /// Nodes of the graph.
+    pub nodes: Vec<NodeKind>,
+}
+impl GraphSpec {
+    /// Builds the graph.
+    pub fn build(&self) -> TestGraph {
+        let mut graph = TestGraph::new();
+        for kind in self.nodes.iter() {
+            graph.add_node(kind.build());
+        }
+        for edit in self.connections.iter() {
+            let _ = edit.apply(&mut graph);
+        }
+        graph
+    }
+}
+
+/// Drives a graph for a number of cycles with scripted input sequences and compares output sequences with expected
+/// values (within a tolerance), reporting mismatches as a readable table.
+///
+/// ```ignore
+/// Harness::new(&mut graph)
+///     .input(var0, InputId(0), &[1.0, 2.0])
+///     .expect(add1, OutputId(0), &[1.0, 3.0, 5.0])
+///     .assert();
+/// ```
+pub struct Harness<'a, N: Node> {
+    /// Expected output sequences.
+    expected: Vec<(NodeId, OutputId, Vec<Value>)>,
+
+    /// Driven graph.
+    graph: &'a mut Graph<N>,
+
+    /// Input sequences (inputs hold their last value once a sequence ends).
+    inputs: Vec<(NodeId, InputId, Vec<Value>)>,
+
+    /// Maximum absolute difference of matching values.
+    tolerance: Value,
+}
+impl<'a, N: Node> Harness<'a, N> {
+    /// Creates new harness driving a graph (with a tolerance of `1e-9`).
+    pub fn new(graph: &'a mut Graph<N>) -> Self {
+        Harness { expected: Vec::new(), graph, inputs: Vec::new(), tolerance: 1e-9 }
+    }
+
+    /// Runs the cycles and panics with a table of mismatches if any output differs from its expected sequence.
+    pub fn assert(self) {
+        if let Err(report) = self.run() {
+            panic!("{}", report);
+        }
+    }
+
+    /// Adds an expected output sequence, one value per cycle.
+    pub fn expect(mut self, node: NodeId, output: OutputId, values: &[Value]) -> Self {
+        self.expected.push((node, output, values.to_vec()));
+        self
+    }
+
+    /// Adds an input sequence, one value set before each cycle.
+    pub fn input(mut self, node: NodeId, input: InputId, values: &[Value]) -> Self {
+        self.inputs.push((node, input, values.to_vec()));
+        self
+    }
+
+    /// Runs as many cycles as the longest sequence and returns a table of mismatches if any output differs from its
+    /// expected sequence.
+    pub fn run(self) -> Result<(), String> {
+        let cycles = self.inputs.iter().map(|(_, _, v)| v.len()).chain(self.expected.iter().map(|(_, _, v)| v.len()));
+        let mut actual = vec![Vec::new(); self.expected.len()];
+        for cycle in 0..cycles.max().unwrap_or(0) {
+            for (node, input, values) in self.inputs.iter() {
+                if let Some(&value) = values.get(cycle) {
+                    self.graph.set_input(*node, *input, value).map_err(|error| format!("{:?}", error))?;
+                }
+            }
+            self.graph.process();
+            for ((node, output, _), actual) in self.expected.iter().zip(actual.iter_mut()) {
+                actual.push(self.graph.get_output(*node, *output).map_err(|error| format!("{:?}", error))?);
+            }
+        }
+
+        let mut report = String::new();
+        for ((node, output, expected), actual) in self.expected.iter().zip(actual.iter()) {
+            let matches = |(expected, actual): (&Value, &Value)| {
+                (expected - actual).abs() <= self.tolerance || (expected.is_nan() && actual.is_nan())
+            };
+            if expected.iter().zip(actual.iter()).all(matches) {
+                continue;
+            }
+            writeln!(report, "Output {} of node {} differs (tolerance {:e}):", output.0, node.0, self.tolerance)
+                .unwrap();
+            writeln!(report, "{:>7} {:>14} {:>14}", "cycle", "expected", "actual").unwrap();
+            for (cycle, (expected, actual)) in expected.iter().zip(actual.iter()).enumerate() {
+                let marker = if matches((expected, actual)) { "" } else { "  <-" };
+                writeln!(report, "{:>7} {:>14} {:>14}{}", cycle, expected, actual, marker).unwrap();
+            }
+        }
+        match report.is_empty() {
+            true => Ok(()),
+            false => Err(report),
+        }
+    }
+
+    /// Sets maximum absolute difference of matching values.
+    pub fn tolerance(mut self, tolerance: Value) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+}
+
+/// Kind of built-in node used by the strategies.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NodeKind {
+    /// `nodes::Addition`.
+    Addition,
+
+    /// `nodes::Delay` (introduces delay, so cycles through it are valid).
+    Delay,
+
+    /// `nodes::SumN` with given number of inputs.
+    Sum(u32),
+
+    /// `nodes::Variable` with given value.
+    Variable(Value),
+}
+impl NodeKind {
+    /// Builds a node of this kind.
+    pub fn build(self) -> Box<dyn Node + Send> {
+        match self {
+            NodeKind::Addition => Box::new(nodes::Addition::new()),
+            NodeKind::Delay => Box::new(nodes::Delay::new()),
+            NodeKind::Sum(inputs) => Box::new(nodes::SumN::new(inputs)),
+            NodeKind::Variable(value) => Box::new(nodes::Variable::new(value)),
+        }
+    }
+}
+
+/// Returns strategy of random edit sequences of up to `max_len` edits.
+pub fn edits(max_len: usize) -> impl Strategy<Value = Vec<Edit>> {
+    let edit = prop_oneof![
+        3 => (any::<u32>(), any::<usize>(), any::<usize>())
+            .prop_map(|(input, source, target)| Edit::AddConnection { input, source, target }),
+        2 => node_kinds(true).prop_map(Edit::AddNode),
+        1 => any::<usize>().prop_map(Edit::RemoveConnection),
+        1 => any::<usize>().prop_map(Edit::RemoveNode),
+    ];
+    collection::vec(edit, 0..=max_len)
+}
+
+/// Returns strategy of random valid graphs (see `GraphSpec`) with up to `max_nodes` built-in nodes. With
+/// `delayed_cycles`, graphs may contain delays and cycles through them, otherwise they are acyclic.
+pub fn graphs(max_nodes: usize, delayed_cycles: bool) -> impl Strategy<Value = GraphSpec> {
+    let connections = collection::vec((any::<u32>(), any::<usize>(), any::<usize>()), 0..=max_nodes * 2);
+    (collection::vec(node_kinds(delayed_cycles), 0..=max_nodes), connections).prop_map(|(nodes, connections)| {
+        let connections = connections
+            .into_iter()
+            .map(|(input, source, target)| Edit::AddConnection { input, source, target })
+            .collect();
+        GraphSpec { connections, nodes }
+    })
+}
+
+/// Returns whether the processing order of a graph contains each node once and every node comes after the nodes it
+/// depends on (sources that introduce delay excepted).
+pub fn is_topological_order<N: Node>(graph: &Graph<N>) -> bool {
+    let positions: HashMap<NodeId, usize> =
+        graph.processing_order().iter().enumerate().map(|(position, &id)| (id, position)).collect();
+    positions.len() == graph.processing_order().len()
+        && graph.iter_nodes().all(|(id, _)| positions.contains_key(id))
+        && graph.connections().iter().all(|c| {
+            graph.get_node(c.source_node).unwrap().delayed_processing()
+                || positions[&c.source_node] < positions[&c.target_node]
+        })
+}
+
+/// Returns strategy of built-in node kinds (with `delayed`, including nodes that introduce delay).
+fn node_kinds(delayed: bool) -> BoxedStrategy<NodeKind> {
+    let kinds = prop_oneof![
+        Just(NodeKind::Addition),
+        (0..4u32).prop_map(NodeKind::Sum),
+        (-1.0..1.0 as Value).prop_map(NodeKind::Variable),
+    ];
+    match delayed {
+        true => prop_oneof![3 => kinds, 1 => Just(NodeKind::Delay)].boxed(),
+        false => kinds.boxed(),
+    }
+}
+
+/// Unit tests.
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::accumulator;
+
+    #[test]
+    fn harness() {
+        let (mut graph, [var0, add1, _]) = accumulator(0.0, |node| node);
+
+        Harness::new(&mut graph)
+            .input(var0, InputId(0), &[1.0, 2.0])
+            .expect(add1, OutputId(0), &[1.0, 3.0, 5.0])
+            .assert();
+        let report = Harness::new(&mut graph).expect(add1, OutputId(0), &[7.0, 9.5]).tolerance(0.1).run();
+        assert_eq!(
+            report.unwrap_err(),
+            concat!(
+                "Output 0 of node 1 differs (tolerance 1e-1):\n",
+                "  cycle       expected         actual\n",
+                "      0              7              7\n",
+                "      1            9.5              9  <-\n"
+            )
+        );
+        let report = Harness::new(&mut graph).input(add1, InputId(2), &[0.0]).run();
+        assert_eq!(report.unwrap_err(), "Input with id 2 does not exist on node with id 1.");
+    }
+
+    proptest! {
+        #[test]
+        fn acyclic_graphs(spec in graphs(8, false)) {
+            let graph = spec.build();
+            prop_assert!(graph.iter_nodes().all(|(_, node)| !node.delayed_processing()));
+            prop_assert!(is_topological_order(&graph));
+        }
+
+        #[test]
+        fn order_stays_topological(spec in graphs(8, true), edits in edits(16)) {
+            let mut graph = spec.build();
+            prop_assert!(is_topological_order(&graph));
+            for edit in edits.iter() {
+                let _ = edit.apply(&mut graph);
+                prop_assert!(is_topological_order(&graph));
+                graph.process();
+            }
+        }
+    }
+}
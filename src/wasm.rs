@@ -0,0 +1,195 @@
+use crate::{Connection, Graph, GraphError, InputId, Node, NodeId, NodeRegistry, OutputId, Value};
+use wasm_bindgen::prelude::*;
+
+/// Graph of builtin nodes for JavaScript (errors are thrown as `Error` with message `<kind>: <description>`).
+#[wasm_bindgen(js_name = Graph)]
+pub struct WasmGraph {
+    /// Wrapped graph.
+    graph: Graph<Box<dyn Node>>,
+
+    /// Registry of builtin node types.
+    registry: NodeRegistry<Box<dyn Node>>,
+}
+#[wasm_bindgen(js_class = Graph)]
+impl WasmGraph {
+    /// Creates new empty graph.
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        WasmGraph { graph: Graph::new(), registry: NodeRegistry::builtin() }
+    }
+
+    /// Adds a connection.
+    #[wasm_bindgen(js_name = addConnection)]
+    pub fn add_connection(
+        &mut self,
+        source_node: u32,
+        source_output: u32,
+        target_node: u32,
+        target_input: u32,
+    ) -> Result<(), JsError> {
+        let connection =
+            Connection::new(NodeId(source_node), OutputId(source_output), NodeId(target_node), InputId(target_input));
+        self.graph.add_connection(connection).map(|_| ()).map_err(js_error)
+    }
+
+    /// Adds a node of a builtin type (see `NodeRegistry::builtin`) and returns its id.
+    #[wasm_bindgen(js_name = addNode)]
+    pub fn add_node(&mut self, type_name: &str, params: Vec<Value>) -> Result<u32, JsError> {
+        let node = self.registry.create(type_name, &params).map_err(js_error)?;
+        Ok(self.graph.add_node(node).0)
+    }
+
+    /// Returns value of a node output.
+    #[wasm_bindgen(js_name = getOutput)]
+    pub fn get_output(&self, node: u32, output: u32) -> Result<Value, JsError> {
+        self.graph.get_output(NodeId(node), OutputId(output)).map_err(js_error)
+    }
+
+    /// Returns ids of nodes (ascending).
+    #[wasm_bindgen(js_name = nodeIds)]
+    pub fn node_ids(&self) -> Vec<u32> {
+        let mut ids: Vec<u32> = self.graph.iter_nodes().map(|(id, _)| id.0).collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    /// Processes nodes in graph.
+    pub fn process(&mut self) {
+        self.graph.process();
+    }
+
+    /// Processes nodes in graph for given number of cycles.
+    #[wasm_bindgen(js_name = processN)]
+    pub fn process_n(&mut self, count: usize) {
+        self.graph.process_n(count);
+    }
+
+    /// Removes a connection.
+    #[wasm_bindgen(js_name = removeConnection)]
+    pub fn remove_connection(
+        &mut self,
+        source_node: u32,
+        source_output: u32,
+        target_node: u32,
+        target_input: u32,
+    ) -> Result<(), JsError> {
+        let connection =
+            Connection::new(NodeId(source_node), OutputId(source_output), NodeId(target_node), InputId(target_input));
+        self.graph.remove_connection(connection).map(|_| ()).map_err(js_error)
+    }
+
+    /// Removes a node (and its connections).
+    #[wasm_bindgen(js_name = removeNode)]
+    pub fn remove_node(&mut self, node: u32) -> Result<(), JsError> {
+        self.graph.remove_node(NodeId(node)).map(|_| ()).map_err(js_error)
+    }
+
+    /// Sets value of a node input (e.g. the value of a variable).
+    #[wasm_bindgen(js_name = setInput)]
+    pub fn set_input(&mut self, node: u32, input: u32, value: Value) -> Result<(), JsError> {
+        self.graph.set_input(NodeId(node), InputId(input), value).map_err(js_error)
+    }
+
+    /// Sets (or removes) label of a node.
+    #[wasm_bindgen(js_name = setLabel)]
+    pub fn set_label(&mut self, node: u32, label: Option<String>) -> Result<(), JsError> {
+        self.graph.set_label(NodeId(node), label.as_deref()).map_err(js_error)
+    }
+
+    /// Sets value of a node parameter.
+    #[wasm_bindgen(js_name = setParameter)]
+    pub fn set_parameter(&mut self, node: u32, name: &str, value: Value) -> Result<(), JsError> {
+        self.graph.set_parameter(NodeId(node), name, value).map_err(js_error)
+    }
+
+    /// Returns Graphviz DOT digraph of the graph.
+    #[wasm_bindgen(js_name = toDot)]
+    pub fn to_dot(&self) -> String {
+        self.graph.to_dot()
+    }
+}
+impl Default for WasmGraph {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns message of a graph error for JavaScript (prefixed with the error kind).
+fn error_message(error: &GraphError) -> String {
+    let kind = match error {
+        GraphError::BackendUnavailable => "BackendUnavailable",
+        GraphError::BufferSizeMismatch(..) => "BufferSizeMismatch",
+        GraphError::ChannelMismatch(..) => "ChannelMismatch",
+        GraphError::ConnectionNotExists(..) => "ConnectionNotExists",
+        GraphError::CycleWithoutDelay => "CycleWithoutDelay",
+        GraphError::ExternalInputNotExists(..) => "ExternalInputNotExists",
+        GraphError::ExternalOutputNotExists(..) => "ExternalOutputNotExists",
+        GraphError::InputAlreadyConnected(..) => "InputAlreadyConnected",
+        GraphError::InputNotExists(..) => "InputNotExists",
+        GraphError::InvalidFormat(..) => "InvalidFormat",
+        GraphError::InvalidRateDivisor(..) => "InvalidRateDivisor",
+        GraphError::LabelAlreadyUsed(..) => "LabelAlreadyUsed",
+        GraphError::LabelNotExists(..) => "LabelNotExists",
+        GraphError::NodeAlreadyExists(..) => "NodeAlreadyExists",
+        GraphError::NodeNotExists(..) => "NodeNotExists",
+        GraphError::NodeTypeNotExists(..) => "NodeTypeNotExists",
+        GraphError::OutputNotExists(..) => "OutputNotExists",
+        GraphError::ParameterNotExists(..) => "ParameterNotExists",
+        GraphError::PortNotExists(..) => "PortNotExists",
+        GraphError::ProbeNotExists(..) => "ProbeNotExists",
+        GraphError::RecordingNotExists(..) => "RecordingNotExists",
+        GraphError::Rejected(..) => "Rejected",
+        GraphError::TypeMismatch(..) => "TypeMismatch",
+        GraphError::UnsupportedNode(..) => "UnsupportedNode",
+        GraphError::WrongNodeType(..) => "WrongNodeType",
+    };
+    format!("{}: {:?}", kind, error)
+}
+
+/// Converts graph error to JavaScript error.
+fn js_error(error: GraphError) -> JsError {
+    JsError::new(&error_message(&error))
+}
+
+/// Unit tests (JavaScript errors can only be created on wasm targets).
+#[cfg(all(test, feature = "dsp", feature = "generators", feature = "math"))]
+mod tests {
+    use super::*;
+    use crate::nodes;
+
+    #[test]
+    fn builds_and_processes() {
+        let mut graph = WasmGraph::new();
+        let var0 = graph.add_node("variable", vec![1.0]).unwrap();
+        let add1 = graph.add_node("addition", vec![]).unwrap();
+        let del2 = graph.add_node("delay", vec![]).unwrap();
+        graph.add_connection(var0, 0, add1, 0).unwrap();
+        graph.add_connection(add1, 0, del2, 0).unwrap();
+        graph.add_connection(del2, 0, add1, 1).unwrap();
+        graph.set_label(var0, Some("input".to_string())).unwrap();
+        assert_eq!(graph.node_ids(), vec![0, 1, 2]);
+
+        graph.set_input(var0, 0, 2.0).unwrap();
+        graph.process_n(2);
+        assert_eq!(graph.get_output(add1, 0).unwrap(), 4.0);
+
+        graph.remove_connection(del2, 0, add1, 1).unwrap();
+        graph.remove_node(del2).unwrap();
+        assert_eq!(graph.node_ids(), vec![0, 1]);
+        assert!(graph.to_dot().contains("0: input"));
+    }
+
+    #[test]
+    fn maps_errors() {
+        let mut graph: Graph<Box<dyn Node>> = Graph::new();
+        let var0 = graph.add_node(Box::new(nodes::Variable::new(1.0)));
+        assert_eq!(
+            graph.set_input(var0, InputId(1), 2.0).map_err(|error| error_message(&error)),
+            Err("InputNotExists: Input with id 1 does not exist on node with id 0.".to_string())
+        );
+        assert_eq!(
+            error_message(&GraphError::NodeTypeNotExists("gain".to_string())),
+            "NodeTypeNotExists: Node type gain is not registered."
+        );
+    }
+}